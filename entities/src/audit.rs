@@ -0,0 +1,84 @@
+use chrono::NaiveDateTime;
+use enum2str::EnumStr;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{datetime_serde, datetime_serde_option, user::URol};
+
+/// Authenticated user performing a state-changing operation
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Actor {
+    pub user_id: Uuid,
+    pub user_rol: URol,
+}
+
+impl Actor {
+    /// Actor of the operations the club performs on its own, like the scheduled jobs
+    pub fn system() -> Self {
+        Self {
+            user_id: Uuid::nil(),
+            user_rol: URol::ADMIN,
+        }
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, EnumStr, ToSchema)]
+pub enum AuditEntity {
+    USER,
+    USER_CATEGORY,
+    CATEGORY,
+    CATEGORY_REQUIREMENT,
+    COURT,
+    COURT_RESERVATION,
+    COURT_CLOSURE,
+    COURT_PRICE_RULE,
+    COURT_CHARGE,
+    TRAINING,
+    TRAINING_REGISTRATION,
+    TRAINING_WAITLIST,
+    TRAINER_TIME_OFF,
+    TOURNAMENT,
+    TOURNAMENT_REGISTRATION,
+    TOURNAMENT_ATTENDANCE,
+    TUITION,
+    REQUEST,
+    NOTIFICATION_PREFERENCES,
+    REQUEST_COMMENT,
+    WEBHOOK,
+    CALENDAR_FEED,
+    JOB,
+}
+
+/// Append-only record of a mutation. `before` is empty for creations and `after` for deletions
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct AuditEntry {
+    pub id_audit: Uuid,
+    pub actor_id: Uuid,
+    pub actor_rol: URol,
+    pub action: String,
+    pub entity: AuditEntity,
+    pub entity_id: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditFilter {
+    pub actor_id: Option<Uuid>,
+    pub action: Option<String>,
+    pub entity: Option<AuditEntity>,
+    pub entity_id: Option<String>,
+    #[serde(with = "datetime_serde_option", default)]
+    #[param(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub created_from: Option<NaiveDateTime>,
+    #[serde(with = "datetime_serde_option", default)]
+    #[param(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub created_to: Option<NaiveDateTime>,
+}
@@ -0,0 +1,67 @@
+use chrono::NaiveDateTime;
+use enum2str::EnumStr;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::datetime_serde;
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, EnumStr, ToSchema)]
+pub enum CalendarFeedKind {
+    /// Every reservation of a court
+    COURT,
+    /// Trainings a trainer leads
+    TRAINER,
+    /// Trainings and tournaments a member is registered to
+    MEMBER,
+}
+
+/// Read-only calendar that calendar apps subscribe to. The token in its link is the only
+/// credential, so it's only shown to whoever created the feed and can be revoked
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct CalendarFeed {
+    pub token: String,
+    /// Who created the feed
+    pub id_user: Uuid,
+    pub kind: CalendarFeedKind,
+    /// The court, the trainer or the member the feed follows
+    pub id_subject: Uuid,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub created_datetime: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct CalendarFeedCreation {
+    pub kind: CalendarFeedKind,
+    /// Empty for the trainer or the member logged in
+    #[serde(default)]
+    pub id_subject: Option<Uuid>,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, EnumStr)]
+pub enum CalendarEntryKind {
+    COURT_RESERVATION,
+    TRAINING,
+    TOURNAMENT,
+}
+
+/// Session shown in a calendar feed
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct CalendarEntry {
+    pub kind: CalendarEntryKind,
+    /// Id of the reservation, the training or the tournament, it never changes
+    pub id_source: Uuid,
+    /// Training or tournament name, empty for member bookings
+    pub name: Option<String>,
+    /// Names of the courts, separated by commas
+    pub location: Option<String>,
+    #[serde(with = "datetime_serde")]
+    pub start_datetime: NaiveDateTime,
+    #[serde(with = "datetime_serde")]
+    pub end_datetime: NaiveDateTime,
+    /// Deleted after it was published, calendars remove it
+    pub cancelled: bool,
+}
@@ -3,12 +3,13 @@ use std::cmp::Ordering;
 use enum2str::EnumStr;
 use partial_struct::Partial;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize, Partial)]
+#[derive(Debug, Serialize, Deserialize, Partial, ToSchema)]
 #[partial(
     "CategoryCreation",
-    derive(Debug, Serialize, Deserialize, Partial),
+    derive(Debug, Serialize, Deserialize, Partial, ToSchema),
     omit(id_category)
 )]
 pub struct Category {
@@ -18,12 +19,12 @@ pub struct Category {
     pub max_age: i32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct Level {
     pub level_name: LevelName,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct CategoryRequirement {
     pub id_category_requirement: Uuid,
     pub id_category: Uuid,
@@ -31,7 +32,7 @@ pub struct CategoryRequirement {
     pub required_level: LevelName,
 }
 
-#[derive(Debug, Serialize, Deserialize, EnumStr, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, EnumStr, PartialEq, Clone, Copy, ToSchema)]
 pub enum LevelName {
     BEGGINER,
     AMATEUR,
@@ -1,42 +1,379 @@
+use crate::court_pricing::CourtCharge;
 use crate::datetime_serde;
 use crate::datetime_serde_option;
-use chrono::NaiveDateTime;
+use crate::time_serde;
+use chrono::{NaiveDateTime, NaiveTime, Weekday};
+use enum2str::EnumStr;
 use partial_struct::Partial;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Partial)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Partial, ToSchema)]
 #[partial(
     "CourtCreation",
-    derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq),
+    derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema),
     omit(id_court)
 )]
 pub struct Court {
     pub id_court: Uuid,
     pub court_name: String,
+    #[serde(default)]
+    pub surface: CourtSurface,
+    #[serde(default)]
+    pub indoor: bool,
+    #[serde(default)]
+    pub has_lighting: bool,
+    /// Players the court fits
+    #[serde(default = "default_capacity")]
+    pub capacity: u32,
+    /// Length the booking grid uses for the court
+    #[serde(default = "default_slot_minutes")]
+    pub default_slot_minutes: u32,
+    /// Inactive courts are kept for the history but can't be reserved
+    #[serde(default = "default_active")]
+    pub active: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Partial)]
+fn default_capacity() -> u32 {
+    4
+}
+
+fn default_slot_minutes() -> u32 {
+    60
+}
+
+fn default_active() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, EnumStr, ToSchema)]
+pub enum CourtSurface {
+    CLAY,
+    #[default]
+    HARD,
+    GRASS,
+    SYNTHETIC,
+}
+
+/// Features a training or a tournament needs from its court, empty ones match any court
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default, ToSchema)]
+pub struct CourtRequirements {
+    pub surface: Option<CourtSurface>,
+    pub indoor: Option<bool>,
+    pub has_lighting: Option<bool>,
+    pub min_capacity: Option<u32>,
+}
+
+impl CourtRequirements {
+    pub fn matches(&self, court: &Court) -> bool {
+        self.surface.is_none_or(|surface| court.surface == surface)
+            && self.indoor.is_none_or(|indoor| court.indoor == indoor)
+            && self
+                .has_lighting
+                .is_none_or(|has_lighting| court.has_lighting == has_lighting)
+            && self
+                .min_capacity
+                .is_none_or(|min_capacity| court.capacity >= min_capacity)
+    }
+}
+
+/// Courts a training or a tournament reserves
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CourtSelection {
+    ById(Uuid),
+    /// First active court matching the requirements that is available
+    ByRequirements(CourtRequirements),
+    /// Several courts, each one for its own block of the event
+    ByBlocks(Vec<EventCourtBlock>),
+}
+
+impl CourtSelection {
+    /// The blocks win over a specific court, which wins over the requirements
+    pub fn from_parts(
+        id_court: Option<Uuid>,
+        requirements: Option<CourtRequirements>,
+        courts: Option<Vec<EventCourtBlock>>,
+    ) -> Option<Self> {
+        courts
+            .map(CourtSelection::ByBlocks)
+            .or(id_court.map(CourtSelection::ById))
+            .or(requirements.map(CourtSelection::ByRequirements))
+    }
+}
+
+/// Court held by a club event, for the whole event unless the block says otherwise
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct EventCourtBlock {
+    pub id_court: Uuid,
+    /// Start of the event when empty
+    #[serde(with = "datetime_serde_option", default)]
+    #[schema(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub start_reservation_datetime: Option<NaiveDateTime>,
+    /// End of the event when empty
+    #[serde(with = "datetime_serde_option", default)]
+    #[schema(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub end_reservation_datetime: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Partial, ToSchema)]
 #[partial(
     "CourtReservationCreation",
-    derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq),
+    derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema),
     omit(id_court_reservation)
 )]
 pub struct CourtReservation {
     pub id_court_reservation: Uuid,
     pub id_court: Uuid,
     #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
     pub start_reservation_datetime: NaiveDateTime,
     #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
     pub end_reservation_datetime: NaiveDateTime,
     pub id_training: Option<Uuid>,
     pub id_tournament: Option<Uuid>,
+    /// Member who booked the court, empty for the reservations of club events
+    #[serde(default)]
+    pub id_owner: Option<Uuid>,
+}
+
+/// Court booked by a member to play on their own, like a friendly match
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct CourtBooking {
+    #[serde(flatten)]
+    pub reservation: CourtReservation,
+    /// Members playing with the owner
+    pub partners: Vec<Uuid>,
+    /// Empty for free bookings. The booking only holds the court until a pending charge is due
+    #[serde(default)]
+    pub charge: Option<CourtCharge>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct CourtBookingCreation {
+    pub id_court: Uuid,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub start_reservation_datetime: NaiveDateTime,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub end_reservation_datetime: NaiveDateTime,
+    #[serde(default)]
+    pub partners: Vec<Uuid>,
+    /// Courts with lighting charge the lights surcharge of their price bands
+    #[serde(default)]
+    pub lights: bool,
+}
+
+#[derive(
+    Debug,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumStr,
+    ToSchema,
+)]
+pub enum DayOfWeek {
+    MONDAY,
+    TUESDAY,
+    WEDNESDAY,
+    THURSDAY,
+    FRIDAY,
+    SATURDAY,
+    SUNDAY,
+}
+
+impl From<Weekday> for DayOfWeek {
+    fn from(weekday: Weekday) -> Self {
+        match weekday {
+            Weekday::Mon => DayOfWeek::MONDAY,
+            Weekday::Tue => DayOfWeek::TUESDAY,
+            Weekday::Wed => DayOfWeek::WEDNESDAY,
+            Weekday::Thu => DayOfWeek::THURSDAY,
+            Weekday::Fri => DayOfWeek::FRIDAY,
+            Weekday::Sat => DayOfWeek::SATURDAY,
+            Weekday::Sun => DayOfWeek::SUNDAY,
+        }
+    }
 }
 
-#[derive(Deserialize, Debug)]
+/// Hours a court is open on a day of the week. Courts without opening hours are always open,
+/// once a court has some, the days without them are closed
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct CourtOpeningHours {
+    pub day_of_week: DayOfWeek,
+    #[serde(with = "time_serde")]
+    #[schema(value_type = time_serde::TimeSchema)]
+    pub open_time: NaiveTime,
+    #[serde(with = "time_serde")]
+    #[schema(value_type = time_serde::TimeSchema)]
+    pub close_time: NaiveTime,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, EnumStr, ToSchema)]
+pub enum ClosureKind {
+    HOLIDAY,
+    MAINTENANCE,
+}
+
+/// Window in which a court, or every court, can't be reserved
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Partial, ToSchema)]
+#[partial(
+    "CourtClosureCreation",
+    derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema),
+    omit(id_closure)
+)]
+pub struct CourtClosure {
+    pub id_closure: Uuid,
+    /// Empty when every court is closed, like on holidays
+    pub id_court: Option<Uuid>,
+    pub kind: ClosureKind,
+    pub reason: String,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub start_datetime: NaiveDateTime,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub end_datetime: NaiveDateTime,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct CourtReservationsQuery {
     #[serde(with = "datetime_serde_option", default)]
+    #[param(value_type = Option<datetime_serde::DateTimeSchema>)]
     pub start_datetime_filter: Option<NaiveDateTime>,
     #[serde(with = "datetime_serde_option", default)]
+    #[param(value_type = Option<datetime_serde::DateTimeSchema>)]
     pub end_datetime_filter: Option<NaiveDateTime>,
 }
+
+#[derive(Deserialize, Debug, Default, Clone, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CourtFilter {
+    /// Partial match on the court name
+    pub court_name: Option<String>,
+    pub surface: Option<CourtSurface>,
+    pub indoor: Option<bool>,
+    pub has_lighting: Option<bool>,
+    pub min_capacity: Option<u32>,
+    pub active: Option<bool>,
+}
+
+impl CourtFilter {
+    pub fn requirements(&self) -> CourtRequirements {
+        CourtRequirements {
+            surface: self.surface,
+            indoor: self.indoor,
+            has_lighting: self.has_lighting,
+            min_capacity: self.min_capacity,
+        }
+    }
+
+    /// Same matching the database applies when listing courts
+    pub fn matches(&self, court: &Court) -> bool {
+        self.court_name.as_ref().is_none_or(|name| {
+            court
+                .court_name
+                .to_lowercase()
+                .contains(&name.to_lowercase())
+        }) && self.active.is_none_or(|active| court.active == active)
+            && self.requirements().matches(court)
+    }
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CourtSlotsQuery {
+    #[serde(with = "datetime_serde")]
+    #[param(value_type = datetime_serde::DateTimeSchema)]
+    pub start_datetime: NaiveDateTime,
+    #[serde(with = "datetime_serde")]
+    #[param(value_type = datetime_serde::DateTimeSchema)]
+    pub end_datetime: NaiveDateTime,
+    /// Length of each slot
+    pub duration_minutes: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct TimeSlot {
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub start_datetime: NaiveDateTime,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub end_datetime: NaiveDateTime,
+}
+
+/// Slots of a court that can be reserved as they are, sorted by start
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct CourtFreeSlots {
+    pub court: Court,
+    pub slots: Vec<TimeSlot>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_court_creation_defaults() {
+        let court_creation: CourtCreation =
+            serde_json::from_str(r#"{"court_name": "Central"}"#).unwrap();
+
+        let court = court_creation.to_court(Uuid::new_v4());
+        assert_eq!(court.surface, CourtSurface::HARD);
+        assert_eq!((court.capacity, court.default_slot_minutes), (4, 60));
+        assert!(court.active && !court.indoor && !court.has_lighting);
+    }
+
+    #[test]
+    fn test_requirements_match() {
+        let court: Court = serde_json::from_str(&format!(
+            r#"{{"id_court": "{}", "court_name": "Central", "surface": "CLAY", "has_lighting": true}}"#,
+            Uuid::new_v4()
+        ))
+        .unwrap();
+
+        let lit_clay = CourtRequirements {
+            surface: Some(CourtSurface::CLAY),
+            has_lighting: Some(true),
+            ..CourtRequirements::default()
+        };
+        assert!(lit_clay.matches(&court));
+        assert!(CourtRequirements::default().matches(&court));
+
+        let indoor = CourtRequirements {
+            indoor: Some(true),
+            ..CourtRequirements::default()
+        };
+        assert!(!indoor.matches(&court));
+    }
+
+    #[test]
+    fn test_court_selection_prefers_blocks() {
+        let id_court = Uuid::new_v4();
+        let blocks: Vec<EventCourtBlock> =
+            serde_json::from_str(&format!(r#"[{{"id_court": "{id_court}"}}]"#)).unwrap();
+        assert_eq!(blocks[0].start_reservation_datetime, None);
+
+        let selection = CourtSelection::from_parts(
+            Some(Uuid::new_v4()),
+            Some(CourtRequirements::default()),
+            Some(blocks.clone()),
+        );
+        assert_eq!(selection, Some(CourtSelection::ByBlocks(blocks)));
+        assert_eq!(
+            CourtSelection::from_parts(Some(id_court), Some(CourtRequirements::default()), None),
+            Some(CourtSelection::ById(id_court))
+        );
+        assert_eq!(CourtSelection::from_parts(None, None, None), None);
+    }
+}
@@ -0,0 +1,57 @@
+use crate::datetime_serde;
+use crate::datetime_serde_option;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct CourtCancellationRequest {
+    pub reason: String,
+}
+
+/// Who cancelled a reservation and why
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct CourtCancellation {
+    pub id_court_reservation: Uuid,
+    pub cancelled_by: Uuid,
+    pub reason: String,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub cancelled_datetime: NaiveDateTime,
+    /// Cancelled by its owner after the free cancellation period, a paid charge isn't refunded
+    pub late: bool,
+}
+
+/// A player of the booking showed up
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct CourtCheckIn {
+    pub id_court_reservation: Uuid,
+    pub id_user: Uuid,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub checked_in_datetime: NaiveDateTime,
+}
+
+/// Booking that ended without anyone checking in, counted against its owner
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct CourtNoShow {
+    pub id_court_reservation: Uuid,
+    pub id_user: Uuid,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub start_datetime: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct MemberNoShows {
+    pub id_user: Uuid,
+    /// Newest first
+    pub no_shows: Vec<CourtNoShow>,
+    /// No-shows counted by the booking policy
+    pub recent_no_shows: u32,
+    /// Empty when the member can book
+    #[serde(with = "datetime_serde_option", default)]
+    #[schema(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub restricted_until: Option<NaiveDateTime>,
+}
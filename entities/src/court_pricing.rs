@@ -0,0 +1,75 @@
+use crate::court::DayOfWeek;
+use crate::datetime_serde;
+use crate::datetime_serde_option;
+use crate::time_serde;
+use chrono::{NaiveDateTime, NaiveTime};
+use enum2str::EnumStr;
+use partial_struct::Partial;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Hourly price of a time band, like the peak hours of the evening. Court time outside every
+/// band is free. When bands overlap, the one of the court wins over the one of every court and
+/// the one of a day over the one of every day
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Partial, ToSchema)]
+#[partial(
+    "CourtPriceRuleCreation",
+    derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema),
+    omit(id_price_rule)
+)]
+pub struct CourtPriceRule {
+    pub id_price_rule: Uuid,
+    /// Shown to the members, like "Peak" or "Off-peak"
+    pub name: String,
+    /// Empty when the band applies to every court
+    pub id_court: Option<Uuid>,
+    /// Empty when the band applies to every day
+    pub day_of_week: Option<DayOfWeek>,
+    #[serde(with = "time_serde")]
+    #[schema(value_type = time_serde::TimeSchema)]
+    pub start_time: NaiveTime,
+    #[serde(with = "time_serde")]
+    #[schema(value_type = time_serde::TimeSchema)]
+    pub end_time: NaiveTime,
+    /// Per hour, for members with an active tuition
+    pub member_price: f64,
+    /// Per hour, for everyone else
+    pub guest_price: f64,
+    /// Added per hour to bookings with the lights on
+    #[serde(default)]
+    pub lights_surcharge: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, EnumStr, ToSchema)]
+pub enum ChargeStatus {
+    /// The booking holds the court until the charge is due
+    PENDING,
+    PAID,
+    /// The booking was cancelled before it was paid
+    CANCELLED,
+    /// The booking was released because it wasn't paid in time
+    EXPIRED,
+    /// The booking was cancelled after it was paid, the club owes the amount back
+    REFUNDED,
+}
+
+/// What a member owes for a booking
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct CourtCharge {
+    pub id_charge: Uuid,
+    pub id_court_reservation: Uuid,
+    pub id_user: Uuid,
+    pub amount: f64,
+    pub status: ChargeStatus,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub created_datetime: NaiveDateTime,
+    /// Pending charges past it release their booking
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub due_datetime: NaiveDateTime,
+    #[serde(with = "datetime_serde_option", default)]
+    #[schema(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub paid_datetime: Option<NaiveDateTime>,
+}
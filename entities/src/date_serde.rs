@@ -1,11 +1,42 @@
+use std::borrow::Cow;
+
 use chrono::NaiveDate;
 use serde::{de, Deserialize, Deserializer, Serializer};
+use serde_json::json;
+use utoipa::openapi::schema::{ObjectBuilder, Schema, SchemaFormat, Type};
+use utoipa::openapi::RefOr;
+use utoipa::{PartialSchema, ToSchema};
+
+pub const FORMAT: &str = "%Y-%m-%d";
+
+/// OpenAPI schema of the fields serialized with this module, use it as
+/// `#[schema(value_type = date_serde::DateSchema)]`
+pub struct DateSchema;
+
+impl PartialSchema for DateSchema {
+    fn schema() -> RefOr<Schema> {
+        RefOr::T(Schema::Object(
+            ObjectBuilder::new()
+                .schema_type(Type::String)
+                .format(Some(SchemaFormat::Custom(FORMAT.to_string())))
+                .description(Some("Calendar date, \"YYYY-MM-DD\""))
+                .examples([json!("2025-03-10")])
+                .build(),
+        ))
+    }
+}
+
+impl ToSchema for DateSchema {
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("Date")
+    }
+}
 
 pub fn serialize<S>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    serializer.serialize_str(&date.format("%Y-%m-%d").to_string())
+    serializer.serialize_str(&date.format(FORMAT).to_string())
 }
 
 pub fn deserialize<'de, D>(deserializer: D) -> Result<NaiveDate, D::Error>
@@ -13,7 +44,7 @@ where
     D: Deserializer<'de>,
 {
     let s = String::deserialize(deserializer)?;
-    NaiveDate::parse_from_str(&s, "%Y-%m-%d").map_err(de::Error::custom)
+    NaiveDate::parse_from_str(&s, FORMAT).map_err(de::Error::custom)
 }
 
 #[cfg(test)]
@@ -1,11 +1,44 @@
+use std::borrow::Cow;
+
 use chrono::NaiveDateTime;
 use serde::{de, Deserialize, Deserializer, Serializer};
+use serde_json::json;
+use utoipa::openapi::schema::{ObjectBuilder, Schema, SchemaFormat, Type};
+use utoipa::openapi::RefOr;
+use utoipa::{PartialSchema, ToSchema};
+
+pub const FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// OpenAPI schema of the fields serialized with this module, use it as
+/// `#[schema(value_type = datetime_serde::DateTimeSchema)]`
+pub struct DateTimeSchema;
+
+impl PartialSchema for DateTimeSchema {
+    fn schema() -> RefOr<Schema> {
+        RefOr::T(Schema::Object(
+            ObjectBuilder::new()
+                .schema_type(Type::String)
+                .format(Some(SchemaFormat::Custom(FORMAT.to_string())))
+                .description(Some(
+                    "Date and time without timezone, \"YYYY-MM-DD HH:MM:SS\"",
+                ))
+                .examples([json!("2025-03-10 18:30:00")])
+                .build(),
+        ))
+    }
+}
+
+impl ToSchema for DateTimeSchema {
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("DateTime")
+    }
+}
 
 pub fn serialize<S>(datetime: &NaiveDateTime, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    serializer.serialize_str(&datetime.format("%Y-%m-%d %H:%M:%S").to_string())
+    serializer.serialize_str(&datetime.format(FORMAT).to_string())
 }
 
 /// Deserializes a string "YYYY-MM-DD HH:MM:SS" into a NaiveDateTime
@@ -14,7 +47,7 @@ where
     D: Deserializer<'de>,
 {
     let s = String::deserialize(deserializer)?;
-    NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S").map_err(de::Error::custom)
+    NaiveDateTime::parse_from_str(&s, FORMAT).map_err(de::Error::custom)
 }
 
 #[cfg(test)]
@@ -21,7 +21,7 @@ where
     let opt = Option::<String>::deserialize(deserializer)?;
     match opt {
         Some(s) => {
-            let datetime = NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S")
+            let datetime = NaiveDateTime::parse_from_str(&s, datetime_serde::FORMAT)
                 .map_err(de::Error::custom)?;
             Ok(Some(datetime))
         }
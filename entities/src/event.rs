@@ -0,0 +1,191 @@
+use chrono::NaiveDateTime;
+use enum2str::EnumStr;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    audit::Actor,
+    datetime_serde, datetime_serde_option,
+    request::{RequestKind, RequestState},
+};
+
+/// Something that happened in a service other services may react to, serialized as
+/// `{"kind": "...", "payload": {...}}`
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", content = "payload")]
+pub enum DomainEvent {
+    USER_REGISTERED {
+        id_user: Uuid,
+    },
+    TRAINING_CREATED {
+        id_training: Uuid,
+    },
+    TRAINING_REGISTERED {
+        id_training: Uuid,
+        id_user: Uuid,
+    },
+    /// `registered_users` are read before the training is deleted
+    TRAINING_CANCELLED {
+        id_training: Uuid,
+        name: String,
+        #[serde(with = "datetime_serde")]
+        start_datetime: NaiveDateTime,
+        registered_users: Vec<Uuid>,
+    },
+    /// Published by the reminder job the day before the session
+    TRAINING_REMINDER {
+        id_training: Uuid,
+        name: String,
+        #[serde(with = "datetime_serde")]
+        start_datetime: NaiveDateTime,
+        registered_users: Vec<Uuid>,
+    },
+    /// A spot of a full training freed up and is held for the first member of its waitlist
+    TRAINING_SPOT_OFFERED {
+        id_training: Uuid,
+        name: String,
+        #[serde(with = "datetime_serde")]
+        start_datetime: NaiveDateTime,
+        id_user: Uuid,
+        #[serde(with = "datetime_serde")]
+        confirm_before: NaiveDateTime,
+    },
+    TOURNAMENT_CANCELLED {
+        id_tournament: Uuid,
+    },
+    TOURNAMENT_REGISTERED {
+        id_tournament: Uuid,
+        id_user: Uuid,
+    },
+    TOURNAMENT_RESULT_RECORDED {
+        id_tournament: Uuid,
+        tournament_name: String,
+        id_user: Uuid,
+        position: i32,
+    },
+    TUITION_PAID {
+        id_tuition: Uuid,
+        id_user: Uuid,
+        amount: f64,
+    },
+    /// Published by the tuition expiry job a few days before the last tuition of a member expires
+    TUITION_EXPIRING {
+        id_user: Uuid,
+        #[serde(with = "datetime_serde")]
+        expires_at: NaiveDateTime,
+    },
+    /// Published once a request is approved or rejected
+    REQUEST_COMPLETED {
+        request_id: Uuid,
+        requester_id: Uuid,
+        request_kind: RequestKind,
+        state: RequestState,
+    },
+    /// A member booking was displaced by a club event or a closure of its court
+    COURT_BOOKING_CANCELLED {
+        id_court_reservation: Uuid,
+        id_court: Uuid,
+        court_name: String,
+        #[serde(with = "datetime_serde")]
+        start_datetime: NaiveDateTime,
+        /// Owner and partners of the booking
+        users: Vec<Uuid>,
+    },
+    /// A member booking was released because its charge wasn't paid in time
+    COURT_BOOKING_RELEASED {
+        id_court_reservation: Uuid,
+        id_court: Uuid,
+        court_name: String,
+        #[serde(with = "datetime_serde")]
+        start_datetime: NaiveDateTime,
+        id_user: Uuid,
+    },
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, EnumStr, ToSchema)]
+pub enum EventKind {
+    USER_REGISTERED,
+    TRAINING_CREATED,
+    TRAINING_REGISTERED,
+    TRAINING_CANCELLED,
+    TRAINING_REMINDER,
+    TRAINING_SPOT_OFFERED,
+    TOURNAMENT_CANCELLED,
+    TOURNAMENT_REGISTERED,
+    TOURNAMENT_RESULT_RECORDED,
+    TUITION_PAID,
+    TUITION_EXPIRING,
+    REQUEST_COMPLETED,
+    COURT_BOOKING_CANCELLED,
+    COURT_BOOKING_RELEASED,
+}
+
+impl DomainEvent {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            DomainEvent::USER_REGISTERED { .. } => EventKind::USER_REGISTERED,
+            DomainEvent::TRAINING_CREATED { .. } => EventKind::TRAINING_CREATED,
+            DomainEvent::TRAINING_REGISTERED { .. } => EventKind::TRAINING_REGISTERED,
+            DomainEvent::TRAINING_CANCELLED { .. } => EventKind::TRAINING_CANCELLED,
+            DomainEvent::TRAINING_REMINDER { .. } => EventKind::TRAINING_REMINDER,
+            DomainEvent::TRAINING_SPOT_OFFERED { .. } => EventKind::TRAINING_SPOT_OFFERED,
+            DomainEvent::TOURNAMENT_CANCELLED { .. } => EventKind::TOURNAMENT_CANCELLED,
+            DomainEvent::TOURNAMENT_REGISTERED { .. } => EventKind::TOURNAMENT_REGISTERED,
+            DomainEvent::TOURNAMENT_RESULT_RECORDED { .. } => EventKind::TOURNAMENT_RESULT_RECORDED,
+            DomainEvent::TUITION_PAID { .. } => EventKind::TUITION_PAID,
+            DomainEvent::TUITION_EXPIRING { .. } => EventKind::TUITION_EXPIRING,
+            DomainEvent::REQUEST_COMPLETED { .. } => EventKind::REQUEST_COMPLETED,
+            DomainEvent::COURT_BOOKING_CANCELLED { .. } => EventKind::COURT_BOOKING_CANCELLED,
+            DomainEvent::COURT_BOOKING_RELEASED { .. } => EventKind::COURT_BOOKING_RELEASED,
+        }
+    }
+}
+
+/// Event waiting in the outbox until every subscriber handled it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OutboxEvent {
+    pub id_event: Uuid,
+    pub event: DomainEvent,
+    /// User whose operation produced the event, subscribers act on its behalf
+    pub actor: Actor,
+    #[serde(with = "datetime_serde")]
+    pub occurred_at: NaiveDateTime,
+    #[serde(with = "datetime_serde_option", default)]
+    pub dispatched_at: Option<NaiveDateTime>,
+    /// Failed dispatch rounds, the event is given up after too many
+    pub attempts: u32,
+    pub last_error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_event_round_trip() {
+        let id_tuition = Uuid::new_v4();
+        let id_user = Uuid::new_v4();
+        let event = DomainEvent::TUITION_PAID {
+            id_tuition,
+            id_user,
+            amount: 50.0,
+        };
+
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "kind": "TUITION_PAID",
+                "payload": { "id_tuition": id_tuition, "id_user": id_user, "amount": 50.0 }
+            })
+        );
+
+        let deserialized: DomainEvent = serde_json::from_value(value).unwrap();
+        assert_eq!(deserialized, event);
+        assert_eq!(deserialized.kind().to_string(), "TUITION_PAID");
+    }
+}
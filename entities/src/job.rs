@@ -0,0 +1,57 @@
+use chrono::NaiveDateTime;
+use enum2str::EnumStr;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{datetime_serde, datetime_serde_option};
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, EnumStr, ToSchema)]
+pub enum JobRunStatus {
+    RUNNING,
+    SUCCEEDED,
+    FAILED,
+}
+
+/// Persisted state of a periodic job. `running_since` is set while a run holds the job lock
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct ScheduledJob {
+    pub name: String,
+    /// Cron expression with the five usual fields, evaluated in UTC
+    pub schedule: String,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub next_run_at: NaiveDateTime,
+    #[serde(with = "datetime_serde_option", default)]
+    #[schema(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub running_since: Option<NaiveDateTime>,
+    #[serde(with = "datetime_serde_option", default)]
+    #[schema(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub last_run_at: Option<NaiveDateTime>,
+    pub last_status: Option<JobRunStatus>,
+}
+
+/// One execution of a job. `triggered_by` is empty for the scheduled runs
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct JobRun {
+    pub id_run: Uuid,
+    pub job_name: String,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub started_at: NaiveDateTime,
+    #[serde(with = "datetime_serde_option", default)]
+    #[schema(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub finished_at: Option<NaiveDateTime>,
+    pub status: JobRunStatus,
+    /// Summary returned by the job, or the error that made it fail
+    pub output: Option<String>,
+    pub triggered_by: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct JobRunFilter {
+    pub job_name: Option<String>,
+    pub status: Option<JobRunStatus>,
+}
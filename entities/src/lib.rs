@@ -1,11 +1,25 @@
+extern crate alloc;
+pub mod audit;
+pub mod calendar;
 pub mod category;
 pub mod court;
+pub mod court_attendance;
+pub mod court_pricing;
 pub mod date_serde;
 pub mod datetime_serde;
 pub mod datetime_serde_option;
+pub mod event;
+pub mod job;
+pub mod notification;
+pub mod pagination;
+pub mod privacy;
 pub mod report;
 pub mod request;
+pub mod schedule;
+pub mod time_serde;
 pub mod tournament;
+pub mod trainer;
 pub mod training;
 pub mod tuition;
 pub mod user;
+pub mod webhook;
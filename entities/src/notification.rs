@@ -0,0 +1,118 @@
+use chrono::NaiveDateTime;
+use enum2str::EnumStr;
+use partial_struct::Partial;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{datetime_serde, datetime_serde_option};
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, EnumStr, ToSchema)]
+pub enum NotificationChannel {
+    EMAIL,
+    SMS,
+    IN_APP,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, EnumStr, ToSchema)]
+pub enum Language {
+    #[default]
+    ES,
+    EN,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, EnumStr, ToSchema)]
+pub enum NotificationKind {
+    TRAINING_CANCELLED,
+    TRAINING_REMINDER,
+    TRAINING_SPOT_OFFERED,
+    TOURNAMENT_RESULT,
+    REQUEST_APPROVED,
+    REQUEST_REJECTED,
+    TUITION_EXPIRING,
+    COURT_BOOKING_CANCELLED,
+    COURT_BOOKING_RELEASED,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, EnumStr, ToSchema)]
+pub enum DeliveryStatus {
+    SENT,
+    FAILED,
+    /// No provider is configured for the channel, so nothing was sent
+    SKIPPED,
+}
+
+/// Members that never saved their preferences get `NotificationPreferences::default_for`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Partial, ToSchema)]
+#[partial(
+    "NotificationPreferencesUpdate",
+    derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema),
+    omit(id_user)
+)]
+pub struct NotificationPreferences {
+    pub id_user: Uuid,
+    pub language: Language,
+    pub email: bool,
+    pub sms: bool,
+    pub in_app: bool,
+}
+
+impl NotificationPreferences {
+    pub fn default_for(id_user: Uuid) -> Self {
+        Self {
+            id_user,
+            language: Language::default(),
+            email: true,
+            sms: false,
+            in_app: true,
+        }
+    }
+
+    pub fn enabled_channels(&self) -> Vec<NotificationChannel> {
+        [
+            (self.email, NotificationChannel::EMAIL),
+            (self.sms, NotificationChannel::SMS),
+            (self.in_app, NotificationChannel::IN_APP),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, channel)| enabled.then_some(channel))
+        .collect()
+    }
+}
+
+/// Delivery log entry. The in-app inbox of a member is made of its IN_APP entries
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct Notification {
+    pub id_notification: Uuid,
+    pub id_user: Uuid,
+    pub kind: NotificationKind,
+    pub channel: NotificationChannel,
+    pub subject: String,
+    pub body: String,
+    pub status: DeliveryStatus,
+    pub error: Option<String>,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "datetime_serde_option", default)]
+    #[schema(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub read_at: Option<NaiveDateTime>,
+}
+
+/// Contact details a channel needs to reach a member
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Recipient {
+    pub id_user: Uuid,
+    pub first_name: String,
+    pub email: String,
+    pub phone_number: String,
+    pub country_code: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct NotificationMessage {
+    pub subject: String,
+    pub body: String,
+}
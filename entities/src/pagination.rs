@@ -0,0 +1,136 @@
+use enum2str::EnumStr;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+pub const DEFAULT_PAGE_SIZE: u32 = 20;
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, EnumStr, ToSchema)]
+pub enum SortOrder {
+    #[default]
+    ASC,
+    DESC,
+}
+
+/// Offset pagination and sorting shared by every list endpoint, pages start at 1
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PageQuery {
+    #[serde(default = "default_page")]
+    pub page: u32,
+    #[serde(default = "default_page_size")]
+    pub page_size: u32,
+    #[serde(default)]
+    pub sort_by: Option<String>,
+    #[serde(default)]
+    pub sort_order: SortOrder,
+}
+
+impl Default for PageQuery {
+    fn default() -> Self {
+        Self {
+            page: default_page(),
+            page_size: default_page_size(),
+            sort_by: None,
+            sort_order: SortOrder::default(),
+        }
+    }
+}
+
+impl PageQuery {
+    pub fn limit(&self) -> u32 {
+        self.page_size.clamp(1, MAX_PAGE_SIZE)
+    }
+
+    pub fn offset(&self) -> u32 {
+        (self.page.max(1) - 1).saturating_mul(self.limit())
+    }
+}
+
+fn default_page() -> u32 {
+    1
+}
+
+fn default_page_size() -> u32 {
+    DEFAULT_PAGE_SIZE
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: u64,
+    pub page: u32,
+    pub page_size: u32,
+    pub total_pages: u32,
+}
+
+impl<T> Page<T> {
+    pub fn new(items: Vec<T>, total: u64, query: &PageQuery) -> Self {
+        let page_size = query.limit();
+        Self {
+            items,
+            total,
+            page: query.page.max(1),
+            page_size,
+            total_pages: total.div_ceil(page_size as u64) as u32,
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            page: self.page,
+            page_size: self.page_size,
+            total_pages: self.total_pages,
+        }
+    }
+
+    pub fn try_map<U, E>(self, f: impl FnMut(T) -> Result<U, E>) -> Result<Page<U>, E> {
+        Ok(Page {
+            items: self.items.into_iter().map(f).collect::<Result<_, _>>()?,
+            total: self.total,
+            page: self.page,
+            page_size: self.page_size,
+            total_pages: self.total_pages,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_query_defaults() {
+        let query: PageQuery = serde_json::from_str("{}").unwrap();
+
+        assert_eq!(query, PageQuery::default());
+        assert_eq!(query.limit(), DEFAULT_PAGE_SIZE);
+        assert_eq!(query.offset(), 0);
+    }
+
+    #[test]
+    fn test_page_query_clamps_page_size() {
+        let query = PageQuery {
+            page: 3,
+            page_size: 1000,
+            ..PageQuery::default()
+        };
+
+        assert_eq!(query.limit(), MAX_PAGE_SIZE);
+        assert_eq!(query.offset(), 2 * MAX_PAGE_SIZE);
+    }
+
+    #[test]
+    fn test_page_total_pages() {
+        let query = PageQuery {
+            page_size: 10,
+            ..PageQuery::default()
+        };
+        let page = Page::new(vec![1, 2, 3], 21, &query);
+
+        assert_eq!(page.total_pages, 3);
+        assert_eq!(page.map(|i| i * 2).items, vec![2, 4, 6]);
+    }
+}
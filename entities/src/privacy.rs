@@ -0,0 +1,35 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    datetime_serde,
+    notification::Notification,
+    request::Request,
+    tournament::{TournamentAttendance, TournamentRegistration},
+    training::TrainingRegistration,
+    tuition::Tuition,
+    user::{UserCategory, UserInfo},
+};
+
+/// Everything the club holds about a single member, as handed out on a data export request
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserDataExport {
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub generated_at: NaiveDateTime,
+    pub profile: UserInfo,
+    pub categories: Vec<UserCategory>,
+    pub training_registrations: Vec<TrainingRegistration>,
+    pub tournament_registrations: Vec<TournamentRegistration>,
+    pub tournament_attendances: Vec<TournamentAttendance>,
+    pub tuitions: Vec<Tuition>,
+    pub requests: Vec<Request>,
+    /// Delivery log of the messages sent to the member, on every channel
+    pub notifications: Vec<Notification>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PrivacyRequestCreation {
+    pub justification: String,
+}
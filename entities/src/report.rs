@@ -1,21 +1,65 @@
 use chrono::NaiveDate;
 use serde::Serialize;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Debug, Serialize)]
+use crate::training::{AttendanceStatus, TrainingRegistration};
+
+#[derive(Debug, Serialize, ToSchema)]
+#[schema(as = ReportUserCategory)]
 pub struct UserCategory {
     pub category_name: String,
     pub user_level: String, // e.g., "BEGINNER", "AMATEUR", "PROFESSIONAL"
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema, PartialEq)]
 pub struct TrainingSummary {
     pub total_registrations: u32,
+    /// Present or late
     pub total_attendances: u32,
+    pub total_present: u32,
+    pub total_late: u32,
+    pub total_absences: u32,
+    pub total_excused: u32,
+    pub total_injured: u32,
+    /// Attended trainings over the ones the member was expected at, excused or injured
+    /// absences and trainings without attendance don't count. Empty when there's none
+    pub attendance_rate: Option<f64>,
     pub most_recent_attendance: Option<NaiveDate>,
 }
 
-#[derive(Debug, Serialize)]
+impl TrainingSummary {
+    pub fn from_registrations(registrations: &[TrainingRegistration]) -> Self {
+        let statuses: Vec<AttendanceStatus> = registrations
+            .iter()
+            .filter_map(|registration| registration.effective_attendance_status())
+            .collect();
+        let count = |status: AttendanceStatus| {
+            statuses.iter().filter(|other| **other == status).count() as u32
+        };
+
+        let total_attendances = statuses.iter().filter(|status| status.attended()).count() as u32;
+        let expected = statuses.iter().filter(|status| !status.excused()).count() as u32;
+
+        Self {
+            total_registrations: registrations.len() as u32,
+            total_attendances,
+            total_present: count(AttendanceStatus::PRESENT),
+            total_late: count(AttendanceStatus::LATE),
+            total_absences: count(AttendanceStatus::ABSENT),
+            total_excused: count(AttendanceStatus::EXCUSED),
+            total_injured: count(AttendanceStatus::INJURED),
+            attendance_rate: (expected > 0).then(|| total_attendances as f64 / expected as f64),
+            most_recent_attendance: registrations
+                .iter()
+                .filter(|registration| registration.attended)
+                .filter_map(|registration| registration.attendance_datetime.map(|dt| dt.date()))
+                .max(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct TournamentSummary {
     pub total_registrations: u32,
     pub total_attendances: u32,
@@ -23,7 +67,7 @@ pub struct TournamentSummary {
     pub most_recent_registration: Option<NaiveDate>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct TuitionSummary {
     pub last_payment_amount: f64,
     pub last_payment_date: NaiveDate,
@@ -31,14 +75,14 @@ pub struct TuitionSummary {
     pub total_payments: f64,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct UserRequest {
     pub request_id: Uuid,
     pub requested_command: String,
     pub state: String, // e.g., "PENDING", "APPROVED", "REJECTED"
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct Report {
     pub full_name: String,
     pub email: String,
@@ -52,3 +96,55 @@ pub struct Report {
     pub tuition_summary: TuitionSummary,
     pub requests: Vec<UserRequest>,
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+
+    use super::*;
+
+    fn registration(status: Option<AttendanceStatus>, attended: bool) -> TrainingRegistration {
+        let now =
+            NaiveDateTime::parse_from_str("2025-06-07 18:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        TrainingRegistration {
+            id_training: Uuid::new_v4(),
+            id_user: Uuid::new_v4(),
+            registration_datetime: now,
+            attended,
+            attendance_datetime: attended.then_some(now),
+            attendance_status: status,
+            attendance_submitted_by: None,
+            attendance_notes: None,
+        }
+    }
+
+    #[test]
+    fn test_excused_absences_keep_the_attendance_rate() {
+        let summary = TrainingSummary::from_registrations(&[
+            registration(Some(AttendanceStatus::PRESENT), true),
+            registration(Some(AttendanceStatus::LATE), true),
+            registration(Some(AttendanceStatus::ABSENT), false),
+            registration(Some(AttendanceStatus::EXCUSED), false),
+            registration(Some(AttendanceStatus::INJURED), false),
+            // Attendance not taken yet
+            registration(None, false),
+            // Marked before the statuses
+            registration(None, true),
+        ]);
+
+        assert_eq!(summary.total_registrations, 7);
+        assert_eq!(summary.total_attendances, 3);
+        assert_eq!(summary.total_present, 2);
+        assert_eq!(summary.total_late, 1);
+        assert_eq!(summary.total_absences, 1);
+        assert_eq!(summary.total_excused, 1);
+        assert_eq!(summary.total_injured, 1);
+        assert_eq!(summary.attendance_rate, Some(0.75));
+
+        let summary = TrainingSummary::from_registrations(&[registration(
+            Some(AttendanceStatus::INJURED),
+            false,
+        )]);
+        assert_eq!(summary.attendance_rate, None);
+    }
+}
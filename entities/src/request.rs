@@ -1,18 +1,216 @@
+use chrono::NaiveDateTime;
+use enum2str::EnumStr;
 use partial_struct::Partial;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Partial)]
-#[partial(
-    "RequestCreation",
-    derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq),
-    omit(request_id, approved, approver_id)
-)]
+use crate::{
+    category::LevelName, court::CourtReservationCreation, datetime_serde, datetime_serde_option,
+    user::URol,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
 pub struct Request {
     pub request_id: Uuid,
     pub requester_id: Uuid,
-    pub requested_command: String,
+    pub requested_command: RequestCommand,
     pub justification: String,
+    pub state: RequestState,
+    /// Index of the approval step waiting for a decision
+    pub current_step: u32,
+    /// Approver who took the final decision
+    pub approver_id: Option<Uuid>,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub created_at: NaiveDateTime,
+    /// Open requests expire once this deadline passes
+    #[serde(with = "datetime_serde_option", default)]
+    #[schema(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub expires_at: Option<NaiveDateTime>,
+    /// Set once an approved request has been applied by its owning service
+    #[serde(with = "datetime_serde_option", default)]
+    #[schema(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub executed_at: Option<NaiveDateTime>,
+    /// Why applying an approved request failed
+    pub execution_error: Option<String>,
+}
+
+/// A new request, filed by the authenticated user
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct RequestCreation {
+    pub requested_command: RequestCommand,
+    pub justification: String,
+}
+
+/// What a request asks for, serialized as `{"kind": "...", "payload": {...}}`
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(tag = "kind", content = "payload")]
+pub enum RequestCommand {
+    ROLE_CHANGE(RoleChange),
+    CATEGORY_LEVEL_CHANGE(CategoryLevelChange),
+    TUITION_EXEMPTION(TuitionExemption),
+    LATE_REGISTRATION(LateRegistration),
+    COURT_BOOKING(CourtReservationCreation),
+    EXPORT_USER_DATA,
+    ERASE_USER_ACCOUNT,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, EnumStr, ToSchema)]
+pub enum RequestKind {
+    ROLE_CHANGE,
+    CATEGORY_LEVEL_CHANGE,
+    TUITION_EXEMPTION,
+    LATE_REGISTRATION,
+    COURT_BOOKING,
+    EXPORT_USER_DATA,
+    ERASE_USER_ACCOUNT,
+}
+
+impl RequestCommand {
+    pub fn kind(&self) -> RequestKind {
+        match self {
+            RequestCommand::ROLE_CHANGE(_) => RequestKind::ROLE_CHANGE,
+            RequestCommand::CATEGORY_LEVEL_CHANGE(_) => RequestKind::CATEGORY_LEVEL_CHANGE,
+            RequestCommand::TUITION_EXEMPTION(_) => RequestKind::TUITION_EXEMPTION,
+            RequestCommand::LATE_REGISTRATION(_) => RequestKind::LATE_REGISTRATION,
+            RequestCommand::COURT_BOOKING(_) => RequestKind::COURT_BOOKING,
+            RequestCommand::EXPORT_USER_DATA => RequestKind::EXPORT_USER_DATA,
+            RequestCommand::ERASE_USER_ACCOUNT => RequestKind::ERASE_USER_ACCOUNT,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct RoleChange {
+    pub user_id: Uuid,
+    pub user_rol: URol,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct CategoryLevelChange {
+    pub user_id: Uuid,
+    pub category_id: Uuid,
+    pub level: LevelName,
+}
+
+/// Grants the user an active tuition of `amount` without a payment
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct TuitionExemption {
+    pub user_id: Uuid,
+    pub amount: f64,
+}
+
+/// Registers the user once the registration window of the event has closed.
+/// Exactly one of `id_training` and `id_tournament` must be set
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct LateRegistration {
+    pub user_id: Uuid,
+    pub id_training: Option<Uuid>,
+    pub id_tournament: Option<Uuid>,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, EnumStr, ToSchema)]
+pub enum RequestState {
+    /// No approval step has been decided yet
+    PENDING,
+    /// Some approval steps were approved, others are still waiting
+    IN_REVIEW,
+    APPROVED,
+    REJECTED,
+    WITHDRAWN,
+    EXPIRED,
+}
+
+impl RequestState {
+    /// Whether the request still accepts decisions, comments and withdrawal
+    pub fn is_open(&self) -> bool {
+        matches!(self, RequestState::PENDING | RequestState::IN_REVIEW)
+    }
+}
+
+/// One link of the approval chain of a request. Steps are decided in order
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct RequestStep {
+    pub request_id: Uuid,
+    pub step: u32,
+    /// Role required to decide this step
+    pub approver_rol: URol,
+    pub approver_id: Option<Uuid>,
     pub approved: Option<bool>,
+    #[serde(with = "datetime_serde_option", default)]
+    #[schema(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub decided_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Partial, ToSchema)]
+#[partial(
+    "RequestCommentCreation",
+    derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema),
+    omit(id_comment, request_id, author_id, created_at)
+)]
+pub struct RequestComment {
+    pub id_comment: Uuid,
+    pub request_id: Uuid,
+    pub author_id: Uuid,
+    pub body: String,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RequestFilter {
+    pub requester_id: Option<Uuid>,
     pub approver_id: Option<Uuid>,
+    pub state: Option<RequestState>,
+    pub kind: Option<RequestKind>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_command_round_trip() {
+        let user_id = Uuid::new_v4();
+        let command = RequestCommand::ROLE_CHANGE(RoleChange {
+            user_id,
+            user_rol: URol::TRAINER,
+        });
+
+        let value = serde_json::to_value(&command).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "kind": "ROLE_CHANGE",
+                "payload": { "user_id": user_id, "user_rol": "TRAINER" }
+            })
+        );
+
+        let deserialized: RequestCommand = serde_json::from_value(value).unwrap();
+        assert_eq!(deserialized, command);
+    }
+
+    #[test]
+    fn test_command_without_payload() {
+        let deserialized: RequestCommand =
+            serde_json::from_value(json!({ "kind": "EXPORT_USER_DATA" })).unwrap();
+        assert_eq!(deserialized, RequestCommand::EXPORT_USER_DATA);
+        assert_eq!(deserialized.kind().to_string(), "EXPORT_USER_DATA");
+    }
+
+    #[test]
+    fn test_command_with_invalid_payload() {
+        let result: Result<RequestCommand, _> = serde_json::from_value(json!({
+            "kind": "TUITION_EXEMPTION",
+            "payload": { "user_id": Uuid::new_v4() }
+        }));
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,62 @@
+use chrono::NaiveDateTime;
+use enum2str::EnumStr;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{datetime_serde, datetime_serde_option};
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, EnumStr, ToSchema)]
+pub enum ScheduleEntryKind {
+    /// Training the user is registered to
+    TRAINING,
+    /// Training the user leads as its trainer
+    TRAINING_LED,
+    /// Tournament the user is registered to
+    TOURNAMENT,
+    /// Court booking the user owns or plays in as a partner
+    COURT_BOOKING,
+}
+
+/// Session in the timeline of a user
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct ScheduleEntry {
+    pub kind: ScheduleEntryKind,
+    /// Id of the training, the tournament or the court reservation
+    pub id_source: Uuid,
+    /// Training or tournament name, the court name for bookings
+    pub name: String,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub start_datetime: NaiveDateTime,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub end_datetime: NaiveDateTime,
+}
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ScheduleQuery {
+    /// Now when empty
+    #[serde(with = "datetime_serde_option", default)]
+    #[param(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub from: Option<NaiveDateTime>,
+    /// 30 days after `from` when empty
+    #[serde(with = "datetime_serde_option", default)]
+    #[param(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub to: Option<NaiveDateTime>,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ScheduleConflictQuery {
+    #[serde(with = "datetime_serde")]
+    #[param(value_type = datetime_serde::DateTimeSchema)]
+    pub start_datetime: NaiveDateTime,
+    #[serde(with = "datetime_serde")]
+    #[param(value_type = datetime_serde::DateTimeSchema)]
+    pub end_datetime: NaiveDateTime,
+    /// Session being checked, it doesn't conflict with itself
+    pub id_source: Option<Uuid>,
+}
@@ -0,0 +1,81 @@
+use std::borrow::Cow;
+
+use chrono::NaiveTime;
+use serde::{de, Deserialize, Deserializer, Serializer};
+use serde_json::json;
+use utoipa::openapi::schema::{ObjectBuilder, Schema, SchemaFormat, Type};
+use utoipa::openapi::RefOr;
+use utoipa::{PartialSchema, ToSchema};
+
+pub const FORMAT: &str = "%H:%M";
+
+/// OpenAPI schema of the fields serialized with this module, use it as
+/// `#[schema(value_type = time_serde::TimeSchema)]`
+pub struct TimeSchema;
+
+impl PartialSchema for TimeSchema {
+    fn schema() -> RefOr<Schema> {
+        RefOr::T(Schema::Object(
+            ObjectBuilder::new()
+                .schema_type(Type::String)
+                .format(Some(SchemaFormat::Custom(FORMAT.to_string())))
+                .description(Some("Time of the day, \"HH:MM\""))
+                .examples([json!("18:30")])
+                .build(),
+        ))
+    }
+}
+
+impl ToSchema for TimeSchema {
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("Time")
+    }
+}
+
+pub fn serialize<S>(time: &NaiveTime, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&time.format(FORMAT).to_string())
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<NaiveTime, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    NaiveTime::parse_from_str(&s, FORMAT).map_err(de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveTime;
+    use serde::{Deserialize, Serialize};
+    use serde_json;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct TestTime {
+        #[serde(with = "super")]
+        time: NaiveTime,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let test_time = TestTime {
+            time: NaiveTime::from_hms_opt(7, 30, 0).unwrap(),
+        };
+
+        let serialized = serde_json::to_string(&test_time).unwrap();
+        assert_eq!(serialized, r#"{"time":"07:30"}"#);
+
+        let deserialized: TestTime = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, test_time);
+    }
+
+    #[test]
+    fn test_deserialize_invalid_time() {
+        let result: Result<TestTime, _> = serde_json::from_str(r#"{"time":"25:00"}"#);
+
+        assert!(result.is_err());
+    }
+}
@@ -1,13 +1,15 @@
 use super::datetime_serde;
+use super::datetime_serde_option;
 use chrono::NaiveDateTime;
 use partial_struct::Partial;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Partial)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Partial, ToSchema)]
 #[partial(
     "TournamentCreation",
-    derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq),
+    derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema),
     omit(id_tournament)
 )]
 pub struct Tournament {
@@ -15,34 +17,50 @@ pub struct Tournament {
     pub name: String,
     pub id_category: Uuid,
     #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
     pub start_datetime: NaiveDateTime,
     #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
     pub end_datetime: NaiveDateTime,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Partial)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TournamentFilter {
+    pub id_category: Option<Uuid>,
+    #[serde(with = "datetime_serde_option", default)]
+    #[param(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub start_datetime_from: Option<NaiveDateTime>,
+    #[serde(with = "datetime_serde_option", default)]
+    #[param(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub start_datetime_to: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Partial, ToSchema)]
 #[partial(
     "TournamentRegistrationRequest",
-    derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq),
+    derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema),
     omit(id_tournament, registration_datetime)
 )]
 pub struct TournamentRegistration {
     pub id_tournament: Uuid,
     pub id_user: Uuid,
     #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
     pub registration_datetime: NaiveDateTime,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Partial)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Partial, ToSchema)]
 #[partial(
     "TournamentAttendanceRequest",
-    derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq),
+    derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema),
     omit(id_tournament, attendance_datetime)
 )]
 pub struct TournamentAttendance {
     pub id_tournament: Uuid,
     pub id_user: Uuid,
     #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
     pub attendance_datetime: NaiveDateTime,
     pub position: i32,
 }
@@ -0,0 +1,73 @@
+use crate::court::DayOfWeek;
+use crate::datetime_serde;
+use crate::datetime_serde_option;
+use crate::time_serde;
+use chrono::{NaiveDateTime, NaiveTime};
+use partial_struct::Partial;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Hours a trainer can lead trainings on a day of the week. Trainers without availability can
+/// be assigned at any time, once a trainer declares some, the days without it are off
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct TrainerAvailability {
+    pub day_of_week: DayOfWeek,
+    #[serde(with = "time_serde")]
+    #[schema(value_type = time_serde::TimeSchema)]
+    pub start_time: NaiveTime,
+    #[serde(with = "time_serde")]
+    #[schema(value_type = time_serde::TimeSchema)]
+    pub end_time: NaiveTime,
+}
+
+/// Window in which a trainer can't lead trainings, like holidays or a sick leave
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Partial, ToSchema)]
+#[partial(
+    "TrainerTimeOffCreation",
+    derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema),
+    omit(id_time_off, id_trainer)
+)]
+pub struct TrainerTimeOff {
+    pub id_time_off: Uuid,
+    pub id_trainer: Uuid,
+    pub reason: String,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub start_datetime: NaiveDateTime,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub end_datetime: NaiveDateTime,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TrainerTimeOffQuery {
+    #[serde(with = "datetime_serde_option", default)]
+    #[param(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub start_datetime_filter: Option<NaiveDateTime>,
+    #[serde(with = "datetime_serde_option", default)]
+    #[param(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub end_datetime_filter: Option<NaiveDateTime>,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AvailableTrainersQuery {
+    #[serde(with = "datetime_serde")]
+    #[param(value_type = datetime_serde::DateTimeSchema)]
+    pub start_datetime: NaiveDateTime,
+    #[serde(with = "datetime_serde")]
+    #[param(value_type = datetime_serde::DateTimeSchema)]
+    pub end_datetime: NaiveDateTime,
+    /// Training being moved, its own trainer stays free for it
+    pub id_training: Option<Uuid>,
+}
+
+/// Trainer who could lead a training in the requested slot
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct AvailableTrainer {
+    pub id_trainer: Uuid,
+    pub first_name: String,
+    pub last_name: String,
+}
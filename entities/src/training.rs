@@ -1,14 +1,16 @@
 use super::datetime_serde;
 use super::datetime_serde_option;
 use chrono::NaiveDateTime;
+use enum2str::EnumStr;
 use partial_struct::Partial;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize, Partial, Clone)]
+#[derive(Debug, Serialize, Deserialize, Partial, Clone, ToSchema)]
 #[partial(
     "TrainingCreation",
-    derive(Debug, Serialize, Deserialize, Clone),
+    derive(Debug, Serialize, Deserialize, Clone, ToSchema),
     omit(id_training)
 )]
 pub struct Training {
@@ -17,19 +19,175 @@ pub struct Training {
     pub id_category: Uuid,
     pub trainer_id: Uuid,
     #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
     pub start_datetime: NaiveDateTime,
     #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
     pub end_datetime: NaiveDateTime,
     pub minimum_payment: f64,
+    /// Empty for trainings without a limit, full trainings take members on a waitlist
+    #[serde(default)]
+    pub max_participants: Option<u32>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TrainingFilter {
+    pub id_category: Option<Uuid>,
+    pub trainer_id: Option<Uuid>,
+    #[serde(with = "datetime_serde_option", default)]
+    #[param(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub start_datetime_from: Option<NaiveDateTime>,
+    #[serde(with = "datetime_serde_option", default)]
+    #[param(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub start_datetime_to: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct TrainingRegistration {
     pub id_training: Uuid,
     pub id_user: Uuid,
     #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
     pub registration_datetime: NaiveDateTime,
     pub attended: bool,
     #[serde(with = "datetime_serde_option")]
+    #[schema(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub attendance_datetime: Option<NaiveDateTime>,
+    /// Empty until the attendance is taken
+    #[serde(default)]
+    pub attendance_status: Option<AttendanceStatus>,
+    /// Trainer or admin who took the attendance, or the member checking in
+    #[serde(default)]
+    pub attendance_submitted_by: Option<Uuid>,
+    /// Reason of an excused absence, or anything the trainer noted
+    #[serde(default)]
+    pub attendance_notes: Option<String>,
+}
+
+impl TrainingRegistration {
+    /// Registrations from before the statuses only tell whether the member attended
+    pub fn effective_attendance_status(&self) -> Option<AttendanceStatus> {
+        self.attendance_status
+            .or(self.attended.then_some(AttendanceStatus::PRESENT))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, EnumStr, ToSchema)]
+pub enum AttendanceStatus {
+    PRESENT,
+    /// Arrived after the start, counts as attended
+    LATE,
+    ABSENT,
+    /// Missed the training with a reason, like a school exam
+    EXCUSED,
+    /// Missed the training or couldn't take part because of an injury
+    INJURED,
+}
+
+impl AttendanceStatus {
+    pub fn attended(&self) -> bool {
+        matches!(self, AttendanceStatus::PRESENT | AttendanceStatus::LATE)
+    }
+
+    /// Absences with a reason, they don't count against the attendance rate
+    pub fn excused(&self) -> bool {
+        matches!(self, AttendanceStatus::EXCUSED | AttendanceStatus::INJURED)
+    }
+}
+
+/// Registration of the roster a trainer fills in, with the name of the member
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct TrainingRosterEntry {
+    pub id_user: Uuid,
+    pub first_name: String,
+    pub last_name: String,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub registration_datetime: NaiveDateTime,
+    #[serde(default)]
+    pub attendance_status: Option<AttendanceStatus>,
+    #[serde(with = "datetime_serde_option", default)]
+    #[schema(value_type = Option<datetime_serde::DateTimeSchema>)]
     pub attendance_datetime: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub attendance_submitted_by: Option<Uuid>,
+    #[serde(default)]
+    pub attendance_notes: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct AttendanceRecord {
+    pub id_user: Uuid,
+    pub status: AttendanceStatus,
+    /// Replaces the notes of the registration, empty clears them
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+/// Attendance of several members of a training, saved all at once. Members left out keep
+/// their attendance
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct AttendanceSubmission {
+    pub records: Vec<AttendanceRecord>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, EnumStr, ToSchema)]
+pub enum WaitlistStatus {
+    /// Waiting for a spot
+    WAITING,
+    /// A spot is held for the member until `confirm_before`
+    OFFERED,
+    /// The member took the spot and is registered
+    CONFIRMED,
+    /// The member didn't confirm in time, the spot went to the next one
+    EXPIRED,
+    /// The member left the waitlist
+    LEFT,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct TrainingWaitlistEntry {
+    pub id_training: Uuid,
+    pub id_user: Uuid,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub joined_datetime: NaiveDateTime,
+    pub status: WaitlistStatus,
+    #[serde(with = "datetime_serde_option", default)]
+    #[schema(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub offered_datetime: Option<NaiveDateTime>,
+    /// The offered spot goes to the next member after it
+    #[serde(with = "datetime_serde_option", default)]
+    #[schema(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub confirm_before: Option<NaiveDateTime>,
+    /// Place among the waiting members, starting at 1. Empty once a spot was offered
+    #[serde(default)]
+    pub position: Option<u32>,
+}
+
+impl TrainingWaitlistEntry {
+    /// Still waiting for a spot or holding one
+    pub fn is_queued(&self) -> bool {
+        matches!(
+            self.status,
+            WaitlistStatus::WAITING | WaitlistStatus::OFFERED
+        )
+    }
+}
+
+/// Short-lived token shown as a QR code during the training, members scan it to check in
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct TrainingCheckInToken {
+    pub id_training: Uuid,
+    pub token: String,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub expires_datetime: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct TrainingCheckIn {
+    /// Content of the scanned QR code
+    pub token: String,
 }
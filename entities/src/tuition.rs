@@ -1,13 +1,28 @@
 use super::datetime_serde;
+use super::datetime_serde_option;
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
 pub struct Tuition {
     pub id_tuition: Uuid,
     pub id_user: Uuid,
     pub amount: f64,
     #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
     pub payment_date: NaiveDateTime,
 }
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TuitionFilter {
+    pub id_user: Option<Uuid>,
+    #[serde(with = "datetime_serde_option", default)]
+    #[param(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub paid_from: Option<NaiveDateTime>,
+    #[serde(with = "datetime_serde_option", default)]
+    #[param(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub paid_to: Option<NaiveDateTime>,
+}
@@ -2,21 +2,25 @@ use crate::category::LevelName;
 
 use super::date_serde;
 use super::datetime_serde;
+use super::datetime_serde_option;
 use chrono::{NaiveDate, NaiveDateTime};
 use enum2str::EnumStr;
 use partial_struct::Partial;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, Partial)]
+// Every partial lists its own derives, ToSchema included, which clippy reads as duplicates
+#[allow(clippy::duplicated_attributes)]
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, Clone, Partial, ToSchema)]
 #[partial(
     "UserInfo",
-    derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq),
+    derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, Clone, ToSchema),
     omit(password)
 )]
 #[partial(
     "UserCreation",
-    derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq),
+    derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, ToSchema),
     omit(id_user, registration_date, email_verified, user_rol)
 )]
 pub struct User {
@@ -24,8 +28,10 @@ pub struct User {
     pub first_name: String,
     pub last_name: String,
     #[serde(with = "date_serde")]
+    #[schema(value_type = date_serde::DateSchema)]
     pub birth_date: NaiveDate,
     #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
     pub registration_date: NaiveDateTime,
     pub email: String,
     pub email_verified: bool,
@@ -37,25 +43,25 @@ pub struct User {
     pub user_rol: URol,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, Clone, ToSchema)]
 pub struct UserLogInInfo {
     pub identifier: String,
     pub password: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, ToSchema)]
 pub struct DocInfo {
     pub identification_number: String,
     pub identification_type: IdType,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, EnumStr, Clone)]
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, EnumStr, Clone, ToSchema)]
 pub enum IdType {
     #[default]
     CC,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, EnumStr, Clone)]
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, EnumStr, Clone, ToSchema)]
 pub enum URol {
     #[default]
     USER,
@@ -63,17 +69,31 @@ pub enum URol {
     TRAINER,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct UserRole {
     pub user_rol: URol,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct IdentificationInfo {
     pub identification_type: IdType,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UserFilter {
+    pub user_rol: Option<URol>,
+    /// Matched against first name, last name and email
+    pub search: Option<String>,
+    #[serde(with = "datetime_serde_option", default)]
+    #[param(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub registered_from: Option<NaiveDateTime>,
+    #[serde(with = "datetime_serde_option", default)]
+    #[param(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub registered_to: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct UserCategory {
     pub id_user: Uuid,
     pub id_category: Uuid,
@@ -0,0 +1,85 @@
+use chrono::NaiveDateTime;
+use enum2str::EnumStr;
+use partial_struct::Partial;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{datetime_serde, datetime_serde_option, event::EventKind};
+
+/// External endpoint receiving the events it subscribed to. Payloads are signed with `secret`,
+/// which is only shown once, when the webhook is created
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Partial, ToSchema)]
+#[partial(
+    "WebhookCreation",
+    derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema),
+    omit(id_webhook, secret, active, created_at)
+)]
+pub struct Webhook {
+    pub id_webhook: Uuid,
+    pub url: String,
+    pub description: String,
+    pub event_kinds: Vec<EventKind>,
+    #[serde(skip_serializing)]
+    pub secret: String,
+    pub active: bool,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub created_at: NaiveDateTime,
+}
+
+impl Webhook {
+    pub fn is_subscribed_to(&self, kind: EventKind) -> bool {
+        self.active && self.event_kinds.contains(&kind)
+    }
+}
+
+/// A new webhook together with its secret, the only response that includes it
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct CreatedWebhook {
+    #[serde(flatten)]
+    pub webhook: Webhook,
+    pub secret: String,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, EnumStr, ToSchema)]
+pub enum WebhookDeliveryStatus {
+    /// Waiting for its first attempt or for a retry
+    PENDING,
+    SUCCEEDED,
+    /// Every attempt failed, only a manual redelivery sends it again
+    FAILED,
+}
+
+/// One event sent to one webhook, together with the outcome of its last attempt
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct WebhookDelivery {
+    pub id_delivery: Uuid,
+    pub id_webhook: Uuid,
+    pub id_event: Uuid,
+    pub event_kind: EventKind,
+    /// JSON body exactly as it is signed and sent
+    pub payload: String,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: u32,
+    pub response_status: Option<u16>,
+    pub last_error: Option<String>,
+    #[serde(with = "datetime_serde_option", default)]
+    #[schema(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub next_attempt_at: Option<NaiveDateTime>,
+    #[serde(with = "datetime_serde")]
+    #[schema(value_type = datetime_serde::DateTimeSchema)]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "datetime_serde_option", default)]
+    #[schema(value_type = Option<datetime_serde::DateTimeSchema>)]
+    pub delivered_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WebhookDeliveryFilter {
+    pub id_webhook: Option<Uuid>,
+    pub event_kind: Option<EventKind>,
+    pub status: Option<WebhookDeliveryStatus>,
+}
@@ -0,0 +1,10 @@
+use hmac::digest::InvalidLength;
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Invalid signing key: {0}")]
+    InvalidKey(#[from] InvalidLength),
+}
@@ -0,0 +1,82 @@
+pub mod err;
+
+use err::Result;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use use_cases::training_service::err::Error::CheckInSigningError;
+use use_cases::training_service::signer_trait::CheckInSigner;
+
+/// Hex encoded HMAC-SHA256 of `payload` keyed with `key`
+pub fn sign_hex(key: &[u8], payload: &str) -> Result<String> {
+    Ok(hex::encode(mac(key, payload)?.finalize().into_bytes()))
+}
+
+fn mac(key: &[u8], payload: &str) -> Result<Hmac<Sha256>> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key)?;
+    mac.update(payload.as_bytes());
+
+    Ok(mac)
+}
+
+/// Hex encoded HMAC-SHA256 signatures keyed with a server secret
+pub struct HmacSigner {
+    key: Vec<u8>,
+}
+
+impl HmacSigner {
+    pub fn new(key: &str) -> Self {
+        Self {
+            key: key.as_bytes().to_vec(),
+        }
+    }
+
+    fn mac(&self, payload: &str) -> use_cases::training_service::err::Result<Hmac<Sha256>> {
+        mac(&self.key, payload).map_err(|err| CheckInSigningError(format!("{err}")))
+    }
+}
+
+impl CheckInSigner for HmacSigner {
+    fn sign(&self, payload: &str) -> use_cases::training_service::err::Result<String> {
+        sign_hex(&self.key, payload).map_err(|err| CheckInSigningError(format!("{err}")))
+    }
+
+    fn verify(
+        &self,
+        payload: &str,
+        signature: &str,
+    ) -> use_cases::training_service::err::Result<bool> {
+        let Ok(signature) = hex::decode(signature) else {
+            return Ok(false);
+        };
+
+        // Constant time comparison
+        Ok(self.mac(payload)?.verify_slice(&signature).is_ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let signer = HmacSigner::new("Jefe");
+        // Reference value from RFC 4231, test case 2
+        let signature = signer.sign("what do ya want for nothing?").unwrap();
+        assert_eq!(
+            signature,
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+
+        assert!(signer
+            .verify("what do ya want for nothing?", &signature)
+            .unwrap());
+        assert!(!signer.verify("what do ya want?", &signature).unwrap());
+        assert!(!signer
+            .verify("what do ya want for nothing?", "not hex")
+            .unwrap());
+        assert!(!HmacSigner::new("other key")
+            .verify("what do ya want for nothing?", &signature)
+            .unwrap());
+    }
+}
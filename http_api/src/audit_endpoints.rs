@@ -0,0 +1,81 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::get,
+    Extension, Json, Router,
+};
+use entities::{
+    audit::{AuditEntry, AuditFilter},
+    pagination::{Page, PageQuery},
+};
+use tracing::error;
+use use_cases::audit_service::{err::Error, AuditService};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::{
+    auth::{auth_middleware, require_admin, UserInfoAuth},
+    err::{Forbidden, HttpError, HttpResult, InternalError, Unauthorized},
+};
+
+pub fn audit_router(audit_service: AuditService, jwt_key: String) -> Router {
+    let (router, _) = audit_routes().split_for_parts();
+
+    router
+        .route("/health-audit", get(alive))
+        .route_layer(middleware::from_fn_with_state(jwt_key, auth_middleware))
+        .with_state(audit_service)
+}
+
+pub fn audit_routes() -> OpenApiRouter<AuditService> {
+    OpenApiRouter::new().routes(routes!(list_audit_entries))
+}
+
+async fn alive() -> &'static str {
+    "Audit service is alive"
+}
+
+#[utoipa::path(
+    get,
+    path = "/audit-log",
+    tag = "audit",
+    params(PageQuery, AuditFilter),
+    responses(
+        (status = 200, description = "Page of audit entries", body = Page<AuditEntry>),
+        Unauthorized,
+        Forbidden,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn list_audit_entries(
+    State(audit_service): State<AuditService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Query(page): Query<PageQuery>,
+    Query(filter): Query<AuditFilter>,
+) -> HttpResult<Json<Page<AuditEntry>>> {
+    require_admin(&user_info, "read the audit log")?;
+
+    let entries = audit_service
+        .list_entries_page(&filter, &page)
+        .await
+        .http_err("list audit entries")?;
+
+    Ok(Json(entries))
+}
+
+impl<T> HttpError<T> for Result<T, Error> {
+    fn http_err(self, endpoint_name: &str) -> crate::err::HttpResult<T> {
+        self.map_err(|err| {
+            error!("Error in audit endpoint ({}): {}", endpoint_name, err);
+            let (status_code, message) = match err {
+                Error::UnknownDatabaseError(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Error reading the audit log.",
+                ),
+            };
+            (status_code, message.to_string()).into_response()
+        })
+    }
+}
@@ -2,10 +2,10 @@ use axum::{
     extract::{Request, State},
     http::StatusCode,
     middleware::Next,
-    response::IntoResponse,
+    response::{IntoResponse, Response},
 };
 use chrono::Utc;
-use entities::user::URol;
+use entities::{audit::Actor, user::URol};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
 use tracing::error;
@@ -26,6 +26,39 @@ pub struct UserInfoAuth {
     pub user_rol: URol,
 }
 
+impl From<UserInfoAuth> for Actor {
+    fn from(user_info: UserInfoAuth) -> Self {
+        Self {
+            user_id: user_info.user_id,
+            user_rol: user_info.user_rol,
+        }
+    }
+}
+
+/// Rejection of a non admin user on an admin only endpoint, kept small so checks can use `?`
+#[derive(Debug)]
+pub struct AdminRequired(String);
+
+impl IntoResponse for AdminRequired {
+    fn into_response(self) -> Response {
+        (StatusCode::FORBIDDEN, self.0).into_response()
+    }
+}
+
+impl From<AdminRequired> for Response {
+    fn from(rejection: AdminRequired) -> Self {
+        rejection.into_response()
+    }
+}
+
+/// `action` completes the message returned to the caller: "Only admins can {action}"
+pub fn require_admin(user_info: &UserInfoAuth, action: &str) -> Result<(), AdminRequired> {
+    if user_info.user_rol != URol::ADMIN {
+        return Err(AdminRequired(format!("Only admins can {action}")));
+    }
+    Ok(())
+}
+
 pub fn generate_jwt(
     log_in_response: &LogInResponse,
     token_key: &str,
@@ -0,0 +1,182 @@
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    middleware,
+    response::IntoResponse,
+    routing::get,
+    Extension, Json, Router,
+};
+use entities::{
+    audit::Actor,
+    calendar::{CalendarFeed, CalendarFeedCreation},
+};
+use tracing::error;
+use use_cases::calendar_service::{err::Error, CalendarService};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::{
+    auth::{auth_middleware, UserInfoAuth},
+    err::{BadRequest, Forbidden, HttpError, HttpResult, InternalError, NotFound, Unauthorized},
+};
+
+pub fn calendar_router(calendar_service: CalendarService, jwt_key: String) -> Router {
+    let (protected_router, _) = protected_calendar_routes().split_for_parts();
+    let (public_router, _) = public_calendar_routes().split_for_parts();
+
+    protected_router
+        // Calendar apps can't log in, the feeds are reached with their token
+        .route_layer(middleware::from_fn_with_state(jwt_key, auth_middleware))
+        .merge(public_router)
+        .route("/health-calendar", get(alive))
+        .with_state(calendar_service)
+}
+
+pub fn calendar_routes() -> OpenApiRouter<CalendarService> {
+    protected_calendar_routes().merge(public_calendar_routes())
+}
+
+fn protected_calendar_routes() -> OpenApiRouter<CalendarService> {
+    OpenApiRouter::new()
+        .routes(routes!(create_feed))
+        .routes(routes!(list_my_feeds))
+        .routes(routes!(revoke_feed))
+}
+
+fn public_calendar_routes() -> OpenApiRouter<CalendarService> {
+    OpenApiRouter::new().routes(routes!(get_feed_calendar))
+}
+
+async fn alive() -> &'static str {
+    "Calendar service is alive"
+}
+
+/// Calendar apps subscribe to `/calendar-feeds/{token}/calendar.ics`
+#[utoipa::path(
+    post,
+    path = "/calendar-feeds",
+    tag = "calendars",
+    request_body = CalendarFeedCreation,
+    responses(
+        (status = 201, description = "The created feed", body = CalendarFeed),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn create_feed(
+    State(calendar_service): State<CalendarService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Json(creation): Json<CalendarFeedCreation>,
+) -> HttpResult<(StatusCode, Json<CalendarFeed>)> {
+    let feed = calendar_service
+        .create_feed(&Actor::from(user_info), creation)
+        .await
+        .http_err("create calendar feed")?;
+    Ok((StatusCode::CREATED, Json(feed)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/calendar-feeds/mine",
+    tag = "calendars",
+    responses(
+        (status = 200, description = "Feeds the logged in user created, newest first", body = Vec<CalendarFeed>),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn list_my_feeds(
+    State(calendar_service): State<CalendarService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<Json<Vec<CalendarFeed>>> {
+    let feeds = calendar_service
+        .list_user_feeds(user_info.user_id)
+        .await
+        .http_err("list my calendar feeds")?;
+    Ok(Json(feeds))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/calendar-feeds/{token}",
+    tag = "calendars",
+    params(("token" = String, Path)),
+    responses(
+        (status = 200, description = "Feed revoked, its link stops working", body = String, content_type = "text/plain"),
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn revoke_feed(
+    State(calendar_service): State<CalendarService>,
+    Path(token): Path<String>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<impl IntoResponse> {
+    calendar_service
+        .revoke_feed(&Actor::from(user_info), &token)
+        .await
+        .http_err("revoke calendar feed")?;
+    Ok((StatusCode::OK, "Calendar feed revoked successfully"))
+}
+
+#[utoipa::path(
+    get,
+    path = "/calendar-feeds/{token}/calendar.ics",
+    tag = "calendars",
+    params(("token" = String, Path)),
+    responses(
+        (status = 200, description = "iCalendar document of the feed", body = String, content_type = "text/calendar"),
+        NotFound,
+        InternalError
+    )
+)]
+async fn get_feed_calendar(
+    State(calendar_service): State<CalendarService>,
+    Path(token): Path<String>,
+) -> HttpResult<impl IntoResponse> {
+    let calendar = calendar_service
+        .render_feed(&token)
+        .await
+        .http_err("get calendar feed")?;
+    Ok((
+        [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+        calendar,
+    ))
+}
+
+impl<T> HttpError<T> for Result<T, Error> {
+    fn http_err(self, endpoint_name: &str) -> crate::err::HttpResult<T> {
+        self.map_err(|err| {
+            error!("Error in calendar endpoint ({}): {}", endpoint_name, err);
+            let (status_code, message) = match &err {
+                Error::FeedNotFound => (StatusCode::NOT_FOUND, err.to_string()),
+                Error::MissingSubject | Error::NotATrainer => {
+                    (StatusCode::BAD_REQUEST, err.to_string())
+                }
+                Error::NotFeedOwner | Error::SubjectNotAllowed => {
+                    (StatusCode::FORBIDDEN, err.to_string())
+                }
+                Error::CourtServiceError(use_cases::court_service::err::Error::CourtNotFound) => {
+                    (StatusCode::NOT_FOUND, "Court not found.".to_string())
+                }
+                Error::UserServiceError(use_cases::user_service::err::Error::UserIdDontExist) => {
+                    (StatusCode::NOT_FOUND, "User not found.".to_string())
+                }
+                Error::UnknownDatabaseError(_)
+                | Error::CourtServiceError(_)
+                | Error::UserServiceError(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Error processing the calendar request.".to_string(),
+                ),
+            };
+            (status_code, message).into_response()
+        })
+    }
+}
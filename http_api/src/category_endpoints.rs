@@ -1,66 +1,78 @@
 use axum::{
     extract::{Path, State},
     http::StatusCode,
+    middleware,
     response::{IntoResponse, Response},
-    routing::{delete, get, post, put},
-    Json, Router,
+    routing::get,
+    Extension, Json, Router,
 };
 use entities::{
+    audit::Actor,
     category::{Category, CategoryCreation, CategoryRequirement, LevelName},
     user::UserCategory,
 };
 use tracing::error;
 use use_cases::category_service::{err::Error, CategoryService};
+use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
 
-use super::err::{HttpError, ToErrResponse};
-use crate::err::HttpResult;
+use super::err::{HttpError, InternalError, ToErrResponse, Unauthorized};
+use crate::{
+    auth::{auth_middleware, UserInfoAuth},
+    err::HttpResult,
+};
+
+pub fn category_router(category_service: CategoryService, jwt_key: String) -> Router {
+    let (protected_router, _) = protected_category_routes().split_for_parts();
+    let (public_router, _) = public_category_routes().split_for_parts();
 
-pub fn category_router(category_service: CategoryService) -> Router {
-    Router::new()
+    protected_router
+        // Only the routes acting for a user require a token, browsing categories stays public
+        .route_layer(middleware::from_fn_with_state(jwt_key, auth_middleware))
+        .merge(public_router)
         .route("/health-category", get(alive))
-        .route(
-            "/categories",
-            post(create_category)
-                .get(list_categories)
-                .put(update_category),
-        )
-        .route(
-            "/categories/{id}",
-            get(get_category).delete(delete_category),
-        )
-        .route(
-            "/categories/{id}/requirements",
-            post(add_requirement).get(get_requirements),
-        )
-        .route(
-            "/categories/{id}/requirements/{requirement_id}",
-            delete(remove_requirement),
-        )
-        .route(
-            "/categories/{id}/user/{id}/level/{level}",
-            put(update_user_category_level),
-        )
-        .route(
-            "/categories/{category_id}/user/{user_id}",
-            delete(delete_user_from_category_endpoint),
-        )
-        .route(
-            "/categories/{category_id}/users/{user_id}",
-            post(register_user_in_category).get(get_user_category),
-        )
-        .route(
-            "/categories/{category_id}/users/{user_id}/eligible",
-            get(check_user_eligibility),
-        )
-        .route("/categories/user/{user_id}", get(get_user_categories))
         .with_state(category_service)
 }
 
+pub fn category_routes() -> OpenApiRouter<CategoryService> {
+    protected_category_routes().merge(public_category_routes())
+}
+
+fn protected_category_routes() -> OpenApiRouter<CategoryService> {
+    OpenApiRouter::new()
+        .routes(routes!(create_category, update_category))
+        .routes(routes!(delete_category))
+        .routes(routes!(add_requirement))
+        .routes(routes!(remove_requirement))
+        .routes(routes!(update_user_category_level))
+        .routes(routes!(delete_user_from_category_endpoint))
+        .routes(routes!(register_user_in_category))
+}
+
+fn public_category_routes() -> OpenApiRouter<CategoryService> {
+    OpenApiRouter::new()
+        .routes(routes!(list_categories))
+        .routes(routes!(get_category))
+        .routes(routes!(get_requirements))
+        .routes(routes!(get_user_category))
+        .routes(routes!(check_user_eligibility))
+        .routes(routes!(get_user_categories))
+}
+
 async fn alive() -> &'static str {
     "Category service is alive"
 }
 
+#[utoipa::path(
+    get,
+    path = "/categories/{category_id}/users/{user_id}/eligible",
+    tag = "categories",
+    params(("category_id" = Uuid, Path), ("user_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "The user is eligible, otherwise an error explains why", body = bool),
+        InternalError
+    )
+)]
 async fn check_user_eligibility(
     State(category_service): State<CategoryService>,
     Path((category_id, user_id)): Path<(Uuid, Uuid)>,
@@ -73,54 +85,116 @@ async fn check_user_eligibility(
     Ok(Json(true))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/categories/{category_id}/user/{user_id}",
+    tag = "categories",
+    params(("category_id" = Uuid, Path), ("user_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "User removed from the category", body = String, content_type = "text/plain"),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn delete_user_from_category_endpoint(
     State(category_service): State<CategoryService>,
     Path((category_id, user_id)): Path<(Uuid, Uuid)>,
+    Extension(user_info): Extension<UserInfoAuth>,
 ) -> HttpResult<impl IntoResponse> {
     category_service
-        .delete_user_from_category(user_id, category_id)
+        .delete_user_from_category(&Actor::from(user_info), user_id, category_id)
         .await
         .http_err("Failed to remove user from category")?;
 
     Ok((StatusCode::OK, "User successfully removed from category."))
 }
 
+#[utoipa::path(
+    put,
+    path = "/categories/{category_id}/user/{user_id}/level/{level}",
+    tag = "categories",
+    params(("category_id" = Uuid, Path), ("user_id" = Uuid, Path), ("level" = LevelName, Path)),
+    responses(
+        (status = 200, description = "Level updated", body = String, content_type = "text/plain"),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn update_user_category_level(
     State(category_service): State<CategoryService>,
     Path((category_id, user_id, level_name)): Path<(Uuid, Uuid, LevelName)>,
+    Extension(user_info): Extension<UserInfoAuth>,
 ) -> HttpResult<impl IntoResponse> {
     category_service
-        .update_user_category_level(user_id, category_id, level_name)
+        .update_user_category_level(&Actor::from(user_info), user_id, category_id, level_name)
         .await
         .http_err("error, update user category level")?;
 
     Ok((StatusCode::OK, "User registered in category successfully"))
 }
 
+#[utoipa::path(
+    post,
+    path = "/categories/{category_id}/users/{user_id}",
+    tag = "categories",
+    params(("category_id" = Uuid, Path), ("user_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "User registered in the category", body = String, content_type = "text/plain"),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn register_user_in_category(
     State(category_service): State<CategoryService>,
     Path((category_id, user_id)): Path<(Uuid, Uuid)>,
+    Extension(user_info): Extension<UserInfoAuth>,
 ) -> HttpResult<impl IntoResponse> {
     category_service
-        .add_user_to_category(user_id, category_id)
+        .add_user_to_category(&Actor::from(user_info), user_id, category_id)
         .await
         .http_err("register user in category")?;
 
     Ok((StatusCode::OK, "User registered in category successfully"))
 }
 
+#[utoipa::path(
+    post,
+    path = "/categories",
+    tag = "categories",
+    request_body = CategoryCreation,
+    responses(
+        (status = 200, description = "Category created", body = String, content_type = "text/plain"),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn create_category(
     State(category_service): State<CategoryService>,
+    Extension(user_info): Extension<UserInfoAuth>,
     Json(category): Json<CategoryCreation>,
 ) -> HttpResult<impl IntoResponse> {
     category_service
-        .add_category(category)
+        .add_category(&Actor::from(user_info), category)
         .await
         .http_err("create category")?;
 
     Ok((StatusCode::OK, "Category created successfully"))
 }
 
+#[utoipa::path(
+    get,
+    path = "/categories/{id}",
+    tag = "categories",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "The category", body = Category),
+        InternalError
+    )
+)]
 async fn get_category(
     State(category_service): State<CategoryService>,
     Path(id): Path<Uuid>,
@@ -133,30 +207,65 @@ async fn get_category(
     Ok(Json(category))
 }
 
+#[utoipa::path(
+    put,
+    path = "/categories",
+    tag = "categories",
+    request_body = Category,
+    responses(
+        (status = 200, description = "The updated category", body = Category),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn update_category(
     State(category_service): State<CategoryService>,
+    Extension(user_info): Extension<UserInfoAuth>,
     Json(category): Json<Category>,
 ) -> Result<Json<Category>, Response> {
     category_service
-        .update_category(&category)
+        .update_category(&Actor::from(user_info), &category)
         .await
         .http_err("update category")?;
 
     Ok(Json(category))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/categories/{id}",
+    tag = "categories",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Category deleted", body = String),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn delete_category(
     State(category_service): State<CategoryService>,
     Path(id): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
 ) -> Result<Json<String>, Response> {
     category_service
-        .delete_category(id)
+        .delete_category(&Actor::from(user_info), id)
         .await
         .http_err("delete category")?;
 
     Ok(Json("Category deleted successfully".to_string()))
 }
 
+#[utoipa::path(
+    get,
+    path = "/categories",
+    tag = "categories",
+    responses(
+        (status = 200, description = "Every category", body = Vec<Category>),
+        InternalError
+    )
+)]
 async fn list_categories(
     State(category_service): State<CategoryService>,
 ) -> Result<Json<Vec<Category>>, Response> {
@@ -168,30 +277,71 @@ async fn list_categories(
     Ok(Json(categories))
 }
 
+#[utoipa::path(
+    post,
+    path = "/categories/{id}/requirements",
+    tag = "categories",
+    params(("id" = Uuid, Path)),
+    request_body = CategoryRequirement,
+    responses(
+        (status = 200, description = "The added requirement", body = CategoryRequirement),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn add_requirement(
     State(category_service): State<CategoryService>,
+    Extension(user_info): Extension<UserInfoAuth>,
     Json(requirement): Json<CategoryRequirement>,
 ) -> Result<Json<CategoryRequirement>, Response> {
     category_service
-        .add_category_requirement(&requirement)
+        .add_category_requirement(&Actor::from(user_info), &requirement)
         .await
         .http_err("add requirement")?;
 
     Ok(Json(requirement))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/categories/{id}/requirements/{requirement_id}",
+    tag = "categories",
+    params(("id" = Uuid, Path), ("requirement_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Requirement removed"),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn remove_requirement(
     State(category_service): State<CategoryService>,
     Path((category_id, category_requirement_id)): Path<(Uuid, Uuid)>,
+    Extension(user_info): Extension<UserInfoAuth>,
 ) -> Result<(), Response> {
     category_service
-        .delete_category_requirement(&category_requirement_id, &category_id)
+        .delete_category_requirement(
+            &Actor::from(user_info),
+            &category_requirement_id,
+            &category_id,
+        )
         .await
         .http_err("add requirement")?;
 
     Ok(())
 }
 
+#[utoipa::path(
+    get,
+    path = "/categories/{id}/requirements",
+    tag = "categories",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Requirements of the category", body = Vec<CategoryRequirement>),
+        InternalError
+    )
+)]
 async fn get_requirements(
     State(category_service): State<CategoryService>,
     Path(category_id): Path<Uuid>,
@@ -204,6 +354,16 @@ async fn get_requirements(
     Ok(Json(requirements))
 }
 
+#[utoipa::path(
+    get,
+    path = "/categories/{category_id}/users/{user_id}",
+    tag = "categories",
+    params(("category_id" = Uuid, Path), ("user_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Level of the user in the category, null when not registered", body = Option<UserCategory>),
+        InternalError
+    )
+)]
 async fn get_user_category(
     State(category_service): State<CategoryService>,
     Path((category_id, user_id)): Path<(Uuid, Uuid)>,
@@ -216,6 +376,16 @@ async fn get_user_category(
     Ok(Json(user_category))
 }
 
+#[utoipa::path(
+    get,
+    path = "/categories/user/{user_id}",
+    tag = "categories",
+    params(("user_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Categories of the user", body = Vec<UserCategory>),
+        InternalError
+    )
+)]
 async fn get_user_categories(
     State(category_service): State<CategoryService>,
     Path(user_id): Path<Uuid>,
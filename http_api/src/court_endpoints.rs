@@ -3,37 +3,36 @@ use axum::{
     http::StatusCode,
     middleware,
     response::IntoResponse,
-    routing::{get, post},
-    Json, Router,
+    routing::get,
+    Extension, Json, Router,
+};
+use entities::{
+    audit::Actor,
+    court::{
+        Court, CourtBooking, CourtBookingCreation, CourtClosure, CourtClosureCreation,
+        CourtCreation, CourtFilter, CourtFreeSlots, CourtOpeningHours, CourtReservation,
+        CourtReservationsQuery, CourtSlotsQuery,
+    },
+    court_attendance::{CourtCancellation, CourtCancellationRequest, CourtCheckIn, MemberNoShows},
+    court_pricing::{CourtCharge, CourtPriceRule, CourtPriceRuleCreation},
+    pagination::{Page, PageQuery},
 };
-use entities::court::{Court, CourtCreation, CourtReservation, CourtReservationsQuery};
 use tracing::error;
 use use_cases::court_service::{err::Error as CourtServiceError, CourtService};
+use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
 
 use super::err::HttpError;
-use crate::{auth::auth_middleware, err::HttpResult};
+use crate::{
+    auth::{auth_middleware, require_admin, UserInfoAuth},
+    err::{BadRequest, Conflict, Forbidden, HttpResult, InternalError, NotFound, Unauthorized},
+};
 
 pub fn court_router(court_service: CourtService, jwt_key: String) -> Router {
-    let reservation_router = Router::new()
-        .route(
-            "/by-training/{training_id}",
-            get(get_reservation_by_training_id),
-        )
-        .route(
-            "/by-tournament/{tournament_id}",
-            get(get_reservation_by_tournament_id),
-        );
+    let (router, _) = court_routes().split_for_parts();
 
-    Router::new()
+    router
         .route("/health-court", get(alive))
-        .route("/courts", post(create_court).get(list_courts))
-        .route("/courts/{id_court}", get(get_court).delete(delete_court))
-        .route(
-            "/courts/{id_court}/reservations",
-            get(get_reservations_for_court_endpoint),
-        )
-        .nest("/court-reservations", reservation_router)
         .route_layer(middleware::from_fn_with_state(
             jwt_key.clone(),
             auth_middleware,
@@ -41,6 +40,45 @@ pub fn court_router(court_service: CourtService, jwt_key: String) -> Router {
         .with_state(court_service)
 }
 
+pub fn court_routes() -> OpenApiRouter<CourtService> {
+    OpenApiRouter::new()
+        .routes(routes!(create_court, list_courts))
+        .routes(routes!(get_court, update_court, delete_court))
+        .routes(routes!(find_free_slots))
+        .routes(routes!(get_reservations_for_court_endpoint))
+        .routes(routes!(get_reservations_by_training_id))
+        .routes(routes!(get_reservations_by_tournament_id))
+        .routes(routes!(create_booking))
+        .routes(routes!(list_my_bookings))
+        .routes(routes!(cancel_reservation))
+        .routes(routes!(get_cancellation))
+        .routes(routes!(check_in))
+        .routes(routes!(list_my_no_shows))
+        .routes(routes!(get_member_no_shows))
+        .routes(routes!(waive_no_show))
+        .routes(routes!(get_opening_hours, set_opening_hours))
+        .routes(routes!(get_closures_for_court))
+        .routes(routes!(create_closure))
+        .routes(routes!(delete_closure))
+        .routes(routes!(create_price_rule, list_price_rules))
+        .routes(routes!(delete_price_rule))
+        .routes(routes!(list_my_charges))
+        .routes(routes!(pay_charge))
+}
+
+#[utoipa::path(
+    get,
+    path = "/courts/{id_court}/reservations",
+    tag = "courts",
+    params(("id_court" = Uuid, Path), CourtReservationsQuery),
+    responses(
+        (status = 200, description = "Reservations of the court in the range", body = Vec<CourtReservation>),
+        Unauthorized,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn get_reservations_for_court_endpoint(
     State(court_service): State<CourtService>,
     Path(id_court): Path<Uuid>,
@@ -61,17 +99,69 @@ async fn alive() -> &'static str {
     "Court service is alive"
 }
 
+#[utoipa::path(
+    post,
+    path = "/courts",
+    tag = "courts",
+    request_body = CourtCreation,
+    responses(
+        (status = 200, description = "The created court", body = Court),
+        Unauthorized,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn create_court(
     State(court_service): State<CourtService>,
+    Extension(user_info): Extension<UserInfoAuth>,
     Json(court_creation): Json<CourtCreation>,
 ) -> HttpResult<Json<Court>> {
     let court = court_service
-        .create_court(court_creation)
+        .create_court(&Actor::from(user_info), court_creation)
         .await
         .http_err("create court")?;
     Ok(Json(court))
 }
 
+#[utoipa::path(
+    get,
+    path = "/courts/free-slots",
+    tag = "courts",
+    params(CourtSlotsQuery, CourtFilter),
+    responses(
+        (status = 200, description = "Free slots of each court matching the filter", body = Vec<CourtFreeSlots>),
+        BadRequest,
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn find_free_slots(
+    State(court_service): State<CourtService>,
+    Query(query): Query<CourtSlotsQuery>,
+    Query(filter): Query<CourtFilter>,
+) -> HttpResult<Json<Vec<CourtFreeSlots>>> {
+    let free_slots = court_service
+        .find_free_slots(&query, &filter)
+        .await
+        .http_err("find free slots")?;
+    Ok(Json(free_slots))
+}
+
+#[utoipa::path(
+    get,
+    path = "/courts/{id_court}",
+    tag = "courts",
+    params(("id_court" = Uuid, Path)),
+    responses(
+        (status = 200, description = "The court", body = Court),
+        Unauthorized,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn get_court(
     State(court_service): State<CourtService>,
     Path(id_court): Path<Uuid>,
@@ -83,58 +173,608 @@ async fn get_court(
     Ok(Json(court))
 }
 
-async fn list_courts(State(court_service): State<CourtService>) -> HttpResult<Json<Vec<Court>>> {
-    let courts = court_service.list_courts().await.http_err("list courts")?;
+#[utoipa::path(
+    put,
+    path = "/courts/{id_court}",
+    tag = "courts",
+    params(("id_court" = Uuid, Path)),
+    request_body = CourtCreation,
+    responses(
+        (status = 200, description = "The updated court", body = Court),
+        Unauthorized,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn update_court(
+    State(court_service): State<CourtService>,
+    Path(id_court): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Json(court_update): Json<CourtCreation>,
+) -> HttpResult<Json<Court>> {
+    let court = court_service
+        .update_court(&Actor::from(user_info), id_court, court_update)
+        .await
+        .http_err("update court")?;
+    Ok(Json(court))
+}
+
+#[utoipa::path(
+    get,
+    path = "/courts",
+    tag = "courts",
+    params(PageQuery, CourtFilter),
+    responses(
+        (status = 200, description = "Page of courts", body = Page<Court>),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn list_courts(
+    State(court_service): State<CourtService>,
+    Query(page): Query<PageQuery>,
+    Query(filter): Query<CourtFilter>,
+) -> HttpResult<Json<Page<Court>>> {
+    let courts = court_service
+        .list_courts_page(&filter, &page)
+        .await
+        .http_err("list courts")?;
     Ok(Json(courts))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/courts/{id_court}",
+    tag = "courts",
+    params(("id_court" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Court deleted", body = String, content_type = "text/plain"),
+        BadRequest,
+        Unauthorized,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn delete_court(
     State(court_service): State<CourtService>,
     Path(id_court): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
 ) -> HttpResult<impl IntoResponse> {
     court_service
-        .delete_court(id_court)
+        .delete_court(&Actor::from(user_info), id_court)
         .await
         .http_err("delete court")?;
     Ok((StatusCode::OK, "Court deleted successfully"))
 }
 
-async fn get_reservation_by_training_id(
+#[utoipa::path(
+    get,
+    path = "/court-reservations/by-training/{training_id}",
+    tag = "courts",
+    params(("training_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Every court the training holds, sorted by start", body = Vec<CourtReservation>),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_reservations_by_training_id(
     State(court_service): State<CourtService>,
     Path(training_id): Path<Uuid>,
-) -> Result<Json<CourtReservation>, impl IntoResponse> {
-    match court_service
-        .get_reservation_for_training(training_id)
-        .await
-        .http_err("get reservation by training id")
-    {
-        Ok(Some(reservation)) => Ok(Json(reservation)),
-        Ok(None) => Err((
-            StatusCode::NOT_FOUND,
-            "No reservation found for this training",
-        )
-            .into_response()),
-        Err(e) => Err(e),
-    }
+) -> HttpResult<Json<Vec<CourtReservation>>> {
+    let reservations = court_service
+        .get_reservations_for_training(training_id)
+        .await
+        .http_err("get reservations by training id")?;
+    Ok(Json(reservations))
 }
 
-async fn get_reservation_by_tournament_id(
+#[utoipa::path(
+    get,
+    path = "/court-reservations/by-tournament/{tournament_id}",
+    tag = "courts",
+    params(("tournament_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Every court the tournament holds, sorted by start", body = Vec<CourtReservation>),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_reservations_by_tournament_id(
     State(court_service): State<CourtService>,
     Path(tournament_id): Path<Uuid>,
-) -> Result<Json<CourtReservation>, impl IntoResponse> {
-    match court_service
-        .get_reservation_for_tournament(tournament_id)
-        .await
-        .http_err("get reservation by tournament id")
-    {
-        Ok(Some(reservation)) => Ok(Json(reservation)),
-        Ok(None) => Err((
-            StatusCode::NOT_FOUND,
-            "No reservation found for this tournament",
+) -> HttpResult<Json<Vec<CourtReservation>>> {
+    let reservations = court_service
+        .get_reservations_for_tournament(tournament_id)
+        .await
+        .http_err("get reservations by tournament id")?;
+    Ok(Json(reservations))
+}
+
+#[utoipa::path(
+    post,
+    path = "/court-bookings",
+    tag = "courts",
+    request_body = CourtBookingCreation,
+    responses(
+        (status = 201, description = "The booking of the logged in member", body = CourtBooking),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn create_booking(
+    State(court_service): State<CourtService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Json(booking_creation): Json<CourtBookingCreation>,
+) -> HttpResult<(StatusCode, Json<CourtBooking>)> {
+    let booking = court_service
+        .create_booking(&Actor::from(user_info), booking_creation)
+        .await
+        .http_err("create booking")?;
+    Ok((StatusCode::CREATED, Json(booking)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/court-bookings/mine",
+    tag = "courts",
+    responses(
+        (status = 200, description = "Upcoming bookings the logged in member owns or plays in", body = Vec<CourtBooking>),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn list_my_bookings(
+    State(court_service): State<CourtService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<Json<Vec<CourtBooking>>> {
+    let bookings = court_service
+        .list_member_bookings(user_info.user_id)
+        .await
+        .http_err("list my bookings")?;
+    Ok(Json(bookings))
+}
+
+#[utoipa::path(
+    post,
+    path = "/court-reservations/{id_reservation}/cancel",
+    tag = "courts",
+    params(("id_reservation" = Uuid, Path)),
+    request_body = CourtCancellationRequest,
+    responses(
+        (status = 200, description = "The cancellation, late ones keep the paid charge", body = CourtCancellation),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn cancel_reservation(
+    State(court_service): State<CourtService>,
+    Path(id_reservation): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Json(request): Json<CourtCancellationRequest>,
+) -> HttpResult<Json<CourtCancellation>> {
+    let cancellation = court_service
+        .cancel_reservation(&Actor::from(user_info), id_reservation, request)
+        .await
+        .http_err("cancel reservation")?;
+    Ok(Json(cancellation))
+}
+
+#[utoipa::path(
+    get,
+    path = "/court-reservations/{id_reservation}/cancellation",
+    tag = "courts",
+    params(("id_reservation" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Who cancelled the reservation and why", body = CourtCancellation),
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_cancellation(
+    State(court_service): State<CourtService>,
+    Path(id_reservation): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<Json<CourtCancellation>> {
+    let cancellation = court_service
+        .get_cancellation(&Actor::from(user_info), id_reservation)
+        .await
+        .http_err("get cancellation")?;
+    Ok(Json(cancellation))
+}
+
+#[utoipa::path(
+    post,
+    path = "/court-bookings/{id_reservation}/check-in",
+    tag = "courts",
+    params(("id_reservation" = Uuid, Path)),
+    responses(
+        (status = 200, description = "The check-in of the booking", body = CourtCheckIn),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn check_in(
+    State(court_service): State<CourtService>,
+    Path(id_reservation): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<Json<CourtCheckIn>> {
+    let check_in = court_service
+        .check_in(&Actor::from(user_info), id_reservation)
+        .await
+        .http_err("check in")?;
+    Ok(Json(check_in))
+}
+
+#[utoipa::path(
+    get,
+    path = "/court-no-shows/mine",
+    tag = "courts",
+    responses(
+        (status = 200, description = "No-shows of the logged in member and whether they can book", body = MemberNoShows),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn list_my_no_shows(
+    State(court_service): State<CourtService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<Json<MemberNoShows>> {
+    let no_shows = court_service
+        .get_member_no_shows(user_info.user_id)
+        .await
+        .http_err("list my no-shows")?;
+    Ok(Json(no_shows))
+}
+
+#[utoipa::path(
+    get,
+    path = "/court-no-shows/{id_user}",
+    tag = "courts",
+    params(("id_user" = Uuid, Path)),
+    responses(
+        (status = 200, description = "No-shows of the member and whether they can book", body = MemberNoShows),
+        Unauthorized,
+        Forbidden,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_member_no_shows(
+    State(court_service): State<CourtService>,
+    Path(id_user): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<Json<MemberNoShows>> {
+    require_admin(&user_info, "manage the court schedules")?;
+
+    let no_shows = court_service
+        .get_member_no_shows(id_user)
+        .await
+        .http_err("get member no-shows")?;
+    Ok(Json(no_shows))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/court-reservations/{id_reservation}/no-show",
+    tag = "courts",
+    params(("id_reservation" = Uuid, Path)),
+    responses(
+        (status = 200, description = "No-show waived", body = String, content_type = "text/plain"),
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn waive_no_show(
+    State(court_service): State<CourtService>,
+    Path(id_reservation): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<impl IntoResponse> {
+    require_admin(&user_info, "manage the court schedules")?;
+
+    court_service
+        .waive_no_show(&Actor::from(user_info), id_reservation)
+        .await
+        .http_err("waive no-show")?;
+    Ok((StatusCode::OK, "No-show waived successfully"))
+}
+
+#[utoipa::path(
+    get,
+    path = "/courts/{id_court}/opening-hours",
+    tag = "courts",
+    params(("id_court" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Opening hours of the court, empty when it's always open", body = Vec<CourtOpeningHours>),
+        Unauthorized,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_opening_hours(
+    State(court_service): State<CourtService>,
+    Path(id_court): Path<Uuid>,
+) -> HttpResult<Json<Vec<CourtOpeningHours>>> {
+    let opening_hours = court_service
+        .get_opening_hours(id_court)
+        .await
+        .http_err("get opening hours")?;
+    Ok(Json(opening_hours))
+}
+
+#[utoipa::path(
+    put,
+    path = "/courts/{id_court}/opening-hours",
+    tag = "courts",
+    params(("id_court" = Uuid, Path)),
+    request_body = Vec<CourtOpeningHours>,
+    responses(
+        (status = 200, description = "The new opening hours of the court", body = Vec<CourtOpeningHours>),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn set_opening_hours(
+    State(court_service): State<CourtService>,
+    Path(id_court): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Json(opening_hours): Json<Vec<CourtOpeningHours>>,
+) -> HttpResult<Json<Vec<CourtOpeningHours>>> {
+    require_admin(&user_info, "manage the court schedules")?;
+
+    let opening_hours = court_service
+        .set_opening_hours(&Actor::from(user_info), id_court, opening_hours)
+        .await
+        .http_err("set opening hours")?;
+    Ok(Json(opening_hours))
+}
+
+#[utoipa::path(
+    get,
+    path = "/courts/{id_court}/closures",
+    tag = "courts",
+    params(("id_court" = Uuid, Path), CourtReservationsQuery),
+    responses(
+        (status = 200, description = "Closures of the court, and of every court, in the range", body = Vec<CourtClosure>),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_closures_for_court(
+    State(court_service): State<CourtService>,
+    Path(id_court): Path<Uuid>,
+    Query(params): Query<CourtReservationsQuery>,
+) -> HttpResult<Json<Vec<CourtClosure>>> {
+    let closures = court_service
+        .get_closures_for_court(
+            id_court,
+            params.start_datetime_filter,
+            params.end_datetime_filter,
         )
-            .into_response()),
-        Err(e) => Err(e),
-    }
+        .await
+        .http_err("get closures for court")?;
+    Ok(Json(closures))
+}
+
+#[utoipa::path(
+    post,
+    path = "/court-closures",
+    tag = "courts",
+    request_body = CourtClosureCreation,
+    responses(
+        (status = 201, description = "The created closure, overlapping member bookings are cancelled", body = CourtClosure),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn create_closure(
+    State(court_service): State<CourtService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Json(closure_creation): Json<CourtClosureCreation>,
+) -> HttpResult<(StatusCode, Json<CourtClosure>)> {
+    require_admin(&user_info, "manage the court schedules")?;
+
+    let closure = court_service
+        .create_closure(&Actor::from(user_info), closure_creation)
+        .await
+        .http_err("create closure")?;
+    Ok((StatusCode::CREATED, Json(closure)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/court-closures/{id_closure}",
+    tag = "courts",
+    params(("id_closure" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Closure deleted", body = String, content_type = "text/plain"),
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn delete_closure(
+    State(court_service): State<CourtService>,
+    Path(id_closure): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<impl IntoResponse> {
+    require_admin(&user_info, "manage the court schedules")?;
+
+    court_service
+        .delete_closure(&Actor::from(user_info), id_closure)
+        .await
+        .http_err("delete closure")?;
+    Ok((StatusCode::OK, "Closure deleted successfully"))
+}
+
+#[utoipa::path(
+    post,
+    path = "/court-price-rules",
+    tag = "courts",
+    request_body = CourtPriceRuleCreation,
+    responses(
+        (status = 201, description = "The created price band", body = CourtPriceRule),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn create_price_rule(
+    State(court_service): State<CourtService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Json(rule_creation): Json<CourtPriceRuleCreation>,
+) -> HttpResult<(StatusCode, Json<CourtPriceRule>)> {
+    require_admin(&user_info, "manage the court schedules")?;
+
+    let rule = court_service
+        .create_price_rule(&Actor::from(user_info), rule_creation)
+        .await
+        .http_err("create price rule")?;
+    Ok((StatusCode::CREATED, Json(rule)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/court-price-rules",
+    tag = "courts",
+    responses(
+        (status = 200, description = "Every price band, court time outside them is free", body = Vec<CourtPriceRule>),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn list_price_rules(
+    State(court_service): State<CourtService>,
+) -> HttpResult<Json<Vec<CourtPriceRule>>> {
+    let rules = court_service
+        .list_price_rules()
+        .await
+        .http_err("list price rules")?;
+    Ok(Json(rules))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/court-price-rules/{id_price_rule}",
+    tag = "courts",
+    params(("id_price_rule" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Price band deleted", body = String, content_type = "text/plain"),
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn delete_price_rule(
+    State(court_service): State<CourtService>,
+    Path(id_price_rule): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<impl IntoResponse> {
+    require_admin(&user_info, "manage the court schedules")?;
+
+    court_service
+        .delete_price_rule(&Actor::from(user_info), id_price_rule)
+        .await
+        .http_err("delete price rule")?;
+    Ok((StatusCode::OK, "Price band deleted successfully"))
+}
+
+#[utoipa::path(
+    get,
+    path = "/court-charges/mine",
+    tag = "courts",
+    responses(
+        (status = 200, description = "Charges of the logged in member, newest first", body = Vec<CourtCharge>),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn list_my_charges(
+    State(court_service): State<CourtService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<Json<Vec<CourtCharge>>> {
+    let charges = court_service
+        .list_user_charges(user_info.user_id)
+        .await
+        .http_err("list my charges")?;
+    Ok(Json(charges))
+}
+
+#[utoipa::path(
+    post,
+    path = "/court-charges/{id_charge}/pay",
+    tag = "courts",
+    params(("id_charge" = Uuid, Path)),
+    responses(
+        (status = 200, description = "The paid charge, its booking is confirmed", body = CourtCharge),
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn pay_charge(
+    State(court_service): State<CourtService>,
+    Path(id_charge): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<Json<CourtCharge>> {
+    let charge = court_service
+        .pay_charge(&Actor::from(user_info), id_charge)
+        .await
+        .http_err("pay charge")?;
+    Ok(Json(charge))
 }
 
 impl<T> HttpError<T> for Result<T, CourtServiceError> {
@@ -175,8 +815,343 @@ impl<T> HttpError<T> for Result<T, CourtServiceError> {
                 StatusCode::BAD_REQUEST,
                     "Can't delete a court, if it has already reservations, first delete the reservations"
             ),
+                CourtServiceError::BookingPurposeConflict => (
+                    StatusCode::BAD_REQUEST,
+                    "Member bookings cannot be for a training or tournament.",
+                ),
+                CourtServiceError::BookingOutsideWindow => (
+                    StatusCode::BAD_REQUEST,
+                    "Court cannot be booked in the past or that far ahead.",
+                ),
+                CourtServiceError::BookingTooLong => {
+                    (StatusCode::BAD_REQUEST, "Booking is longer than allowed.")
+                }
+                CourtServiceError::InvalidPartners(_) => {
+                    return (StatusCode::BAD_REQUEST, err.to_string()).into_response()
+                }
+                CourtServiceError::BookingQuotaExceeded => (
+                    StatusCode::CONFLICT,
+                    "Maximum number of upcoming bookings reached.",
+                ),
+                CourtServiceError::NotABooking => (
+                    StatusCode::BAD_REQUEST,
+                    "Reservation is not a member booking.",
+                ),
+                CourtServiceError::NotBookingOwner => (
+                    StatusCode::FORBIDDEN,
+                    "Only the owner of the booking can cancel it.",
+                ),
+                CourtServiceError::BookingAlreadyStarted => {
+                    (StatusCode::CONFLICT, "Booking already started.")
+                }
+                CourtServiceError::OutsideOpeningHours => (
+                    StatusCode::CONFLICT,
+                    "Court is not open at the selected time.",
+                ),
+                CourtServiceError::CourtClosed(_) => {
+                    return (StatusCode::CONFLICT, err.to_string()).into_response()
+                }
+                CourtServiceError::InvalidOpeningHours(_) => {
+                    return (StatusCode::BAD_REQUEST, err.to_string()).into_response()
+                }
+                CourtServiceError::ClosureNotFound => {
+                    (StatusCode::NOT_FOUND, "Court closure not found.")
+                }
+                CourtServiceError::InvalidSlotSearch(_) => {
+                    return (StatusCode::BAD_REQUEST, err.to_string()).into_response()
+                }
+                CourtServiceError::CourtInactive => (StatusCode::CONFLICT, "Court is not active."),
+                CourtServiceError::NoMatchingCourtAvailable => (
+                    StatusCode::CONFLICT,
+                    "No court matching the requirements is available.",
+                ),
+                CourtServiceError::InvalidCourtBlocks(_) => {
+                    return (StatusCode::BAD_REQUEST, err.to_string()).into_response()
+                }
+                CourtServiceError::ClosureConflict => (
+                    StatusCode::CONFLICT,
+                    "Court is reserved for a training or tournament during the closure.",
+                ),
+                CourtServiceError::InvalidPriceRule(_) => {
+                    return (StatusCode::BAD_REQUEST, err.to_string()).into_response()
+                }
+                CourtServiceError::PriceRuleOverlap => (
+                    StatusCode::CONFLICT,
+                    "Price band overlaps another one of the same courts and days.",
+                ),
+                CourtServiceError::PriceRuleNotFound => {
+                    (StatusCode::NOT_FOUND, "Price band not found.")
+                }
+                CourtServiceError::CourtWithoutLights => {
+                    (StatusCode::BAD_REQUEST, "Court has no lights.")
+                }
+                CourtServiceError::ChargeNotFound => (StatusCode::NOT_FOUND, "Charge not found."),
+                CourtServiceError::NotChargeOwner => (
+                    StatusCode::FORBIDDEN,
+                    "Only the member who owes the charge can pay it.",
+                ),
+                CourtServiceError::ChargeNotPending => {
+                    (StatusCode::CONFLICT, "Charge is not pending.")
+                }
+                CourtServiceError::ChargeExpired => (
+                    StatusCode::CONFLICT,
+                    "Charge is past due, the booking was released.",
+                ),
+                CourtServiceError::CancellationReasonMissing => (
+                    StatusCode::BAD_REQUEST,
+                    "A reason is needed to cancel a reservation.",
+                ),
+                CourtServiceError::CancellationNotFound => {
+                    (StatusCode::NOT_FOUND, "Cancellation not found.")
+                }
+                CourtServiceError::NotBookingPlayer => (
+                    StatusCode::FORBIDDEN,
+                    "Only the players of the booking can check in.",
+                ),
+                CourtServiceError::CheckInClosed => (
+                    StatusCode::CONFLICT,
+                    "Check-in is only open around the booking.",
+                ),
+                CourtServiceError::NoShowNotFound => (StatusCode::NOT_FOUND, "No-show not found."),
+                CourtServiceError::BookingRestricted(_) => {
+                    return (StatusCode::FORBIDDEN, err.to_string()).into_response()
+                }
+                CourtServiceError::TuitionServiceError(e) => {
+                    error!("Court tuition error: {}", e);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "Error checking the membership of the member.",
+                    )
+                }
             };
             (status, msg.to_string()).into_response()
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{path::PathBuf, sync::Arc};
+
+    use axum::{body::Body, http::Request};
+    use chrono::{Duration, NaiveDateTime, Utc};
+    use tokio::task::JoinSet;
+    use tower::ServiceExt;
+    use turso_db::{TestDbBuilder, TursoDb};
+    use use_cases::{
+        audit_service::AuditService, court_service::booking_policy::BookingPolicy,
+        event_service::EventService, tournament_service::repository_trait::TournamentRepository,
+        tuition_service::TuitionService, user_service::LogInResponse,
+    };
+
+    use super::*;
+    use crate::auth::generate_jwt;
+    use entities::{court::CourtReservationCreation, tournament::Tournament, user::URol};
+
+    const JWT_KEY: &str = "test-key";
+
+    /// Court service over a database file, each request gets its own connection like in the
+    /// server, so concurrent writes really race for the database
+    struct FileBackedCourts {
+        db: Arc<TursoDb>,
+        court_service: CourtService,
+        court: Court,
+        path: PathBuf,
+    }
+
+    impl FileBackedCourts {
+        async fn create() -> Self {
+            let path = std::env::temp_dir().join(format!("courts-{}.db", Uuid::new_v4()));
+            let db = Arc::new(
+                TestDbBuilder::create_in_file(&path)
+                    .await
+                    .apply_doc_types()
+                    .await
+                    .apply_user_roles()
+                    .await
+                    .build_with_connection_per_call(),
+            );
+            let court_service = CourtService::new(
+                db.clone(),
+                db.clone(),
+                db.clone(),
+                db.clone(),
+                db.clone(),
+                TuitionService::new(
+                    db.clone(),
+                    EventService::new(db.clone()),
+                    AuditService::new(db.clone()),
+                ),
+                EventService::new(db.clone()),
+                AuditService::new(db.clone()),
+                BookingPolicy::default(),
+            );
+            let court = court_service
+                .create_court(
+                    &Actor::system(),
+                    serde_json::from_value(serde_json::json!({ "court_name": "Central" })).unwrap(),
+                )
+                .await
+                .unwrap();
+
+            Self {
+                db,
+                court_service,
+                court,
+                path,
+            }
+        }
+
+        async fn member_booking(&self, start: NaiveDateTime, end: NaiveDateTime) -> Request<Body> {
+            let user_id = Uuid::new_v4();
+            self.db.create_test_user(user_id).await.unwrap();
+            let token = generate_jwt(
+                &LogInResponse {
+                    user_id,
+                    user_rol: URol::USER,
+                },
+                JWT_KEY,
+            )
+            .unwrap();
+            let body = serde_json::json!({
+                "id_court": self.court.id_court,
+                "start_reservation_datetime": start.format("%Y-%m-%d %H:%M:%S").to_string(),
+                "end_reservation_datetime": end.format("%Y-%m-%d %H:%M:%S").to_string(),
+            });
+
+            Request::post("/court-bookings")
+                .header("Authorization", format!("Bearer {token}"))
+                .header("Content-Type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        }
+
+        async fn active_reservations(
+            &self,
+            start: NaiveDateTime,
+            end: NaiveDateTime,
+        ) -> Vec<CourtReservation> {
+            self.court_service
+                .get_reservations_for_court(self.court.id_court, Some(start), Some(end))
+                .await
+                .unwrap()
+        }
+    }
+
+    impl Drop for FileBackedCourts {
+        fn drop(&mut self) {
+            for suffix in ["", "-wal", "-shm"] {
+                let _ = std::fs::remove_file(format!("{}{suffix}", self.path.display()));
+            }
+        }
+    }
+
+    fn slot_in_days(days: i64) -> (NaiveDateTime, NaiveDateTime) {
+        let start = (Utc::now().date_naive() + Duration::days(days))
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        (start, start + Duration::hours(1))
+    }
+
+    /// Members racing for the same slot get one booking and a conflict for everyone else
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_parallel_bookings_of_a_slot() {
+        let courts = FileBackedCourts::create().await;
+        let router = court_router(courts.court_service.clone(), JWT_KEY.to_string());
+        let (start, end) = slot_in_days(1);
+
+        let mut requests = JoinSet::new();
+        for _ in 0..20 {
+            let request = courts.member_booking(start, end).await;
+            requests.spawn(router.clone().oneshot(request));
+        }
+
+        let statuses: Vec<StatusCode> = requests
+            .join_all()
+            .await
+            .into_iter()
+            .map(|response| response.unwrap().status())
+            .collect();
+        assert_eq!(
+            statuses
+                .iter()
+                .filter(|status| **status == StatusCode::CREATED)
+                .count(),
+            1
+        );
+        assert!(statuses
+            .iter()
+            .all(|status| [StatusCode::CREATED, StatusCode::CONFLICT].contains(status)));
+        assert_eq!(courts.active_reservations(start, end).await.len(), 1);
+    }
+
+    /// Club events racing with each other and with member bookings leave a single reservation.
+    /// Events may take the slot over a booking, never over another event
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_parallel_event_reservations_and_bookings_of_a_slot() {
+        let courts = FileBackedCourts::create().await;
+        let router = court_router(courts.court_service.clone(), JWT_KEY.to_string());
+        // Far enough ahead for the events to have priority over the bookings
+        let (start, end) = slot_in_days(5);
+
+        let mut bookings = JoinSet::new();
+        for _ in 0..10 {
+            let request = courts.member_booking(start, end).await;
+            bookings.spawn(router.clone().oneshot(request));
+        }
+        let id_category = Uuid::new_v4();
+        courts.db.create_test_category(id_category).await.unwrap();
+        let mut events = JoinSet::new();
+        for _ in 0..5 {
+            let tournament = Tournament {
+                id_tournament: Uuid::new_v4(),
+                name: "Open".to_string(),
+                id_category,
+                start_datetime: start,
+                end_datetime: end,
+            };
+            courts.db.create_tournament(&tournament).await.unwrap();
+            let court_service = courts.court_service.clone();
+            let reservation = CourtReservationCreation {
+                id_court: courts.court.id_court,
+                start_reservation_datetime: start,
+                end_reservation_datetime: end,
+                id_training: None,
+                id_tournament: Some(tournament.id_tournament),
+                id_owner: None,
+            };
+            events.spawn(async move {
+                court_service
+                    .create_reservation(&Actor::system(), reservation)
+                    .await
+            });
+        }
+
+        let statuses: Vec<StatusCode> = bookings
+            .join_all()
+            .await
+            .into_iter()
+            .map(|response| response.unwrap().status())
+            .collect();
+        assert!(statuses
+            .iter()
+            .all(|status| [StatusCode::CREATED, StatusCode::CONFLICT].contains(status)));
+
+        let results = events.join_all().await;
+        assert!(results
+            .iter()
+            .all(|result| matches!(result, Ok(_) | Err(CourtServiceError::CourtUnavailable))));
+        let created_events: Vec<CourtReservation> =
+            results.into_iter().filter_map(Result::ok).collect();
+        assert!(created_events.len() <= 1);
+
+        let remaining = courts.active_reservations(start, end).await;
+        assert_eq!(remaining.len(), 1);
+        if let [event] = created_events.as_slice() {
+            assert_eq!(
+                remaining[0].id_court_reservation,
+                event.id_court_reservation
+            );
+        }
+    }
+}
@@ -2,6 +2,7 @@ use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
 };
+use utoipa::IntoResponses;
 
 pub type HttpResult<T> = std::result::Result<T, Response>;
 
@@ -24,3 +25,37 @@ impl ToErrResponse for String {
         (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()).into_response()
     }
 }
+
+/// Error responses of the OpenAPI document, the body is a plain text message
+#[derive(IntoResponses)]
+#[response(
+    status = 400,
+    description = "The request is invalid, the body has the reason"
+)]
+pub struct BadRequest;
+
+#[derive(IntoResponses)]
+#[response(status = 401, description = "Missing, invalid or expired token")]
+pub struct Unauthorized;
+
+#[derive(IntoResponses)]
+#[response(
+    status = 403,
+    description = "The user is not allowed to perform the operation"
+)]
+pub struct Forbidden;
+
+#[derive(IntoResponses)]
+#[response(status = 404, description = "The resource doesn't exist")]
+pub struct NotFound;
+
+#[derive(IntoResponses)]
+#[response(
+    status = 409,
+    description = "The operation conflicts with the current state"
+)]
+pub struct Conflict;
+
+#[derive(IntoResponses)]
+#[response(status = 500, description = "Unexpected error in the server")]
+pub struct InternalError;
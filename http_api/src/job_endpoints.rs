@@ -0,0 +1,142 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::get,
+    Extension, Json, Router,
+};
+use entities::{
+    audit::Actor,
+    job::{JobRun, JobRunFilter, ScheduledJob},
+    pagination::{Page, PageQuery},
+};
+use tracing::error;
+use use_cases::job_service::{err::Error, JobService};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::{
+    auth::{auth_middleware, require_admin, UserInfoAuth},
+    err::{Conflict, Forbidden, HttpError, HttpResult, InternalError, NotFound, Unauthorized},
+};
+
+pub fn job_router(job_service: JobService, jwt_key: String) -> Router {
+    let (router, _) = job_routes().split_for_parts();
+
+    router
+        .route("/health-job", get(alive))
+        .layer(middleware::from_fn_with_state(jwt_key, auth_middleware))
+        .with_state(job_service)
+}
+
+pub fn job_routes() -> OpenApiRouter<JobService> {
+    OpenApiRouter::new()
+        .routes(routes!(list_jobs))
+        .routes(routes!(trigger_job))
+        .routes(routes!(list_job_runs))
+}
+
+async fn alive() -> &'static str {
+    "Job service is alive"
+}
+
+#[utoipa::path(
+    get,
+    path = "/jobs",
+    tag = "jobs",
+    responses(
+        (status = 200, description = "Every registered job", body = Vec<ScheduledJob>),
+        Unauthorized,
+        Forbidden,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn list_jobs(
+    State(job_service): State<JobService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<Json<Vec<ScheduledJob>>> {
+    require_admin(&user_info, "manage jobs")?;
+
+    let jobs = job_service.list_jobs().await.http_err("list jobs")?;
+
+    Ok(Json(jobs))
+}
+
+/// Runs the job right away and returns the finished run, a failed job is reported in the run
+#[utoipa::path(
+    post,
+    path = "/jobs/{name}/run",
+    tag = "jobs",
+    params(("name" = String, Path)),
+    responses(
+        (status = 200, description = "The finished run", body = JobRun),
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn trigger_job(
+    State(job_service): State<JobService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Path(name): Path<String>,
+) -> HttpResult<Json<JobRun>> {
+    require_admin(&user_info, "manage jobs")?;
+
+    let run = job_service
+        .trigger_job(&Actor::from(user_info), &name)
+        .await
+        .http_err("trigger job")?;
+
+    Ok(Json(run))
+}
+
+#[utoipa::path(
+    get,
+    path = "/job-runs",
+    tag = "jobs",
+    params(PageQuery, JobRunFilter),
+    responses(
+        (status = 200, description = "Page of runs", body = Page<JobRun>),
+        Unauthorized,
+        Forbidden,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn list_job_runs(
+    State(job_service): State<JobService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Query(page): Query<PageQuery>,
+    Query(filter): Query<JobRunFilter>,
+) -> HttpResult<Json<Page<JobRun>>> {
+    require_admin(&user_info, "manage jobs")?;
+
+    let runs = job_service
+        .list_job_runs_page(&filter, &page)
+        .await
+        .http_err("list job runs")?;
+
+    Ok(Json(runs))
+}
+
+impl<T> HttpError<T> for Result<T, Error> {
+    fn http_err(self, endpoint_name: &str) -> crate::err::HttpResult<T> {
+        self.map_err(|err| {
+            error!("Error in job endpoint ({}): {}", endpoint_name, err);
+            let (status_code, message) = match &err {
+                Error::JobNotFound => (StatusCode::NOT_FOUND, err.to_string()),
+                Error::JobAlreadyRunning => (StatusCode::CONFLICT, err.to_string()),
+                Error::JobFailed(_) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+                Error::InvalidSchedule(_) | Error::UnknownDatabaseError(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Error processing the job request.".to_string(),
+                ),
+            };
+            (status_code, message).into_response()
+        })
+    }
+}
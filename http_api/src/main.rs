@@ -1,40 +1,87 @@
 use std::{
     net::{Ipv4Addr, SocketAddrV4},
     sync::Arc,
+    time::Duration,
 };
 
-use axum::Router;
+use audit_endpoints::audit_router;
+use axum::{routing::get, Json, Router};
+use calendar_endpoints::calendar_router;
+use entities::notification::NotificationChannel;
+use http_notifier::{HttpEmailNotifier, TwilioSmsNotifier};
+use job_endpoints::job_router;
+use local_notifier::LogNotifier;
+use notification_endpoints::notification_router;
 // Import new endpoint modules if you create them (e.g., court_endpoints)
+use privacy_endpoints::privacy_router;
 use report_endpoints::report_router;
 use request_endpoints::request_router;
+use schedule_endpoints::schedule_router;
 use serde::Deserialize;
 use tower_http::cors::CorsLayer;
 use tower_http::trace::TraceLayer;
-use tracing::{error, info};
+use tracing::{error, info, warn};
+use trainer_endpoints::trainer_router;
 use training_endpoints::training_router;
 use tuition_endpoints::tuition_router;
 use turso_db::TursoDb;
 use use_cases::{
-    category_service::CategoryService,
-    court_service::CourtService, // New
+    audit_service::AuditService,
+    calendar_service::CalendarService,
+    category_service::{jobs::CategoryAgeRolloverJob, CategoryService},
+    court_service::{
+        booking_policy::BookingPolicy,
+        jobs::{CourtNoShowJob, CourtPaymentExpiryJob},
+        CourtService,
+    }, // New
+    event_service::{subscriber_trait::EventSubscriber, EventService},
+    job_service::{job_trait::Job, JobService},
+    notification_service::{notifier_trait::Notifier, NotificationService},
+    privacy_service::PrivacyService,
     report_service::ReportService,
-    request_service::RequestService,
+    request_service::{
+        approval_policy::ApprovalPolicy, executor::RequestExecutor, jobs::RequestExpiryJob,
+        RequestService,
+    },
+    schedule_service::ScheduleService,
     tournament_service::TournamentService,
-    training_service::TrainingService,
-    tuition_service::TuitionService,
+    trainer_service::TrainerService,
+    training_service::{
+        jobs::{TrainingAttendanceCloseJob, TrainingReminderJob, TrainingWaitlistExpiryJob},
+        TrainingService, DEFAULT_ATTENDANCE_GRACE_HOURS,
+    },
+    tuition_service::{jobs::TuitionExpiryJob, TuitionService},
     user_service::UserService,
+    webhook_service::WebhookService,
 };
+use utoipa_redoc::{Redoc, Servable};
+use webhook_endpoints::webhook_router;
+use webhook_sender::HttpWebhookSender;
 
+mod audit_endpoints;
 mod auth;
+mod calendar_endpoints;
 mod category_endpoints;
 mod court_endpoints;
 mod err;
+mod job_endpoints;
+mod notification_endpoints;
+mod openapi;
+mod privacy_endpoints;
 mod report_endpoints;
 mod request_endpoints;
+mod schedule_endpoints;
 mod tournament_endpoints;
+mod trainer_endpoints;
 mod training_endpoints;
 mod tuition_endpoints;
 mod user_endpoints;
+mod webhook_endpoints;
+
+const EVENT_DISPATCH_SECONDS: u64 = 5;
+const EVENT_DISPATCH_BATCH: u32 = 100;
+/// Cron schedules have a one minute resolution
+const JOB_POLL_SECONDS: u64 = 60;
 
 #[derive(Debug, Deserialize)]
 struct Config {
@@ -42,6 +89,18 @@ struct Config {
     db_token: String,
     port: u16,
     token_key: String,
+    /// IANA name of the club timezone shown by the calendar feeds, UTC when empty
+    club_timezone: Option<String>,
+    /// Hours after a training ends its attendance can still be corrected
+    attendance_grace_hours: Option<i64>,
+    /// Transactional email API, emails are only logged unless the url, key and sender are set
+    email_api_url: Option<String>,
+    email_api_key: Option<String>,
+    email_from: Option<String>,
+    /// Twilio account sending the SMS, they are only logged unless the three values are set
+    twilio_account_sid: Option<String>,
+    twilio_auth_token: Option<String>,
+    sms_from: Option<String>,
 }
 
 #[tokio::main]
@@ -66,27 +125,103 @@ async fn main() {
     // initial_conn.execute_batch(&turso_db::migration::get_migration_sql()).await.expect("Migration failed");
     // info!("Database migrations applied successfully.");
 
+    let audit_service = AuditService::new(turso_db_arc.clone());
+    let event_service = EventService::new(turso_db_arc.clone());
+
     let password_hasher = Arc::new(bcrypt_hasher::BcryptHasher);
-    let user_service = UserService::new(turso_db_arc.clone(), password_hasher);
+    let user_service = UserService::new(
+        turso_db_arc.clone(),
+        password_hasher,
+        event_service.clone(),
+        audit_service.clone(),
+    );
+
+    // Channels without a configured provider record their messages as skipped
+    let email_notifier: Arc<dyn Notifier> =
+        match (&config.email_api_url, &config.email_api_key, &config.email_from) {
+            (Some(api_url), Some(api_key), Some(from)) => {
+                Arc::new(HttpEmailNotifier::new(api_url, api_key, from))
+            }
+            _ => {
+                warn!("No email provider configured, emails won't be sent");
+                Arc::new(LogNotifier::new(NotificationChannel::EMAIL))
+            }
+        };
+    let sms_notifier: Arc<dyn Notifier> = match (
+        &config.twilio_account_sid,
+        &config.twilio_auth_token,
+        &config.sms_from,
+    ) {
+        (Some(account_sid), Some(auth_token), Some(from)) => {
+            Arc::new(TwilioSmsNotifier::new(account_sid, auth_token, from))
+        }
+        _ => {
+            warn!("No SMS provider configured, SMS won't be sent");
+            Arc::new(LogNotifier::new(NotificationChannel::SMS))
+        }
+    };
+    let notifiers: Vec<Arc<dyn Notifier>> = vec![email_notifier, sms_notifier];
+    let notification_service = NotificationService::new(
+        turso_db_arc.clone(),
+        notifiers,
+        user_service.clone(),
+        audit_service.clone(),
+    );
 
     let category_service = CategoryService::new(
         turso_db_arc.clone(),
         turso_db_arc.clone(),
         turso_db_arc.clone(),
         user_service.clone(), // Pass Arc<UserService>
+        audit_service.clone(),
+    );
+
+    let tuition_service_arc = TuitionService::new(
+        turso_db_arc.clone(),
+        event_service.clone(),
+        audit_service.clone(),
     );
 
-    let court_service_arc = CourtService::new(turso_db_arc.clone(), turso_db_arc.clone()); // New
+    let court_service_arc = CourtService::new(
+        turso_db_arc.clone(),
+        turso_db_arc.clone(),
+        turso_db_arc.clone(),
+        turso_db_arc.clone(),
+        turso_db_arc.clone(),
+        tuition_service_arc.clone(),
+        event_service.clone(),
+        audit_service.clone(),
+        BookingPolicy::default(),
+    ); // New
+
+    let schedule_service = ScheduleService::new(turso_db_arc.clone());
 
-    let tuition_service_arc = TuitionService::new(turso_db_arc.clone()); // New
+    let trainer_service = TrainerService::new(
+        turso_db_arc.clone(),
+        user_service.clone(),
+        schedule_service.clone(),
+        audit_service.clone(),
+    );
 
+    let check_in_signer = Arc::new(hmac_signer::HmacSigner::new(&config.token_key));
     let training_service = TrainingService::new(
         turso_db_arc.clone(),
         turso_db_arc.clone(),
+        turso_db_arc.clone(),
+        check_in_signer,
+        chrono::Duration::hours(
+            config
+                .attendance_grace_hours
+                .unwrap_or(DEFAULT_ATTENDANCE_GRACE_HOURS),
+        ),
         category_service.clone(),
         court_service_arc.clone(),   // Pass Arc<CourtService>
         user_service.clone(),        // Pass Arc<UserService>
         tuition_service_arc.clone(), // Pass Arc<TuitionService>
+        schedule_service.clone(),
+        trainer_service.clone(),
+        event_service.clone(),
+        audit_service.clone(),
     );
 
     let tournament_service = TournamentService::new(
@@ -95,9 +230,90 @@ async fn main() {
         turso_db_arc.clone(),
         category_service.clone(),
         court_service_arc.clone(), // Pass Arc<CourtService>
+        schedule_service.clone(),
+        event_service.clone(),
+        audit_service.clone(),
     );
 
-    let request_service = RequestService::new(turso_db_arc.clone());
+    // Approved requests are executed by the service owning their kind
+    let request_executors: Vec<Arc<dyn RequestExecutor>> = vec![
+        Arc::new(user_service.clone()),
+        Arc::new(category_service.clone()),
+        Arc::new(tuition_service_arc.clone()),
+        Arc::new(training_service.clone()),
+        Arc::new(tournament_service.clone()),
+        Arc::new(court_service_arc.clone()),
+    ];
+    let request_service = RequestService::new(
+        turso_db_arc.clone(),
+        event_service.clone(),
+        audit_service.clone(),
+        request_executors,
+        ApprovalPolicy::default(),
+    );
+
+    let webhook_service = WebhookService::new(
+        turso_db_arc.clone(),
+        Arc::new(HttpWebhookSender::new()),
+        audit_service.clone(),
+    );
+
+    let event_subscribers: Vec<Arc<dyn EventSubscriber>> = vec![
+        Arc::new(court_service_arc.clone()),
+        Arc::new(notification_service.clone()),
+        Arc::new(webhook_service.clone()),
+    ];
+    for subscriber in event_subscribers {
+        event_service.subscribe(subscriber);
+    }
+
+    // Delivers the outbox to the subscribers and retries webhook deliveries in the background
+    let dispatcher = event_service.clone();
+    let webhook_retrier = webhook_service.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(EVENT_DISPATCH_SECONDS));
+        loop {
+            interval.tick().await;
+            if let Err(err) = dispatcher.dispatch_pending(EVENT_DISPATCH_BATCH).await {
+                error!("Error dispatching outbox events: {err}");
+            }
+            if let Err(err) = webhook_retrier
+                .retry_due_deliveries(EVENT_DISPATCH_BATCH)
+                .await
+            {
+                error!("Error retrying webhook deliveries: {err}");
+            }
+        }
+    });
+
+    let jobs: Vec<Arc<dyn Job>> = vec![
+        Arc::new(TuitionExpiryJob(tuition_service_arc.clone())),
+        Arc::new(TrainingAttendanceCloseJob(training_service.clone())),
+        Arc::new(TrainingReminderJob(training_service.clone())),
+        Arc::new(TrainingWaitlistExpiryJob(training_service.clone())),
+        Arc::new(RequestExpiryJob(request_service.clone())),
+        Arc::new(CategoryAgeRolloverJob(category_service.clone())),
+        Arc::new(CourtPaymentExpiryJob(court_service_arc.clone())),
+        Arc::new(CourtNoShowJob(court_service_arc.clone())),
+    ];
+    let job_service = JobService::new(turso_db_arc.clone(), jobs, audit_service.clone());
+    job_service
+        .sync_jobs()
+        .await
+        .inspect_err(|err| error!("Error registering the jobs: {err}"))
+        .expect("Error registering the jobs");
+
+    // Runs the periodic jobs, the persisted lock keeps other instances from running them twice
+    let scheduler = job_service.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(JOB_POLL_SECONDS));
+        loop {
+            interval.tick().await;
+            if let Err(err) = scheduler.run_due_jobs().await {
+                error!("Error running the scheduled jobs: {err}");
+            }
+        }
+    });
 
     let report_service = ReportService::new(
         user_service.clone(),
@@ -108,26 +324,79 @@ async fn main() {
         request_service.clone(),
     );
 
+    let privacy_service = PrivacyService::new(
+        user_service.clone(),
+        category_service.clone(),
+        training_service.clone(),
+        tournament_service.clone(),
+        tuition_service_arc.clone(),
+        request_service.clone(),
+        notification_service.clone(),
+    );
+
+    let club_timezone = config
+        .club_timezone
+        .as_deref()
+        .map(|name| name.parse().expect("CLUB_TIMEZONE isn't a valid IANA timezone"))
+        .unwrap_or(chrono_tz::UTC);
+    let calendar_service = CalendarService::new(
+        turso_db_arc.clone(),
+        court_service_arc.clone(),
+        user_service.clone(),
+        audit_service.clone(),
+        club_timezone,
+    );
+
     let mut main_router = Router::new()
         .merge(user_endpoints::user_router(
             Arc::new(user_service),
             &config.token_key,
         ))
-        .merge(category_endpoints::category_router(category_service))
+        .merge(category_endpoints::category_router(
+            category_service,
+            config.token_key.clone(),
+        ))
         .merge(court_endpoints::court_router(
             court_service_arc.clone(),
             config.token_key.clone(),
         )) // New
-        .merge(training_router(training_service.clone())) // Pass cloned services
+        .merge(training_router(
+            training_service.clone(),
+            config.token_key.clone(),
+        )) // Pass cloned services
         .merge(tournament_endpoints::tournament_router(
             tournament_service.clone(),
+            config.token_key.clone(),
         ))
         .merge(request_router(request_service, config.token_key.clone()))
         .merge(tuition_router(
             tuition_service_arc.clone(),
             config.token_key.clone(),
         ))
-        .merge(report_router(report_service));
+        .merge(report_router(report_service))
+        .merge(privacy_router(privacy_service, config.token_key.clone()))
+        .merge(webhook_router(webhook_service, config.token_key.clone()))
+        .merge(calendar_router(calendar_service, config.token_key.clone()))
+        .merge(schedule_router(schedule_service, config.token_key.clone()))
+        .merge(trainer_router(trainer_service, config.token_key.clone()))
+        .merge(job_router(job_service, config.token_key.clone()))
+        .merge(notification_router(
+            notification_service,
+            config.token_key.clone(),
+        ))
+        .merge(audit_router(audit_service, config.token_key.clone()));
+
+    // The API description and its docs page are public, the documented routes keep their auth
+    let api_doc = openapi::api_doc();
+    main_router = main_router
+        .merge(Redoc::with_url("/docs", api_doc.clone()))
+        .route(
+            "/openapi.json",
+            get(move || {
+                let api_doc = api_doc.clone();
+                async move { Json(api_doc) }
+            }),
+        );
 
     let cors_layer = CorsLayer::permissive();
     main_router = main_router
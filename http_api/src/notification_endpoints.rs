@@ -0,0 +1,165 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::get,
+    Extension, Json, Router,
+};
+use entities::{
+    audit::Actor,
+    notification::{Notification, NotificationPreferences, NotificationPreferencesUpdate},
+    pagination::{Page, PageQuery},
+};
+use tracing::error;
+use use_cases::notification_service::{err::Error, NotificationService};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+use crate::{
+    auth::{auth_middleware, UserInfoAuth},
+    err::{HttpError, HttpResult, InternalError, NotFound, Unauthorized},
+};
+
+pub fn notification_router(notification_service: NotificationService, jwt_key: String) -> Router {
+    let (router, _) = notification_routes().split_for_parts();
+
+    router
+        .route("/health-notification", get(alive))
+        .layer(middleware::from_fn_with_state(jwt_key, auth_middleware))
+        .with_state(notification_service)
+}
+
+pub fn notification_routes() -> OpenApiRouter<NotificationService> {
+    OpenApiRouter::new()
+        .routes(routes!(get_preferences, update_preferences))
+        .routes(routes!(list_inbox))
+        .routes(routes!(mark_as_read))
+}
+
+async fn alive() -> &'static str {
+    "Notification service is alive"
+}
+
+#[utoipa::path(
+    get,
+    path = "/notifications/preferences",
+    tag = "notifications",
+    responses(
+        (status = 200, description = "Preferences of the user", body = NotificationPreferences),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_preferences(
+    State(notification_service): State<NotificationService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<Json<NotificationPreferences>> {
+    let preferences = notification_service
+        .get_preferences(user_info.user_id)
+        .await
+        .http_err("get notification preferences")?;
+
+    Ok(Json(preferences))
+}
+
+#[utoipa::path(
+    put,
+    path = "/notifications/preferences",
+    tag = "notifications",
+    request_body = NotificationPreferencesUpdate,
+    responses(
+        (status = 200, description = "The updated preferences", body = NotificationPreferences),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn update_preferences(
+    State(notification_service): State<NotificationService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Json(update): Json<NotificationPreferencesUpdate>,
+) -> HttpResult<Json<NotificationPreferences>> {
+    let user_id = user_info.user_id;
+    let preferences = notification_service
+        .update_preferences(&Actor::from(user_info), user_id, update)
+        .await
+        .http_err("update notification preferences")?;
+
+    Ok(Json(preferences))
+}
+
+#[utoipa::path(
+    get,
+    path = "/notifications/inbox",
+    tag = "notifications",
+    params(PageQuery),
+    responses(
+        (status = 200, description = "Page of in-app notifications", body = Page<Notification>),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn list_inbox(
+    State(notification_service): State<NotificationService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Query(page): Query<PageQuery>,
+) -> HttpResult<Json<Page<Notification>>> {
+    let inbox = notification_service
+        .list_inbox_page(user_info.user_id, &page)
+        .await
+        .http_err("list notification inbox")?;
+
+    Ok(Json(inbox))
+}
+
+#[utoipa::path(
+    post,
+    path = "/notifications/{id}/read",
+    tag = "notifications",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 204, description = "Notification marked as read"),
+        Unauthorized,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn mark_as_read(
+    State(notification_service): State<NotificationService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Path(id): Path<Uuid>,
+) -> HttpResult<StatusCode> {
+    notification_service
+        .mark_as_read(user_info.user_id, id)
+        .await
+        .http_err("mark notification as read")?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+impl<T> HttpError<T> for Result<T, Error> {
+    fn http_err(self, endpoint_name: &str) -> crate::err::HttpResult<T> {
+        self.map_err(|err| {
+            error!(
+                "Error in notification endpoint ({}): {}",
+                endpoint_name, err
+            );
+            let (status_code, message) = match err {
+                Error::NotificationNotFound => (StatusCode::NOT_FOUND, "Notification not found."),
+                Error::UnknownDatabaseError(_)
+                | Error::UserServiceError(_)
+                | Error::ChannelUnavailable(_)
+                | Error::DeliveryFailed(_)
+                | Error::ProviderNotConfigured(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Error processing the notification request.",
+                ),
+            };
+            (status_code, message.to_string()).into_response()
+        })
+    }
+}
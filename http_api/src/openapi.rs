@@ -0,0 +1,98 @@
+use utoipa::{
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi,
+};
+
+use crate::{
+    audit_endpoints::audit_routes, calendar_endpoints::calendar_routes,
+    category_endpoints::category_routes, court_endpoints::court_routes, job_endpoints::job_routes,
+    notification_endpoints::notification_routes, privacy_endpoints::privacy_routes,
+    report_endpoints::report_routes, request_endpoints::request_routes,
+    schedule_endpoints::schedule_routes, tournament_endpoints::tournament_routes,
+    trainer_endpoints::trainer_routes, training_endpoints::training_routes,
+    tuition_endpoints::tuition_routes, user_endpoints::user_routes,
+    webhook_endpoints::webhook_routes,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Club API",
+        description = "Dates are sent as \"YYYY-MM-DD\" and date times as \"YYYY-MM-DD HH:MM:SS\" \
+        without timezone. Errors are answered with a plain text message"
+    ),
+    modifiers(&BearerAuth)
+)]
+struct ApiDoc;
+
+/// JWT returned by `/users/login`, sent as `Authorization: Bearer <token>`
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+    }
+}
+
+/// OpenAPI document of every router, built from the same route definitions the server uses
+pub fn api_doc() -> utoipa::openapi::OpenApi {
+    let mut api_doc = ApiDoc::openapi();
+    for routes_doc in [
+        user_routes().into_openapi(),
+        category_routes().into_openapi(),
+        court_routes().into_openapi(),
+        training_routes().into_openapi(),
+        trainer_routes().into_openapi(),
+        tournament_routes().into_openapi(),
+        request_routes().into_openapi(),
+        tuition_routes().into_openapi(),
+        report_routes().into_openapi(),
+        privacy_routes().into_openapi(),
+        webhook_routes().into_openapi(),
+        calendar_routes().into_openapi(),
+        schedule_routes().into_openapi(),
+        job_routes().into_openapi(),
+        notification_routes().into_openapi(),
+        audit_routes().into_openapi(),
+    ] {
+        api_doc.merge(routes_doc);
+    }
+
+    api_doc
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf};
+
+    use super::*;
+
+    /// Fails when the API changes without updating `openapi.json`, which the clients are
+    /// generated from. Run with `UPDATE_OPENAPI=1` to accept the changes
+    #[test]
+    fn test_openapi_document_is_up_to_date() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("openapi.json");
+        let generated = api_doc().to_pretty_json().unwrap() + "\n";
+
+        if env::var_os("UPDATE_OPENAPI").is_some() {
+            fs::write(&path, &generated).unwrap();
+        }
+        let committed = fs::read_to_string(&path).unwrap_or_else(|_| {
+            panic!("openapi.json is missing, run the tests with UPDATE_OPENAPI=1 and commit it")
+        });
+
+        assert!(
+            committed == generated,
+            "openapi.json is out of date, run the tests with UPDATE_OPENAPI=1 and commit it"
+        );
+    }
+}
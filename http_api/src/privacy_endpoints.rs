@@ -0,0 +1,163 @@
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    middleware,
+    response::IntoResponse,
+    routing::get,
+    Extension, Json, Router,
+};
+use entities::{
+    audit::Actor,
+    privacy::{PrivacyRequestCreation, UserDataExport},
+    request::Request,
+};
+use tracing::error;
+use use_cases::privacy_service::{err::Error, PrivacyService};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+use crate::{
+    auth::{auth_middleware, UserInfoAuth},
+    err::{BadRequest, Forbidden, HttpError, HttpResult, InternalError, NotFound, Unauthorized},
+};
+
+pub fn privacy_router(privacy_service: PrivacyService, jwt_key: String) -> Router {
+    let (router, _) = privacy_routes().split_for_parts();
+
+    router
+        .route("/health-privacy", get(alive))
+        .route_layer(middleware::from_fn_with_state(jwt_key, auth_middleware))
+        .with_state(privacy_service)
+}
+
+pub fn privacy_routes() -> OpenApiRouter<PrivacyService> {
+    OpenApiRouter::new()
+        .routes(routes!(request_data_export))
+        .routes(routes!(download_user_data))
+        .routes(routes!(request_account_erasure))
+}
+
+async fn alive() -> &'static str {
+    "Privacy service is alive"
+}
+
+#[utoipa::path(
+    post,
+    path = "/privacy/export-requests",
+    tag = "privacy",
+    request_body = PrivacyRequestCreation,
+    responses(
+        (status = 201, description = "The created request", body = Request),
+        Unauthorized,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn request_data_export(
+    State(privacy_service): State<PrivacyService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Json(payload): Json<PrivacyRequestCreation>,
+) -> HttpResult<(StatusCode, Json<Request>)> {
+    let request = privacy_service
+        .request_data_export(&Actor::from(user_info), payload.justification)
+        .await
+        .http_err("request data export")?;
+
+    Ok((StatusCode::CREATED, Json(request)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/privacy/export-requests/{request_id}/download",
+    tag = "privacy",
+    params(("request_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Attachment with the data of the user", body = UserDataExport),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn download_user_data(
+    State(privacy_service): State<PrivacyService>,
+    Path(request_id): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<impl IntoResponse> {
+    let export: UserDataExport = privacy_service
+        .export_user_data(request_id, user_info.user_id)
+        .await
+        .http_err("download user data")?;
+
+    let content_disposition = format!(
+        "attachment; filename=\"user-data-{}.json\"",
+        export.profile.id_user
+    );
+
+    Ok((
+        [(header::CONTENT_DISPOSITION, content_disposition)],
+        Json(export),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/privacy/erasure-requests",
+    tag = "privacy",
+    request_body = PrivacyRequestCreation,
+    responses(
+        (status = 201, description = "The created request", body = Request),
+        Unauthorized,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn request_account_erasure(
+    State(privacy_service): State<PrivacyService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Json(payload): Json<PrivacyRequestCreation>,
+) -> HttpResult<(StatusCode, Json<Request>)> {
+    let request = privacy_service
+        .request_account_erasure(&Actor::from(user_info), payload.justification)
+        .await
+        .http_err("request account erasure")?;
+
+    Ok((StatusCode::CREATED, Json(request)))
+}
+
+impl<T> HttpError<T> for Result<T, Error> {
+    fn http_err(self, endpoint_name: &str) -> crate::err::HttpResult<T> {
+        self.map_err(|err| {
+            error!("Error in privacy endpoint ({}): {}", endpoint_name, err);
+            let (status_code, message) = match err {
+                Error::UserServiceError(use_cases::user_service::err::Error::UserIdDontExist) => {
+                    (StatusCode::NOT_FOUND, "User not found.")
+                }
+                Error::RequestServiceError(
+                    use_cases::request_service::err::Error::RequestNotFound,
+                ) => (StatusCode::NOT_FOUND, "Request not found."),
+                Error::InvalidRequestCommand(_) => (
+                    StatusCode::BAD_REQUEST,
+                    "The request does not match this privacy operation.",
+                ),
+                Error::RequestNotApproved => (
+                    StatusCode::FORBIDDEN,
+                    "The request has not been approved yet.",
+                ),
+                Error::NotRequestOwner => (
+                    StatusCode::FORBIDDEN,
+                    "Only the requester can access this export.",
+                ),
+                _ => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Error processing the privacy request.",
+                ),
+            };
+            (status_code, message.to_string()).into_response()
+        })
+    }
+}
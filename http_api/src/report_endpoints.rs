@@ -1,20 +1,36 @@
 use axum::{
     extract::{Path, State},
     http::StatusCode,
-    routing::get,
     Json, Router,
 };
 use entities::report::Report;
 use uuid::Uuid;
 
 use use_cases::report_service::ReportService;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::err::InternalError;
 
 pub fn report_router(report_service: ReportService) -> Router {
-    Router::new()
-        .route("/reports/user/{user_id}", get(get_user_report))
-        .with_state(report_service)
+    let (router, _) = report_routes().split_for_parts();
+
+    router.with_state(report_service)
+}
+
+pub fn report_routes() -> OpenApiRouter<ReportService> {
+    OpenApiRouter::new().routes(routes!(get_user_report))
 }
 
+#[utoipa::path(
+    get,
+    path = "/reports/user/{user_id}",
+    tag = "reports",
+    params(("user_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Report of the user", body = Report),
+        InternalError
+    )
+)]
 async fn get_user_report(
     State(report_service): State<ReportService>,
     Path(user_id): Path<Uuid>,
@@ -1,44 +1,75 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     middleware,
     response::IntoResponse,
-    routing::{get, post},
+    routing::get,
     Extension, Json, Router,
 };
-use entities::request::{Request, RequestCreation};
+use entities::{
+    audit::Actor,
+    pagination::{Page, PageQuery},
+    request::{
+        Request, RequestComment, RequestCommentCreation, RequestCreation, RequestFilter,
+        RequestStep,
+    },
+};
 use tracing::error;
 use use_cases::request_service::{err::Error, RequestService};
+use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
 
-use super::err::{HttpError, ToErrResponse};
+use super::err::HttpError;
 use crate::{
     auth::{auth_middleware, UserInfoAuth},
-    err::HttpResult,
+    err::{BadRequest, Conflict, Forbidden, HttpResult, InternalError, NotFound, Unauthorized},
 };
 
 pub fn request_router(request_service: RequestService, jwt_key: String) -> Router {
-    Router::new()
+    let (router, _) = request_routes().split_for_parts();
+
+    router
         .route("/health-request", get(alive))
-        .route("/requests", post(create_request).get(list_requests))
-        .route("/requests/{id}", get(get_request))
-        .route("/requests/{id}/complete/{approved}", post(complete_request))
-        .route("/requests/user/{user_id}", get(list_user_requests))
         .layer(middleware::from_fn_with_state(jwt_key, auth_middleware))
         .with_state(request_service)
 }
 
+pub fn request_routes() -> OpenApiRouter<RequestService> {
+    OpenApiRouter::new()
+        .routes(routes!(create_request, list_requests))
+        .routes(routes!(get_request))
+        .routes(routes!(complete_request))
+        .routes(routes!(withdraw_request))
+        .routes(routes!(list_request_steps))
+        .routes(routes!(add_request_comment, list_request_comments))
+        .routes(routes!(list_user_requests))
+}
+
 async fn alive() -> &'static str {
     "Request service is alive"
 }
 
+#[utoipa::path(
+    post,
+    path = "/requests",
+    tag = "requests",
+    request_body = RequestCreation,
+    responses(
+        (status = 201, description = "Request created", body = String, content_type = "text/plain"),
+        BadRequest,
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn create_request(
     State(request_service): State<RequestService>,
+    Extension(user_info): Extension<UserInfoAuth>,
     Json(request): Json<RequestCreation>,
 ) -> HttpResult<impl IntoResponse> {
     request_service
         .create_request(
-            request.requester_id,
+            &Actor::from(user_info),
             request.requested_command,
             request.justification,
         )
@@ -48,6 +79,19 @@ async fn create_request(
     Ok((StatusCode::CREATED, "Request created successfully"))
 }
 
+#[utoipa::path(
+    get,
+    path = "/requests/{id}",
+    tag = "requests",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "The request", body = Request),
+        Unauthorized,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn get_request(
     State(request_service): State<RequestService>,
     Path(id): Path<Uuid>,
@@ -62,30 +106,181 @@ async fn get_request(
     Ok(Json(request))
 }
 
+/// Returns the completed request, an approved request shows whether it could be executed
+#[utoipa::path(
+    post,
+    path = "/requests/{id}/complete/{approved}",
+    tag = "requests",
+    params(("id" = Uuid, Path), ("approved" = bool, Path)),
+    responses(
+        (status = 200, description = "The request after the decision", body = Request),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn complete_request(
     State(request_service): State<RequestService>,
     Path((id, approved)): Path<(Uuid, bool)>,
     Extension(user_info): Extension<UserInfoAuth>,
-) -> HttpResult<impl IntoResponse> {
-    request_service
-        .complete_request(id, user_info.user_id, approved)
+) -> HttpResult<Json<Request>> {
+    let request = request_service
+        .complete_request(&Actor::from(user_info), id, approved)
         .await
         .http_err("complete request")?;
 
-    Ok((StatusCode::OK, "Request completed successfully"))
+    Ok(Json(request))
 }
 
+#[utoipa::path(
+    post,
+    path = "/requests/{id}/withdraw",
+    tag = "requests",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "The withdrawn request", body = Request),
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn withdraw_request(
+    State(request_service): State<RequestService>,
+    Path(id): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<Json<Request>> {
+    let request = request_service
+        .withdraw_request(&Actor::from(user_info), id)
+        .await
+        .http_err("withdraw request")?;
+
+    Ok(Json(request))
+}
+
+#[utoipa::path(
+    get,
+    path = "/requests/{id}/steps",
+    tag = "requests",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Approval steps of the request", body = Vec<RequestStep>),
+        Unauthorized,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn list_request_steps(
+    State(request_service): State<RequestService>,
+    Path(id): Path<Uuid>,
+) -> HttpResult<Json<Vec<RequestStep>>> {
+    let steps = request_service
+        .list_steps(id)
+        .await
+        .http_err("list request steps")?;
+
+    Ok(Json(steps))
+}
+
+#[utoipa::path(
+    post,
+    path = "/requests/{id}/comments",
+    tag = "requests",
+    params(("id" = Uuid, Path)),
+    request_body = RequestCommentCreation,
+    responses(
+        (status = 201, description = "The added comment", body = RequestComment),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn add_request_comment(
+    State(request_service): State<RequestService>,
+    Path(id): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Json(comment): Json<RequestCommentCreation>,
+) -> HttpResult<(StatusCode, Json<RequestComment>)> {
+    let comment = request_service
+        .add_comment(&Actor::from(user_info), id, comment.body)
+        .await
+        .http_err("add request comment")?;
+
+    Ok((StatusCode::CREATED, Json(comment)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/requests/{id}/comments",
+    tag = "requests",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Comments of the request", body = Vec<RequestComment>),
+        Unauthorized,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn list_request_comments(
+    State(request_service): State<RequestService>,
+    Path(id): Path<Uuid>,
+) -> HttpResult<Json<Vec<RequestComment>>> {
+    let comments = request_service
+        .list_comments(id)
+        .await
+        .http_err("list request comments")?;
+
+    Ok(Json(comments))
+}
+
+#[utoipa::path(
+    get,
+    path = "/requests",
+    tag = "requests",
+    params(PageQuery, RequestFilter),
+    responses(
+        (status = 200, description = "Page of requests", body = Page<Request>),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn list_requests(
     State(request_service): State<RequestService>,
-) -> HttpResult<Json<Vec<Request>>> {
+    Query(page): Query<PageQuery>,
+    Query(filter): Query<RequestFilter>,
+) -> HttpResult<Json<Page<Request>>> {
     let requests = request_service
-        .list_requests()
+        .list_requests_page(&filter, &page)
         .await
         .http_err("list requests")?;
 
     Ok(Json(requests))
 }
 
+#[utoipa::path(
+    get,
+    path = "/requests/user/{user_id}",
+    tag = "requests",
+    params(("user_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Requests of the user", body = Vec<Request>),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn list_user_requests(
     State(request_service): State<RequestService>,
     Path(user_id): Path<Uuid>,
@@ -102,17 +297,32 @@ impl<T> HttpError<T> for use_cases::request_service::err::Result<T> {
     fn http_err(self, endpoint_name: &str) -> crate::err::HttpResult<T> {
         self.map_err(|err| {
             error!("Error in: {endpoint_name}");
-            match err {
+            let (status_code, message) = match &err {
                 Error::UnknownDatabaseError(error) => {
                     error!("{error}");
-                    "We are having problems in the server, try again"
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "We are having problems in the server, try again".to_string(),
+                    )
+                }
+                Error::RequestNotFound => (StatusCode::NOT_FOUND, err.to_string()),
+                Error::RequestAlreadyCompleted
+                | Error::RequestExpired
+                | Error::RequestChangedConcurrently => (StatusCode::CONFLICT, err.to_string()),
+                Error::SelfApprovalNotAllowed
+                | Error::ApproverRoleNotAllowed(_)
+                | Error::ApproverAlreadyDecided
+                | Error::NotRequester
+                | Error::CommentNotAllowed => (StatusCode::FORBIDDEN, err.to_string()),
+                Error::EmptyComment | Error::InvalidRequestPayload(_) => {
+                    (StatusCode::BAD_REQUEST, err.to_string())
                 }
-                Error::RequestNotFound => "Request not found",
-                Error::RequestAlreadyCompleted => "Request already completed",
-                Error::SelfApprovalNotAllowed => "Cannot approve/reject your own request",
-                Error::InvalidApprover => "Invalid approver ID",
-            }
-            .to_err_response()
+                Error::ExecutionFailed(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "The request could not be executed".to_string(),
+                ),
+            };
+            (status_code, message).into_response()
         })
     }
 }
@@ -0,0 +1,140 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::get,
+    Extension, Json, Router,
+};
+use entities::{
+    audit::Actor,
+    schedule::{ScheduleConflictQuery, ScheduleEntry, ScheduleQuery},
+};
+use tracing::error;
+use use_cases::schedule_service::{err::Error, ScheduleService};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+use crate::{
+    auth::{auth_middleware, UserInfoAuth},
+    err::{BadRequest, Forbidden, HttpError, HttpResult, InternalError, Unauthorized},
+};
+
+pub fn schedule_router(schedule_service: ScheduleService, jwt_key: String) -> Router {
+    let (router, _) = schedule_routes().split_for_parts();
+
+    router
+        .route("/health-schedule", get(alive))
+        .layer(middleware::from_fn_with_state(jwt_key, auth_middleware))
+        .with_state(schedule_service)
+}
+
+pub fn schedule_routes() -> OpenApiRouter<ScheduleService> {
+    OpenApiRouter::new()
+        .routes(routes!(get_my_schedule))
+        .routes(routes!(get_user_schedule))
+        .routes(routes!(get_schedule_conflicts))
+}
+
+async fn alive() -> &'static str {
+    "Schedule service is alive"
+}
+
+#[utoipa::path(
+    get,
+    path = "/schedule/mine",
+    tag = "schedule",
+    params(ScheduleQuery),
+    responses(
+        (status = 200, description = "Trainings, tournaments and court bookings of the logged in user, sorted by start", body = Vec<ScheduleEntry>),
+        BadRequest,
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_my_schedule(
+    State(schedule_service): State<ScheduleService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Query(query): Query<ScheduleQuery>,
+) -> HttpResult<Json<Vec<ScheduleEntry>>> {
+    let id_user = user_info.user_id;
+    let schedule = schedule_service
+        .get_user_schedule(&Actor::from(user_info), id_user, &query)
+        .await
+        .http_err("get my schedule")?;
+    Ok(Json(schedule))
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/{id_user}/schedule",
+    tag = "schedule",
+    params(("id_user" = Uuid, Path), ScheduleQuery),
+    responses(
+        (status = 200, description = "Trainings, tournaments and court bookings of the user, sorted by start", body = Vec<ScheduleEntry>),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_user_schedule(
+    State(schedule_service): State<ScheduleService>,
+    Path(id_user): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Query(query): Query<ScheduleQuery>,
+) -> HttpResult<Json<Vec<ScheduleEntry>>> {
+    let schedule = schedule_service
+        .get_user_schedule(&Actor::from(user_info), id_user, &query)
+        .await
+        .http_err("get user schedule")?;
+    Ok(Json(schedule))
+}
+
+/// Registrations overlapping these sessions are rejected, clients use it to warn beforehand
+#[utoipa::path(
+    get,
+    path = "/users/{id_user}/schedule/conflicts",
+    tag = "schedule",
+    params(("id_user" = Uuid, Path), ScheduleConflictQuery),
+    responses(
+        (status = 200, description = "Sessions of the user overlapping the window, empty when the user is free", body = Vec<ScheduleEntry>),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_schedule_conflicts(
+    State(schedule_service): State<ScheduleService>,
+    Path(id_user): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Query(query): Query<ScheduleConflictQuery>,
+) -> HttpResult<Json<Vec<ScheduleEntry>>> {
+    let conflicts = schedule_service
+        .get_conflicts(&Actor::from(user_info), id_user, &query)
+        .await
+        .http_err("get schedule conflicts")?;
+    Ok(Json(conflicts))
+}
+
+impl<T> HttpError<T> for Result<T, Error> {
+    fn http_err(self, endpoint_name: &str) -> crate::err::HttpResult<T> {
+        self.map_err(|err| {
+            error!("Error in schedule endpoint ({}): {}", endpoint_name, err);
+            let (status_code, message) = match &err {
+                Error::InvalidWindow => (StatusCode::BAD_REQUEST, err.to_string()),
+                Error::NotAllowed => (StatusCode::FORBIDDEN, err.to_string()),
+                Error::ScheduleConflict(_) => (StatusCode::CONFLICT, err.to_string()),
+                Error::UnknownDatabaseError(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Error processing the schedule request.".to_string(),
+                ),
+            };
+            (status_code, message).into_response()
+        })
+    }
+}
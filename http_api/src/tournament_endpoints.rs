@@ -1,133 +1,201 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
+    middleware,
     response::IntoResponse,
-    routing::{delete, get, post, put},
-    Json, Router,
+    routing::get,
+    Extension, Json, Router,
 };
-use entities::tournament::{
-    Tournament, TournamentAttendance, TournamentAttendanceRequest, TournamentCreation,
-    TournamentRegistration, TournamentRegistrationRequest,
+use entities::{
+    audit::Actor,
+    court::{CourtRequirements, CourtSelection, EventCourtBlock},
+    pagination::{Page, PageQuery},
+    tournament::{
+        Tournament, TournamentAttendance, TournamentAttendanceRequest, TournamentCreation,
+        TournamentFilter, TournamentRegistration, TournamentRegistrationRequest,
+    },
 };
 use serde::Deserialize;
 use tracing::error;
 use use_cases::tournament_service::{err::Error, TournamentService};
+use utoipa::ToSchema;
+use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
 
-use crate::err::{HttpError, HttpResult};
+use crate::{
+    auth::{auth_middleware, UserInfoAuth},
+    err::{
+        BadRequest, Conflict, Forbidden, HttpError, HttpResult, InternalError, NotFound,
+        Unauthorized,
+    },
+};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct TournamentCreationPayload {
     #[serde(flatten)]
     pub tournament_data: TournamentCreation,
     pub id_court: Option<Uuid>,
+    /// Reserves the first available court matching them, used when `id_court` is empty
+    pub court_requirements: Option<CourtRequirements>,
+    /// Reserves several courts, each one for its own block of the tournament. Wins over `id_court`
+    /// and `court_requirements`
+    pub courts: Option<Vec<EventCourtBlock>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct TournamentUpdatePayload {
     #[serde(flatten)]
     pub tournament_data: TournamentCreation,
     pub id_court: Option<Uuid>,
+    /// Reserves the first available court matching them, used when `id_court` is empty
+    pub court_requirements: Option<CourtRequirements>,
+    /// Reserves several courts, each one for its own block of the tournament. Wins over `id_court`
+    /// and `court_requirements`
+    pub courts: Option<Vec<EventCourtBlock>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdatePositionPayload {
     pub position: i32,
 }
 
-pub fn tournament_router(tournament_service: TournamentService) -> Router {
-    Router::new()
+pub fn tournament_router(tournament_service: TournamentService, jwt_key: String) -> Router {
+    let (protected_router, _) = protected_tournament_routes().split_for_parts();
+    let (public_router, _) = public_tournament_routes().split_for_parts();
+
+    protected_router
+        // Only the routes acting for a user require a token, browsing tournaments stays public
+        .route_layer(middleware::from_fn_with_state(jwt_key, auth_middleware))
+        .merge(public_router)
         .route("/health-tournament", get(alive))
-        .route(
-            "/tournaments",
-            post(create_tournament).get(list_tournaments),
-        )
-        .route(
-            "/tournaments/{id_tournament}",
-            get(get_tournament)
-                .put(update_tournament)
-                .delete(delete_tournament),
-        )
-        .route(
-            "/tournaments/{id_tournament}/register",
-            post(register_user_for_tournament),
-        )
-        .route(
-            "/tournaments/{id_tournament}/attendance",
-            post(record_attendance),
-        )
-        .route(
-            "/tournaments/{id_tournament}/users/{id_user}/position",
-            put(update_user_position_in_tournament),
-        )
-        .route(
-            "/tournaments/registrations/user/{user_id}",
-            get(get_user_registrations),
-        )
-        .route(
-            "/tournaments/registrations/tournament/{id_tournament}",
-            get(get_tournament_registrations),
-        )
-        .route(
-            "/users/{user_id}/eligible-tournaments",
-            get(get_eligible_tournaments_for_user),
-        )
-        .route(
-            "/tournaments/{id_tournament}/attendance",
-            get(get_tournament_attendance_list),
-        )
-        .route(
-            "/users/{user_id}/tournament-attendance",
-            get(get_user_tournament_attendance_list),
-        )
-        .route(
-            "/tournaments/{id_tournament}/attendance/{user_id}",
-            delete(delete_user_attendance_from_tournament),
-        )
-        .route(
-            "/tournaments/{id_tournament}/registrations/{user_id}",
-            delete(delete_user_registration_from_tournament),
-        )
         .with_state(tournament_service)
 }
 
+pub fn tournament_routes() -> OpenApiRouter<TournamentService> {
+    protected_tournament_routes().merge(public_tournament_routes())
+}
+
+fn protected_tournament_routes() -> OpenApiRouter<TournamentService> {
+    OpenApiRouter::new()
+        .routes(routes!(create_tournament))
+        .routes(routes!(update_tournament, delete_tournament))
+        .routes(routes!(register_user_for_tournament))
+        .routes(routes!(record_attendance))
+        .routes(routes!(update_user_position_in_tournament))
+        .routes(routes!(delete_user_attendance_from_tournament))
+        .routes(routes!(delete_user_registration_from_tournament))
+}
+
+fn public_tournament_routes() -> OpenApiRouter<TournamentService> {
+    OpenApiRouter::new()
+        .routes(routes!(list_tournaments))
+        .routes(routes!(get_tournament))
+        .routes(routes!(get_tournament_attendance_list))
+        .routes(routes!(get_user_registrations))
+        .routes(routes!(get_tournament_registrations))
+        .routes(routes!(get_eligible_tournaments_for_user))
+        .routes(routes!(get_user_tournament_attendance_list))
+}
+
 async fn alive() -> &'static str {
     "Tournament service is alive"
 }
 
+#[utoipa::path(
+    delete,
+    path = "/tournaments/{id_tournament}/attendance/{user_id}",
+    tag = "tournaments",
+    params(("id_tournament" = Uuid, Path), ("user_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Attendance deleted", body = String, content_type = "text/plain"),
+        Unauthorized,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn delete_user_attendance_from_tournament(
     State(tournament_service): State<TournamentService>,
     Path((id_tournament, user_id)): Path<(Uuid, Uuid)>,
+    Extension(user_info): Extension<UserInfoAuth>,
 ) -> HttpResult<impl IntoResponse> {
     tournament_service
-        .delete_attendance(id_tournament, user_id)
+        .delete_attendance(&Actor::from(user_info), id_tournament, user_id)
         .await
         .http_err("delete attendance")?;
     Ok((StatusCode::OK, "Attendance deleted successfully"))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/tournaments/{id_tournament}/registrations/{user_id}",
+    tag = "tournaments",
+    params(("id_tournament" = Uuid, Path), ("user_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Registration deleted", body = String, content_type = "text/plain"),
+        Unauthorized,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn delete_user_registration_from_tournament(
     State(tournament_service): State<TournamentService>,
     Path((id_tournament, user_id)): Path<(Uuid, Uuid)>,
+    Extension(user_info): Extension<UserInfoAuth>,
 ) -> HttpResult<impl IntoResponse> {
     tournament_service
-        .delete_registration(id_tournament, user_id)
+        .delete_registration(&Actor::from(user_info), id_tournament, user_id)
         .await
         .http_err("delete registration")?;
     Ok((StatusCode::OK, "Registration deleted successfully"))
 }
 
+#[utoipa::path(
+    post,
+    path = "/tournaments",
+    tag = "tournaments",
+    request_body = TournamentCreationPayload,
+    responses(
+        (status = 200, description = "The created tournament", body = Tournament),
+        BadRequest,
+        Unauthorized,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn create_tournament(
     State(tournament_service): State<TournamentService>,
+    Extension(user_info): Extension<UserInfoAuth>,
     Json(payload): Json<TournamentCreationPayload>,
 ) -> HttpResult<Json<Tournament>> {
     let created_tournament = tournament_service
-        .create_tournament(payload.tournament_data, payload.id_court)
+        .create_tournament(
+            &Actor::from(user_info),
+            payload.tournament_data,
+            CourtSelection::from_parts(
+                payload.id_court,
+                payload.court_requirements,
+                payload.courts,
+            ),
+        )
         .await
         .http_err("create tournament")?;
     Ok(Json(created_tournament))
 }
 
+#[utoipa::path(
+    get,
+    path = "/tournaments/{id_tournament}",
+    tag = "tournaments",
+    params(("id_tournament" = Uuid, Path)),
+    responses(
+        (status = 200, description = "The tournament", body = Tournament),
+        NotFound,
+        InternalError
+    )
+)]
 async fn get_tournament(
     State(tournament_service): State<TournamentService>,
     Path(id_tournament): Path<Uuid>,
@@ -139,64 +207,161 @@ async fn get_tournament(
     Ok(Json(tournament))
 }
 
+#[utoipa::path(
+    put,
+    path = "/tournaments/{id_tournament}",
+    tag = "tournaments",
+    params(("id_tournament" = Uuid, Path)),
+    request_body = TournamentUpdatePayload,
+    responses(
+        (status = 200, description = "The updated tournament", body = Tournament),
+        BadRequest,
+        Unauthorized,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn update_tournament(
     State(tournament_service): State<TournamentService>,
     Path(id_tournament): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
     Json(payload): Json<TournamentUpdatePayload>,
 ) -> HttpResult<Json<Tournament>> {
     let updated_tournament = tournament_service
-        .update_tournament(id_tournament, payload.tournament_data, payload.id_court)
+        .update_tournament(
+            &Actor::from(user_info),
+            id_tournament,
+            payload.tournament_data,
+            CourtSelection::from_parts(
+                payload.id_court,
+                payload.court_requirements,
+                payload.courts,
+            ),
+        )
         .await
         .http_err("update tournament")?;
     Ok(Json(updated_tournament))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/tournaments/{id_tournament}",
+    tag = "tournaments",
+    params(("id_tournament" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Tournament deleted", body = String, content_type = "text/plain"),
+        Unauthorized,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn delete_tournament(
     State(tournament_service): State<TournamentService>,
     Path(id_tournament): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
 ) -> HttpResult<impl IntoResponse> {
     tournament_service
-        .delete_tournament(id_tournament)
+        .delete_tournament(&Actor::from(user_info), id_tournament)
         .await
         .http_err("delete tournament")?;
     Ok((StatusCode::OK, "Tournament deleted successfully"))
 }
 
+#[utoipa::path(
+    get,
+    path = "/tournaments",
+    tag = "tournaments",
+    params(PageQuery, TournamentFilter),
+    responses(
+        (status = 200, description = "Page of tournaments", body = Page<Tournament>),
+        InternalError
+    )
+)]
 async fn list_tournaments(
     State(tournament_service): State<TournamentService>,
-) -> HttpResult<Json<Vec<Tournament>>> {
+    Query(page): Query<PageQuery>,
+    Query(filter): Query<TournamentFilter>,
+) -> HttpResult<Json<Page<Tournament>>> {
     let tournaments = tournament_service
-        .list_tournaments()
+        .list_tournaments_page(&filter, &page)
         .await
         .http_err("list tournaments")?;
     Ok(Json(tournaments))
 }
 
+#[utoipa::path(
+    post,
+    path = "/tournaments/{id_tournament}/register",
+    tag = "tournaments",
+    params(("id_tournament" = Uuid, Path)),
+    request_body = TournamentRegistrationRequest,
+    responses(
+        (status = 200, description = "The registration", body = TournamentRegistration),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn register_user_for_tournament(
     State(tournament_service): State<TournamentService>,
     Path(id_tournament): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
     Json(registration_payload): Json<TournamentRegistrationRequest>,
 ) -> HttpResult<Json<TournamentRegistration>> {
     let registration = tournament_service
-        .register_user(registration_payload, id_tournament)
+        .register_user(&Actor::from(user_info), registration_payload, id_tournament)
         .await
         .http_err("register user for tournament")?;
 
     Ok(Json(registration))
 }
 
+#[utoipa::path(
+    post,
+    path = "/tournaments/{id_tournament}/attendance",
+    tag = "tournaments",
+    params(("id_tournament" = Uuid, Path)),
+    request_body = TournamentAttendanceRequest,
+    responses(
+        (status = 200, description = "The recorded attendance", body = TournamentAttendance),
+        BadRequest,
+        Unauthorized,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn record_attendance(
     State(tournament_service): State<TournamentService>,
     Path(id_tournament): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
     Json(attendance_payload): Json<TournamentAttendanceRequest>,
 ) -> HttpResult<Json<TournamentAttendance>> {
     let attendance = tournament_service
-        .record_attendance(attendance_payload, id_tournament)
+        .record_attendance(&Actor::from(user_info), attendance_payload, id_tournament)
         .await
         .http_err("record attendance")?;
     Ok(Json(attendance))
 }
 
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/eligible-tournaments",
+    tag = "tournaments",
+    params(("user_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Tournaments the user can register to", body = Vec<Tournament>),
+        InternalError
+    )
+)]
 async fn get_eligible_tournaments_for_user(
     State(tournament_service): State<TournamentService>,
     Path(user_id): Path<Uuid>,
@@ -208,6 +373,17 @@ async fn get_eligible_tournaments_for_user(
     Ok(Json(tournaments))
 }
 
+#[utoipa::path(
+    get,
+    path = "/tournaments/{id_tournament}/attendance",
+    tag = "tournaments",
+    params(("id_tournament" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Attendance of the tournament", body = Vec<TournamentAttendance>),
+        NotFound,
+        InternalError
+    )
+)]
 async fn get_tournament_attendance_list(
     State(tournament_service): State<TournamentService>,
     Path(id_tournament): Path<Uuid>,
@@ -219,6 +395,16 @@ async fn get_tournament_attendance_list(
     Ok(Json(attendance_list))
 }
 
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/tournament-attendance",
+    tag = "tournaments",
+    params(("user_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Tournament attendance of the user", body = Vec<TournamentAttendance>),
+        InternalError
+    )
+)]
 async fn get_user_tournament_attendance_list(
     State(tournament_service): State<TournamentService>,
     Path(user_id): Path<Uuid>,
@@ -230,6 +416,16 @@ async fn get_user_tournament_attendance_list(
     Ok(Json(attendance_list))
 }
 
+#[utoipa::path(
+    get,
+    path = "/tournaments/registrations/user/{user_id}",
+    tag = "tournaments",
+    params(("user_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Tournament registrations of the user", body = Vec<TournamentRegistration>),
+        InternalError
+    )
+)]
 async fn get_user_registrations(
     State(tournament_service): State<TournamentService>,
     Path(user_id): Path<Uuid>,
@@ -241,6 +437,17 @@ async fn get_user_registrations(
     Ok(Json(registrations))
 }
 
+#[utoipa::path(
+    get,
+    path = "/tournaments/registrations/tournament/{id_tournament}",
+    tag = "tournaments",
+    params(("id_tournament" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Registrations of the tournament", body = Vec<TournamentRegistration>),
+        NotFound,
+        InternalError
+    )
+)]
 async fn get_tournament_registrations(
     State(tournament_service): State<TournamentService>,
     Path(id_tournament): Path<Uuid>,
@@ -252,13 +459,35 @@ async fn get_tournament_registrations(
     Ok(Json(registrations))
 }
 
+#[utoipa::path(
+    put,
+    path = "/tournaments/{id_tournament}/users/{id_user}/position",
+    tag = "tournaments",
+    params(("id_tournament" = Uuid, Path), ("id_user" = Uuid, Path)),
+    request_body = UpdatePositionPayload,
+    responses(
+        (status = 200, description = "Position updated", body = String, content_type = "text/plain"),
+        BadRequest,
+        Unauthorized,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn update_user_position_in_tournament(
     State(tournament_service): State<TournamentService>,
     Path((id_tournament, user_id)): Path<(Uuid, Uuid)>,
+    Extension(user_info): Extension<UserInfoAuth>,
     Json(payload): Json<UpdatePositionPayload>,
 ) -> HttpResult<impl IntoResponse> {
     tournament_service
-        .update_position(id_tournament, user_id, payload.position)
+        .update_position(
+            &Actor::from(user_info),
+            id_tournament,
+            user_id,
+            payload.position,
+        )
         .await
         .http_err("update user position in tournament")?;
     Ok((StatusCode::OK, "Position updated successfully"))
@@ -325,12 +554,41 @@ impl<T> HttpError<T> for Result<T, Error> {
                         use_cases::court_service::err::Error::CourtNotFound => {
                             (StatusCode::BAD_REQUEST, "Selected court not found.")
                         }
+                        use_cases::court_service::err::Error::NoMatchingCourtAvailable => (
+                            StatusCode::CONFLICT,
+                            "No court matching the requirements is available for the tournament time.",
+                        ),
+                        use_cases::court_service::err::Error::CourtInactive => {
+                            (StatusCode::BAD_REQUEST, "Selected court is not active.")
+                        }
+                        use_cases::court_service::err::Error::InvalidReservationTime
+                        | use_cases::court_service::err::Error::InvalidCourtBlocks(_) => (
+                            StatusCode::BAD_REQUEST,
+                            "Court blocks must be within the tournament and can't overlap on the same court.",
+                        ),
+                        use_cases::court_service::err::Error::OutsideOpeningHours
+                        | use_cases::court_service::err::Error::CourtClosed(_) => (
+                            StatusCode::CONFLICT,
+                            "Selected court is closed for the tournament time.",
+                        ),
                         _ => (
                             StatusCode::INTERNAL_SERVER_ERROR,
                             "Internal error with court service.",
                         ),
                     }
                 }
+                Error::ScheduleServiceError(e) => match e {
+                    use_cases::schedule_service::err::Error::ScheduleConflict(_) => {
+                        return (StatusCode::CONFLICT, e.to_string()).into_response();
+                    }
+                    _ => {
+                        error!("Schedule service error via tournament: {}", e);
+                        (
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            "Internal error with schedule service.",
+                        )
+                    }
+                },
                 Error::InvalidAssistanceDate => (
                     StatusCode::BAD_REQUEST,
                     "Invalid assistance date, the tournament hasn't started",
@@ -0,0 +1,241 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::get,
+    Extension, Json, Router,
+};
+use entities::{
+    audit::Actor,
+    trainer::{
+        AvailableTrainer, AvailableTrainersQuery, TrainerAvailability, TrainerTimeOff,
+        TrainerTimeOffCreation, TrainerTimeOffQuery,
+    },
+};
+use tracing::error;
+use use_cases::{
+    trainer_service::{err::Error, TrainerService},
+    user_service::err::Error as UserError,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+use crate::{
+    auth::{auth_middleware, UserInfoAuth},
+    err::{BadRequest, Forbidden, HttpError, HttpResult, InternalError, NotFound, Unauthorized},
+};
+
+pub fn trainer_router(trainer_service: TrainerService, jwt_key: String) -> Router {
+    let (router, _) = trainer_routes().split_for_parts();
+
+    router
+        .route("/health-trainer", get(alive))
+        .layer(middleware::from_fn_with_state(jwt_key, auth_middleware))
+        .with_state(trainer_service)
+}
+
+pub fn trainer_routes() -> OpenApiRouter<TrainerService> {
+    OpenApiRouter::new()
+        .routes(routes!(get_available_trainers))
+        .routes(routes!(get_availability, set_availability))
+        .routes(routes!(get_time_off, create_time_off))
+        .routes(routes!(delete_time_off))
+}
+
+async fn alive() -> &'static str {
+    "Trainer service is alive"
+}
+
+/// Trainers to pick from when creating or moving a training, the rest would be rejected
+#[utoipa::path(
+    get,
+    path = "/trainers/available",
+    tag = "trainers",
+    params(AvailableTrainersQuery),
+    responses(
+        (status = 200, description = "Trainers available, not off and without other sessions in the slot", body = Vec<AvailableTrainer>),
+        BadRequest,
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_available_trainers(
+    State(trainer_service): State<TrainerService>,
+    Query(query): Query<AvailableTrainersQuery>,
+) -> HttpResult<Json<Vec<AvailableTrainer>>> {
+    let trainers = trainer_service
+        .get_available_trainers(&query)
+        .await
+        .http_err("get available trainers")?;
+    Ok(Json(trainers))
+}
+
+#[utoipa::path(
+    get,
+    path = "/trainers/{trainer_id}/availability",
+    tag = "trainers",
+    params(("trainer_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Weekly availability of the trainer, empty when available at any time", body = Vec<TrainerAvailability>),
+        BadRequest,
+        Unauthorized,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_availability(
+    State(trainer_service): State<TrainerService>,
+    Path(trainer_id): Path<Uuid>,
+) -> HttpResult<Json<Vec<TrainerAvailability>>> {
+    let availability = trainer_service
+        .get_availability(trainer_id)
+        .await
+        .http_err("get trainer availability")?;
+    Ok(Json(availability))
+}
+
+#[utoipa::path(
+    put,
+    path = "/trainers/{trainer_id}/availability",
+    tag = "trainers",
+    params(("trainer_id" = Uuid, Path)),
+    request_body = Vec<TrainerAvailability>,
+    responses(
+        (status = 200, description = "The new weekly availability of the trainer", body = Vec<TrainerAvailability>),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn set_availability(
+    State(trainer_service): State<TrainerService>,
+    Path(trainer_id): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Json(availability): Json<Vec<TrainerAvailability>>,
+) -> HttpResult<Json<Vec<TrainerAvailability>>> {
+    let availability = trainer_service
+        .set_availability(&Actor::from(user_info), trainer_id, availability)
+        .await
+        .http_err("set trainer availability")?;
+    Ok(Json(availability))
+}
+
+#[utoipa::path(
+    get,
+    path = "/trainers/{trainer_id}/time-off",
+    tag = "trainers",
+    params(("trainer_id" = Uuid, Path), TrainerTimeOffQuery),
+    responses(
+        (status = 200, description = "Time off of the trainer overlapping the filter", body = Vec<TrainerTimeOff>),
+        Unauthorized,
+        Forbidden,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_time_off(
+    State(trainer_service): State<TrainerService>,
+    Path(trainer_id): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Query(params): Query<TrainerTimeOffQuery>,
+) -> HttpResult<Json<Vec<TrainerTimeOff>>> {
+    let time_off = trainer_service
+        .get_time_off(
+            &Actor::from(user_info),
+            trainer_id,
+            params.start_datetime_filter,
+            params.end_datetime_filter,
+        )
+        .await
+        .http_err("get trainer time off")?;
+    Ok(Json(time_off))
+}
+
+#[utoipa::path(
+    post,
+    path = "/trainers/{trainer_id}/time-off",
+    tag = "trainers",
+    params(("trainer_id" = Uuid, Path)),
+    request_body = TrainerTimeOffCreation,
+    responses(
+        (status = 201, description = "Time off created, trainings already assigned in it are kept", body = TrainerTimeOff),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn create_time_off(
+    State(trainer_service): State<TrainerService>,
+    Path(trainer_id): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Json(time_off_creation): Json<TrainerTimeOffCreation>,
+) -> HttpResult<impl IntoResponse> {
+    let time_off = trainer_service
+        .create_time_off(&Actor::from(user_info), trainer_id, time_off_creation)
+        .await
+        .http_err("create trainer time off")?;
+    Ok((StatusCode::CREATED, Json(time_off)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/trainer-time-off/{id_time_off}",
+    tag = "trainers",
+    params(("id_time_off" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Time off deleted", body = String, content_type = "text/plain"),
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn delete_time_off(
+    State(trainer_service): State<TrainerService>,
+    Path(id_time_off): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<impl IntoResponse> {
+    trainer_service
+        .delete_time_off(&Actor::from(user_info), id_time_off)
+        .await
+        .http_err("delete trainer time off")?;
+    Ok((StatusCode::OK, "Time off deleted successfully"))
+}
+
+impl<T> HttpError<T> for Result<T, Error> {
+    fn http_err(self, endpoint_name: &str) -> crate::err::HttpResult<T> {
+        self.map_err(|err| {
+            error!("Error in trainer endpoint ({}): {}", endpoint_name, err);
+            let (status_code, message) = match &err {
+                Error::InvalidAvailability(_) | Error::InvalidWindow => {
+                    (StatusCode::BAD_REQUEST, err.to_string())
+                }
+                Error::NotATrainer => (StatusCode::BAD_REQUEST, err.to_string()),
+                Error::NotAllowed => (StatusCode::FORBIDDEN, err.to_string()),
+                Error::TimeOffNotFound | Error::UserServiceError(UserError::UserIdDontExist) => {
+                    (StatusCode::NOT_FOUND, err.to_string())
+                }
+                Error::TrainerOnTimeOff(_) | Error::OutsideAvailability => {
+                    (StatusCode::CONFLICT, err.to_string())
+                }
+                Error::UnknownDatabaseError(_)
+                | Error::UserServiceError(_)
+                | Error::ScheduleServiceError(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Error processing the trainer request.".to_string(),
+                ),
+            };
+            (status_code, message).into_response()
+        })
+    }
+}
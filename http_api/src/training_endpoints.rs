@@ -1,81 +1,127 @@
+use std::io::Cursor;
+
 use axum::{
-    extract::{Path, State}, // Added Query
-    http::StatusCode,
-    response::IntoResponse,
-    routing::{delete, get, post}, // Added put
-    Json,
-    Router,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    middleware,
+    response::{IntoResponse, Response},
+    routing::get,
+    Extension, Json, Router,
+};
+use entities::{
+    audit::Actor,
+    court::{CourtRequirements, CourtSelection, EventCourtBlock},
+    pagination::{Page, PageQuery},
+    training::{
+        AttendanceStatus, AttendanceSubmission, Training, TrainingCheckIn, TrainingCheckInToken,
+        TrainingCreation, TrainingFilter, TrainingRegistration, TrainingRosterEntry,
+        TrainingWaitlistEntry,
+    },
 };
-use entities::training::{Training, TrainingCreation, TrainingRegistration};
+use image::{ImageFormat, Luma};
+use qrcode::{render::svg, QrCode};
 use serde::Deserialize; // Added
 use tracing::error;
 use use_cases::training_service::{err::Error, TrainingService};
+use utoipa::{IntoParams, ToSchema};
+use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
 
-use crate::err::{HttpError, HttpResult};
+use crate::{
+    auth::{auth_middleware, UserInfoAuth},
+    err::{
+        BadRequest, Conflict, Forbidden, HttpError, HttpResult, InternalError, NotFound,
+        ToErrResponse, Unauthorized,
+    },
+};
 
 // DTO for training creation that includes optional court ID
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct TrainingCreationPayload {
     #[serde(flatten)]
     pub training_data: TrainingCreation,
     pub id_court: Option<Uuid>,
+    /// Reserves the first available court matching them, used when `id_court` is empty
+    pub court_requirements: Option<CourtRequirements>,
+    /// Reserves several courts, each one for its own block of the training. Wins over `id_court`
+    /// and `court_requirements`
+    pub courts: Option<Vec<EventCourtBlock>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct TrainingUpdatePayload {
     // Similar for updates
     #[serde(flatten)]
     pub training_data: TrainingCreation, // Re-use TrainingCreation for update fields
     pub id_court: Option<Uuid>,
+    /// Reserves the first available court matching them, used when `id_court` is empty
+    pub court_requirements: Option<CourtRequirements>,
+    /// Reserves several courts, each one for its own block of the training. Wins over `id_court`
+    /// and `court_requirements`
+    pub courts: Option<Vec<EventCourtBlock>>,
 }
 
-pub fn training_router(training_service: TrainingService) -> Router {
-    Router::new()
+pub fn training_router(training_service: TrainingService, jwt_key: String) -> Router {
+    let (protected_router, _) = protected_training_routes().split_for_parts();
+    let (public_router, _) = public_training_routes().split_for_parts();
+
+    protected_router
+        // Only the routes acting for a user require a token, browsing trainings stays public
+        .route_layer(middleware::from_fn_with_state(jwt_key, auth_middleware))
+        .merge(public_router)
         .route("/health-training", get(alive))
-        .route("/trainings", post(create_training).get(list_trainings))
-        .route(
-            "/trainings/{id}",
-            get(get_training)
-                .put(update_training) // Added PUT
-                .delete(delete_training),
-        )
-        .route(
-            "/trainings/{id}/register/{user_id}",
-            post(register_user_for_training),
-        ) // Renamed for clarity
-        .route(
-            "/trainings/{training_id}/attendance/{user_id}",
-            post(mark_attendance),
-        )
-        .route(
-            "/users/{id}/eligible-trainings",
-            get(get_eligible_trainings),
-        )
-        .route(
-            "/users/{id}/training-registrations",
-            get(get_user_training_registrations),
-        )
-        .route(
-            "/trainings/{id}/registrations",
-            get(get_training_registrations),
-        )
-        .route(
-            "/trainings/{training_id}/registrations/{user_id}",
-            delete(delete_training_registration),
-        )
-        .route(
-            // New route for trainings by trainer
-            "/trainers/{trainer_id}/trainings",
-            get(get_trainings_by_trainer),
-        )
         .with_state(training_service)
 }
 
+pub fn training_routes() -> OpenApiRouter<TrainingService> {
+    protected_training_routes().merge(public_training_routes())
+}
+
+fn protected_training_routes() -> OpenApiRouter<TrainingService> {
+    OpenApiRouter::new()
+        .routes(routes!(create_training))
+        .routes(routes!(update_training, delete_training))
+        .routes(routes!(register_user_for_training))
+        .routes(routes!(mark_attendance))
+        .routes(routes!(get_training_roster))
+        .routes(routes!(submit_training_attendance))
+        .routes(routes!(get_check_in_token))
+        .routes(routes!(get_check_in_qr))
+        .routes(routes!(check_in_to_training))
+        .routes(routes!(delete_training_registration))
+        .routes(routes!(
+            join_training_waitlist,
+            get_training_waitlist_entry,
+            leave_training_waitlist
+        ))
+        .routes(routes!(confirm_training_waitlist_spot))
+}
+
+fn public_training_routes() -> OpenApiRouter<TrainingService> {
+    OpenApiRouter::new()
+        .routes(routes!(list_trainings))
+        .routes(routes!(get_training))
+        .routes(routes!(get_eligible_trainings))
+        .routes(routes!(get_user_training_registrations))
+        .routes(routes!(get_training_registrations))
+        .routes(routes!(get_training_waitlist))
+        .routes(routes!(get_trainings_by_trainer))
+}
+
 async fn alive() -> &'static str {
     "Training service is alive"
 }
 
+#[utoipa::path(
+    get,
+    path = "/trainers/{trainer_id}/trainings",
+    tag = "trainings",
+    params(("trainer_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Trainings of the trainer", body = Vec<Training>),
+        InternalError
+    )
+)]
 async fn get_trainings_by_trainer(
     State(training_service): State<TrainingService>,
     Path(trainer_id): Path<Uuid>,
@@ -87,6 +133,16 @@ async fn get_trainings_by_trainer(
     Ok(Json(trainings))
 }
 
+#[utoipa::path(
+    get,
+    path = "/users/{id}/training-registrations",
+    tag = "trainings",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Registrations of the user", body = Vec<TrainingRegistration>),
+        InternalError
+    )
+)]
 async fn get_user_training_registrations(
     State(training_service): State<TrainingService>,
     Path(user_id): Path<Uuid>,
@@ -99,6 +155,17 @@ async fn get_user_training_registrations(
     Ok(Json(registrations))
 }
 
+#[utoipa::path(
+    get,
+    path = "/trainings/{id}/registrations",
+    tag = "trainings",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Registrations of the training", body = Vec<TrainingRegistration>),
+        NotFound,
+        InternalError
+    )
+)]
 async fn get_training_registrations(
     State(training_service): State<TrainingService>,
     Path(training_id): Path<Uuid>,
@@ -111,13 +178,27 @@ async fn get_training_registrations(
     Ok(Json(registrations))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/trainings/{training_id}/registrations/{user_id}",
+    tag = "trainings",
+    params(("training_id" = Uuid, Path), ("user_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Registration deleted", body = String, content_type = "text/plain"),
+        Unauthorized,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn delete_training_registration(
     State(training_service): State<TrainingService>,
     Path((training_id, user_id)): Path<(Uuid, Uuid)>,
+    Extension(user_info): Extension<UserInfoAuth>,
 ) -> HttpResult<impl IntoResponse> {
     // Return type changed
     training_service
-        .delete_training_registration(training_id, user_id)
+        .delete_training_registration(&Actor::from(user_info), training_id, user_id)
         .await
         .http_err("delete training registration")?;
     Ok((
@@ -126,18 +207,52 @@ async fn delete_training_registration(
     ))
 }
 
+#[utoipa::path(
+    post,
+    path = "/trainings",
+    tag = "trainings",
+    request_body = TrainingCreationPayload,
+    responses(
+        (status = 200, description = "The created training", body = Training),
+        BadRequest,
+        Unauthorized,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn create_training(
     State(training_service): State<TrainingService>,
+    Extension(user_info): Extension<UserInfoAuth>,
     Json(payload): Json<TrainingCreationPayload>, // Use new payload
 ) -> HttpResult<Json<Training>> {
     // Return created training
     let created_training = training_service
-        .create_training(payload.training_data, payload.id_court)
+        .create_training(
+            &Actor::from(user_info),
+            payload.training_data,
+            CourtSelection::from_parts(
+                payload.id_court,
+                payload.court_requirements,
+                payload.courts,
+            ),
+        )
         .await
         .http_err("create training")?;
     Ok(Json(created_training))
 }
 
+#[utoipa::path(
+    get,
+    path = "/trainings/{id}",
+    tag = "trainings",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "The training", body = Training),
+        NotFound,
+        InternalError
+    )
+)]
 async fn get_training(
     State(training_service): State<TrainingService>,
     Path(id): Path<Uuid>,
@@ -150,71 +265,531 @@ async fn get_training(
     Ok(Json(training))
 }
 
+#[utoipa::path(
+    put,
+    path = "/trainings/{id}",
+    tag = "trainings",
+    params(("id" = Uuid, Path)),
+    request_body = TrainingUpdatePayload,
+    responses(
+        (status = 200, description = "The updated training", body = Training),
+        BadRequest,
+        Unauthorized,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn update_training(
     State(training_service): State<TrainingService>,
     Path(id_training): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
     Json(payload): Json<TrainingUpdatePayload>, // Use new payload for update
 ) -> HttpResult<Json<Training>> {
     // Return updated training
     let updated_training = training_service
-        .update_training(id_training, payload.training_data, payload.id_court)
+        .update_training(
+            &Actor::from(user_info),
+            id_training,
+            payload.training_data,
+            CourtSelection::from_parts(
+                payload.id_court,
+                payload.court_requirements,
+                payload.courts,
+            ),
+        )
         .await
         .http_err("update training")?;
     Ok(Json(updated_training))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/trainings/{id}",
+    tag = "trainings",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Training deleted", body = String, content_type = "text/plain"),
+        Unauthorized,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn delete_training(
     State(training_service): State<TrainingService>,
     Path(id): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
 ) -> HttpResult<impl IntoResponse> {
     // Return type changed
     training_service
-        .delete_training(id)
+        .delete_training(&Actor::from(user_info), id)
         .await
         .http_err("delete training")?;
     Ok((StatusCode::OK, "Training deleted successfully"))
 }
 
+#[utoipa::path(
+    get,
+    path = "/trainings",
+    tag = "trainings",
+    params(PageQuery, TrainingFilter),
+    responses(
+        (status = 200, description = "Page of trainings", body = Page<Training>),
+        InternalError
+    )
+)]
 async fn list_trainings(
     State(training_service): State<TrainingService>,
-) -> HttpResult<Json<Vec<Training>>> {
-    // Return type changed
+    Query(page): Query<PageQuery>,
+    Query(filter): Query<TrainingFilter>,
+) -> HttpResult<Json<Page<Training>>> {
     let trainings = training_service
-        .list_trainings()
+        .list_trainings_page(&filter, &page)
         .await
         .http_err("list trainings")?;
     Ok(Json(trainings))
 }
 
+#[utoipa::path(
+    post,
+    path = "/trainings/{id}/register/{user_id}",
+    tag = "trainings",
+    params(("id" = Uuid, Path), ("user_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "The registration", body = TrainingRegistration),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn register_user_for_training(
     State(training_service): State<TrainingService>,
     Path((id_training, id_user)): Path<(Uuid, Uuid)>, // id_training is now in the body
+    Extension(user_info): Extension<UserInfoAuth>,
 ) -> HttpResult<Json<TrainingRegistration>> {
     let registration = training_service
-        .register_user(id_training, id_user) // Pass the whole payload
+        .register_user(&Actor::from(user_info), id_training, id_user) // Pass the whole payload
         .await
         .http_err("register user for training")?;
     Ok(Json(registration))
 }
 
-#[derive(Deserialize)]
+/// Only full trainings have a waitlist, spots are offered in the order members joined
+#[utoipa::path(
+    post,
+    path = "/trainings/{id}/waitlist/{user_id}",
+    tag = "trainings",
+    params(("id" = Uuid, Path), ("user_id" = Uuid, Path)),
+    responses(
+        (status = 201, description = "The waitlist entry, with its position", body = TrainingWaitlistEntry),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn join_training_waitlist(
+    State(training_service): State<TrainingService>,
+    Path((id_training, id_user)): Path<(Uuid, Uuid)>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<impl IntoResponse> {
+    let entry = training_service
+        .join_waitlist(&Actor::from(user_info), id_training, id_user)
+        .await
+        .http_err("join training waitlist")?;
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/trainings/{id}/waitlist/{user_id}",
+    tag = "trainings",
+    params(("id" = Uuid, Path), ("user_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "The waitlist entry of the user, with its position while queued", body = TrainingWaitlistEntry),
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_training_waitlist_entry(
+    State(training_service): State<TrainingService>,
+    Path((id_training, id_user)): Path<(Uuid, Uuid)>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<Json<TrainingWaitlistEntry>> {
+    let entry = training_service
+        .get_waitlist_entry(&Actor::from(user_info), id_training, id_user)
+        .await
+        .http_err("get training waitlist entry")?;
+    Ok(Json(entry))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/trainings/{id}/waitlist/{user_id}",
+    tag = "trainings",
+    params(("id" = Uuid, Path), ("user_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Left the waitlist, a held spot goes to the next member", body = String, content_type = "text/plain"),
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn leave_training_waitlist(
+    State(training_service): State<TrainingService>,
+    Path((id_training, id_user)): Path<(Uuid, Uuid)>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<impl IntoResponse> {
+    training_service
+        .leave_waitlist(&Actor::from(user_info), id_training, id_user)
+        .await
+        .http_err("leave training waitlist")?;
+    Ok((StatusCode::OK, "Left the training waitlist"))
+}
+
+#[utoipa::path(
+    post,
+    path = "/trainings/{id}/waitlist/{user_id}/confirm",
+    tag = "trainings",
+    params(("id" = Uuid, Path), ("user_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "The registration taking the offered spot", body = TrainingRegistration),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn confirm_training_waitlist_spot(
+    State(training_service): State<TrainingService>,
+    Path((id_training, id_user)): Path<(Uuid, Uuid)>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<Json<TrainingRegistration>> {
+    let registration = training_service
+        .confirm_waitlist_spot(&Actor::from(user_info), id_training, id_user)
+        .await
+        .http_err("confirm training waitlist spot")?;
+    Ok(Json(registration))
+}
+
+#[utoipa::path(
+    get,
+    path = "/trainings/{id}/waitlist",
+    tag = "trainings",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Waiting members and held offers, in queue order", body = Vec<TrainingWaitlistEntry>),
+        NotFound,
+        InternalError
+    )
+)]
+async fn get_training_waitlist(
+    State(training_service): State<TrainingService>,
+    Path(id_training): Path<Uuid>,
+) -> HttpResult<Json<Vec<TrainingWaitlistEntry>>> {
+    let waitlist = training_service
+        .list_waitlist(id_training)
+        .await
+        .http_err("get training waitlist")?;
+    Ok(Json(waitlist))
+}
+
+#[utoipa::path(
+    get,
+    path = "/trainings/{id}/roster",
+    tag = "trainings",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Registered members with their names and attendance, sorted by last name", body = Vec<TrainingRosterEntry>),
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_training_roster(
+    State(training_service): State<TrainingService>,
+    Path(id_training): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<Json<Vec<TrainingRosterEntry>>> {
+    let roster = training_service
+        .get_training_roster(&Actor::from(user_info), id_training)
+        .await
+        .http_err("get training roster")?;
+    Ok(Json(roster))
+}
+
+/// Saves the attendance of several members at once, none is saved when one of them fails.
+/// Corrections are accepted until the grace period after the end passes
+#[utoipa::path(
+    put,
+    path = "/trainings/{training_id}/attendance",
+    tag = "trainings",
+    params(("training_id" = Uuid, Path)),
+    request_body = AttendanceSubmission,
+    responses(
+        (status = 200, description = "The updated registrations", body = Vec<TrainingRegistration>),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn submit_training_attendance(
+    State(training_service): State<TrainingService>,
+    Path(id_training): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Json(submission): Json<AttendanceSubmission>,
+) -> HttpResult<Json<Vec<TrainingRegistration>>> {
+    let registrations = training_service
+        .submit_attendance(&Actor::from(user_info), id_training, &submission)
+        .await
+        .http_err("submit training attendance")?;
+    Ok(Json(registrations))
+}
+
+/// Pixels per side of the rendered QR codes, big enough to scan from a few meters
+const QR_CODE_SIZE: u32 = 512;
+
+#[derive(Debug, Default, Clone, Copy, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+enum QrFormat {
+    #[default]
+    Svg,
+    Png,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct CheckInQrQuery {
+    /// svg when empty
+    #[serde(default)]
+    #[param(inline)]
+    format: QrFormat,
+}
+
+/// The token lasts 30 seconds, the screen showing it fetches a new one before it expires
+#[utoipa::path(
+    get,
+    path = "/trainings/{id}/check-in-token",
+    tag = "trainings",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Check-in token of the running training", body = TrainingCheckInToken),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_check_in_token(
+    State(training_service): State<TrainingService>,
+    Path(id_training): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<impl IntoResponse> {
+    let token = training_service
+        .issue_check_in_token(&Actor::from(user_info), id_training)
+        .await
+        .http_err("get check-in token")?;
+    Ok(([(header::CACHE_CONTROL, "no-store")], Json(token)))
+}
+
+/// QR code of the check-in token, for the trainer to show while the training runs
+#[utoipa::path(
+    get,
+    path = "/trainings/{id}/check-in-qr",
+    tag = "trainings",
+    params(("id" = Uuid, Path), CheckInQrQuery),
+    responses(
+        (status = 200, description = "QR code of a fresh check-in token", content(
+            (String = "image/svg+xml"),
+            (Vec<u8> = "image/png")
+        )),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_check_in_qr(
+    State(training_service): State<TrainingService>,
+    Path(id_training): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Query(query): Query<CheckInQrQuery>,
+) -> HttpResult<Response> {
+    let token = training_service
+        .issue_check_in_token(&Actor::from(user_info), id_training)
+        .await
+        .http_err("get check-in qr")?;
+    Ok(render_qr(&token.token, query.format))
+}
+
+/// Both the image and a failure to render it are complete responses
+fn render_qr(content: &str, format: QrFormat) -> Response {
+    let code = match QrCode::new(content.as_bytes()) {
+        Ok(code) => code,
+        Err(err) => {
+            error!("Error encoding the check-in QR code: {err}");
+            return "Error rendering the QR code".to_err_response();
+        }
+    };
+
+    match format {
+        QrFormat::Svg => {
+            let image = code
+                .render::<svg::Color>()
+                .min_dimensions(QR_CODE_SIZE, QR_CODE_SIZE)
+                .build();
+            (
+                [
+                    (header::CONTENT_TYPE, "image/svg+xml"),
+                    (header::CACHE_CONTROL, "no-store"),
+                ],
+                image,
+            )
+                .into_response()
+        }
+        QrFormat::Png => {
+            let image = code
+                .render::<Luma<u8>>()
+                .min_dimensions(QR_CODE_SIZE, QR_CODE_SIZE)
+                .build();
+            let mut png = Vec::new();
+            if let Err(err) = image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png) {
+                error!("Error encoding the check-in QR code as png: {err}");
+                return "Error rendering the QR code".to_err_response();
+            }
+            (
+                [
+                    (header::CONTENT_TYPE, "image/png"),
+                    (header::CACHE_CONTROL, "no-store"),
+                ],
+                png,
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Marks the logged in member present with the scanned code of a running training
+#[utoipa::path(
+    post,
+    path = "/trainings/check-in",
+    tag = "trainings",
+    request_body = TrainingCheckIn,
+    responses(
+        (status = 200, description = "The registration with the attendance", body = TrainingRegistration),
+        BadRequest,
+        Unauthorized,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn check_in_to_training(
+    State(training_service): State<TrainingService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Json(payload): Json<TrainingCheckIn>,
+) -> HttpResult<Json<TrainingRegistration>> {
+    let registration = training_service
+        .check_in(&Actor::from(user_info), &payload.token)
+        .await
+        .http_err("check in to training")?;
+    Ok(Json(registration))
+}
+
+#[derive(Deserialize, ToSchema)]
 struct MarkAttendancePayload {
+    /// Used when there's no status, marks the member present or absent
+    #[serde(default)]
     attended: bool,
+    status: Option<AttendanceStatus>,
+    /// Reason of an excused absence, or anything the trainer noted
+    notes: Option<String>,
+}
+
+impl MarkAttendancePayload {
+    fn status(&self) -> AttendanceStatus {
+        self.status.unwrap_or(if self.attended {
+            AttendanceStatus::PRESENT
+        } else {
+            AttendanceStatus::ABSENT
+        })
+    }
 }
 
+#[utoipa::path(
+    post,
+    path = "/trainings/{training_id}/attendance/{user_id}",
+    tag = "trainings",
+    params(("training_id" = Uuid, Path), ("user_id" = Uuid, Path)),
+    request_body = MarkAttendancePayload,
+    responses(
+        (status = 200, description = "Attendance marked", body = String, content_type = "text/plain"),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn mark_attendance(
     State(training_service): State<TrainingService>,
     Path((training_id, user_id)): Path<(Uuid, Uuid)>,
+    Extension(user_info): Extension<UserInfoAuth>,
     Json(payload): Json<MarkAttendancePayload>, // Use payload for attended status
 ) -> HttpResult<impl IntoResponse> {
     // Return type changed
     training_service
-        .mark_attendance(training_id, user_id, payload.attended)
+        .mark_attendance(
+            &Actor::from(user_info),
+            training_id,
+            user_id,
+            payload.status(),
+            payload.notes,
+        )
         .await
         .http_err("mark attendance")?;
     Ok((StatusCode::OK, "Attendance marked successfully"))
 }
 
+#[utoipa::path(
+    get,
+    path = "/users/{id}/eligible-trainings",
+    tag = "trainings",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Trainings the user can register to", body = Vec<Training>),
+        InternalError
+    )
+)]
 async fn get_eligible_trainings(
     State(training_service): State<TrainingService>,
     Path(user_id): Path<Uuid>,
@@ -260,6 +835,71 @@ impl<T> HttpError<T> for Result<T, Error> {
                 Error::RegistrationNotFound => {
                     (StatusCode::NOT_FOUND, "Training registration not found.")
                 }
+                Error::TrainingFull => (
+                    StatusCode::CONFLICT,
+                    "Training is full, join its waitlist instead.",
+                ),
+                Error::TrainingNotFull => (
+                    StatusCode::CONFLICT,
+                    "Training has free spots, register instead.",
+                ),
+                Error::AlreadyOnWaitlist => (
+                    StatusCode::CONFLICT,
+                    "User already on the waitlist of this training.",
+                ),
+                Error::WaitlistEntryNotFound => (
+                    StatusCode::NOT_FOUND,
+                    "User not on the waitlist of this training.",
+                ),
+                Error::NoSpotOffered => (
+                    StatusCode::CONFLICT,
+                    "No spot is held for the user, or the confirmation deadline passed.",
+                ),
+                Error::NotWaitlistMember => (
+                    StatusCode::FORBIDDEN,
+                    "Only the member, the trainer of the training or an admin can manage its waitlist entry.",
+                ),
+                Error::CapacityBelowRegistrations(registered) => {
+                    return (
+                        StatusCode::CONFLICT,
+                        format!("The training has {registered} registered members, its capacity can't be lower."),
+                    )
+                        .into_response();
+                }
+                Error::NotTrainingTrainer => (
+                    StatusCode::FORBIDDEN,
+                    "Only the trainer of the training or an admin can take its attendance.",
+                ),
+                Error::AttendanceNotesTooLong(max_chars) => {
+                    return (
+                        StatusCode::BAD_REQUEST,
+                        format!("Attendance notes can't be longer than {max_chars} characters."),
+                    )
+                        .into_response();
+                }
+                Error::AttendanceClosed => (
+                    StatusCode::CONFLICT,
+                    "The attendance of the training is closed.",
+                ),
+                Error::DuplicatedAttendanceRecord(id_user) => {
+                    return (
+                        StatusCode::BAD_REQUEST,
+                        format!("The attendance of the user {id_user} was submitted more than once."),
+                    )
+                        .into_response();
+                }
+                Error::InvalidCheckInToken => (StatusCode::BAD_REQUEST, "Invalid check-in code."),
+                Error::CheckInTokenExpired => (
+                    StatusCode::BAD_REQUEST,
+                    "The check-in code expired, scan the current one.",
+                ),
+                Error::CheckInSigningError(e) => {
+                    error!("Check-in signing error: {}", e);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "Error processing the check-in code.",
+                    )
+                }
                 Error::CategoryServiceError(e) => {
                     error!("Category service error via training: {}", e);
                     (
@@ -278,6 +918,23 @@ impl<T> HttpError<T> for Result<T, Error> {
                         use_cases::court_service::err::Error::CourtNotFound => {
                             (StatusCode::BAD_REQUEST, "Selected court not found.")
                         }
+                        use_cases::court_service::err::Error::NoMatchingCourtAvailable => (
+                            StatusCode::CONFLICT,
+                            "No court matching the requirements is available for the training time.",
+                        ),
+                        use_cases::court_service::err::Error::CourtInactive => {
+                            (StatusCode::BAD_REQUEST, "Selected court is not active.")
+                        }
+                        use_cases::court_service::err::Error::InvalidReservationTime
+                        | use_cases::court_service::err::Error::InvalidCourtBlocks(_) => (
+                            StatusCode::BAD_REQUEST,
+                            "Court blocks must be within the training and can't overlap on the same court.",
+                        ),
+                        use_cases::court_service::err::Error::OutsideOpeningHours
+                        | use_cases::court_service::err::Error::CourtClosed(_) => (
+                            StatusCode::CONFLICT,
+                            "Selected court is closed for the training time.",
+                        ),
                         _ => (
                             StatusCode::INTERNAL_SERVER_ERROR,
                             "Internal error with court service.",
@@ -298,6 +955,31 @@ impl<T> HttpError<T> for Result<T, Error> {
                         "Tuition requirement not met for training.",
                     )
                 }
+                Error::ScheduleServiceError(e) => match e {
+                    use_cases::schedule_service::err::Error::ScheduleConflict(_) => {
+                        return (StatusCode::CONFLICT, e.to_string()).into_response();
+                    }
+                    _ => {
+                        error!("Schedule service error via training: {}", e);
+                        (
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            "Internal error with schedule service.",
+                        )
+                    }
+                },
+                Error::TrainerServiceError(e) => match e {
+                    use_cases::trainer_service::err::Error::TrainerOnTimeOff(_)
+                    | use_cases::trainer_service::err::Error::OutsideAvailability => {
+                        return (StatusCode::CONFLICT, e.to_string()).into_response();
+                    }
+                    _ => {
+                        error!("Trainer service error via training: {}", e);
+                        (
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            "Internal error with trainer service.",
+                        )
+                    }
+                },
                 Error::InvalidAssistanceDate => (
                     StatusCode::BAD_REQUEST,
                     "Invalid assistance date, the training hasn't started",
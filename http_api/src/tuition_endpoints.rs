@@ -1,60 +1,110 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     middleware,
     response::IntoResponse,
-    routing::{get, post},
+    routing::get,
     Extension, Json, Router,
 };
-use entities::tuition::Tuition;
+use entities::{
+    audit::Actor,
+    pagination::{Page, PageQuery},
+    tuition::{Tuition, TuitionFilter},
+};
 use tracing::error;
 use use_cases::tuition_service::{err::Error, TuitionService};
+use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid;
 
 use super::err::{HttpError, ToErrResponse};
 use crate::{
     auth::{auth_middleware, UserInfoAuth},
-    err::HttpResult,
+    err::{HttpResult, InternalError, Unauthorized},
 };
 
 pub fn tuition_router(tuition_service: TuitionService, jwt_key: String) -> Router {
-    Router::new()
+    let (router, _) = tuition_routes().split_for_parts();
+
+    router
         .route("/health-tuition", get(alive))
-        .route("/tuitions/pay/{amount}", post(pay_tuition))
-        .route("/tuitions", get(list_tuitions))
-        .route("/tuitions/{user_id}", get(list_user_tuitions))
-        .route("/tuitions/active/{user_id}", get(has_active_tuition))
         .layer(middleware::from_fn_with_state(jwt_key, auth_middleware))
         .with_state(tuition_service)
 }
 
+pub fn tuition_routes() -> OpenApiRouter<TuitionService> {
+    OpenApiRouter::new()
+        .routes(routes!(pay_tuition))
+        .routes(routes!(list_tuitions))
+        .routes(routes!(list_user_tuitions))
+        .routes(routes!(has_active_tuition))
+}
+
 async fn alive() -> &'static str {
     "Tuition service is alive"
 }
+#[utoipa::path(
+    post,
+    path = "/tuitions/pay/{amount}",
+    tag = "tuitions",
+    params(("amount" = f64, Path)),
+    responses(
+        (status = 201, description = "Payment recorded", body = String, content_type = "text/plain"),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn pay_tuition(
     State(tuition_service): State<TuitionService>,
     Path(amount): Path<f64>,
     Extension(user_info): Extension<UserInfoAuth>,
 ) -> HttpResult<impl IntoResponse> {
+    let actor = Actor::from(user_info);
     tuition_service
-        .pay_tuition(user_info.user_id, amount)
+        .pay_tuition(&actor, actor.user_id, amount)
         .await
         .http_err("pay tuition")?;
 
     Ok((StatusCode::CREATED, "Tuition payment recorded successfully"))
 }
 
+#[utoipa::path(
+    get,
+    path = "/tuitions",
+    tag = "tuitions",
+    params(PageQuery, TuitionFilter),
+    responses(
+        (status = 200, description = "Page of tuitions", body = Page<Tuition>),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn list_tuitions(
     State(tuition_service): State<TuitionService>,
-) -> HttpResult<Json<Vec<Tuition>>> {
+    Query(page): Query<PageQuery>,
+    Query(filter): Query<TuitionFilter>,
+) -> HttpResult<Json<Page<Tuition>>> {
     let tuitions = tuition_service
-        .get_all_tuitions()
+        .list_tuitions_page(&filter, &page)
         .await
         .http_err("list tuitions")?;
 
     Ok(Json(tuitions))
 }
 
+#[utoipa::path(
+    get,
+    path = "/tuitions/{user_id}",
+    tag = "tuitions",
+    params(("user_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Tuitions of the user", body = Vec<Tuition>),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn list_user_tuitions(
     State(tuition_service): State<TuitionService>,
     Path(user_id): Path<Uuid>,
@@ -67,6 +117,18 @@ async fn list_user_tuitions(
     Ok(Json(tuitions))
 }
 
+#[utoipa::path(
+    get,
+    path = "/tuitions/active/{user_id}",
+    tag = "tuitions",
+    params(("user_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Whether the user has an active tuition", body = bool),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn has_active_tuition(
     State(tuition_service): State<TuitionService>,
     Path(user_id): Path<Uuid>,
@@ -1,13 +1,16 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
+    middleware,
     response::IntoResponse,
-    routing::{get, post, put},
-    Json, Router,
+    routing::get,
+    Extension, Json, Router,
 };
 
 // Removed super::err::ToErrResponse as HttpError is used directly
-use entities::user::{URol, UserCreation, UserInfo, UserLogInInfo};
+use entities::audit::Actor;
+use entities::pagination::{Page, PageQuery};
+use entities::user::{URol, UserCreation, UserFilter, UserInfo, UserLogInInfo};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::error;
@@ -15,37 +18,88 @@ use use_cases::user_service::{
     err::Error as UserServiceError, // Renamed Error to UserServiceError
     UserService,
 };
+use utoipa::ToSchema;
+use utoipa_axum::{router::OpenApiRouter, routes};
 use uuid::Uuid; // Added for Arc<UserService>
 
 use crate::{
-    auth::generate_jwt,
-    err::{HttpError, HttpResult}, // HttpResult might need adjustment if ToErrResponse was its only user
+    auth::{auth_middleware, generate_jwt, UserInfoAuth},
+    err::{BadRequest, Conflict, HttpError, HttpResult, InternalError, NotFound, Unauthorized},
 };
 
+type UserState = (Arc<UserService>, String);
+
 // Router function now takes Arc<UserService>
 pub fn user_router(user_service: Arc<UserService>, token_key: &str) -> Router {
-    Router::new()
+    let (protected_router, _) = protected_user_routes().split_for_parts();
+    let (public_router, _) = public_user_routes().split_for_parts();
+
+    protected_router
+        // Only the protected routes require a token, registration and log in stay public
+        .route_layer(middleware::from_fn_with_state(
+            token_key.to_string(),
+            auth_middleware,
+        ))
+        .merge(public_router)
         .route("/health-user", get(alive))
-        .route("/users/register", post(register_user)) // Changed route
-        .route("/users/login", post(log_in_user)) // Changed route
-        .route("/users", get(get_all_users))
-        .route("/users/{id_user}", get(get_user_by_id).put(update_user)) // Combined get and put, changed path
-        .route("/users/{id_user}/role", put(update_user_role)) // Changed path for role update
-        .route("/users/{id_user}/verify-email", post(verify_email)) // New endpoint for email verification
         .with_state((user_service, token_key.to_string()))
 }
 
+pub fn user_routes() -> OpenApiRouter<UserState> {
+    protected_user_routes().merge(public_user_routes())
+}
+
+fn protected_user_routes() -> OpenApiRouter<UserState> {
+    OpenApiRouter::new()
+        .routes(routes!(get_all_users))
+        .routes(routes!(get_user_by_id, update_user))
+        .routes(routes!(update_user_role))
+        .routes(routes!(verify_email))
+}
+
+fn public_user_routes() -> OpenApiRouter<UserState> {
+    OpenApiRouter::new()
+        .routes(routes!(register_user))
+        .routes(routes!(log_in_user))
+}
+
+#[utoipa::path(
+    get,
+    path = "/users",
+    tag = "users",
+    params(PageQuery, UserFilter),
+    responses(
+        (status = 200, description = "Page of users", body = Page<UserInfo>),
+        Unauthorized,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn get_all_users(
     State((user_service, _)): State<(Arc<UserService>, String)>,
-) -> HttpResult<Json<Vec<UserInfo>>> {
-    // Return HttpResult
+    Query(page): Query<PageQuery>,
+    Query(filter): Query<UserFilter>,
+) -> HttpResult<Json<Page<UserInfo>>> {
     let users = user_service
-        .get_all_users()
+        .list_users_page(&filter, &page)
         .await
         .http_err("get all users")?;
     Ok(Json(users))
 }
 
+#[utoipa::path(
+    get,
+    path = "/users/{id_user}",
+    tag = "users",
+    params(("id_user" = Uuid, Path)),
+    responses(
+        (status = 200, description = "The user", body = UserInfo),
+        Unauthorized,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn get_user_by_id(
     State((user_service, _)): State<(Arc<UserService>, String)>,
     Path(id_user): Path<Uuid>, // Changed path variable name
@@ -58,32 +112,64 @@ async fn get_user_by_id(
     Ok(Json(user))
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct UpdateUserRolePayload {
     user_rol: URol,
 }
 
+#[utoipa::path(
+    put,
+    path = "/users/{id_user}/role",
+    tag = "users",
+    params(("id_user" = Uuid, Path)),
+    request_body = UpdateUserRolePayload,
+    responses(
+        (status = 200, description = "The updated user", body = UserInfo),
+        Unauthorized,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn update_user_role(
     State((user_service, _)): State<(Arc<UserService>, String)>,
     Path(id_user): Path<Uuid>, // Changed path variable name
+    Extension(user_info): Extension<UserInfoAuth>,
     Json(payload): Json<UpdateUserRolePayload>,
 ) -> HttpResult<Json<UserInfo>> {
     // Return updated UserInfo
     let updated_user = user_service
-        .update_user_role(id_user, payload.user_rol)
+        .update_user_role(&Actor::from(user_info), id_user, payload.user_rol)
         .await
         .http_err("update user role")?;
     Ok(Json(updated_user))
 }
 
+#[utoipa::path(
+    put,
+    path = "/users/{id_user}",
+    tag = "users",
+    params(("id_user" = Uuid, Path)),
+    request_body = UserCreation,
+    responses(
+        (status = 200, description = "The updated user", body = UserInfo),
+        BadRequest,
+        Unauthorized,
+        NotFound,
+        Conflict,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn update_user(
     State((user_service, _)): State<(Arc<UserService>, String)>,
     Path(id_user): Path<Uuid>, // Changed path variable name
+    Extension(user_info): Extension<UserInfoAuth>,
     Json(user_update_payload): Json<UserCreation>, // Use UserCreation as DTO for update
 ) -> HttpResult<Json<UserInfo>> {
     // Return updated UserInfo
     let updated_user = user_service
-        .update_user(id_user, user_update_payload)
+        .update_user(&Actor::from(user_info), id_user, user_update_payload)
         .await
         .http_err("update user")?;
     Ok(Json(updated_user))
@@ -94,7 +180,7 @@ async fn alive() -> Json<String> {
     Json("User service is alive".to_string())
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug, ToSchema)]
 struct ApiLogInResponse {
     // Renamed to avoid conflict with service layer response
     token: String,
@@ -102,6 +188,19 @@ struct ApiLogInResponse {
     user_rol: URol,
 }
 
+#[utoipa::path(
+    post,
+    path = "/users/login",
+    tag = "users",
+    request_body = UserLogInInfo,
+    responses(
+        (status = 200, description = "Token for the other endpoints", body = ApiLogInResponse),
+        BadRequest,
+        Unauthorized,
+        NotFound,
+        InternalError
+    )
+)]
 async fn log_in_user(
     State((user_service, token_key)): State<(Arc<UserService>, String)>,
     Json(user_log_in_info): Json<UserLogInInfo>,
@@ -128,6 +227,18 @@ async fn log_in_user(
     }))
 }
 
+#[utoipa::path(
+    post,
+    path = "/users/register",
+    tag = "users",
+    request_body = UserCreation,
+    responses(
+        (status = 200, description = "The registered user", body = UserInfo),
+        BadRequest,
+        Conflict,
+        InternalError
+    )
+)]
 async fn register_user(
     State((user_service, _)): State<(Arc<UserService>, String)>,
     Json(user_creation): Json<UserCreation>,
@@ -140,18 +251,34 @@ async fn register_user(
     Ok(Json(new_user_info)) // Axum will default to 200 OK, change to (StatusCode::CREATED, Json(...)) if needed
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct VerifyEmailPayload {
     code: String,
 }
 
+#[utoipa::path(
+    post,
+    path = "/users/{id_user}/verify-email",
+    tag = "users",
+    params(("id_user" = Uuid, Path)),
+    request_body = VerifyEmailPayload,
+    responses(
+        (status = 200, description = "Email verified", body = String, content_type = "text/plain"),
+        BadRequest,
+        Unauthorized,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn verify_email(
     State((user_service, _)): State<(Arc<UserService>, String)>,
     Path(id_user): Path<Uuid>,
+    Extension(user_info): Extension<UserInfoAuth>,
     Json(payload): Json<VerifyEmailPayload>,
 ) -> HttpResult<impl IntoResponse> {
     user_service
-        .verify_email_with_code(id_user, &payload.code)
+        .verify_email_with_code(&Actor::from(user_info), id_user, &payload.code)
         .await
         .http_err("verify email")?;
     Ok((StatusCode::OK, "Email verified successfully"))
@@ -0,0 +1,268 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::get,
+    Extension, Json, Router,
+};
+use entities::{
+    audit::Actor,
+    pagination::{Page, PageQuery},
+    webhook::{CreatedWebhook, Webhook, WebhookCreation, WebhookDelivery, WebhookDeliveryFilter},
+};
+use tracing::error;
+use use_cases::webhook_service::{err::Error, WebhookService};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+use crate::{
+    auth::{auth_middleware, require_admin, UserInfoAuth},
+    err::{BadRequest, Forbidden, HttpError, HttpResult, InternalError, NotFound, Unauthorized},
+};
+
+pub fn webhook_router(webhook_service: WebhookService, jwt_key: String) -> Router {
+    let (router, _) = webhook_routes().split_for_parts();
+
+    router
+        .route("/health-webhook", get(alive))
+        .layer(middleware::from_fn_with_state(jwt_key, auth_middleware))
+        .with_state(webhook_service)
+}
+
+pub fn webhook_routes() -> OpenApiRouter<WebhookService> {
+    OpenApiRouter::new()
+        .routes(routes!(create_webhook, list_webhooks))
+        .routes(routes!(get_webhook, delete_webhook))
+        .routes(routes!(set_webhook_active))
+        .routes(routes!(list_deliveries))
+        .routes(routes!(redeliver))
+}
+
+async fn alive() -> &'static str {
+    "Webhook service is alive"
+}
+
+/// The secret used to sign the payloads is returned here only, to be copied to the receiver
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    tag = "webhooks",
+    request_body = WebhookCreation,
+    responses(
+        (status = 200, description = "The created webhook with its secret, which isn't shown again", body = CreatedWebhook),
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn create_webhook(
+    State(webhook_service): State<WebhookService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Json(creation): Json<WebhookCreation>,
+) -> HttpResult<Json<CreatedWebhook>> {
+    require_admin(&user_info, "manage webhooks")?;
+
+    let webhook = webhook_service
+        .create_webhook(&Actor::from(user_info), creation)
+        .await
+        .http_err("create webhook")?;
+
+    Ok(Json(webhook))
+}
+
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    tag = "webhooks",
+    responses(
+        (status = 200, description = "Every webhook", body = Vec<Webhook>),
+        Unauthorized,
+        Forbidden,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn list_webhooks(
+    State(webhook_service): State<WebhookService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+) -> HttpResult<Json<Vec<Webhook>>> {
+    require_admin(&user_info, "manage webhooks")?;
+
+    let webhooks = webhook_service
+        .list_webhooks()
+        .await
+        .http_err("list webhooks")?;
+
+    Ok(Json(webhooks))
+}
+
+#[utoipa::path(
+    get,
+    path = "/webhooks/{id}",
+    tag = "webhooks",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "The webhook", body = Webhook),
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_webhook(
+    State(webhook_service): State<WebhookService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Path(id): Path<Uuid>,
+) -> HttpResult<Json<Webhook>> {
+    require_admin(&user_info, "manage webhooks")?;
+
+    let webhook = webhook_service
+        .get_webhook(id)
+        .await
+        .http_err("get webhook")?;
+
+    Ok(Json(webhook))
+}
+
+#[utoipa::path(
+    put,
+    path = "/webhooks/{id}/active/{active}",
+    tag = "webhooks",
+    params(("id" = Uuid, Path), ("active" = bool, Path)),
+    responses(
+        (status = 200, description = "The updated webhook", body = Webhook),
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn set_webhook_active(
+    State(webhook_service): State<WebhookService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Path((id, active)): Path<(Uuid, bool)>,
+) -> HttpResult<Json<Webhook>> {
+    require_admin(&user_info, "manage webhooks")?;
+
+    let webhook = webhook_service
+        .set_webhook_active(&Actor::from(user_info), id, active)
+        .await
+        .http_err("set webhook active")?;
+
+    Ok(Json(webhook))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{id}",
+    tag = "webhooks",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 204, description = "Webhook deleted"),
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn delete_webhook(
+    State(webhook_service): State<WebhookService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Path(id): Path<Uuid>,
+) -> HttpResult<StatusCode> {
+    require_admin(&user_info, "manage webhooks")?;
+
+    webhook_service
+        .delete_webhook(&Actor::from(user_info), id)
+        .await
+        .http_err("delete webhook")?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/webhook-deliveries",
+    tag = "webhooks",
+    params(PageQuery, WebhookDeliveryFilter),
+    responses(
+        (status = 200, description = "Page of deliveries", body = Page<WebhookDelivery>),
+        Unauthorized,
+        Forbidden,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn list_deliveries(
+    State(webhook_service): State<WebhookService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Query(page): Query<PageQuery>,
+    Query(filter): Query<WebhookDeliveryFilter>,
+) -> HttpResult<Json<Page<WebhookDelivery>>> {
+    require_admin(&user_info, "manage webhooks")?;
+
+    let deliveries = webhook_service
+        .list_deliveries_page(&filter, &page)
+        .await
+        .http_err("list webhook deliveries")?;
+
+    Ok(Json(deliveries))
+}
+
+/// Returns the delivery with the outcome of the new attempt
+#[utoipa::path(
+    post,
+    path = "/webhook-deliveries/{id}/redeliver",
+    tag = "webhooks",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "The delivery after the new attempt", body = WebhookDelivery),
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalError
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn redeliver(
+    State(webhook_service): State<WebhookService>,
+    Extension(user_info): Extension<UserInfoAuth>,
+    Path(id): Path<Uuid>,
+) -> HttpResult<Json<WebhookDelivery>> {
+    require_admin(&user_info, "manage webhooks")?;
+
+    let delivery = webhook_service
+        .redeliver(id)
+        .await
+        .http_err("redeliver webhook")?;
+
+    Ok(Json(delivery))
+}
+
+impl<T> HttpError<T> for Result<T, Error> {
+    fn http_err(self, endpoint_name: &str) -> crate::err::HttpResult<T> {
+        self.map_err(|err| {
+            error!("Error in webhook endpoint ({}): {}", endpoint_name, err);
+            let (status_code, message) = match &err {
+                Error::WebhookNotFound | Error::DeliveryNotFound => {
+                    (StatusCode::NOT_FOUND, err.to_string())
+                }
+                Error::InvalidUrl | Error::MissingEventKinds => {
+                    (StatusCode::BAD_REQUEST, err.to_string())
+                }
+                Error::SendFailed(_) => (StatusCode::BAD_GATEWAY, err.to_string()),
+                Error::UnknownDatabaseError(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Error processing the webhook request.".to_string(),
+                ),
+            };
+            (status_code, message).into_response()
+        })
+    }
+}
@@ -0,0 +1,210 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use entities::notification::{NotificationChannel, NotificationMessage, Recipient};
+use serde_json::json;
+use use_cases::notification_service::err::{Error::DeliveryFailed, Result};
+use use_cases::notification_service::notifier_trait::Notifier;
+
+const REQUEST_TIMEOUT_SECONDS: u64 = 10;
+const TWILIO_API_URL: &str = "https://api.twilio.com/2010-04-01";
+
+fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECONDS))
+        .build()
+        .expect("Error building the notifier http client")
+}
+
+async fn check_status(response: reqwest::Response) -> Result<()> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+
+    let body = response.text().await.unwrap_or_default();
+    Err(DeliveryFailed(format!(
+        "Provider answered {status}: {body}"
+    )))
+}
+
+/// Sends emails through a transactional email HTTP API that takes a bearer key and a JSON body
+/// `{"from", "to", "subject", "text"}`, e.g. Resend's `https://api.resend.com/emails`
+pub struct HttpEmailNotifier {
+    client: reqwest::Client,
+    api_url: String,
+    api_key: String,
+    from: String,
+}
+
+impl HttpEmailNotifier {
+    pub fn new(api_url: &str, api_key: &str, from: &str) -> Self {
+        Self {
+            client: http_client(),
+            api_url: api_url.to_string(),
+            api_key: api_key.to_string(),
+            from: from.to_string(),
+        }
+    }
+
+    fn payload(&self, recipient: &Recipient, message: &NotificationMessage) -> String {
+        json!({
+            "from": self.from,
+            "to": [recipient.email],
+            "subject": message.subject,
+            "text": message.body,
+        })
+        .to_string()
+    }
+}
+
+#[async_trait]
+impl Notifier for HttpEmailNotifier {
+    fn channel(&self) -> NotificationChannel {
+        NotificationChannel::EMAIL
+    }
+
+    async fn send(&self, recipient: &Recipient, message: &NotificationMessage) -> Result<()> {
+        let response = self
+            .client
+            .post(&self.api_url)
+            .bearer_auth(&self.api_key)
+            .header("Content-Type", "application/json")
+            .body(self.payload(recipient, message))
+            .send()
+            .await
+            .map_err(|err| DeliveryFailed(format!("{err}")))?;
+
+        check_status(response).await
+    }
+}
+
+/// Sends SMS through the Twilio messages API
+pub struct TwilioSmsNotifier {
+    client: reqwest::Client,
+    account_sid: String,
+    auth_token: String,
+    from: String,
+}
+
+impl TwilioSmsNotifier {
+    pub fn new(account_sid: &str, auth_token: &str, from: &str) -> Self {
+        Self {
+            client: http_client(),
+            account_sid: account_sid.to_string(),
+            auth_token: auth_token.to_string(),
+            from: from.to_string(),
+        }
+    }
+}
+
+/// E.164 number of the recipient, `+{dialing code}{number}`. Numbers already starting with `+`
+/// are kept, and the country code must be a dialing code since there's no way to send to a
+/// country name
+fn phone_number(recipient: &Recipient) -> Option<String> {
+    let number: String = recipient
+        .phone_number
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect();
+    if number.starts_with('+') {
+        return Some(number);
+    }
+
+    let dialing_code = recipient.country_code.trim().trim_start_matches('+');
+    if dialing_code.is_empty() || !dialing_code.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    Some(format!("+{dialing_code}{number}"))
+}
+
+#[async_trait]
+impl Notifier for TwilioSmsNotifier {
+    fn channel(&self) -> NotificationChannel {
+        NotificationChannel::SMS
+    }
+
+    async fn send(&self, recipient: &Recipient, message: &NotificationMessage) -> Result<()> {
+        let to = phone_number(recipient).ok_or_else(|| {
+            DeliveryFailed(format!(
+                "No dialing code for the country {}",
+                recipient.country_code
+            ))
+        })?;
+
+        let response = self
+            .client
+            .post(format!(
+                "{TWILIO_API_URL}/Accounts/{}/Messages.json",
+                self.account_sid
+            ))
+            .basic_auth(&self.account_sid, Some(&self.auth_token))
+            .form(&[
+                ("To", to.as_str()),
+                ("From", self.from.as_str()),
+                ("Body", message.body.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(|err| DeliveryFailed(format!("{err}")))?;
+
+        check_status(response).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn recipient(phone_number: &str, country_code: &str) -> Recipient {
+        Recipient {
+            id_user: Uuid::new_v4(),
+            first_name: "Ana".to_string(),
+            email: "ana@example.com".to_string(),
+            phone_number: phone_number.to_string(),
+            country_code: country_code.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_email_payload() {
+        let notifier =
+            HttpEmailNotifier::new("https://mail.example.com", "key", "club@example.com");
+        let message = NotificationMessage {
+            subject: "Subject".to_string(),
+            body: "Body".to_string(),
+        };
+
+        let payload: serde_json::Value =
+            serde_json::from_str(&notifier.payload(&recipient("600000000", "34"), &message))
+                .unwrap();
+        assert_eq!(
+            payload,
+            json!({
+                "from": "club@example.com",
+                "to": ["ana@example.com"],
+                "subject": "Subject",
+                "text": "Body",
+            })
+        );
+    }
+
+    #[test]
+    fn test_phone_number() {
+        assert_eq!(
+            phone_number(&recipient("600 000-000", "34")).as_deref(),
+            Some("+34600000000")
+        );
+        assert_eq!(
+            phone_number(&recipient("3001234567", "+57")).as_deref(),
+            Some("+573001234567")
+        );
+        assert_eq!(
+            phone_number(&recipient("+573001234567", "CO")).as_deref(),
+            Some("+573001234567")
+        );
+        assert_eq!(phone_number(&recipient("3001234567", "CO")), None);
+    }
+}
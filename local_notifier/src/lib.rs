@@ -0,0 +1,141 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use entities::notification::{NotificationChannel, NotificationMessage, Recipient};
+use serde_json::json;
+use tokio::{fs::OpenOptions, io::AsyncWriteExt};
+use tracing::info;
+use use_cases::notification_service::err::{
+    Error::{DeliveryFailed, ProviderNotConfigured},
+    Result,
+};
+use use_cases::notification_service::notifier_trait::Notifier;
+
+/// Stands in for a channel without a provider. Nothing is delivered, so every message is
+/// recorded as skipped, and the log only tells which member it was meant for, never their
+/// contact details or the content
+pub struct LogNotifier {
+    channel: NotificationChannel,
+}
+
+impl LogNotifier {
+    pub fn new(channel: NotificationChannel) -> Self {
+        Self { channel }
+    }
+}
+
+#[async_trait]
+impl Notifier for LogNotifier {
+    fn channel(&self) -> NotificationChannel {
+        self.channel
+    }
+
+    async fn send(&self, recipient: &Recipient, _message: &NotificationMessage) -> Result<()> {
+        info!(
+            "[{}] No provider configured, skipped message to {}",
+            self.channel, recipient.id_user
+        );
+
+        Err(ProviderNotConfigured(self.channel.to_string()))
+    }
+}
+
+/// Appends every message as a JSON line to a file, handy to inspect deliveries in tests
+pub struct FileNotifier {
+    channel: NotificationChannel,
+    path: PathBuf,
+}
+
+impl FileNotifier {
+    pub fn new(channel: NotificationChannel, path: impl Into<PathBuf>) -> Self {
+        Self {
+            channel,
+            path: path.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for FileNotifier {
+    fn channel(&self) -> NotificationChannel {
+        self.channel
+    }
+
+    async fn send(&self, recipient: &Recipient, message: &NotificationMessage) -> Result<()> {
+        let line = json!({
+            "channel": self.channel,
+            "recipient": recipient,
+            "message": message,
+        });
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|err| DeliveryFailed(format!("{err}")))?;
+        file.write_all(format!("{line}\n").as_bytes())
+            .await
+            .map_err(|err| DeliveryFailed(format!("{err}")))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_log_notifier_delivers_nothing() {
+        let notifier = LogNotifier::new(NotificationChannel::SMS);
+        let recipient = Recipient {
+            id_user: Uuid::new_v4(),
+            first_name: "Ana".to_string(),
+            email: "ana@example.com".to_string(),
+            phone_number: "600000000".to_string(),
+            country_code: "34".to_string(),
+        };
+        let message = NotificationMessage {
+            subject: "Subject".to_string(),
+            body: "Body".to_string(),
+        };
+
+        assert!(matches!(
+            notifier.send(&recipient, &message).await,
+            Err(ProviderNotConfigured(channel)) if channel == "SMS"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_file_notifier_appends_lines() {
+        let path = std::env::temp_dir().join(format!("notifier-{}.jsonl", Uuid::new_v4()));
+        let notifier = FileNotifier::new(NotificationChannel::EMAIL, &path);
+        let recipient = Recipient {
+            id_user: Uuid::new_v4(),
+            first_name: "Ana".to_string(),
+            email: "ana@example.com".to_string(),
+            phone_number: "600000000".to_string(),
+            country_code: "34".to_string(),
+        };
+        let message = NotificationMessage {
+            subject: "Subject".to_string(),
+            body: "Body".to_string(),
+        };
+
+        notifier.send(&recipient, &message).await.unwrap();
+        notifier.send(&recipient, &message).await.unwrap();
+
+        let content = tokio::fs::read_to_string(&path).await.unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["channel"], "EMAIL");
+        assert_eq!(lines[0]["message"]["subject"], "Subject");
+
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+}
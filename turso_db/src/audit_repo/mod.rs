@@ -0,0 +1,208 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use entities::audit::{AuditEntity, AuditEntry, AuditFilter};
+use entities::datetime_serde;
+use entities::pagination::{Page, PageQuery};
+use entities::user::URol;
+use libsql::params;
+use serde::Deserialize;
+use use_cases::audit_service::{
+    err::{Error, Result},
+    repository_trait::AuditRepository,
+};
+use uuid::Uuid;
+
+use crate::pagination::{PageSql, SqlFilter};
+use crate::TursoDb;
+
+const AUDIT_PAGE_SQL: PageSql<'static> = PageSql {
+    select: "SELECT id_audit, actor_id, actor_rol, action, entity, entity_id, before_snapshot,
+after_snapshot, created_at
+FROM audit_log
+WHERE 1 = 1",
+    count: "SELECT COUNT(*) AS count FROM audit_log WHERE 1 = 1",
+    sortable_columns: &["created_at", "action", "entity", "actor_id"],
+    default_sort_column: "created_at",
+    primary_key: "id_audit",
+};
+
+/// Snapshots are stored as JSON text
+#[derive(Deserialize)]
+struct AuditEntryRow {
+    id_audit: Uuid,
+    actor_id: Uuid,
+    actor_rol: URol,
+    action: String,
+    entity: AuditEntity,
+    entity_id: String,
+    before_snapshot: Option<String>,
+    after_snapshot: Option<String>,
+    #[serde(with = "datetime_serde")]
+    created_at: NaiveDateTime,
+}
+
+impl From<AuditEntryRow> for AuditEntry {
+    fn from(row: AuditEntryRow) -> Self {
+        Self {
+            id_audit: row.id_audit,
+            actor_id: row.actor_id,
+            actor_rol: row.actor_rol,
+            action: row.action,
+            entity: row.entity,
+            entity_id: row.entity_id,
+            before: row
+                .before_snapshot
+                .and_then(|snapshot| serde_json::from_str(&snapshot).ok()),
+            after: row
+                .after_snapshot
+                .and_then(|snapshot| serde_json::from_str(&snapshot).ok()),
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[async_trait]
+impl AuditRepository for TursoDb {
+    async fn create_audit_entry(&self, entry: &AuditEntry) -> Result<()> {
+        self.execute_with_error(
+            "INSERT INTO audit_log (
+                id_audit, actor_id, actor_rol, action, entity, entity_id, before_snapshot,
+                after_snapshot, created_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                entry.id_audit.to_string(),
+                entry.actor_id.to_string(),
+                entry.actor_rol.to_string(),
+                entry.action.clone(),
+                entry.entity.to_string(),
+                entry.entity_id.clone(),
+                entry.before.as_ref().map(|snapshot| snapshot.to_string()),
+                entry.after.as_ref().map(|snapshot| snapshot.to_string()),
+                entry.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_audit_entries_page(
+        &self,
+        filter: &AuditFilter,
+        page: &PageQuery,
+    ) -> Result<Page<AuditEntry>> {
+        let mut sql_filter = SqlFilter::new();
+        sql_filter
+            .push_if("actor_id = ?", filter.actor_id.map(|id| id.to_string()))
+            .push_if("action = ?", filter.action.clone())
+            .push_if("entity = ?", filter.entity.map(|entity| entity.to_string()))
+            .push_if("entity_id = ?", filter.entity_id.clone())
+            .push_if(
+                "created_at >= ?",
+                filter
+                    .created_from
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+            )
+            .push_if(
+                "created_at <= ?",
+                filter
+                    .created_to
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+            );
+
+        let page: Page<AuditEntryRow> = self
+            .query_page_with_error(
+                &AUDIT_PAGE_SQL,
+                &sql_filter,
+                page,
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+
+        Ok(page.map(AuditEntry::from))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::future::Future;
+
+    use super::*;
+    use chrono::Utc;
+    use rstest::{fixture, rstest};
+    use serde_json::json;
+
+    #[fixture]
+    async fn repository() -> TursoDb {
+        crate::TestDbBuilder::create().await.build()
+    }
+
+    fn audit_entry(entity: AuditEntity, before: Option<serde_json::Value>) -> AuditEntry {
+        AuditEntry {
+            id_audit: Uuid::new_v4(),
+            actor_id: Uuid::new_v4(),
+            actor_rol: URol::ADMIN,
+            action: "UPDATE_USER_ROLE".to_string(),
+            entity,
+            entity_id: Uuid::new_v4().to_string(),
+            before,
+            after: Some(json!({ "user_rol": "TRAINER" })),
+            created_at: NaiveDateTime::parse_from_str(
+                &Utc::now()
+                    .naive_utc()
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string(),
+                "%Y-%m-%d %H:%M:%S",
+            )
+            .unwrap(),
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_create_and_list_audit_entries(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+
+        let user_entry = audit_entry(AuditEntity::USER, Some(json!({ "user_rol": "USER" })));
+        let court_entry = audit_entry(AuditEntity::COURT, None);
+
+        db.create_audit_entry(&user_entry)
+            .await
+            .expect("Error creating user audit entry");
+        db.create_audit_entry(&court_entry)
+            .await
+            .expect("Error creating court audit entry");
+
+        let filter = AuditFilter {
+            entity: Some(AuditEntity::USER),
+            ..AuditFilter::default()
+        };
+        let page = db
+            .list_audit_entries_page(&filter, &PageQuery::default())
+            .await
+            .expect("Error listing audit entries");
+
+        assert_eq!(page.total, 1);
+        assert_eq!(page.items, vec![user_entry]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_audit_log_is_append_only(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+
+        let entry = audit_entry(AuditEntity::USER, None);
+        db.create_audit_entry(&entry)
+            .await
+            .expect("Error creating audit entry");
+
+        let result = db
+            .execute_with_error(
+                "DELETE FROM audit_log WHERE id_audit = ?1",
+                params![entry.id_audit.to_string()],
+                Error::UnknownDatabaseError,
+            )
+            .await;
+
+        assert!(result.is_err());
+    }
+}
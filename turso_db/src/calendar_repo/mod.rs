@@ -0,0 +1,302 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use entities::calendar::{CalendarEntry, CalendarFeed};
+use libsql::params;
+use use_cases::calendar_service::{
+    err::{Error, Result},
+    repository_trait::CalendarRepository,
+};
+use uuid::Uuid;
+
+use crate::TursoDb;
+
+/// Names of the courts the training `t` holds
+const TRAINING_COURTS_SQL: &str = "(SELECT GROUP_CONCAT(c.court_name, ', ') FROM court c
+     WHERE c.id_court IN (SELECT r.id_court FROM court_reservation r
+                          WHERE r.id_training = t.id_training AND r.deleted = 0))";
+/// Names of the courts the tournament `tt` holds
+const TOURNAMENT_COURTS_SQL: &str = "(SELECT GROUP_CONCAT(c.court_name, ', ') FROM court c
+     WHERE c.id_court IN (SELECT r.id_court FROM court_reservation r
+                          WHERE r.id_tournament = tt.id_tournament AND r.deleted = 0))";
+
+#[async_trait]
+impl CalendarRepository for TursoDb {
+    async fn create_feed(&self, feed: &CalendarFeed) -> Result<()> {
+        self.execute_with_error(
+            "INSERT INTO calendar_feed (token, id_user, kind, id_subject, created_datetime, deleted)
+             VALUES (?1, ?2, ?3, ?4, ?5, 0)",
+            params![
+                feed.token.clone(),
+                feed.id_user.to_string(),
+                feed.kind.to_string(),
+                feed.id_subject.to_string(),
+                feed.created_datetime.format("%Y-%m-%d %H:%M:%S").to_string()
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn get_feed(&self, token: &str) -> Result<Option<CalendarFeed>> {
+        self.query_one_with_error(
+            "SELECT token, id_user, kind, id_subject, created_datetime FROM calendar_feed
+             WHERE token = ?1 AND deleted = 0",
+            params![token],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_user_feeds(&self, id_user: Uuid) -> Result<Vec<CalendarFeed>> {
+        self.query_many_with_error(
+            "SELECT token, id_user, kind, id_subject, created_datetime FROM calendar_feed
+             WHERE id_user = ?1 AND deleted = 0
+             ORDER BY created_datetime DESC",
+            params![id_user.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn revoke_feed(&self, token: &str) -> Result<()> {
+        self.execute_with_error(
+            "UPDATE calendar_feed SET deleted = 1 WHERE token = ?1",
+            params![token],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_court_entries(
+        &self,
+        id_court: Uuid,
+        from: NaiveDateTime,
+    ) -> Result<Vec<CalendarEntry>> {
+        self.query_many_with_error(
+            "SELECT 'COURT_RESERVATION' AS kind, r.id_court_reservation AS id_source,
+                    COALESCE(t.name, tt.name) AS name, c.court_name AS location,
+                    r.start_reservation_datetime AS start_datetime,
+                    r.end_reservation_datetime AS end_datetime, r.deleted AS cancelled
+             FROM court_reservation r
+             JOIN court c ON c.id_court = r.id_court
+             LEFT JOIN training t ON t.id_training = r.id_training
+             LEFT JOIN tournament tt ON tt.id_tournament = r.id_tournament
+             WHERE r.id_court = ?1 AND r.end_reservation_datetime > ?2
+             ORDER BY r.start_reservation_datetime",
+            params![
+                id_court.to_string(),
+                from.format("%Y-%m-%d %H:%M:%S").to_string()
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_trainer_entries(
+        &self,
+        trainer_id: Uuid,
+        from: NaiveDateTime,
+    ) -> Result<Vec<CalendarEntry>> {
+        self.query_many_with_error(
+            &format!(
+                "SELECT 'TRAINING' AS kind, t.id_training AS id_source, t.name AS name,
+                        {TRAINING_COURTS_SQL} AS location, t.start_datetime, t.end_datetime,
+                        t.deleted AS cancelled
+                 FROM training t
+                 WHERE t.trainer_id = ?1 AND t.end_datetime > ?2
+                 ORDER BY t.start_datetime"
+            ),
+            params![
+                trainer_id.to_string(),
+                from.format("%Y-%m-%d %H:%M:%S").to_string()
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_member_entries(
+        &self,
+        id_user: Uuid,
+        from: NaiveDateTime,
+    ) -> Result<Vec<CalendarEntry>> {
+        self.query_many_with_error(
+            &format!(
+                "SELECT 'TRAINING' AS kind, t.id_training AS id_source, t.name AS name,
+                        {TRAINING_COURTS_SQL} AS location, t.start_datetime, t.end_datetime,
+                        t.deleted AS cancelled
+                 FROM training_registration tr
+                 JOIN training t ON t.id_training = tr.id_training
+                 WHERE tr.id_user = ?1 AND t.end_datetime > ?2
+                 UNION ALL
+                 SELECT 'TOURNAMENT' AS kind, tt.id_tournament AS id_source, tt.name AS name,
+                        {TOURNAMENT_COURTS_SQL} AS location, tt.start_datetime, tt.end_datetime,
+                        MAX(tt.deleted, treg.deleted) AS cancelled
+                 FROM tournament_registration treg
+                 JOIN tournament tt ON tt.id_tournament = treg.id_tournament
+                 WHERE treg.id_user = ?1 AND tt.end_datetime > ?2
+                 ORDER BY start_datetime"
+            ),
+            params![
+                id_user.to_string(),
+                from.format("%Y-%m-%d %H:%M:%S").to_string()
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::future::Future;
+
+    use super::*;
+    use chrono::{Duration, Utc};
+    use entities::calendar::{CalendarEntryKind, CalendarFeedKind};
+    use entities::court::{Court, CourtReservation, CourtSurface};
+    use entities::training::Training;
+    use rstest::{fixture, rstest};
+    use use_cases::court_service::repository_trait::{CourtRepository, CourtReservationRepository};
+    use use_cases::training_service::repository_trait::TrainingRepository;
+
+    #[fixture]
+    async fn repository() -> TursoDb {
+        crate::TestDbBuilder::create_full().await
+    }
+
+    fn test_datetime() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(
+            &Utc::now()
+                .naive_utc()
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+            "%Y-%m-%d %H:%M:%S",
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_feed_lifecycle(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let id_user = Uuid::new_v4();
+        db.create_test_user(id_user)
+            .await
+            .expect("Error creating user");
+        let feed = CalendarFeed {
+            token: Uuid::new_v4().simple().to_string(),
+            id_user,
+            kind: CalendarFeedKind::MEMBER,
+            id_subject: id_user,
+            created_datetime: test_datetime(),
+        };
+
+        db.create_feed(&feed).await.expect("Error creating feed");
+        assert_eq!(db.get_feed(&feed.token).await.unwrap(), Some(feed.clone()));
+        assert_eq!(
+            db.list_user_feeds(id_user).await.unwrap(),
+            vec![feed.clone()]
+        );
+
+        db.revoke_feed(&feed.token)
+            .await
+            .expect("Error revoking feed");
+        assert_eq!(db.get_feed(&feed.token).await.unwrap(), None);
+        assert!(db.list_user_feeds(id_user).await.unwrap().is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_deleted_sessions_are_cancelled_entries(
+        repository: impl Future<Output = TursoDb>,
+    ) {
+        let db = repository.await;
+        let court = Court {
+            id_court: Uuid::new_v4(),
+            court_name: "Central".to_string(),
+            surface: CourtSurface::CLAY,
+            indoor: false,
+            has_lighting: true,
+            capacity: 4,
+            default_slot_minutes: 60,
+            active: true,
+        };
+        db.create_court(&court).await.expect("Error creating court");
+
+        let start = test_datetime() + Duration::days(1);
+        let trainer_id = Uuid::new_v4();
+        let id_category = Uuid::new_v4();
+        db.create_test_user(trainer_id)
+            .await
+            .expect("Error creating trainer");
+        db.create_test_category(id_category)
+            .await
+            .expect("Error creating category");
+        let training = Training {
+            id_training: Uuid::new_v4(),
+            name: "Juniors".to_string(),
+            id_category,
+            trainer_id,
+            start_datetime: start,
+            end_datetime: start + Duration::hours(2),
+            minimum_payment: 0.0,
+            max_participants: None,
+        };
+        db.create_training(&training, None)
+            .await
+            .expect("Error creating training");
+        let reservation = CourtReservation {
+            id_court_reservation: Uuid::new_v4(),
+            id_court: court.id_court,
+            start_reservation_datetime: training.start_datetime,
+            end_reservation_datetime: training.end_datetime,
+            id_training: Some(training.id_training),
+            id_tournament: None,
+            id_owner: None,
+        };
+        db.create_reservation(&reservation)
+            .await
+            .expect("Error creating reservation");
+
+        let from = test_datetime();
+        let entry = CalendarEntry {
+            kind: CalendarEntryKind::TRAINING,
+            id_source: training.id_training,
+            name: Some("Juniors".to_string()),
+            location: Some("Central".to_string()),
+            start_datetime: training.start_datetime,
+            end_datetime: training.end_datetime,
+            cancelled: false,
+        };
+        assert_eq!(
+            db.list_trainer_entries(trainer_id, from).await.unwrap(),
+            vec![entry.clone()]
+        );
+        assert_eq!(
+            db.list_court_entries(court.id_court, from).await.unwrap(),
+            vec![CalendarEntry {
+                kind: CalendarEntryKind::COURT_RESERVATION,
+                id_source: reservation.id_court_reservation,
+                ..entry.clone()
+            }]
+        );
+
+        db.delete_reservation_by_event_id(training.id_training, "training")
+            .await
+            .expect("Error deleting reservation");
+        db.delete_training(training.id_training, None)
+            .await
+            .expect("Error deleting training");
+
+        assert_eq!(
+            db.list_trainer_entries(trainer_id, from).await.unwrap(),
+            vec![CalendarEntry {
+                location: None,
+                cancelled: true,
+                ..entry.clone()
+            }]
+        );
+        assert!(db.list_court_entries(court.id_court, from).await.unwrap()[0].cancelled);
+    }
+}
@@ -213,6 +213,17 @@ impl UserCategoryRepository for TursoDb {
 
         Ok(categories)
     }
+
+    async fn list_category_members(&self, id_category: Uuid) -> Result<Vec<UserCategory>> {
+        self.query_many_with_error(
+            "SELECT id_user, id_category, user_level
+             FROM user_category
+             WHERE id_category = ?1 AND deleted = 0",
+            params![id_category.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
 }
 
 #[async_trait]
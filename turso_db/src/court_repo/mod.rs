@@ -1,21 +1,69 @@
 use async_trait::async_trait;
 use chrono::NaiveDateTime;
-use entities::court::{Court, CourtReservation};
-use libsql::params;
+use entities::court::{Court, CourtClosure, CourtFilter, CourtOpeningHours, CourtReservation};
+use entities::court_attendance::{CourtCancellation, CourtCheckIn, CourtNoShow};
+use entities::court_pricing::{CourtCharge, CourtPriceRule};
+use entities::event::OutboxEvent;
+use entities::pagination::{Page, PageQuery};
+use libsql::{params, params::IntoParams, TransactionBehavior};
+use serde::Deserialize;
 use use_cases::court_service::{
     err::{Error, Result},
-    repository_trait::{CourtRepository, CourtReservationRepository},
+    repository_trait::{
+        CourtAttendanceRepository, CourtPricingRepository, CourtRepository,
+        CourtReservationRepository, CourtScheduleRepository,
+    },
 };
 use uuid::Uuid;
 
+use crate::outbox_repo::insert_outbox_event;
+use crate::pagination::{PageSql, SqlFilter};
 use crate::TursoDb;
 
+const COURT_PAGE_SQL: PageSql<'static> = PageSql {
+    select: "SELECT id_court, court_name, surface, indoor, has_lighting, capacity, default_slot_minutes, active FROM court WHERE deleted = 0",
+    count: "SELECT COUNT(*) AS count FROM court WHERE deleted = 0",
+    sortable_columns: &["court_name"],
+    default_sort_column: "court_name",
+    primary_key: "id_court",
+};
+
 #[async_trait]
 impl CourtRepository for TursoDb {
     async fn create_court(&self, court: &Court) -> Result<()> {
         self.execute_with_error(
-            "INSERT INTO court (id_court, court_name, deleted) VALUES (?1, ?2, 0)",
-            params![court.id_court.to_string(), court.court_name.clone()],
+            "INSERT INTO court (id_court, court_name, surface, indoor, has_lighting, capacity, default_slot_minutes, active, deleted)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 0)",
+            params![
+                court.id_court.to_string(),
+                court.court_name.clone(),
+                court.surface.to_string(),
+                court.indoor,
+                court.has_lighting,
+                court.capacity,
+                court.default_slot_minutes,
+                court.active
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn update_court(&self, court: &Court) -> Result<()> {
+        self.execute_with_error(
+            "UPDATE court SET court_name = ?2, surface = ?3, indoor = ?4, has_lighting = ?5,
+             capacity = ?6, default_slot_minutes = ?7, active = ?8
+             WHERE id_court = ?1 AND deleted = 0",
+            params![
+                court.id_court.to_string(),
+                court.court_name.clone(),
+                court.surface.to_string(),
+                court.indoor,
+                court.has_lighting,
+                court.capacity,
+                court.default_slot_minutes,
+                court.active
+            ],
             Error::UnknownDatabaseError,
         )
         .await
@@ -23,7 +71,7 @@ impl CourtRepository for TursoDb {
 
     async fn get_court_by_id(&self, id_court: Uuid) -> Result<Option<Court>> {
         self.query_one_with_error(
-            "SELECT id_court, court_name FROM court WHERE id_court = ?1 AND deleted = 0",
+            "SELECT id_court, court_name, surface, indoor, has_lighting, capacity, default_slot_minutes, active FROM court WHERE id_court = ?1 AND deleted = 0",
             params![id_court.to_string()],
             Error::UnknownDatabaseError,
         )
@@ -32,7 +80,7 @@ impl CourtRepository for TursoDb {
 
     async fn get_court_by_name(&self, court_name: &str) -> Result<Option<Court>> {
         self.query_one_with_error(
-            "SELECT id_court, court_name FROM court WHERE court_name = ?1 AND deleted = 0",
+            "SELECT id_court, court_name, surface, indoor, has_lighting, capacity, default_slot_minutes, active FROM court WHERE court_name = ?1 AND deleted = 0",
             params![court_name],
             Error::UnknownDatabaseError,
         )
@@ -41,7 +89,7 @@ impl CourtRepository for TursoDb {
 
     async fn list_courts(&self) -> Result<Vec<Court>> {
         self.query_many_with_error(
-            "SELECT id_court, court_name FROM court WHERE deleted = 0",
+            "SELECT id_court, court_name, surface, indoor, has_lighting, capacity, default_slot_minutes, active FROM court WHERE deleted = 0",
             params![],
             Error::UnknownDatabaseError,
         )
@@ -56,6 +104,74 @@ impl CourtRepository for TursoDb {
         )
         .await
     }
+
+    async fn list_courts_page(
+        &self,
+        filter: &CourtFilter,
+        page: &PageQuery,
+    ) -> Result<Page<Court>> {
+        let mut sql_filter = SqlFilter::new();
+        sql_filter
+            .push_if(
+                "court_name LIKE ?",
+                filter.court_name.as_ref().map(|name| format!("%{name}%")),
+            )
+            .push_if(
+                "surface = ?",
+                filter.surface.map(|surface| surface.to_string()),
+            )
+            .push_if("indoor = ?", filter.indoor)
+            .push_if("has_lighting = ?", filter.has_lighting)
+            .push_if("capacity >= ?", filter.min_capacity)
+            .push_if("active = ?", filter.active);
+
+        self.query_page_with_error(
+            &COURT_PAGE_SQL,
+            &sql_filter,
+            page,
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+}
+
+const INSERT_RESERVATION_SQL: &str = "INSERT INTO court_reservation (id_court_reservation, id_court, start_reservation_datetime, end_reservation_datetime, id_training, id_tournament, id_owner, deleted)
+     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0)";
+
+fn reservation_params(reservation: &CourtReservation) -> impl IntoParams {
+    params![
+        reservation.id_court_reservation.to_string(),
+        reservation.id_court.to_string(),
+        reservation
+            .start_reservation_datetime
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string(),
+        reservation
+            .end_reservation_datetime
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string(),
+        reservation.id_training.map(|id| id.to_string()),
+        reservation.id_tournament.map(|id| id.to_string()),
+        reservation.id_owner.map(|id| id.to_string()),
+    ]
+}
+
+/// Run before deleting a reservation, so a member isn't asked to pay for a court they lost
+const SETTLE_CHARGE_SQL: &str = "UPDATE court_charge
+     SET status = CASE status WHEN 'PENDING' THEN 'CANCELLED' ELSE 'REFUNDED' END
+     WHERE id_court_reservation = ?1 AND status IN ('PENDING', 'PAID')";
+
+/// Late cancellations keep the paid charge, only the pending one is cancelled
+const SETTLE_LATE_CHARGE_SQL: &str = "UPDATE court_charge SET status = 'CANCELLED'
+     WHERE id_court_reservation = ?1 AND status = 'PENDING'";
+
+/// The overlap triggers of `court_reservation` abort with `COURT_UNAVAILABLE`
+fn reservation_error(message: String) -> Error {
+    if message.contains("COURT_UNAVAILABLE") {
+        Error::CourtUnavailable
+    } else {
+        Error::UnknownDatabaseError(message)
+    }
 }
 
 #[async_trait]
@@ -78,21 +194,66 @@ impl CourtReservationRepository for TursoDb {
 
     async fn create_reservation(&self, reservation: &CourtReservation) -> Result<()> {
         self.execute_with_error(
-            "INSERT INTO court_reservation (id_court_reservation, id_court, start_reservation_datetime, end_reservation_datetime, id_training, id_tournament, deleted) 
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0)",
-            params![
-                reservation.id_court_reservation.to_string(),
-                reservation.id_court.to_string(),
-                reservation.start_reservation_datetime.format("%Y-%m-%d %H:%M:%S").to_string(),
-                reservation.end_reservation_datetime.format("%Y-%m-%d %H:%M:%S").to_string(),
-                reservation.id_training.map(|id| id.to_string()),
-                reservation.id_tournament.map(|id| id.to_string()),
-            ],
-            Error::UnknownDatabaseError,
+            INSERT_RESERVATION_SQL,
+            reservation_params(reservation),
+            reservation_error,
         )
         .await
     }
 
+    async fn create_reservations_replacing(
+        &self,
+        reservations: &[CourtReservation],
+        replaced: &[Uuid],
+        events: &[OutboxEvent],
+    ) -> Result<()> {
+        if let ([reservation], [], []) = (reservations, replaced, events) {
+            return self.create_reservation(reservation).await;
+        }
+
+        let conn = self
+            .get_connection_with_error(Error::UnknownDatabaseError)
+            .await?;
+        let tx = conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .await
+            .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+
+        let result = async {
+            for id_reservation in replaced {
+                tx.execute(SETTLE_CHARGE_SQL, params![id_reservation.to_string()])
+                    .await?;
+                tx.execute(
+                    "UPDATE court_reservation SET deleted = 1 WHERE id_court_reservation = ?1",
+                    params![id_reservation.to_string()],
+                )
+                .await?;
+            }
+            for reservation in reservations {
+                tx.execute(INSERT_RESERVATION_SQL, reservation_params(reservation))
+                    .await?;
+            }
+            for event in events {
+                insert_outbox_event(&tx, event).await?;
+            }
+            Ok::<(), libsql::Error>(())
+        }
+        .await;
+
+        match result {
+            Ok(()) => tx
+                .commit()
+                .await
+                .map_err(|err| Error::UnknownDatabaseError(err.to_string())),
+            Err(err) => {
+                tx.rollback()
+                    .await
+                    .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+                Err(reservation_error(err.to_string()))
+            }
+        }
+    }
+
     async fn get_reservations_for_court_in_range(
         &self,
         id_court: Uuid,
@@ -100,7 +261,7 @@ impl CourtReservationRepository for TursoDb {
         end_time: NaiveDateTime,
     ) -> Result<Vec<CourtReservation>> {
         self.query_many_with_error(
-            "SELECT id_court_reservation, id_court, start_reservation_datetime, end_reservation_datetime, id_training, id_tournament 
+            "SELECT id_court_reservation, id_court, start_reservation_datetime, end_reservation_datetime, id_training, id_tournament, id_owner
              FROM court_reservation 
              WHERE id_court = ?1 AND deleted = 0 
              AND (
@@ -121,7 +282,7 @@ impl CourtReservationRepository for TursoDb {
         id_reservation: Uuid,
     ) -> Result<Option<CourtReservation>> {
         self.query_one_with_error(
-            "SELECT id_court_reservation, id_court, start_reservation_datetime, end_reservation_datetime, id_training, id_tournament
+            "SELECT id_court_reservation, id_court, start_reservation_datetime, end_reservation_datetime, id_training, id_tournament, id_owner
              FROM court_reservation
              WHERE id_court_reservation = ?1 AND deleted = 0",
             params![id_reservation.to_string()],
@@ -147,29 +308,1197 @@ impl CourtReservationRepository for TursoDb {
         .await
     }
 
-    async fn get_reservation_for_training(
+    async fn get_reservations_for_training(
         &self,
         training_id: Uuid,
-    ) -> Result<Option<CourtReservation>> {
-        self.query_one_with_error(
-            "SELECT id_court_reservation, id_court, start_reservation_datetime, end_reservation_datetime, id_training, id_tournament 
-             FROM court_reservation 
-             WHERE id_training = ?1 AND deleted = 0",
+    ) -> Result<Vec<CourtReservation>> {
+        self.query_many_with_error(
+            "SELECT id_court_reservation, id_court, start_reservation_datetime, end_reservation_datetime, id_training, id_tournament, id_owner
+             FROM court_reservation
+             WHERE id_training = ?1 AND deleted = 0
+             ORDER BY start_reservation_datetime, id_court",
             params![training_id.to_string()],
             Error::UnknownDatabaseError,
         ).await
     }
 
-    async fn get_reservation_for_tournament(
+    async fn get_reservations_for_tournament(
         &self,
         tournament_id: Uuid,
-    ) -> Result<Option<CourtReservation>> {
-        self.query_one_with_error(
-            "SELECT id_court_reservation, id_court, start_reservation_datetime, end_reservation_datetime, id_training, id_tournament 
-             FROM court_reservation 
-             WHERE id_tournament = ?1 AND deleted = 0",
+    ) -> Result<Vec<CourtReservation>> {
+        self.query_many_with_error(
+            "SELECT id_court_reservation, id_court, start_reservation_datetime, end_reservation_datetime, id_training, id_tournament, id_owner
+             FROM court_reservation
+             WHERE id_tournament = ?1 AND deleted = 0
+             ORDER BY start_reservation_datetime, id_court",
             params![tournament_id.to_string()],
             Error::UnknownDatabaseError,
         ).await
     }
+
+    async fn create_booking(
+        &self,
+        reservation: &CourtReservation,
+        partners: &[Uuid],
+        charge: Option<&CourtCharge>,
+    ) -> Result<()> {
+        self.create_reservation(reservation).await?;
+
+        for id_user in partners {
+            self.execute_with_error(
+                "INSERT INTO court_reservation_partner (id_court_reservation, id_user) VALUES (?1, ?2)",
+                params![
+                    reservation.id_court_reservation.to_string(),
+                    id_user.to_string()
+                ],
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+        }
+
+        if let Some(charge) = charge {
+            self.execute_with_error(
+                "INSERT INTO court_charge (id_charge, id_court_reservation, id_user, amount, status, created_datetime, due_datetime, paid_datetime)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    charge.id_charge.to_string(),
+                    charge.id_court_reservation.to_string(),
+                    charge.id_user.to_string(),
+                    charge.amount,
+                    charge.status.to_string(),
+                    charge.created_datetime.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    charge.due_datetime.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    charge
+                        .paid_datetime
+                        .map(|paid| paid.format("%Y-%m-%d %H:%M:%S").to_string())
+                ],
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn get_booking_partners(&self, id_reservation: Uuid) -> Result<Vec<Uuid>> {
+        #[derive(Deserialize)]
+        struct Partner {
+            id_user: Uuid,
+        }
+
+        let partners: Vec<Partner> = self
+            .query_many_with_error(
+                "SELECT id_user FROM court_reservation_partner WHERE id_court_reservation = ?1",
+                params![id_reservation.to_string()],
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+
+        Ok(partners
+            .into_iter()
+            .map(|partner| partner.id_user)
+            .collect())
+    }
+
+    async fn list_member_bookings(
+        &self,
+        id_user: Uuid,
+        from: NaiveDateTime,
+    ) -> Result<Vec<CourtReservation>> {
+        self.query_many_with_error(
+            "SELECT id_court_reservation, id_court, start_reservation_datetime, end_reservation_datetime, id_training, id_tournament, id_owner
+             FROM court_reservation
+             WHERE deleted = 0 AND end_reservation_datetime > ?2
+             AND (
+                 id_owner = ?1
+                 OR id_court_reservation IN (
+                     SELECT id_court_reservation FROM court_reservation_partner WHERE id_user = ?1
+                 )
+             )
+             ORDER BY start_reservation_datetime",
+            params![
+                id_user.to_string(),
+                from.format("%Y-%m-%d %H:%M:%S").to_string()
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn count_active_bookings(&self, id_owner: Uuid, from: NaiveDateTime) -> Result<u32> {
+        #[derive(Deserialize)]
+        struct Count {
+            count: u32,
+        }
+
+        let result: Option<Count> = self
+            .query_one_with_error(
+                "SELECT COUNT(*) AS count FROM court_reservation
+                 WHERE id_owner = ?1 AND deleted = 0 AND end_reservation_datetime > ?2",
+                params![
+                    id_owner.to_string(),
+                    from.format("%Y-%m-%d %H:%M:%S").to_string()
+                ],
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+
+        Ok(result.map_or(0, |c| c.count))
+    }
+
+    async fn delete_reservation(
+        &self,
+        id_reservation: Uuid,
+        event: Option<&OutboxEvent>,
+    ) -> Result<()> {
+        let conn = self
+            .get_connection_with_error(Error::UnknownDatabaseError)
+            .await?;
+        let tx = conn
+            .transaction()
+            .await
+            .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+
+        let result = async {
+            tx.execute(SETTLE_CHARGE_SQL, params![id_reservation.to_string()])
+                .await?;
+            tx.execute(
+                "UPDATE court_reservation SET deleted = 1 WHERE id_court_reservation = ?1",
+                params![id_reservation.to_string()],
+            )
+            .await?;
+            if let Some(event) = event {
+                insert_outbox_event(&tx, event).await?;
+            }
+            Ok::<(), libsql::Error>(())
+        }
+        .await;
+
+        match result {
+            Ok(()) => tx
+                .commit()
+                .await
+                .map_err(|err| Error::UnknownDatabaseError(err.to_string())),
+            Err(err) => {
+                tx.rollback()
+                    .await
+                    .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+                Err(Error::UnknownDatabaseError(err.to_string()))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl CourtScheduleRepository for TursoDb {
+    async fn get_opening_hours(&self, id_court: Uuid) -> Result<Vec<CourtOpeningHours>> {
+        let mut opening_hours: Vec<CourtOpeningHours> = self
+            .query_many_with_error(
+                "SELECT day_of_week, open_time, close_time FROM court_opening_hours WHERE id_court = ?1",
+                params![id_court.to_string()],
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+
+        opening_hours.sort_by_key(|hours| hours.day_of_week);
+        Ok(opening_hours)
+    }
+
+    async fn set_opening_hours(
+        &self,
+        id_court: Uuid,
+        opening_hours: &[CourtOpeningHours],
+    ) -> Result<()> {
+        self.execute_with_error(
+            "DELETE FROM court_opening_hours WHERE id_court = ?1",
+            params![id_court.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await?;
+
+        for hours in opening_hours {
+            self.execute_with_error(
+                "INSERT INTO court_opening_hours (id_court, day_of_week, open_time, close_time)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    id_court.to_string(),
+                    hours.day_of_week.to_string(),
+                    hours.open_time.format("%H:%M").to_string(),
+                    hours.close_time.format("%H:%M").to_string()
+                ],
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn create_closure(&self, closure: &CourtClosure) -> Result<()> {
+        self.execute_with_error(
+            "INSERT INTO court_closure (id_closure, id_court, kind, reason, start_datetime, end_datetime, deleted)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0)",
+            params![
+                closure.id_closure.to_string(),
+                closure.id_court.map(|id| id.to_string()),
+                closure.kind.to_string(),
+                closure.reason.clone(),
+                closure.start_datetime.format("%Y-%m-%d %H:%M:%S").to_string(),
+                closure.end_datetime.format("%Y-%m-%d %H:%M:%S").to_string()
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn get_closure_by_id(&self, id_closure: Uuid) -> Result<Option<CourtClosure>> {
+        self.query_one_with_error(
+            "SELECT id_closure, id_court, kind, reason, start_datetime, end_datetime
+             FROM court_closure
+             WHERE id_closure = ?1 AND deleted = 0",
+            params![id_closure.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn get_closures_for_court_in_range(
+        &self,
+        id_court: Uuid,
+        start_time: NaiveDateTime,
+        end_time: NaiveDateTime,
+    ) -> Result<Vec<CourtClosure>> {
+        self.query_many_with_error(
+            "SELECT id_closure, id_court, kind, reason, start_datetime, end_datetime
+             FROM court_closure
+             WHERE deleted = 0 AND (id_court = ?1 OR id_court IS NULL)
+             AND start_datetime < ?3 AND end_datetime > ?2
+             ORDER BY start_datetime",
+            params![
+                id_court.to_string(),
+                start_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+                end_time.format("%Y-%m-%d %H:%M:%S").to_string()
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn delete_closure(&self, id_closure: Uuid) -> Result<()> {
+        self.execute_with_error(
+            "UPDATE court_closure SET deleted = 1 WHERE id_closure = ?1",
+            params![id_closure.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+}
+
+const PRICE_RULE_COLUMNS: &str = "id_price_rule, name, id_court, day_of_week, start_time, end_time, member_price, guest_price, lights_surcharge";
+const CHARGE_COLUMNS: &str = "id_charge, id_court_reservation, id_user, amount, status, created_datetime, due_datetime, paid_datetime";
+
+#[async_trait]
+impl CourtPricingRepository for TursoDb {
+    async fn create_price_rule(&self, rule: &CourtPriceRule) -> Result<()> {
+        self.execute_with_error(
+            &format!(
+                "INSERT INTO court_price_rule ({PRICE_RULE_COLUMNS}, deleted)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 0)"
+            ),
+            params![
+                rule.id_price_rule.to_string(),
+                rule.name.clone(),
+                rule.id_court.map(|id| id.to_string()),
+                rule.day_of_week.map(|day| day.to_string()),
+                rule.start_time.format("%H:%M").to_string(),
+                rule.end_time.format("%H:%M").to_string(),
+                rule.member_price,
+                rule.guest_price,
+                rule.lights_surcharge
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn get_price_rule_by_id(&self, id_price_rule: Uuid) -> Result<Option<CourtPriceRule>> {
+        self.query_one_with_error(
+            &format!(
+                "SELECT {PRICE_RULE_COLUMNS} FROM court_price_rule
+                 WHERE id_price_rule = ?1 AND deleted = 0"
+            ),
+            params![id_price_rule.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_price_rules(&self) -> Result<Vec<CourtPriceRule>> {
+        self.query_many_with_error(
+            &format!(
+                "SELECT {PRICE_RULE_COLUMNS} FROM court_price_rule
+                 WHERE deleted = 0
+                 ORDER BY start_time"
+            ),
+            params![],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_price_rules_for_court(&self, id_court: Uuid) -> Result<Vec<CourtPriceRule>> {
+        self.query_many_with_error(
+            &format!(
+                "SELECT {PRICE_RULE_COLUMNS} FROM court_price_rule
+                 WHERE deleted = 0 AND (id_court = ?1 OR id_court IS NULL)
+                 ORDER BY start_time"
+            ),
+            params![id_court.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn delete_price_rule(&self, id_price_rule: Uuid) -> Result<()> {
+        self.execute_with_error(
+            "UPDATE court_price_rule SET deleted = 1 WHERE id_price_rule = ?1",
+            params![id_price_rule.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn get_charge_by_id(&self, id_charge: Uuid) -> Result<Option<CourtCharge>> {
+        self.query_one_with_error(
+            &format!("SELECT {CHARGE_COLUMNS} FROM court_charge WHERE id_charge = ?1"),
+            params![id_charge.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn get_charge_for_reservation(
+        &self,
+        id_reservation: Uuid,
+    ) -> Result<Option<CourtCharge>> {
+        self.query_one_with_error(
+            &format!("SELECT {CHARGE_COLUMNS} FROM court_charge WHERE id_court_reservation = ?1"),
+            params![id_reservation.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_user_charges(&self, id_user: Uuid) -> Result<Vec<CourtCharge>> {
+        self.query_many_with_error(
+            &format!(
+                "SELECT {CHARGE_COLUMNS} FROM court_charge
+                 WHERE id_user = ?1
+                 ORDER BY created_datetime DESC"
+            ),
+            params![id_user.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_overdue_charges(&self, now: NaiveDateTime) -> Result<Vec<CourtCharge>> {
+        self.query_many_with_error(
+            &format!(
+                "SELECT {CHARGE_COLUMNS} FROM court_charge
+                 WHERE status = 'PENDING' AND due_datetime <= ?1
+                 ORDER BY due_datetime"
+            ),
+            params![now.format("%Y-%m-%d %H:%M:%S").to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn update_charge(&self, charge: &CourtCharge) -> Result<()> {
+        self.execute_with_error(
+            "UPDATE court_charge SET status = ?2, paid_datetime = ?3 WHERE id_charge = ?1",
+            params![
+                charge.id_charge.to_string(),
+                charge.status.to_string(),
+                charge
+                    .paid_datetime
+                    .map(|paid| paid.format("%Y-%m-%d %H:%M:%S").to_string())
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl CourtAttendanceRepository for TursoDb {
+    async fn cancel_reservation(
+        &self,
+        cancellation: &CourtCancellation,
+        event: Option<&OutboxEvent>,
+    ) -> Result<()> {
+        let conn = self
+            .get_connection_with_error(Error::UnknownDatabaseError)
+            .await?;
+        let tx = conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .await
+            .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+
+        let id_reservation = cancellation.id_court_reservation.to_string();
+        let settle_charge_sql = if cancellation.late {
+            SETTLE_LATE_CHARGE_SQL
+        } else {
+            SETTLE_CHARGE_SQL
+        };
+        let result = async {
+            tx.execute(
+                "INSERT INTO court_cancellation (id_court_reservation, cancelled_by, reason, cancelled_datetime, late)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    id_reservation.clone(),
+                    cancellation.cancelled_by.to_string(),
+                    cancellation.reason.clone(),
+                    cancellation
+                        .cancelled_datetime
+                        .format("%Y-%m-%d %H:%M:%S")
+                        .to_string(),
+                    cancellation.late
+                ],
+            )
+            .await?;
+            tx.execute(settle_charge_sql, params![id_reservation.clone()])
+                .await?;
+            tx.execute(
+                "UPDATE court_reservation SET deleted = 1 WHERE id_court_reservation = ?1",
+                params![id_reservation.clone()],
+            )
+            .await?;
+            if let Some(event) = event {
+                insert_outbox_event(&tx, event).await?;
+            }
+            Ok::<(), libsql::Error>(())
+        }
+        .await;
+
+        match result {
+            Ok(()) => tx
+                .commit()
+                .await
+                .map_err(|err| Error::UnknownDatabaseError(err.to_string())),
+            Err(err) => {
+                tx.rollback()
+                    .await
+                    .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+                Err(Error::UnknownDatabaseError(err.to_string()))
+            }
+        }
+    }
+
+    async fn get_cancellation(&self, id_reservation: Uuid) -> Result<Option<CourtCancellation>> {
+        self.query_one_with_error(
+            "SELECT id_court_reservation, cancelled_by, reason, cancelled_datetime, late
+             FROM court_cancellation WHERE id_court_reservation = ?1",
+            params![id_reservation.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn create_check_in(&self, check_in: &CourtCheckIn) -> Result<()> {
+        self.execute_with_error(
+            "INSERT INTO court_check_in (id_court_reservation, id_user, checked_in_datetime)
+             VALUES (?1, ?2, ?3)",
+            params![
+                check_in.id_court_reservation.to_string(),
+                check_in.id_user.to_string(),
+                check_in
+                    .checked_in_datetime
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn get_check_in(&self, id_reservation: Uuid) -> Result<Option<CourtCheckIn>> {
+        self.query_one_with_error(
+            "SELECT id_court_reservation, id_user, checked_in_datetime
+             FROM court_check_in WHERE id_court_reservation = ?1",
+            params![id_reservation.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_unattended_bookings(
+        &self,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<Vec<CourtReservation>> {
+        self.query_many_with_error(
+            "SELECT r.id_court_reservation, r.id_court, r.start_reservation_datetime, r.end_reservation_datetime, r.id_training, r.id_tournament, r.id_owner
+             FROM court_reservation r
+             WHERE r.id_owner IS NOT NULL AND r.deleted = 0
+             AND r.end_reservation_datetime > ?1 AND r.end_reservation_datetime <= ?2
+             AND NOT EXISTS (SELECT 1 FROM court_check_in c WHERE c.id_court_reservation = r.id_court_reservation)
+             AND NOT EXISTS (SELECT 1 FROM court_no_show n WHERE n.id_court_reservation = r.id_court_reservation)
+             ORDER BY r.end_reservation_datetime",
+            params![
+                from.format("%Y-%m-%d %H:%M:%S").to_string(),
+                to.format("%Y-%m-%d %H:%M:%S").to_string()
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn create_no_show(&self, no_show: &CourtNoShow) -> Result<()> {
+        self.execute_with_error(
+            "INSERT INTO court_no_show (id_court_reservation, id_user, start_datetime, waived)
+             VALUES (?1, ?2, ?3, 0)",
+            params![
+                no_show.id_court_reservation.to_string(),
+                no_show.id_user.to_string(),
+                no_show
+                    .start_datetime
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn get_no_show(&self, id_reservation: Uuid) -> Result<Option<CourtNoShow>> {
+        self.query_one_with_error(
+            "SELECT id_court_reservation, id_user, start_datetime FROM court_no_show
+             WHERE id_court_reservation = ?1 AND waived = 0",
+            params![id_reservation.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_no_shows(&self, id_user: Uuid) -> Result<Vec<CourtNoShow>> {
+        self.query_many_with_error(
+            "SELECT id_court_reservation, id_user, start_datetime FROM court_no_show
+             WHERE id_user = ?1 AND waived = 0
+             ORDER BY start_datetime DESC",
+            params![id_user.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn waive_no_show(&self, id_reservation: Uuid) -> Result<()> {
+        self.execute_with_error(
+            "UPDATE court_no_show SET waived = 1 WHERE id_court_reservation = ?1",
+            params![id_reservation.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::future::Future;
+
+    use super::*;
+    use chrono::{Duration, NaiveTime, Utc};
+    use entities::court::{ClosureKind, CourtSurface, DayOfWeek};
+    use entities::court_pricing::ChargeStatus;
+    use entities::tournament::Tournament;
+    use rstest::{fixture, rstest};
+    use tokio::task::JoinSet;
+    use use_cases::tournament_service::repository_trait::TournamentRepository;
+
+    #[fixture]
+    async fn repository() -> TursoDb {
+        crate::TestDbBuilder::create_full().await
+    }
+
+    fn test_datetime() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(
+            &Utc::now()
+                .naive_utc()
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+            "%Y-%m-%d %H:%M:%S",
+        )
+        .unwrap()
+    }
+
+    async fn create_test_court(db: &TursoDb) -> Court {
+        let court = Court {
+            id_court: Uuid::new_v4(),
+            court_name: "Court 1".to_string(),
+            surface: CourtSurface::CLAY,
+            indoor: false,
+            has_lighting: true,
+            capacity: 4,
+            default_slot_minutes: 60,
+            active: true,
+        };
+        db.create_court(&court).await.expect("Error creating court");
+        court
+    }
+
+    fn booking(id_court: Uuid, id_owner: Uuid, start: NaiveDateTime) -> CourtReservation {
+        CourtReservation {
+            id_court_reservation: Uuid::new_v4(),
+            id_court,
+            start_reservation_datetime: start,
+            end_reservation_datetime: start + Duration::hours(1),
+            id_training: None,
+            id_tournament: None,
+            id_owner: Some(id_owner),
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_create_booking_with_partners(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let court = create_test_court(&db).await;
+        let (owner_id, partner_id) = (Uuid::new_v4(), Uuid::new_v4());
+        db.create_test_user(owner_id)
+            .await
+            .expect("Error creating owner");
+        db.create_test_user(partner_id)
+            .await
+            .expect("Error creating partner");
+
+        let reservation = booking(
+            court.id_court,
+            owner_id,
+            test_datetime() + Duration::days(1),
+        );
+        db.create_booking(&reservation, &[partner_id], None)
+            .await
+            .expect("Error creating booking");
+
+        let saved = db
+            .get_reservation_by_id(reservation.id_court_reservation)
+            .await
+            .unwrap();
+        assert_eq!(saved, Some(reservation.clone()));
+        assert_eq!(
+            db.get_booking_partners(reservation.id_court_reservation)
+                .await
+                .unwrap(),
+            vec![partner_id]
+        );
+
+        for id_user in [owner_id, partner_id] {
+            let bookings = db
+                .list_member_bookings(id_user, test_datetime())
+                .await
+                .unwrap();
+            assert_eq!(bookings, vec![reservation.clone()]);
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_count_and_delete_bookings(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let court = create_test_court(&db).await;
+        let owner_id = Uuid::new_v4();
+        db.create_test_user(owner_id)
+            .await
+            .expect("Error creating owner");
+
+        let now = test_datetime();
+        let past = booking(court.id_court, owner_id, now - Duration::days(1));
+        let upcoming = booking(court.id_court, owner_id, now + Duration::days(1));
+        for reservation in [&past, &upcoming] {
+            db.create_booking(reservation, &[], None)
+                .await
+                .expect("Error creating booking");
+        }
+
+        assert_eq!(db.count_active_bookings(owner_id, now).await.unwrap(), 1);
+
+        db.delete_reservation(upcoming.id_court_reservation, None)
+            .await
+            .expect("Error deleting booking");
+
+        assert_eq!(db.count_active_bookings(owner_id, now).await.unwrap(), 0);
+        assert!(db
+            .list_member_bookings(owner_id, now)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_set_opening_hours_replaces_week(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let court = create_test_court(&db).await;
+        let hours = |day_of_week, open, close| CourtOpeningHours {
+            day_of_week,
+            open_time: NaiveTime::from_hms_opt(open, 0, 0).unwrap(),
+            close_time: NaiveTime::from_hms_opt(close, 0, 0).unwrap(),
+        };
+
+        db.set_opening_hours(
+            court.id_court,
+            &[
+                hours(DayOfWeek::SATURDAY, 8, 14),
+                hours(DayOfWeek::MONDAY, 7, 22),
+            ],
+        )
+        .await
+        .expect("Error setting opening hours");
+        db.set_opening_hours(
+            court.id_court,
+            &[
+                hours(DayOfWeek::TUESDAY, 7, 22),
+                hours(DayOfWeek::MONDAY, 9, 21),
+            ],
+        )
+        .await
+        .expect("Error replacing opening hours");
+
+        assert_eq!(
+            db.get_opening_hours(court.id_court).await.unwrap(),
+            vec![
+                hours(DayOfWeek::MONDAY, 9, 21),
+                hours(DayOfWeek::TUESDAY, 7, 22)
+            ]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_closures_for_court_include_every_court_closures(
+        repository: impl Future<Output = TursoDb>,
+    ) {
+        let db = repository.await;
+        let court = create_test_court(&db).await;
+        let start = test_datetime();
+        let closure = |id_court, kind| CourtClosure {
+            id_closure: Uuid::new_v4(),
+            id_court,
+            kind,
+            reason: "Closed".to_string(),
+            start_datetime: start,
+            end_datetime: start + Duration::days(1),
+        };
+
+        let maintenance = closure(Some(court.id_court), ClosureKind::MAINTENANCE);
+        let holiday = closure(None, ClosureKind::HOLIDAY);
+        let other_court = closure(
+            Some(create_test_court(&db).await.id_court),
+            ClosureKind::MAINTENANCE,
+        );
+        for closure in [&maintenance, &holiday, &other_court] {
+            db.create_closure(closure)
+                .await
+                .expect("Error creating closure");
+        }
+
+        let closures = db
+            .get_closures_for_court_in_range(
+                court.id_court,
+                start + Duration::hours(1),
+                start + Duration::hours(2),
+            )
+            .await
+            .unwrap();
+        assert_eq!(closures.len(), 2);
+        assert!(closures.contains(&maintenance) && closures.contains(&holiday));
+
+        db.delete_closure(holiday.id_closure)
+            .await
+            .expect("Error deleting closure");
+        assert_eq!(
+            db.get_closure_by_id(holiday.id_closure).await.unwrap(),
+            None
+        );
+        assert!(db
+            .get_closures_for_court_in_range(
+                court.id_court,
+                start + Duration::days(2),
+                start + Duration::days(3),
+            )
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_update_court_and_filter_by_attributes(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let mut court = create_test_court(&db).await;
+
+        court.surface = CourtSurface::HARD;
+        court.indoor = true;
+        court.active = false;
+        db.update_court(&court).await.expect("Error updating court");
+
+        assert_eq!(
+            db.get_court_by_id(court.id_court).await.unwrap(),
+            Some(court.clone())
+        );
+
+        let filter = |indoor, active| CourtFilter {
+            surface: Some(CourtSurface::HARD),
+            indoor: Some(indoor),
+            active: Some(active),
+            ..CourtFilter::default()
+        };
+        let page = db
+            .list_courts_page(&filter(true, false), &PageQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(page.items, vec![court]);
+
+        let page = db
+            .list_courts_page(&filter(false, false), &PageQuery::default())
+            .await
+            .unwrap();
+        assert!(page.items.is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_overlapping_reservations_are_rejected(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let court = create_test_court(&db).await;
+        let owner_id = Uuid::new_v4();
+        db.create_test_user(owner_id)
+            .await
+            .expect("Error creating owner");
+        let start = test_datetime() + Duration::days(1);
+
+        let first = booking(court.id_court, owner_id, start);
+        db.create_reservation(&first)
+            .await
+            .expect("Error creating reservation");
+        let overlapping = booking(court.id_court, owner_id, start + Duration::minutes(30));
+        assert_eq!(
+            db.create_reservation(&overlapping).await,
+            Err(Error::CourtUnavailable)
+        );
+
+        let mut tasks = JoinSet::new();
+        for _ in 0..10 {
+            let db = db.clone();
+            let reservation = booking(court.id_court, owner_id, start + Duration::days(1));
+            tasks.spawn(async move { db.create_reservation(&reservation).await });
+        }
+        let results = tasks.join_all().await;
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+        assert!(results
+            .iter()
+            .all(|result| matches!(result, Ok(()) | Err(Error::CourtUnavailable))));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_create_reservation_replacing_bookings(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let court = create_test_court(&db).await;
+        let start = test_datetime() + Duration::days(1);
+
+        let owner_id = Uuid::new_v4();
+        db.create_test_user(owner_id)
+            .await
+            .expect("Error creating owner");
+        let member_booking = booking(court.id_court, owner_id, start);
+        db.create_booking(&member_booking, &[], None)
+            .await
+            .expect("Error creating booking");
+
+        let event_reservation = CourtReservation {
+            id_court_reservation: Uuid::new_v4(),
+            id_owner: None,
+            ..member_booking.clone()
+        };
+        db.create_reservations_replacing(
+            std::slice::from_ref(&event_reservation),
+            &[member_booking.id_court_reservation],
+            &[],
+        )
+        .await
+        .expect("Error replacing booking");
+
+        assert_eq!(
+            db.get_reservation_by_id(member_booking.id_court_reservation)
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            db.get_reservation_by_id(event_reservation.id_court_reservation)
+                .await
+                .unwrap(),
+            Some(event_reservation)
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_tournament_holds_several_courts(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let court_1 = create_test_court(&db).await;
+        let court_2 = Court {
+            id_court: Uuid::new_v4(),
+            court_name: "Court 2".to_string(),
+            ..court_1.clone()
+        };
+        db.create_court(&court_2)
+            .await
+            .expect("Error creating court");
+
+        let start = test_datetime() + Duration::days(1);
+        let id_category = Uuid::new_v4();
+        db.create_test_category(id_category)
+            .await
+            .expect("Error creating category");
+        let tournament = Tournament {
+            id_tournament: Uuid::new_v4(),
+            name: "Club open".to_string(),
+            id_category,
+            start_datetime: start,
+            end_datetime: start + Duration::hours(5),
+        };
+        db.create_tournament(&tournament)
+            .await
+            .expect("Error creating tournament");
+        let id_tournament = tournament.id_tournament;
+        let event_block = |id_court: Uuid, start: NaiveDateTime, hours: i64| CourtReservation {
+            id_court_reservation: Uuid::new_v4(),
+            id_court,
+            start_reservation_datetime: start,
+            end_reservation_datetime: start + Duration::hours(hours),
+            id_training: None,
+            id_tournament: Some(id_tournament),
+            id_owner: None,
+        };
+
+        let blocks = vec![
+            event_block(court_1.id_court, start, 4),
+            event_block(court_2.id_court, start + Duration::hours(1), 2),
+        ];
+        db.create_reservations_replacing(&blocks, &[], &[])
+            .await
+            .expect("Error creating the tournament courts");
+        assert_eq!(
+            db.get_reservations_for_tournament(id_tournament)
+                .await
+                .unwrap(),
+            blocks
+        );
+
+        // The whole set is kept when one of the new blocks conflicts
+        let replaced: Vec<Uuid> = blocks.iter().map(|r| r.id_court_reservation).collect();
+        let overlapping = vec![
+            event_block(court_1.id_court, start, 2),
+            event_block(court_1.id_court, start + Duration::hours(1), 2),
+        ];
+        assert_eq!(
+            db.create_reservations_replacing(&overlapping, &replaced, &[])
+                .await,
+            Err(Error::CourtUnavailable)
+        );
+        assert_eq!(
+            db.get_reservations_for_tournament(id_tournament)
+                .await
+                .unwrap(),
+            blocks
+        );
+
+        db.delete_reservation_by_event_id(id_tournament, "tournament")
+            .await
+            .expect("Error deleting the tournament courts");
+        assert!(db
+            .get_reservations_for_tournament(id_tournament)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_charge_follows_its_booking(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let court = create_test_court(&db).await;
+        let owner_id = Uuid::new_v4();
+        db.create_test_user(owner_id)
+            .await
+            .expect("Error creating owner");
+
+        let now = test_datetime();
+        let reservation = booking(court.id_court, owner_id, now + Duration::days(1));
+        let charge = CourtCharge {
+            id_charge: Uuid::new_v4(),
+            id_court_reservation: reservation.id_court_reservation,
+            id_user: owner_id,
+            amount: 12.5,
+            status: ChargeStatus::PENDING,
+            created_datetime: now,
+            due_datetime: now + Duration::minutes(30),
+            paid_datetime: None,
+        };
+        db.create_booking(&reservation, &[], Some(&charge))
+            .await
+            .expect("Error creating booking");
+
+        assert_eq!(
+            db.get_charge_for_reservation(reservation.id_court_reservation)
+                .await
+                .unwrap(),
+            Some(charge.clone())
+        );
+        assert!(db
+            .list_overdue_charges(now + Duration::minutes(10))
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            db.list_overdue_charges(now + Duration::minutes(30))
+                .await
+                .unwrap(),
+            vec![charge.clone()]
+        );
+
+        db.delete_reservation(reservation.id_court_reservation, None)
+            .await
+            .expect("Error deleting booking");
+        assert_eq!(
+            db.get_charge_by_id(charge.id_charge)
+                .await
+                .unwrap()
+                .map(|charge| charge.status),
+            Some(ChargeStatus::CANCELLED)
+        );
+        assert_eq!(db.list_user_charges(owner_id).await.unwrap().len(), 1);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_late_cancellation_keeps_the_paid_charge(
+        repository: impl Future<Output = TursoDb>,
+    ) {
+        let db = repository.await;
+        let court = create_test_court(&db).await;
+        let owner_id = Uuid::new_v4();
+        db.create_test_user(owner_id)
+            .await
+            .expect("Error creating owner");
+
+        let now = test_datetime();
+        let reservation = booking(court.id_court, owner_id, now + Duration::hours(2));
+        let charge = CourtCharge {
+            id_charge: Uuid::new_v4(),
+            id_court_reservation: reservation.id_court_reservation,
+            id_user: owner_id,
+            amount: 20.0,
+            status: ChargeStatus::PAID,
+            created_datetime: now,
+            due_datetime: now + Duration::minutes(30),
+            paid_datetime: Some(now),
+        };
+        db.create_booking(&reservation, &[], Some(&charge))
+            .await
+            .expect("Error creating booking");
+
+        let cancellation = CourtCancellation {
+            id_court_reservation: reservation.id_court_reservation,
+            cancelled_by: owner_id,
+            reason: "Injured".to_string(),
+            cancelled_datetime: now,
+            late: true,
+        };
+        db.cancel_reservation(&cancellation, None)
+            .await
+            .expect("Error cancelling booking");
+
+        assert_eq!(
+            db.get_reservation_by_id(reservation.id_court_reservation)
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            db.get_cancellation(reservation.id_court_reservation)
+                .await
+                .unwrap(),
+            Some(cancellation)
+        );
+        assert_eq!(
+            db.get_charge_by_id(charge.id_charge)
+                .await
+                .unwrap()
+                .map(|charge| charge.status),
+            Some(ChargeStatus::PAID)
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_unattended_bookings_become_no_shows(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let court = create_test_court(&db).await;
+        let owner_id = Uuid::new_v4();
+        db.create_test_user(owner_id)
+            .await
+            .expect("Error creating owner");
+
+        let now = test_datetime();
+        let attended = booking(court.id_court, owner_id, now - Duration::hours(3));
+        let missed = booking(court.id_court, owner_id, now - Duration::hours(2));
+        for reservation in [&attended, &missed] {
+            db.create_booking(reservation, &[], None)
+                .await
+                .expect("Error creating booking");
+        }
+        db.create_check_in(&CourtCheckIn {
+            id_court_reservation: attended.id_court_reservation,
+            id_user: owner_id,
+            checked_in_datetime: attended.start_reservation_datetime,
+        })
+        .await
+        .expect("Error checking in");
+
+        let from = now - Duration::days(1);
+        assert_eq!(
+            db.list_unattended_bookings(from, now).await.unwrap(),
+            vec![missed.clone()]
+        );
+
+        let no_show = CourtNoShow {
+            id_court_reservation: missed.id_court_reservation,
+            id_user: owner_id,
+            start_datetime: missed.start_reservation_datetime,
+        };
+        db.create_no_show(&no_show)
+            .await
+            .expect("Error recording no-show");
+        assert!(db
+            .list_unattended_bookings(from, now)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(db.list_no_shows(owner_id).await.unwrap(), vec![no_show]);
+
+        // Waived no-shows stop counting and aren't recorded again
+        db.waive_no_show(missed.id_court_reservation)
+            .await
+            .expect("Error waiving no-show");
+        assert!(db.list_no_shows(owner_id).await.unwrap().is_empty());
+        assert!(db
+            .list_unattended_bookings(from, now)
+            .await
+            .unwrap()
+            .is_empty());
+    }
 }
@@ -0,0 +1,313 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use entities::job::{JobRun, JobRunFilter, JobRunStatus, ScheduledJob};
+use entities::pagination::{Page, PageQuery};
+use libsql::params;
+use use_cases::job_service::{
+    err::{Error, Result},
+    repository_trait::JobRepository,
+};
+
+use crate::pagination::{PageSql, SqlFilter};
+use crate::TursoDb;
+
+const JOB_RUN_PAGE_SQL: PageSql<'static> = PageSql {
+    select: "SELECT id_run, job_name, started_at, finished_at, status, output, triggered_by
+FROM job_run
+WHERE 1 = 1",
+    count: "SELECT COUNT(*) AS count FROM job_run WHERE 1 = 1",
+    sortable_columns: &["started_at", "finished_at", "job_name", "status"],
+    default_sort_column: "started_at",
+    primary_key: "id_run",
+};
+
+fn format_datetime(datetime: NaiveDateTime) -> String {
+    datetime.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+#[async_trait]
+impl JobRepository for TursoDb {
+    async fn create_job(&self, job: &ScheduledJob) -> Result<()> {
+        self.execute_with_error(
+            "INSERT INTO job (
+                name, schedule, next_run_at, running_since, last_run_at, last_status
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                job.name.clone(),
+                job.schedule.clone(),
+                format_datetime(job.next_run_at),
+                job.running_since.map(format_datetime),
+                job.last_run_at.map(format_datetime),
+                job.last_status.map(|status| status.to_string()),
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn get_job(&self, name: &str) -> Result<Option<ScheduledJob>> {
+        self.query_one_with_error(
+            "SELECT name, schedule, next_run_at, running_since, last_run_at, last_status
+            FROM job
+            WHERE name = ?1",
+            params![name.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_jobs(&self) -> Result<Vec<ScheduledJob>> {
+        self.query_many_with_error(
+            "SELECT name, schedule, next_run_at, running_since, last_run_at, last_status
+            FROM job
+            ORDER BY name",
+            params![],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn update_job_schedule(
+        &self,
+        name: &str,
+        schedule: &str,
+        next_run_at: NaiveDateTime,
+    ) -> Result<()> {
+        self.execute_with_error(
+            "UPDATE job SET schedule = ?1, next_run_at = ?2 WHERE name = ?3",
+            params![
+                schedule.to_string(),
+                format_datetime(next_run_at),
+                name.to_string()
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn lock_job(
+        &self,
+        name: &str,
+        now: NaiveDateTime,
+        stale_before: NaiveDateTime,
+        due_only: bool,
+    ) -> Result<bool> {
+        // A single conditional update, so only one instance can take the lock
+        let affected_rows = self
+            .execute_returning_affected_with_error(
+                "UPDATE job SET running_since = ?1
+                WHERE name = ?2 AND (running_since IS NULL OR running_since < ?3)
+                AND (?4 = 0 OR next_run_at <= ?1)",
+                params![
+                    format_datetime(now),
+                    name.to_string(),
+                    format_datetime(stale_before),
+                    due_only
+                ],
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+
+        Ok(affected_rows > 0)
+    }
+
+    async fn unlock_job(
+        &self,
+        name: &str,
+        last_run_at: NaiveDateTime,
+        last_status: JobRunStatus,
+        next_run_at: NaiveDateTime,
+    ) -> Result<()> {
+        self.execute_with_error(
+            "UPDATE job SET running_since = NULL, last_run_at = ?1, last_status = ?2,
+            next_run_at = ?3
+            WHERE name = ?4",
+            params![
+                format_datetime(last_run_at),
+                last_status.to_string(),
+                format_datetime(next_run_at),
+                name.to_string()
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn create_job_run(&self, run: &JobRun) -> Result<()> {
+        self.execute_with_error(
+            "INSERT INTO job_run (
+                id_run, job_name, started_at, finished_at, status, output, triggered_by
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                run.id_run.to_string(),
+                run.job_name.clone(),
+                format_datetime(run.started_at),
+                run.finished_at.map(format_datetime),
+                run.status.to_string(),
+                run.output.clone(),
+                run.triggered_by.map(|id| id.to_string()),
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn update_job_run(&self, run: &JobRun) -> Result<()> {
+        self.execute_with_error(
+            "UPDATE job_run SET finished_at = ?1, status = ?2, output = ?3 WHERE id_run = ?4",
+            params![
+                run.finished_at.map(format_datetime),
+                run.status.to_string(),
+                run.output.clone(),
+                run.id_run.to_string(),
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_job_runs_page(
+        &self,
+        filter: &JobRunFilter,
+        page: &PageQuery,
+    ) -> Result<Page<JobRun>> {
+        let mut sql_filter = SqlFilter::new();
+        sql_filter
+            .push_if("job_name = ?", filter.job_name.clone())
+            .push_if("status = ?", filter.status.map(|status| status.to_string()));
+
+        self.query_page_with_error(
+            &JOB_RUN_PAGE_SQL,
+            &sql_filter,
+            page,
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::future::Future;
+
+    use super::*;
+    use chrono::{Duration, Utc};
+    use rstest::{fixture, rstest};
+    use uuid::Uuid;
+
+    #[fixture]
+    async fn repository() -> TursoDb {
+        crate::TestDbBuilder::create().await.build()
+    }
+
+    fn test_datetime() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(
+            &Utc::now()
+                .naive_utc()
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+            "%Y-%m-%d %H:%M:%S",
+        )
+        .unwrap()
+    }
+
+    fn job(name: &str) -> ScheduledJob {
+        ScheduledJob {
+            name: name.to_string(),
+            schedule: "0 * * * *".to_string(),
+            next_run_at: test_datetime(),
+            running_since: None,
+            last_run_at: None,
+            last_status: None,
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_lock_job_only_once(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        db.create_job(&job("request_expiry")).await.unwrap();
+        let now = test_datetime();
+        let stale_before = now - Duration::hours(1);
+
+        assert!(db
+            .lock_job("request_expiry", now, stale_before, true)
+            .await
+            .unwrap());
+        assert!(!db
+            .lock_job("request_expiry", now, stale_before, false)
+            .await
+            .unwrap());
+        // A lock older than the staleness limit is taken over
+        assert!(db
+            .lock_job("request_expiry", now, now + Duration::minutes(1), true)
+            .await
+            .unwrap());
+
+        let next_run_at = now + Duration::hours(1);
+        db.unlock_job("request_expiry", now, JobRunStatus::SUCCEEDED, next_run_at)
+            .await
+            .unwrap();
+
+        let stored = db.get_job("request_expiry").await.unwrap().unwrap();
+        assert_eq!(stored.running_since, None);
+        assert_eq!(stored.last_status, Some(JobRunStatus::SUCCEEDED));
+        assert_eq!(stored.next_run_at, next_run_at);
+        // A scheduled run waits until the job is due again, a manual one doesn't
+        assert!(!db
+            .lock_job("request_expiry", now, stale_before, true)
+            .await
+            .unwrap());
+        assert!(db
+            .lock_job("request_expiry", now, stale_before, false)
+            .await
+            .unwrap());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_job_runs_page(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        db.create_job(&job("tuition_expiry")).await.unwrap();
+        db.create_job(&job("training_reminder")).await.unwrap();
+
+        let mut run = JobRun {
+            id_run: Uuid::new_v4(),
+            job_name: "tuition_expiry".to_string(),
+            started_at: test_datetime(),
+            finished_at: None,
+            status: JobRunStatus::RUNNING,
+            output: None,
+            triggered_by: Some(Uuid::new_v4()),
+        };
+        db.create_job_run(&run).await.unwrap();
+        run.finished_at = Some(test_datetime());
+        run.status = JobRunStatus::FAILED;
+        run.output = Some("Database error".to_string());
+        db.update_job_run(&run).await.unwrap();
+
+        db.create_job_run(&JobRun {
+            id_run: Uuid::new_v4(),
+            job_name: "training_reminder".to_string(),
+            started_at: test_datetime(),
+            finished_at: None,
+            status: JobRunStatus::RUNNING,
+            output: None,
+            triggered_by: None,
+        })
+        .await
+        .unwrap();
+
+        let filter = JobRunFilter {
+            job_name: Some("tuition_expiry".to_string()),
+            status: None,
+        };
+        let page = db
+            .list_job_runs_page(&filter, &PageQuery::default())
+            .await
+            .unwrap();
+
+        assert_eq!(page.total, 1);
+        assert_eq!(page.items, vec![run]);
+    }
+}
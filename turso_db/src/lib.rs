@@ -1,23 +1,37 @@
-use std::{error::Error, sync::Arc};
+use std::{error::Error, path::Path, sync::Arc, time::Duration};
 
+use entities::event::OutboxEvent;
 use libsql::params;
 use libsql::{de, params::IntoParams, Connection, Rows};
 use serde::Deserialize;
 use uuid::Uuid;
 
+pub mod audit_repo;
+pub mod calendar_repo;
 pub mod category_repo;
 pub mod court_repo; // New
+pub mod job_repo;
 mod migration;
+pub mod notification_repo;
+pub mod outbox_repo;
+mod pagination;
+#[cfg(test)]
+mod privacy_test;
 pub mod request_repo;
+pub mod schedule_repo;
 pub mod tournament_repo;
+pub mod trainer_repo;
 pub mod training_repo;
 pub mod tuition_repo;
 pub mod user_repo;
+pub mod webhook_repo;
 
 #[derive(Clone)]
 pub struct TursoDb {
     db: Arc<libsql::Database>,
     conn: Option<Connection>,
+    /// Local file databases wait this long for the write lock instead of failing right away
+    busy_timeout: Option<Duration>,
 }
 
 impl TursoDb {
@@ -29,29 +43,40 @@ impl TursoDb {
         let turso_db = Self {
             db: Arc::new(db),
             conn: None,
+            busy_timeout: None,
         };
 
         Ok(turso_db)
     }
 
     pub async fn get_connection(&self) -> Result<libsql::Connection, Box<dyn Error>> {
-        match self.conn.clone() {
-            Some(conn) => Ok(conn),
-            None => Ok(self.db.connect()?),
-        }
+        Ok(self.connect().await?)
     }
 
     pub async fn get_connection_with_error<E>(
         &self,
         error_builder: impl Fn(String) -> E,
     ) -> Result<libsql::Connection, E> {
-        match self.conn.clone() {
-            Some(conn) => Ok(conn),
-            None => Ok(self
-                .db
-                .connect()
-                .map_err(|err| error_builder(format!("Error in connection: {err}"))))?,
+        self.connect()
+            .await
+            .map_err(|err| error_builder(format!("Error in connection: {err}")))
+    }
+
+    async fn connect(&self) -> Result<libsql::Connection, libsql::Error> {
+        if let Some(conn) = &self.conn {
+            return Ok(conn.clone());
         }
+
+        let conn = self.db.connect()?;
+        if let Some(busy_timeout) = self.busy_timeout {
+            conn.query(
+                &format!("PRAGMA busy_timeout = {}", busy_timeout.as_millis()),
+                (),
+            )
+            .await?;
+        }
+
+        Ok(conn)
     }
 
     pub async fn query_one_with_error<T, E>(
@@ -95,6 +120,46 @@ impl TursoDb {
         Ok(())
     }
 
+    /// Executes the statement and stores the event it produced in the outbox in one transaction,
+    /// so the event is published only if the statement changes a row. Returns the affected rows
+    pub async fn execute_with_event<E>(
+        &self,
+        sql: &str,
+        params: impl IntoParams,
+        event: Option<&OutboxEvent>,
+        error_builder: impl Fn(String) -> E,
+    ) -> Result<u64, E> {
+        let conn = self.get_connection_with_error(&error_builder).await?;
+        let tx = conn
+            .transaction()
+            .await
+            .map_err(|err| error_builder(err.to_string()))?;
+
+        let result = async {
+            let affected_rows = tx.execute(sql, params).await?;
+            if let Some(event) = event.filter(|_| affected_rows > 0) {
+                outbox_repo::insert_outbox_event(&tx, event).await?;
+            }
+            Ok::<u64, libsql::Error>(affected_rows)
+        }
+        .await;
+
+        match result {
+            Ok(affected_rows) => {
+                tx.commit()
+                    .await
+                    .map_err(|err| error_builder(err.to_string()))?;
+                Ok(affected_rows)
+            }
+            Err(err) => {
+                tx.rollback()
+                    .await
+                    .map_err(|err| error_builder(err.to_string()))?;
+                Err(error_builder(err.to_string()))
+            }
+        }
+    }
+
     pub async fn execute_returning_affected_with_error<E>(
         &self,
         sql: &str,
@@ -177,6 +242,17 @@ identification_number, identification_type, user_rol, deleted
         .await?;
         Ok(())
     }
+
+    pub async fn create_test_category(&self, category_id: Uuid) -> Result<(), Box<dyn Error>> {
+        let conn = self.get_connection().await?;
+        conn.execute(
+            "INSERT OR IGNORE INTO category (id_category, name, min_age, max_age, deleted)
+VALUES (?1, 'Test', 0, 99, 0)",
+            params![category_id.to_string()],
+        )
+        .await?;
+        Ok(())
+    }
 }
 
 pub struct TestDbBuilder {
@@ -203,6 +279,28 @@ impl TestDbBuilder {
         }
     }
 
+    /// Database stored in a file, for tests that need several connections writing at once.
+    /// Build it with `build_with_connection_per_call`
+    pub async fn create_in_file(path: &Path) -> Self {
+        let db = libsql::Builder::new_local(path)
+            .build()
+            .await
+            .expect("Error building file db");
+        let conn = db.connect().expect("Error getting connection");
+
+        conn.query("PRAGMA journal_mode = WAL", ())
+            .await
+            .expect("Error enabling the write-ahead log");
+        conn.execute_batch(&migration::get_migration())
+            .await
+            .expect("Error applying migration");
+
+        Self {
+            db: Arc::new(db),
+            conn,
+        }
+    }
+
     pub async fn create_full() -> TursoDb {
         Self::create()
             .await
@@ -268,6 +366,16 @@ impl TestDbBuilder {
         TursoDb {
             db: self.db,
             conn: Some(self.conn),
+            busy_timeout: None,
+        }
+    }
+
+    /// Every operation opens its own connection, like concurrent requests against the server do
+    pub fn build_with_connection_per_call(self) -> TursoDb {
+        TursoDb {
+            db: self.db,
+            conn: None,
+            busy_timeout: Some(Duration::from_secs(5)),
         }
     }
 }
@@ -113,8 +113,12 @@ CREATE TABLE training (
     start_datetime TEXT NOT NULL,     -- Example: 'YYYY-MM-DD HH:MM:SS'
     end_datetime   TEXT NOT NULL,     -- Example: 'YYYY-MM-DD HH:MM:SS'
     minimum_payment REAL,
+    trainer_id     TEXT NOT NULL,     -- Trainer leading the training
+    max_participants INTEGER,         -- NULL for trainings without a limit
+    attendance_closed INTEGER NOT NULL DEFAULT 0,  -- Set once the attendance can't change anymore
     deleted        INTEGER NOT NULL DEFAULT 0,
-    FOREIGN KEY (id_category) REFERENCES category(id_category)
+    FOREIGN KEY (id_category) REFERENCES category(id_category),
+    FOREIGN KEY (trainer_id)  REFERENCES person(id_user)
 );
 
 -- 12) training_registration (updated: attendance_datetime is now nullable)
@@ -124,10 +128,14 @@ CREATE TABLE training_registration (
     registration_datetime TEXT NOT NULL,   -- Example: 'YYYY-MM-DD HH:MM:SS'
     attended             INTEGER NOT NULL DEFAULT 0,  -- 0 = false, 1 = true
     attendance_datetime  TEXT,              -- Changed to nullable
+    attendance_status    TEXT,              -- PRESENT, LATE, ABSENT, EXCUSED or INJURED, NULL until taken
+    attendance_submitted_by TEXT,           -- Who took the attendance
+    attendance_notes     TEXT,              -- Reason of an excused absence or trainer notes
     deleted              INTEGER NOT NULL DEFAULT 0,
     PRIMARY KEY (id_training, id_user),
     FOREIGN KEY (id_training) REFERENCES training(id_training),
-    FOREIGN KEY (id_user)     REFERENCES person(id_user)
+    FOREIGN KEY (id_user)     REFERENCES person(id_user),
+    FOREIGN KEY (attendance_submitted_by) REFERENCES person(id_user)
 );
 
 -- 13) tuition
@@ -144,14 +152,398 @@ CREATE TABLE tuition (
 CREATE TABLE request (
     request_id         TEXT PRIMARY KEY,
     requester_id       TEXT NOT NULL,
-    requested_command  TEXT NOT NULL,
+    requested_command  TEXT NOT NULL,    -- Kind of the command, e.g. 'ROLE_CHANGE'
+    command_payload    TEXT,             -- JSON payload of the command, NULL for kinds without one
     justification      TEXT NOT NULL,
-    approved           INTEGER,  -- Bool stored as INTEGER (0 = false, 1 = true)
-    approver_id        TEXT,             -- Nullable, as approval may be pending
+    state              TEXT NOT NULL DEFAULT 'PENDING',  -- PENDING, IN_REVIEW, APPROVED, ...
+    current_step       INTEGER NOT NULL DEFAULT 0,       -- Approval step waiting for a decision
+    approver_id        TEXT,             -- Approver of the final decision, NULL while open
+    created_at         TEXT NOT NULL,    -- Example: 'YYYY-MM-DD HH:MM:SS'
+    expires_at         TEXT,             -- Open requests expire after this deadline
+    executed_at        TEXT,             -- When the approved command was applied
+    execution_error    TEXT,             -- Why applying the approved command failed
     deleted            INTEGER NOT NULL DEFAULT 0,  -- Added for consistency
     FOREIGN KEY (requester_id) REFERENCES person(id_user),
     FOREIGN KEY (approver_id)  REFERENCES person(id_user)
 );
+
+-- 14.1) request_step, approval chain of each request
+CREATE TABLE request_step (
+    request_id    TEXT NOT NULL,
+    step          INTEGER NOT NULL,  -- Steps are decided in ascending order
+    approver_rol  TEXT NOT NULL,     -- Role required to decide the step
+    approver_id   TEXT,
+    approved      INTEGER,           -- Bool stored as INTEGER, NULL while undecided
+    decided_at    TEXT,
+    PRIMARY KEY (request_id, step),
+    FOREIGN KEY (request_id)  REFERENCES request(request_id),
+    FOREIGN KEY (approver_id) REFERENCES person(id_user)
+);
+
+-- 14.2) request_comment, discussion thread of a request
+CREATE TABLE request_comment (
+    id_comment  TEXT PRIMARY KEY,
+    request_id  TEXT NOT NULL,
+    author_id   TEXT NOT NULL,
+    body        TEXT NOT NULL,
+    created_at  TEXT NOT NULL,  -- Example: 'YYYY-MM-DD HH:MM:SS'
+    FOREIGN KEY (request_id) REFERENCES request(request_id),
+    FOREIGN KEY (author_id)  REFERENCES person(id_user)
+);
+
+-- 15) audit_log, append-only: rows can't be updated nor deleted
+CREATE TABLE audit_log (
+    id_audit        TEXT PRIMARY KEY,
+    actor_id        TEXT NOT NULL,
+    actor_rol       TEXT NOT NULL,
+    action          TEXT NOT NULL,
+    entity          TEXT NOT NULL,
+    entity_id       TEXT NOT NULL,
+    before_snapshot TEXT,           -- JSON, NULL for creations
+    after_snapshot  TEXT,           -- JSON, NULL for deletions
+    created_at      TEXT NOT NULL   -- Example: 'YYYY-MM-DD HH:MM:SS'
+);
+
+CREATE INDEX idx_audit_log_entity ON audit_log (entity, entity_id);
+
+CREATE TRIGGER audit_log_no_update BEFORE UPDATE ON audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'audit_log is append-only');
+END;
+
+CREATE TRIGGER audit_log_no_delete BEFORE DELETE ON audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'audit_log is append-only');
+END;
+
+-- 16) notification_preference, members without a row use the defaults
+CREATE TABLE notification_preference (
+    id_user   TEXT PRIMARY KEY,
+    language  TEXT NOT NULL,     -- 'ES' or 'EN'
+    email     INTEGER NOT NULL,  -- Bool stored as INTEGER
+    sms       INTEGER NOT NULL,  -- Bool stored as INTEGER
+    in_app    INTEGER NOT NULL,  -- Bool stored as INTEGER
+    FOREIGN KEY (id_user) REFERENCES person(id_user)
+);
+
+-- 17) notification, delivery log. IN_APP rows make up the inbox of each member
+CREATE TABLE notification (
+    id_notification  TEXT PRIMARY KEY,
+    id_user          TEXT NOT NULL,
+    kind             TEXT NOT NULL,
+    channel          TEXT NOT NULL,
+    subject          TEXT NOT NULL,
+    body             TEXT NOT NULL,
+    status           TEXT NOT NULL,  -- 'SENT', 'FAILED' or 'SKIPPED'
+    error            TEXT,
+    created_at       TEXT NOT NULL,  -- Example: 'YYYY-MM-DD HH:MM:SS'
+    read_at          TEXT,
+    FOREIGN KEY (id_user) REFERENCES person(id_user)
+);
+
+CREATE INDEX idx_notification_user ON notification (id_user, channel);
+
+-- 18) outbox_event, domain events waiting to be dispatched to their subscribers
+CREATE TABLE outbox_event (
+    id_event       TEXT PRIMARY KEY,
+    kind           TEXT NOT NULL,
+    payload        TEXT NOT NULL,     -- JSON payload of the event
+    actor_id       TEXT NOT NULL,
+    actor_rol      TEXT NOT NULL,
+    occurred_at    TEXT NOT NULL,     -- Example: 'YYYY-MM-DD HH:MM:SS'
+    dispatched_at  TEXT,              -- NULL while pending
+    attempts       INTEGER NOT NULL DEFAULT 0,
+    last_error     TEXT
+);
+
+CREATE INDEX idx_outbox_event_pending ON outbox_event (dispatched_at, occurred_at);
+
+-- 18.1) outbox_delivery, subscribers that already handled an event
+CREATE TABLE outbox_delivery (
+    id_event      TEXT NOT NULL,
+    subscriber    TEXT NOT NULL,
+    delivered_at  TEXT NOT NULL,  -- Example: 'YYYY-MM-DD HH:MM:SS'
+    PRIMARY KEY (id_event, subscriber),
+    FOREIGN KEY (id_event) REFERENCES outbox_event(id_event)
+);
+
+-- 19) webhook, external endpoints registered by admins
+CREATE TABLE webhook (
+    id_webhook   TEXT PRIMARY KEY,
+    url          TEXT NOT NULL,
+    description  TEXT NOT NULL,
+    event_kinds  TEXT NOT NULL,     -- JSON array of the subscribed event kinds
+    secret       TEXT NOT NULL,     -- Key of the HMAC signature of the payloads
+    active       INTEGER NOT NULL,  -- Bool stored as INTEGER
+    created_at   TEXT NOT NULL,     -- Example: 'YYYY-MM-DD HH:MM:SS'
+    deleted      INTEGER NOT NULL DEFAULT 0
+);
+
+-- 19.1) webhook_delivery, delivery history of each event to each webhook
+CREATE TABLE webhook_delivery (
+    id_delivery      TEXT PRIMARY KEY,
+    id_webhook       TEXT NOT NULL,
+    id_event         TEXT NOT NULL,
+    event_kind       TEXT NOT NULL,
+    payload          TEXT NOT NULL,     -- JSON body as signed and sent
+    status           TEXT NOT NULL,     -- 'PENDING', 'SUCCEEDED' or 'FAILED'
+    attempts         INTEGER NOT NULL DEFAULT 0,
+    response_status  INTEGER,
+    last_error       TEXT,
+    next_attempt_at  TEXT,              -- NULL once the delivery is no longer retried
+    created_at       TEXT NOT NULL,     -- Example: 'YYYY-MM-DD HH:MM:SS'
+    delivered_at     TEXT,
+    UNIQUE (id_webhook, id_event),
+    FOREIGN KEY (id_webhook) REFERENCES webhook(id_webhook)
+);
+
+CREATE INDEX idx_webhook_delivery_due ON webhook_delivery (status, next_attempt_at);
+
+-- 20) job, state of the periodic jobs. running_since works as a lock between instances
+CREATE TABLE job (
+    name           TEXT PRIMARY KEY,
+    schedule       TEXT NOT NULL,  -- Cron expression, evaluated in UTC
+    next_run_at    TEXT NOT NULL,  -- Example: 'YYYY-MM-DD HH:MM:SS'
+    running_since  TEXT,           -- NULL while the job isn't running
+    last_run_at    TEXT,
+    last_status    TEXT            -- 'SUCCEEDED' or 'FAILED'
+);
+
+-- 20.1) job_run, outcome of every execution of a job
+CREATE TABLE job_run (
+    id_run        TEXT PRIMARY KEY,
+    job_name      TEXT NOT NULL,
+    started_at    TEXT NOT NULL,  -- Example: 'YYYY-MM-DD HH:MM:SS'
+    finished_at   TEXT,
+    status        TEXT NOT NULL,  -- 'RUNNING', 'SUCCEEDED' or 'FAILED'
+    output        TEXT,
+    triggered_by  TEXT,           -- Admin who triggered the run, NULL for scheduled runs
+    FOREIGN KEY (job_name) REFERENCES job(name)
+);
+
+CREATE INDEX idx_job_run_job ON job_run (job_name, started_at);
+
+-- 21) court
+CREATE TABLE court (
+    id_court              TEXT PRIMARY KEY,
+    court_name            TEXT NOT NULL,
+    surface               TEXT NOT NULL DEFAULT 'HARD',  -- 'CLAY', 'HARD', 'GRASS' or 'SYNTHETIC'
+    indoor                INTEGER NOT NULL DEFAULT 0,
+    has_lighting          INTEGER NOT NULL DEFAULT 0,
+    capacity              INTEGER NOT NULL DEFAULT 4,
+    default_slot_minutes  INTEGER NOT NULL DEFAULT 60,
+    active                INTEGER NOT NULL DEFAULT 1,
+    deleted               INTEGER NOT NULL DEFAULT 0
+);
+
+-- 21.1) court_reservation, linked to a training, a tournament or the member who booked it
+CREATE TABLE court_reservation (
+    id_court_reservation        TEXT PRIMARY KEY,
+    id_court                    TEXT NOT NULL,
+    start_reservation_datetime  TEXT NOT NULL,  -- Example: 'YYYY-MM-DD HH:MM:SS'
+    end_reservation_datetime    TEXT NOT NULL,  -- Example: 'YYYY-MM-DD HH:MM:SS'
+    id_training                 TEXT,
+    id_tournament               TEXT,
+    id_owner                    TEXT,           -- Member who booked the court
+    deleted                     INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (id_court)      REFERENCES court(id_court),
+    FOREIGN KEY (id_training)   REFERENCES training(id_training),
+    FOREIGN KEY (id_tournament) REFERENCES tournament(id_tournament),
+    FOREIGN KEY (id_owner)      REFERENCES person(id_user)
+);
+
+CREATE INDEX idx_court_reservation_court ON court_reservation (id_court, start_reservation_datetime);
+
+-- Overlapping reservations are rejected by the database, so two requests checking the
+-- availability at the same time can't both reserve the court
+CREATE TRIGGER court_reservation_no_overlap_insert
+BEFORE INSERT ON court_reservation
+WHEN NEW.deleted = 0
+BEGIN
+    SELECT RAISE(ABORT, 'COURT_UNAVAILABLE')
+    WHERE EXISTS (
+        SELECT 1 FROM court_reservation
+        WHERE id_court = NEW.id_court AND deleted = 0
+        AND start_reservation_datetime < NEW.end_reservation_datetime
+        AND end_reservation_datetime > NEW.start_reservation_datetime
+    );
+END;
+
+CREATE TRIGGER court_reservation_no_overlap_update
+BEFORE UPDATE OF id_court, start_reservation_datetime, end_reservation_datetime, deleted
+ON court_reservation
+WHEN NEW.deleted = 0
+BEGIN
+    SELECT RAISE(ABORT, 'COURT_UNAVAILABLE')
+    WHERE EXISTS (
+        SELECT 1 FROM court_reservation
+        WHERE id_court = NEW.id_court AND deleted = 0
+        AND id_court_reservation <> NEW.id_court_reservation
+        AND start_reservation_datetime < NEW.end_reservation_datetime
+        AND end_reservation_datetime > NEW.start_reservation_datetime
+    );
+END;
+
+-- 21.2) court_reservation_partner, members playing with the owner of a booking
+CREATE TABLE court_reservation_partner (
+    id_court_reservation  TEXT NOT NULL,
+    id_user               TEXT NOT NULL,
+    PRIMARY KEY (id_court_reservation, id_user),
+    FOREIGN KEY (id_court_reservation) REFERENCES court_reservation(id_court_reservation),
+    FOREIGN KEY (id_user)              REFERENCES person(id_user)
+);
+
+-- 21.3) court_opening_hours, a court without rows is always open
+CREATE TABLE court_opening_hours (
+    id_court     TEXT NOT NULL,
+    day_of_week  TEXT NOT NULL,  -- 'MONDAY' ... 'SUNDAY'
+    open_time    TEXT NOT NULL,  -- Example: 'HH:MM'
+    close_time   TEXT NOT NULL,  -- Example: 'HH:MM'
+    PRIMARY KEY (id_court, day_of_week),
+    FOREIGN KEY (id_court) REFERENCES court(id_court)
+);
+
+-- 21.4) court_closure, holidays and maintenance windows. id_court is NULL when every court closes
+CREATE TABLE court_closure (
+    id_closure      TEXT PRIMARY KEY,
+    id_court        TEXT,
+    kind            TEXT NOT NULL,  -- 'HOLIDAY' or 'MAINTENANCE'
+    reason          TEXT NOT NULL,
+    start_datetime  TEXT NOT NULL,  -- Example: 'YYYY-MM-DD HH:MM:SS'
+    end_datetime    TEXT NOT NULL,  -- Example: 'YYYY-MM-DD HH:MM:SS'
+    deleted         INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (id_court) REFERENCES court(id_court)
+);
+
+-- 21.5) court_price_rule, hourly prices of a time band. id_court and day_of_week are NULL when the
+-- band applies to every court or every day
+CREATE TABLE court_price_rule (
+    id_price_rule     TEXT PRIMARY KEY,
+    name              TEXT NOT NULL,
+    id_court          TEXT,
+    day_of_week       TEXT,           -- 'MONDAY' ... 'SUNDAY'
+    start_time        TEXT NOT NULL,  -- Example: 'HH:MM'
+    end_time          TEXT NOT NULL,  -- Example: 'HH:MM'
+    member_price      REAL NOT NULL,
+    guest_price       REAL NOT NULL,
+    lights_surcharge  REAL NOT NULL DEFAULT 0,
+    deleted           INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (id_court) REFERENCES court(id_court)
+);
+
+-- 21.6) court_charge, what a member owes for a booking
+CREATE TABLE court_charge (
+    id_charge             TEXT PRIMARY KEY,
+    id_court_reservation  TEXT NOT NULL UNIQUE,
+    id_user               TEXT NOT NULL,
+    amount                REAL NOT NULL,
+    status                TEXT NOT NULL,  -- 'PENDING', 'PAID', 'CANCELLED', 'EXPIRED' or 'REFUNDED'
+    created_datetime      TEXT NOT NULL,  -- Example: 'YYYY-MM-DD HH:MM:SS'
+    due_datetime          TEXT NOT NULL,  -- Example: 'YYYY-MM-DD HH:MM:SS'
+    paid_datetime         TEXT,
+    FOREIGN KEY (id_court_reservation) REFERENCES court_reservation(id_court_reservation),
+    FOREIGN KEY (id_user)              REFERENCES person(id_user)
+);
+
+CREATE INDEX idx_court_charge_due ON court_charge (status, due_datetime);
+
+-- 21.7) court_cancellation, why a reservation was cancelled
+CREATE TABLE court_cancellation (
+    id_court_reservation  TEXT PRIMARY KEY,
+    cancelled_by          TEXT NOT NULL,
+    reason                TEXT NOT NULL,
+    cancelled_datetime    TEXT NOT NULL,  -- Example: 'YYYY-MM-DD HH:MM:SS'
+    late                  INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (id_court_reservation) REFERENCES court_reservation(id_court_reservation),
+    FOREIGN KEY (cancelled_by)         REFERENCES person(id_user)
+);
+
+-- 21.8) court_check_in, a player of the booking showed up
+CREATE TABLE court_check_in (
+    id_court_reservation  TEXT PRIMARY KEY,
+    id_user               TEXT NOT NULL,
+    checked_in_datetime   TEXT NOT NULL,  -- Example: 'YYYY-MM-DD HH:MM:SS'
+    FOREIGN KEY (id_court_reservation) REFERENCES court_reservation(id_court_reservation),
+    FOREIGN KEY (id_user)              REFERENCES person(id_user)
+);
+
+-- 21.9) court_no_show, bookings that ended without a check-in. Waived rows are kept so the
+-- booking isn't counted again
+CREATE TABLE court_no_show (
+    id_court_reservation  TEXT PRIMARY KEY,
+    id_user               TEXT NOT NULL,
+    start_datetime        TEXT NOT NULL,  -- Example: 'YYYY-MM-DD HH:MM:SS'
+    waived                INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (id_court_reservation) REFERENCES court_reservation(id_court_reservation),
+    FOREIGN KEY (id_user)              REFERENCES person(id_user)
+);
+
+CREATE INDEX idx_court_no_show_user ON court_no_show (id_user, start_datetime);
+
+-- 22) calendar_feed, read-only iCalendar links. The token is the only credential of the link
+CREATE TABLE calendar_feed (
+    token             TEXT PRIMARY KEY,
+    id_user           TEXT NOT NULL,  -- Who created the feed
+    kind              TEXT NOT NULL,  -- 'COURT', 'TRAINER' or 'MEMBER'
+    id_subject        TEXT NOT NULL,  -- The court, the trainer or the member the feed follows
+    created_datetime  TEXT NOT NULL,  -- Example: 'YYYY-MM-DD HH:MM:SS'
+    deleted           INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (id_user) REFERENCES person(id_user)
+);
+
+-- 23) training_waitlist, members waiting for a spot in a full training. Rows are kept after
+-- they leave the queue, the queue order is the insertion order
+CREATE TABLE training_waitlist (
+    id_training       TEXT NOT NULL,
+    id_user           TEXT NOT NULL,
+    joined_datetime   TEXT NOT NULL,  -- Example: 'YYYY-MM-DD HH:MM:SS'
+    status            TEXT NOT NULL,  -- 'WAITING', 'OFFERED', 'CONFIRMED', 'EXPIRED' or 'LEFT'
+    offered_datetime  TEXT,
+    confirm_before    TEXT,           -- Deadline of the offered spot
+    PRIMARY KEY (id_training, id_user),
+    FOREIGN KEY (id_training) REFERENCES training(id_training),
+    FOREIGN KEY (id_user)     REFERENCES person(id_user)
+);
+
+CREATE INDEX idx_training_waitlist_offer ON training_waitlist (status, confirm_before);
+
+-- 23.1) Capacity of a training. Spots offered to the waitlist are taken, except the one of the
+-- member registering. Checked on insert, so members registering at once can't overfill it
+CREATE TRIGGER training_registration_capacity
+BEFORE INSERT ON training_registration
+BEGIN
+    SELECT RAISE(ABORT, 'TRAINING_FULL')
+    WHERE (SELECT max_participants FROM training WHERE id_training = NEW.id_training) <= (
+        SELECT COUNT(*) FROM training_registration WHERE id_training = NEW.id_training
+    ) + (
+        SELECT COUNT(*) FROM training_waitlist
+        WHERE id_training = NEW.id_training AND status = 'OFFERED' AND id_user <> NEW.id_user
+    );
+END;
+
+-- 24) trainer_availability, weekly hours a trainer leads trainings. A trainer without rows is
+-- available at any time
+CREATE TABLE trainer_availability (
+    id_trainer   TEXT NOT NULL,
+    day_of_week  TEXT NOT NULL,  -- 'MONDAY' ... 'SUNDAY'
+    start_time   TEXT NOT NULL,  -- Example: 'HH:MM'
+    end_time     TEXT NOT NULL,  -- Example: 'HH:MM'
+    PRIMARY KEY (id_trainer, day_of_week),
+    FOREIGN KEY (id_trainer) REFERENCES person(id_user)
+);
+
+-- 24.1) trainer_time_off, holidays and leaves in which a trainer can't be assigned
+CREATE TABLE trainer_time_off (
+    id_time_off     TEXT PRIMARY KEY,
+    id_trainer      TEXT NOT NULL,
+    reason          TEXT NOT NULL,
+    start_datetime  TEXT NOT NULL,  -- Example: 'YYYY-MM-DD HH:MM:SS'
+    end_datetime    TEXT NOT NULL,  -- Example: 'YYYY-MM-DD HH:MM:SS'
+    deleted         INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (id_trainer) REFERENCES person(id_user)
+);
+
+CREATE INDEX idx_trainer_time_off_range ON trainer_time_off (id_trainer, start_datetime);
 "#
     .to_string()
 }
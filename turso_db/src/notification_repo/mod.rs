@@ -0,0 +1,250 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use entities::notification::{Notification, NotificationChannel, NotificationPreferences};
+use entities::pagination::{Page, PageQuery};
+use libsql::params;
+use use_cases::notification_service::{
+    err::{Error, Result},
+    repository_trait::NotificationRepository,
+};
+use uuid::Uuid;
+
+use crate::pagination::{PageSql, SqlFilter};
+use crate::TursoDb;
+
+const INBOX_PAGE_SQL: PageSql<'static> = PageSql {
+    select: "SELECT id_notification, id_user, kind, channel, subject, body, status, error,
+created_at, read_at
+FROM notification
+WHERE 1 = 1",
+    count: "SELECT COUNT(*) AS count FROM notification WHERE 1 = 1",
+    sortable_columns: &["created_at", "kind", "read_at"],
+    default_sort_column: "created_at",
+    primary_key: "id_notification",
+};
+
+#[async_trait]
+impl NotificationRepository for TursoDb {
+    async fn get_preferences(&self, user_id: Uuid) -> Result<Option<NotificationPreferences>> {
+        self.query_one_with_error(
+            "SELECT id_user, language, email, sms, in_app
+            FROM notification_preference
+            WHERE id_user = ?1",
+            params![user_id.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn save_preferences(&self, preferences: &NotificationPreferences) -> Result<()> {
+        self.execute_with_error(
+            "INSERT INTO notification_preference (id_user, language, email, sms, in_app)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(id_user) DO UPDATE SET
+                language = excluded.language,
+                email = excluded.email,
+                sms = excluded.sms,
+                in_app = excluded.in_app",
+            params![
+                preferences.id_user.to_string(),
+                preferences.language.to_string(),
+                preferences.email,
+                preferences.sms,
+                preferences.in_app,
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn create_notification(&self, notification: &Notification) -> Result<()> {
+        self.execute_with_error(
+            "INSERT INTO notification (
+                id_notification, id_user, kind, channel, subject, body, status, error,
+                created_at, read_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                notification.id_notification.to_string(),
+                notification.id_user.to_string(),
+                notification.kind.to_string(),
+                notification.channel.to_string(),
+                notification.subject.clone(),
+                notification.body.clone(),
+                notification.status.to_string(),
+                notification.error.clone(),
+                notification
+                    .created_at
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string(),
+                notification
+                    .read_at
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_inbox_page(&self, user_id: Uuid, page: &PageQuery) -> Result<Page<Notification>> {
+        let mut sql_filter = SqlFilter::new();
+        sql_filter
+            .push("id_user = ?", user_id.to_string())
+            .push("channel = ?", NotificationChannel::IN_APP.to_string());
+
+        self.query_page_with_error(
+            &INBOX_PAGE_SQL,
+            &sql_filter,
+            page,
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_user_notifications(&self, user_id: Uuid) -> Result<Vec<Notification>> {
+        self.query_many_with_error(
+            "SELECT id_notification, id_user, kind, channel, subject, body, status, error,
+            created_at, read_at
+            FROM notification
+            WHERE id_user = ?1
+            ORDER BY created_at, id_notification",
+            params![user_id.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn mark_notification_read(
+        &self,
+        id_notification: Uuid,
+        user_id: Uuid,
+        read_at: NaiveDateTime,
+    ) -> Result<bool> {
+        let affected = self
+            .execute_returning_affected_with_error(
+                "UPDATE notification SET read_at = COALESCE(read_at, ?1)
+                WHERE id_notification = ?2 AND id_user = ?3",
+                params![
+                    read_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    id_notification.to_string(),
+                    user_id.to_string(),
+                ],
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+
+        Ok(affected > 0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::future::Future;
+
+    use super::*;
+    use chrono::Utc;
+    use entities::notification::{DeliveryStatus, Language, NotificationKind};
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    async fn repository() -> TursoDb {
+        crate::TestDbBuilder::create_full().await
+    }
+
+    fn test_datetime() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(
+            &Utc::now()
+                .naive_utc()
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+            "%Y-%m-%d %H:%M:%S",
+        )
+        .unwrap()
+    }
+
+    fn notification(id_user: Uuid, channel: NotificationChannel) -> Notification {
+        Notification {
+            id_notification: Uuid::new_v4(),
+            id_user,
+            kind: NotificationKind::TRAINING_CANCELLED,
+            channel,
+            subject: "Training cancelled".to_string(),
+            body: "The training has been cancelled.".to_string(),
+            status: DeliveryStatus::SENT,
+            error: None,
+            created_at: test_datetime(),
+            read_at: None,
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_save_preferences(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let user_id = Uuid::new_v4();
+        db.create_test_user(user_id)
+            .await
+            .expect("Error creating test user");
+
+        assert_eq!(db.get_preferences(user_id).await.unwrap(), None);
+
+        let mut preferences = NotificationPreferences::default_for(user_id);
+        db.save_preferences(&preferences)
+            .await
+            .expect("Error creating preferences");
+
+        preferences.language = Language::EN;
+        preferences.sms = true;
+        db.save_preferences(&preferences)
+            .await
+            .expect("Error updating preferences");
+
+        let saved = db.get_preferences(user_id).await.unwrap();
+        assert_eq!(saved, Some(preferences));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_inbox_and_mark_read(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let user_id = Uuid::new_v4();
+        let other_user_id = Uuid::new_v4();
+        db.create_test_user(user_id)
+            .await
+            .expect("Error creating test user");
+        db.create_test_user(other_user_id)
+            .await
+            .expect("Error creating test user");
+
+        let in_app = notification(user_id, NotificationChannel::IN_APP);
+        let email = notification(user_id, NotificationChannel::EMAIL);
+        let other_in_app = notification(other_user_id, NotificationChannel::IN_APP);
+        for notification in [&in_app, &email, &other_in_app] {
+            db.create_notification(notification)
+                .await
+                .expect("Error creating notification");
+        }
+
+        let inbox = db
+            .list_inbox_page(user_id, &PageQuery::default())
+            .await
+            .expect("Error listing inbox");
+        assert_eq!(inbox.total, 1);
+        assert_eq!(inbox.items, vec![in_app.clone()]);
+
+        let read_at = test_datetime();
+        assert!(!db
+            .mark_notification_read(in_app.id_notification, other_user_id, read_at)
+            .await
+            .unwrap());
+        assert!(db
+            .mark_notification_read(in_app.id_notification, user_id, read_at)
+            .await
+            .unwrap());
+
+        let inbox = db
+            .list_inbox_page(user_id, &PageQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(inbox.items[0].read_at, Some(read_at));
+    }
+}
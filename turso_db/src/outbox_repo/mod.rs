@@ -0,0 +1,336 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use entities::audit::Actor;
+use entities::event::OutboxEvent;
+use entities::user::URol;
+use entities::{datetime_serde, datetime_serde_option};
+use libsql::{params, Connection};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use use_cases::event_service::{
+    err::{Error, Result},
+    repository_trait::OutboxRepository,
+};
+use uuid::Uuid;
+
+use crate::TursoDb;
+
+/// The kind of the event is stored in `kind` and its payload as JSON text
+#[derive(Deserialize)]
+struct OutboxEventRow {
+    id_event: Uuid,
+    kind: String,
+    payload: String,
+    actor_id: Uuid,
+    actor_rol: URol,
+    #[serde(with = "datetime_serde")]
+    occurred_at: NaiveDateTime,
+    #[serde(with = "datetime_serde_option", default)]
+    dispatched_at: Option<NaiveDateTime>,
+    attempts: u32,
+    last_error: Option<String>,
+}
+
+impl TryFrom<OutboxEventRow> for OutboxEvent {
+    type Error = Error;
+
+    fn try_from(row: OutboxEventRow) -> Result<Self> {
+        let payload: Value = serde_json::from_str(&row.payload)
+            .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+
+        Ok(Self {
+            id_event: row.id_event,
+            event: serde_json::from_value(json!({ "kind": row.kind, "payload": payload }))
+                .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?,
+            actor: Actor {
+                user_id: row.actor_id,
+                user_rol: row.actor_rol,
+            },
+            occurred_at: row.occurred_at,
+            dispatched_at: row.dispatched_at,
+            attempts: row.attempts,
+            last_error: row.last_error,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct DeliveryRow {
+    subscriber: String,
+}
+
+fn format_datetime(datetime: NaiveDateTime) -> String {
+    datetime.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+/// Stores the event with the connection, or the transaction, of the operation that produced it.
+/// Inside a transaction the event is only kept if the operation is
+pub(crate) async fn insert_outbox_event(
+    conn: &Connection,
+    event: &OutboxEvent,
+) -> std::result::Result<(), libsql::Error> {
+    let value = serde_json::to_value(&event.event)
+        .map_err(|err| libsql::Error::ToSqlConversionFailure(Box::new(err)))?;
+    let payload = value.get("payload").cloned().unwrap_or(Value::Null);
+
+    conn.execute(
+        "INSERT INTO outbox_event (
+            id_event, kind, payload, actor_id, actor_rol, occurred_at, dispatched_at,
+            attempts, last_error
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            event.id_event.to_string(),
+            event.event.kind().to_string(),
+            payload.to_string(),
+            event.actor.user_id.to_string(),
+            event.actor.user_rol.to_string(),
+            format_datetime(event.occurred_at),
+            event.dispatched_at.map(format_datetime),
+            event.attempts,
+            event.last_error.clone(),
+        ],
+    )
+    .await?;
+    Ok(())
+}
+
+#[async_trait]
+impl OutboxRepository for TursoDb {
+    async fn append_event(&self, event: &OutboxEvent) -> Result<()> {
+        let conn = self
+            .get_connection_with_error(Error::UnknownDatabaseError)
+            .await?;
+        insert_outbox_event(&conn, event)
+            .await
+            .map_err(|err| Error::UnknownDatabaseError(err.to_string()))
+    }
+
+    async fn list_pending_events(&self, limit: u32, max_attempts: u32) -> Result<Vec<OutboxEvent>> {
+        let rows: Vec<OutboxEventRow> = self
+            .query_many_with_error(
+                "SELECT id_event, kind, payload, actor_id, actor_rol, occurred_at, dispatched_at,
+                attempts, last_error
+                FROM outbox_event
+                WHERE dispatched_at IS NULL AND attempts < ?1
+                ORDER BY occurred_at ASC
+                LIMIT ?2",
+                params![max_attempts, limit],
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+
+        rows.into_iter().map(OutboxEvent::try_from).collect()
+    }
+
+    async fn mark_event_dispatched(
+        &self,
+        id_event: Uuid,
+        dispatched_at: NaiveDateTime,
+    ) -> Result<()> {
+        self.execute_with_error(
+            "UPDATE outbox_event SET dispatched_at = ?1, last_error = NULL WHERE id_event = ?2",
+            params![format_datetime(dispatched_at), id_event.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn mark_event_failed(&self, id_event: Uuid, error: &str) -> Result<()> {
+        self.execute_with_error(
+            "UPDATE outbox_event SET attempts = attempts + 1, last_error = ?1 WHERE id_event = ?2",
+            params![error.to_string(), id_event.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_event_deliveries(&self, id_event: Uuid) -> Result<Vec<String>> {
+        let rows: Vec<DeliveryRow> = self
+            .query_many_with_error(
+                "SELECT subscriber FROM outbox_delivery WHERE id_event = ?1",
+                params![id_event.to_string()],
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(|row| row.subscriber).collect())
+    }
+
+    async fn record_event_delivery(&self, id_event: Uuid, subscriber: &str) -> Result<()> {
+        self.execute_with_error(
+            "INSERT OR IGNORE INTO outbox_delivery (id_event, subscriber, delivered_at)
+            VALUES (?1, ?2, datetime('now'))",
+            params![id_event.to_string(), subscriber.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::future::Future;
+
+    use super::*;
+    use chrono::Utc;
+    use entities::event::DomainEvent;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    async fn repository() -> TursoDb {
+        crate::TestDbBuilder::create()
+            .await
+            .apply_doc_types()
+            .await
+            .apply_user_roles()
+            .await
+            .build()
+    }
+
+    fn test_datetime() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(
+            &Utc::now()
+                .naive_utc()
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+            "%Y-%m-%d %H:%M:%S",
+        )
+        .unwrap()
+    }
+
+    fn outbox_event(event: DomainEvent) -> OutboxEvent {
+        OutboxEvent {
+            id_event: Uuid::new_v4(),
+            event,
+            actor: Actor {
+                user_id: Uuid::new_v4(),
+                user_rol: URol::ADMIN,
+            },
+            occurred_at: test_datetime(),
+            dispatched_at: None,
+            attempts: 0,
+            last_error: None,
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_pending_events(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+
+        let registered = outbox_event(DomainEvent::USER_REGISTERED {
+            id_user: Uuid::new_v4(),
+        });
+        let cancelled = outbox_event(DomainEvent::TRAINING_CANCELLED {
+            id_training: Uuid::new_v4(),
+            name: "Morning training".to_string(),
+            start_datetime: test_datetime(),
+            registered_users: vec![Uuid::new_v4()],
+        });
+        db.append_event(&registered)
+            .await
+            .expect("Error appending event");
+        db.append_event(&cancelled)
+            .await
+            .expect("Error appending event");
+
+        let pending = db.list_pending_events(10, 2).await.unwrap();
+        assert_eq!(pending.len(), 2);
+        assert!(pending.contains(&cancelled));
+
+        db.mark_event_dispatched(registered.id_event, test_datetime())
+            .await
+            .unwrap();
+        db.mark_event_failed(cancelled.id_event, "court_reservations: timeout")
+            .await
+            .unwrap();
+
+        let pending = db.list_pending_events(10, 2).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].attempts, 1);
+        assert_eq!(
+            pending[0].last_error.as_deref(),
+            Some("court_reservations: timeout")
+        );
+
+        db.mark_event_failed(cancelled.id_event, "court_reservations: timeout")
+            .await
+            .unwrap();
+        assert!(db.list_pending_events(10, 2).await.unwrap().is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_event_deliveries(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+
+        let event = outbox_event(DomainEvent::TOURNAMENT_CANCELLED {
+            id_tournament: Uuid::new_v4(),
+        });
+        db.append_event(&event)
+            .await
+            .expect("Error appending event");
+
+        db.record_event_delivery(event.id_event, "notifications")
+            .await
+            .unwrap();
+        db.record_event_delivery(event.id_event, "notifications")
+            .await
+            .unwrap();
+
+        let delivered = db.list_event_deliveries(event.id_event).await.unwrap();
+        assert_eq!(delivered, vec!["notifications".to_string()]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_event_kept_only_with_its_operation(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let id_user = Uuid::new_v4();
+        db.create_test_user(id_user)
+            .await
+            .expect("Error creating test user");
+        let verify_email = "UPDATE person SET email_verified = 1 WHERE id_user = ?1";
+
+        let applied = outbox_event(DomainEvent::USER_REGISTERED { id_user });
+        db.execute_with_event(
+            verify_email,
+            params![id_user.to_string()],
+            Some(&applied),
+            Error::UnknownDatabaseError,
+        )
+        .await
+        .expect("Error executing the statement");
+
+        // An operation changing nothing publishes nothing
+        let unknown_user = Uuid::new_v4();
+        let unchanged = outbox_event(DomainEvent::USER_REGISTERED {
+            id_user: unknown_user,
+        });
+        let affected_rows = db
+            .execute_with_event(
+                verify_email,
+                params![unknown_user.to_string()],
+                Some(&unchanged),
+                Error::UnknownDatabaseError,
+            )
+            .await
+            .expect("Error executing the statement");
+        assert_eq!(affected_rows, 0);
+
+        // A failing operation is rolled back with its event
+        let failed = outbox_event(DomainEvent::USER_REGISTERED { id_user });
+        assert!(db
+            .execute_with_event(
+                "INSERT INTO person (id_user) VALUES (?1)",
+                params![id_user.to_string()],
+                Some(&failed),
+                Error::UnknownDatabaseError,
+            )
+            .await
+            .is_err());
+
+        let pending = db.list_pending_events(10, 5).await.unwrap();
+        assert_eq!(pending, vec![applied]);
+    }
+}
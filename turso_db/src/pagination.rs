@@ -0,0 +1,175 @@
+use entities::pagination::{Page, PageQuery};
+use libsql::{params::Params, Value};
+use serde::Deserialize;
+
+use crate::TursoDb;
+
+/// Accumulates the optional `AND` conditions of a list query together with their positional
+/// parameters. Conditions must use anonymous `?` placeholders
+#[derive(Default)]
+pub(crate) struct SqlFilter {
+    conditions: Vec<String>,
+    values: Vec<Value>,
+}
+
+impl SqlFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, condition: &str, value: impl Into<Value>) -> &mut Self {
+        self.conditions.push(condition.to_string());
+        self.values.push(value.into());
+        self
+    }
+
+    pub fn push_if(&mut self, condition: &str, value: Option<impl Into<Value>>) -> &mut Self {
+        if let Some(value) = value {
+            self.push(condition, value);
+        }
+        self
+    }
+
+    /// For conditions that repeat the same value several times, like a search over many columns
+    pub fn push_repeated(
+        &mut self,
+        condition: &str,
+        value: impl Into<Value>,
+        times: usize,
+    ) -> &mut Self {
+        let value = value.into();
+        self.conditions.push(condition.to_string());
+        self.values.extend(std::iter::repeat_n(value, times));
+        self
+    }
+
+    fn and_clause(&self) -> String {
+        self.conditions
+            .iter()
+            .map(|condition| format!(" AND ({condition})"))
+            .collect()
+    }
+}
+
+#[derive(Deserialize)]
+struct Count {
+    count: i64,
+}
+
+/// Statements of a paginated list. `select` and `count` must end in a `WHERE` clause, so the
+/// filter conditions can be chained with `AND`
+pub(crate) struct PageSql<'a> {
+    pub select: &'a str,
+    pub count: &'a str,
+    pub sortable_columns: &'a [&'a str],
+    pub default_sort_column: &'a str,
+    /// Unique column used to break ties, so rows sharing the sort value keep the same order on
+    /// every page
+    pub primary_key: &'a str,
+}
+
+impl PageSql<'_> {
+    fn order_by_clause(&self, page: &PageQuery) -> String {
+        // Only whitelisted column names ever reach the SQL string
+        let column = page
+            .sort_by
+            .as_deref()
+            .and_then(|sort_by| {
+                self.sortable_columns
+                    .iter()
+                    .find(|column| **column == sort_by)
+                    .copied()
+            })
+            .unwrap_or(self.default_sort_column);
+
+        format!(
+            " ORDER BY {column} {order}, {primary_key} {order}",
+            order = page.sort_order,
+            primary_key = self.primary_key
+        )
+    }
+}
+
+impl TursoDb {
+    pub(crate) async fn query_page_with_error<T, E>(
+        &self,
+        sql: &PageSql<'_>,
+        filter: &SqlFilter,
+        page: &PageQuery,
+        error_builder: impl Fn(String) -> E,
+    ) -> Result<Page<T>, E>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let and_clause = filter.and_clause();
+
+        let count: Option<Count> = self
+            .query_one_with_error(
+                &format!("{}{and_clause}", sql.count),
+                Params::Positional(filter.values.clone()),
+                &error_builder,
+            )
+            .await?;
+
+        let mut values = filter.values.clone();
+        values.push(Value::Integer(page.limit() as i64));
+        values.push(Value::Integer(page.offset() as i64));
+
+        let items = self
+            .query_many_with_error(
+                &format!(
+                    "{}{and_clause}{} LIMIT ? OFFSET ?",
+                    sql.select,
+                    sql.order_by_clause(page)
+                ),
+                Params::Positional(values),
+                &error_builder,
+            )
+            .await?;
+
+        Ok(Page::new(
+            items,
+            count.map_or(0, |c| c.count.max(0) as u64),
+            page,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use entities::pagination::SortOrder;
+
+    use super::*;
+
+    const SQL: PageSql<'static> = PageSql {
+        select: "SELECT id_court, court_name FROM court WHERE deleted = 0",
+        count: "SELECT COUNT(*) AS count FROM court WHERE deleted = 0",
+        sortable_columns: &["court_name"],
+        default_sort_column: "court_name",
+        primary_key: "id_court",
+    };
+
+    #[test]
+    fn test_order_by_breaks_ties_with_the_primary_key() {
+        let page = PageQuery {
+            sort_by: Some("court_name".to_string()),
+            sort_order: SortOrder::DESC,
+            ..PageQuery::default()
+        };
+        assert_eq!(
+            SQL.order_by_clause(&page),
+            " ORDER BY court_name DESC, id_court DESC"
+        );
+
+        // Unknown columns fall back to the default one
+        let page = PageQuery {
+            sort_by: Some("password; DROP TABLE court".to_string()),
+            sort_order: SortOrder::ASC,
+            ..PageQuery::default()
+        };
+        assert_eq!(
+            SQL.order_by_clause(&page),
+            " ORDER BY court_name ASC, id_court ASC"
+        );
+    }
+}
@@ -0,0 +1,375 @@
+use std::sync::Arc;
+
+use entities::{
+    audit::Actor,
+    notification::NotificationKind,
+    request::RequestState,
+    user::{URol, User},
+};
+use libsql::params;
+use rstest::{fixture, rstest};
+use use_cases::{
+    audit_service::AuditService,
+    category_service::CategoryService,
+    court_service::{booking_policy::BookingPolicy, CourtService},
+    event_service::EventService,
+    notification_service::NotificationService,
+    privacy_service::PrivacyService,
+    request_service::{approval_policy::ApprovalPolicy, executor::RequestExecutor, RequestService},
+    schedule_service::ScheduleService,
+    tournament_service::TournamentService,
+    trainer_service::TrainerService,
+    training_service::{signer_trait::CheckInSigner, TrainingService},
+    tuition_service::TuitionService,
+    user_service::{err::Error, hasher_trait::PasswordHasher, UserService},
+};
+use uuid::Uuid;
+
+use crate::{TestDbBuilder, TursoDb};
+
+struct PlainHasher;
+
+impl PasswordHasher for PlainHasher {
+    fn hash(&self, content: &str) -> use_cases::user_service::err::Result<String> {
+        Ok(content.to_string())
+    }
+
+    fn verify(&self, original: &str, hashed: &str) -> use_cases::user_service::err::Result<bool> {
+        Ok(original == hashed)
+    }
+}
+
+struct PlainSigner;
+
+impl CheckInSigner for PlainSigner {
+    fn sign(&self, payload: &str) -> use_cases::training_service::err::Result<String> {
+        Ok(payload.to_string())
+    }
+
+    fn verify(
+        &self,
+        payload: &str,
+        signature: &str,
+    ) -> use_cases::training_service::err::Result<bool> {
+        Ok(payload == signature)
+    }
+}
+
+struct Services {
+    db: Arc<TursoDb>,
+    user_service: UserService,
+    tuition_service: TuitionService,
+    request_service: RequestService,
+    notification_service: NotificationService,
+    privacy_service: PrivacyService,
+}
+
+#[fixture]
+async fn services() -> Services {
+    let db = Arc::new(TestDbBuilder::create_full().await);
+
+    let audit_service = AuditService::new(db.clone());
+    let event_service = EventService::new(db.clone());
+    let user_service = UserService::new(
+        db.clone(),
+        Arc::new(PlainHasher),
+        event_service.clone(),
+        audit_service.clone(),
+    );
+    // No channel has a notifier, deliveries are still written to the log
+    let notification_service = NotificationService::new(
+        db.clone(),
+        Vec::new(),
+        user_service.clone(),
+        audit_service.clone(),
+    );
+    let category_service = CategoryService::new(
+        db.clone(),
+        db.clone(),
+        db.clone(),
+        user_service.clone(),
+        audit_service.clone(),
+    );
+    let tuition_service =
+        TuitionService::new(db.clone(), event_service.clone(), audit_service.clone());
+    let court_service = CourtService::new(
+        db.clone(),
+        db.clone(),
+        db.clone(),
+        db.clone(),
+        db.clone(),
+        tuition_service.clone(),
+        event_service.clone(),
+        audit_service.clone(),
+        BookingPolicy::default(),
+    );
+    let schedule_service = ScheduleService::new(db.clone());
+    let trainer_service = TrainerService::new(
+        db.clone(),
+        user_service.clone(),
+        schedule_service.clone(),
+        audit_service.clone(),
+    );
+    let training_service = TrainingService::new(
+        db.clone(),
+        db.clone(),
+        db.clone(),
+        Arc::new(PlainSigner),
+        chrono::Duration::hours(1),
+        category_service.clone(),
+        court_service.clone(),
+        user_service.clone(),
+        tuition_service.clone(),
+        schedule_service.clone(),
+        trainer_service,
+        event_service.clone(),
+        audit_service.clone(),
+    );
+    let tournament_service = TournamentService::new(
+        db.clone(),
+        db.clone(),
+        db.clone(),
+        category_service.clone(),
+        court_service,
+        schedule_service,
+        event_service.clone(),
+        audit_service.clone(),
+    );
+    let executors: Vec<Arc<dyn RequestExecutor>> = vec![Arc::new(user_service.clone())];
+    let request_service = RequestService::new(
+        db.clone(),
+        event_service,
+        audit_service,
+        executors,
+        ApprovalPolicy::default(),
+    );
+    let privacy_service = PrivacyService::new(
+        user_service.clone(),
+        category_service,
+        training_service,
+        tournament_service,
+        tuition_service.clone(),
+        request_service.clone(),
+        notification_service.clone(),
+    );
+
+    Services {
+        db,
+        user_service,
+        tuition_service,
+        request_service,
+        notification_service,
+        privacy_service,
+    }
+}
+
+fn actors() -> (Actor, Actor) {
+    let user = Actor {
+        user_id: Uuid::new_v4(),
+        user_rol: URol::USER,
+    };
+    let admin = Actor {
+        user_id: Uuid::new_v4(),
+        user_rol: URol::ADMIN,
+    };
+    (user, admin)
+}
+
+async fn notify_cancelled_training(services: &Services, user_id: Uuid) {
+    services
+        .notification_service
+        .notify(
+            user_id,
+            NotificationKind::TRAINING_CANCELLED,
+            &[
+                ("training_name", "Morning drills".to_string()),
+                ("start_datetime", "2025-03-01 08:00".to_string()),
+            ],
+        )
+        .await;
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_export_contains_user_data(services: impl std::future::Future<Output = Services>) {
+    let services = services.await;
+    let (user, admin) = actors();
+    services
+        .db
+        .create_test_user(user.user_id)
+        .await
+        .expect("Error creating user");
+    services
+        .db
+        .create_test_user(admin.user_id)
+        .await
+        .expect("Error creating admin");
+    let tuition = services
+        .tuition_service
+        .pay_tuition(&admin, user.user_id, 50_000.0)
+        .await
+        .expect("Error paying tuition");
+    notify_cancelled_training(&services, user.user_id).await;
+
+    let request = services
+        .privacy_service
+        .request_data_export(&user, "I want a copy of my data".to_string())
+        .await
+        .expect("Error requesting export");
+    services
+        .request_service
+        .complete_request(&admin, request.request_id, true)
+        .await
+        .expect("Error approving export");
+
+    let export = services
+        .privacy_service
+        .export_user_data(request.request_id, user.user_id)
+        .await
+        .expect("Error exporting data");
+
+    assert_eq!(export.profile.id_user, user.user_id);
+    assert_eq!(
+        export.profile.email,
+        format!("test{}@example.com", user.user_id)
+    );
+    assert_eq!(
+        export
+            .tuitions
+            .iter()
+            .map(|tuition| tuition.id_tuition)
+            .collect::<Vec<_>>(),
+        vec![tuition.id_tuition]
+    );
+    assert!(export
+        .requests
+        .iter()
+        .any(|exported| exported.request_id == request.request_id));
+    assert!(!export.notifications.is_empty());
+    assert!(export.notifications.iter().all(|notification| {
+        notification.id_user == user.user_id
+            && notification.kind == NotificationKind::TRAINING_CANCELLED
+            && notification.body.contains("Morning drills")
+    }));
+
+    assert!(services
+        .privacy_service
+        .export_user_data(request.request_id, admin.user_id)
+        .await
+        .is_err());
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_erasure_removes_personal_data(services: impl std::future::Future<Output = Services>) {
+    let services = services.await;
+    let (user, admin) = actors();
+    services
+        .db
+        .create_test_user(user.user_id)
+        .await
+        .expect("Error creating user");
+    services
+        .db
+        .create_test_user(admin.user_id)
+        .await
+        .expect("Error creating admin");
+    let original: User = services
+        .db
+        .query_one_with_error(
+            "SELECT id_user, first_name, last_name, birth_date, registration_date, email,
+             email_verified, phone_number, country_code, password, identification_number,
+             identification_type, user_rol
+             FROM person WHERE id_user = ?1",
+            params![user.user_id.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+        .unwrap()
+        .expect("User wasn't created");
+
+    services
+        .user_service
+        .update_user_role(&admin, user.user_id, URol::USER)
+        .await
+        .expect("Error updating role");
+    notify_cancelled_training(&services, user.user_id).await;
+    assert!(!services
+        .notification_service
+        .list_user_notifications(user.user_id)
+        .await
+        .unwrap()
+        .is_empty());
+
+    let request = services
+        .privacy_service
+        .request_account_erasure(&user, "Please forget me".to_string())
+        .await
+        .expect("Error requesting erasure");
+    let request = services
+        .request_service
+        .complete_request(&admin, request.request_id, true)
+        .await
+        .expect("Error approving erasure");
+
+    assert_eq!(request.state, RequestState::APPROVED);
+    assert_eq!(request.execution_error, None);
+    assert!(request.executed_at.is_some());
+    assert!(services
+        .user_service
+        .get_user_by_id(user.user_id)
+        .await
+        .is_err());
+
+    let erased: User = services
+        .db
+        .query_one_with_error(
+            "SELECT id_user, first_name, last_name, birth_date, registration_date, email,
+             email_verified, phone_number, country_code, password, identification_number,
+             identification_type, user_rol
+             FROM person WHERE id_user = ?1",
+            params![user.user_id.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+        .unwrap()
+        .expect("Erased user row was removed");
+    assert_ne!(erased.first_name, original.first_name);
+    assert_ne!(erased.last_name, original.last_name);
+    assert_ne!(erased.email, original.email);
+    assert_ne!(erased.phone_number, original.phone_number);
+    assert_ne!(erased.identification_number, original.identification_number);
+    assert_ne!(erased.password, original.password);
+    // Delivered messages are addressed by name, the delivery log goes with the account
+    assert!(services
+        .notification_service
+        .list_user_notifications(user.user_id)
+        .await
+        .unwrap()
+        .is_empty());
+
+    #[derive(serde::Deserialize)]
+    struct Snapshots {
+        snapshots: String,
+    }
+    let audited: Vec<Snapshots> = services
+        .db
+        .query_many_with_error(
+            "SELECT COALESCE(before_snapshot, '') || COALESCE(after_snapshot, '') AS snapshots
+             FROM audit_log WHERE entity = 'USER' AND entity_id = ?1",
+            params![user.user_id.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+        .unwrap();
+    assert_eq!(audited.len(), 2);
+    for entry in audited {
+        for personal_data in [
+            &original.email,
+            &original.phone_number,
+            &original.identification_number,
+        ] {
+            assert!(!entry.snapshots.contains(personal_data.as_str()));
+        }
+    }
+}
@@ -1,81 +1,460 @@
 use async_trait::async_trait;
-use entities::request::Request;
-use libsql::params;
+use chrono::NaiveDateTime;
+use entities::event::OutboxEvent;
+use entities::pagination::{Page, PageQuery};
+use entities::request::{
+    Request, RequestCommand, RequestComment, RequestFilter, RequestState, RequestStep,
+};
+use entities::{datetime_serde, datetime_serde_option};
+use libsql::{params, params::IntoParams};
+use serde::Deserialize;
+use serde_json::{Map, Value};
 use use_cases::request_service::err::{Error, Result};
 use use_cases::request_service::repository_trait::RequestRepository;
 use uuid::Uuid;
 
+use crate::outbox_repo::insert_outbox_event;
+use crate::pagination::{PageSql, SqlFilter};
 use crate::TursoDb;
 
+const REQUEST_COLUMNS: &str = "request_id, requester_id, requested_command, command_payload,
+justification, state, current_step, approver_id, created_at, expires_at, executed_at,
+execution_error";
+
+const REQUEST_PAGE_SQL: PageSql<'static> = PageSql {
+    select: "SELECT request_id, requester_id, requested_command, command_payload,
+justification, state, current_step, approver_id, created_at, expires_at, executed_at,
+execution_error
+FROM request
+WHERE deleted = 0",
+    count: "SELECT COUNT(*) AS count FROM request WHERE deleted = 0",
+    sortable_columns: &[
+        "requested_command",
+        "requester_id",
+        "state",
+        "created_at",
+        "expires_at",
+    ],
+    default_sort_column: "created_at",
+    primary_key: "request_id",
+};
+
+/// The kind of the command is stored in `requested_command` and its payload as JSON text
+#[derive(Deserialize)]
+struct RequestRow {
+    request_id: Uuid,
+    requester_id: Uuid,
+    requested_command: String,
+    command_payload: Option<String>,
+    justification: String,
+    state: RequestState,
+    current_step: u32,
+    approver_id: Option<Uuid>,
+    #[serde(with = "datetime_serde")]
+    created_at: NaiveDateTime,
+    #[serde(with = "datetime_serde_option", default)]
+    expires_at: Option<NaiveDateTime>,
+    #[serde(with = "datetime_serde_option", default)]
+    executed_at: Option<NaiveDateTime>,
+    execution_error: Option<String>,
+}
+
+impl TryFrom<RequestRow> for Request {
+    type Error = Error;
+
+    fn try_from(row: RequestRow) -> Result<Self> {
+        let mut command = Map::new();
+        command.insert("kind".to_string(), Value::String(row.requested_command));
+        if let Some(payload) = row.command_payload {
+            let payload = serde_json::from_str(&payload)
+                .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+            command.insert("payload".to_string(), payload);
+        }
+
+        Ok(Self {
+            request_id: row.request_id,
+            requester_id: row.requester_id,
+            requested_command: serde_json::from_value(Value::Object(command))
+                .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?,
+            justification: row.justification,
+            state: row.state,
+            current_step: row.current_step,
+            approver_id: row.approver_id,
+            created_at: row.created_at,
+            expires_at: row.expires_at,
+            executed_at: row.executed_at,
+            execution_error: row.execution_error,
+        })
+    }
+}
+
+fn command_payload(command: &RequestCommand) -> Result<Option<String>> {
+    let value = serde_json::to_value(command)
+        .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+    Ok(value.get("payload").map(Value::to_string))
+}
+
+fn format_datetime(datetime: NaiveDateTime) -> String {
+    datetime.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+/// Moves an open request on, only while nobody else changed its state or step
+const UPDATE_OPEN_REQUEST_SQL: &str = "UPDATE request SET
+    state = ?1,
+    current_step = ?2,
+    approver_id = ?3,
+    executed_at = ?4,
+    execution_error = ?5
+ WHERE request_id = ?6 AND state = ?7 AND current_step = ?8";
+
+fn update_open_request_params(
+    request: &Request,
+    state: RequestState,
+    current_step: u32,
+) -> impl IntoParams {
+    params![
+        request.state.to_string(),
+        request.current_step,
+        request.approver_id.map(|id| id.to_string()),
+        request.executed_at.map(format_datetime),
+        request.execution_error.clone(),
+        request.request_id.to_string(),
+        state.to_string(),
+        current_step
+    ]
+}
+
 #[async_trait]
 impl RequestRepository for TursoDb {
-    async fn create_request(&self, request: &Request) -> Result<()> {
-        self.execute_with_error(
-            "INSERT INTO request (
-                request_id, requester_id, requested_command, justification, approved, approver_id
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    async fn create_request(&self, request: &Request, steps: &[RequestStep]) -> Result<()> {
+        let conn = self
+            .get_connection_with_error(Error::UnknownDatabaseError)
+            .await?;
+        let transaction = conn
+            .transaction()
+            .await
+            .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+
+        let created: Result<()> = async {
+            transaction
+                .execute(
+                    "INSERT INTO request (
+                        request_id, requester_id, requested_command, command_payload,
+                        justification, state, current_step, approver_id, created_at, expires_at,
+                        executed_at, execution_error
+                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                    params![
+                        request.request_id.to_string(),
+                        request.requester_id.to_string(),
+                        request.requested_command.kind().to_string(),
+                        command_payload(&request.requested_command)?,
+                        request.justification.clone(),
+                        request.state.to_string(),
+                        request.current_step,
+                        request.approver_id.map(|id| id.to_string()),
+                        format_datetime(request.created_at),
+                        request.expires_at.map(format_datetime),
+                        request.executed_at.map(format_datetime),
+                        request.execution_error.clone()
+                    ],
+                )
+                .await
+                .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+
+            for step in steps {
+                transaction
+                    .execute(
+                        "INSERT INTO request_step (
+                            request_id, step, approver_rol, approver_id, approved, decided_at
+                        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                        params![
+                            step.request_id.to_string(),
+                            step.step,
+                            step.approver_rol.to_string(),
+                            step.approver_id.map(|id| id.to_string()),
+                            step.approved,
+                            step.decided_at.map(format_datetime)
+                        ],
+                    )
+                    .await
+                    .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+            }
+            Ok(())
+        }
+        .await;
+
+        if let Err(err) = created {
+            transaction
+                .rollback()
+                .await
+                .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+            return Err(err);
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|err| Error::UnknownDatabaseError(err.to_string()))
+    }
+
+    async fn get_request_by_id(&self, id: Uuid) -> Result<Option<Request>> {
+        let row: Option<RequestRow> = self
+            .query_one_with_error(
+                &format!("SELECT {REQUEST_COLUMNS} FROM request WHERE request_id = ?1"),
+                params![id.to_string()],
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+
+        row.map(Request::try_from).transpose()
+    }
+
+    async fn update_request(&self, request: &Request, event: Option<&OutboxEvent>) -> Result<()> {
+        self.execute_with_event(
+            "UPDATE request SET 
+                requester_id = ?1, 
+                requested_command = ?2, 
+                command_payload = ?3, 
+                justification = ?4, 
+                state = ?5, 
+                current_step = ?6, 
+                approver_id = ?7, 
+                expires_at = ?8, 
+                executed_at = ?9, 
+                execution_error = ?10
+             WHERE request_id = ?11",
             params![
-                request.request_id.to_string(),
                 request.requester_id.to_string(),
-                request.requested_command.clone(),
+                request.requested_command.kind().to_string(),
+                command_payload(&request.requested_command)?,
                 request.justification.clone(),
-                request.approved,
-                request.approver_id.map(|id| id.to_string())
+                request.state.to_string(),
+                request.current_step,
+                request.approver_id.map(|id| id.to_string()),
+                request.expires_at.map(format_datetime),
+                request.executed_at.map(format_datetime),
+                request.execution_error.clone(),
+                request.request_id.to_string()
             ],
+            event,
             Error::UnknownDatabaseError,
         )
-        .await
+        .await?;
+        Ok(())
     }
 
-    async fn get_request_by_id(&self, id: Uuid) -> Result<Option<Request>> {
-        self.query_one_with_error(
-            "SELECT request_id, requester_id, requested_command, justification, approved, approver_id 
-             FROM request 
-             WHERE request_id = ?1",
-            params![id.to_string()],
+    async fn update_open_request(
+        &self,
+        request: &Request,
+        state: RequestState,
+        current_step: u32,
+    ) -> Result<bool> {
+        let affected_rows = self
+            .execute_returning_affected_with_error(
+                UPDATE_OPEN_REQUEST_SQL,
+                update_open_request_params(request, state, current_step),
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+        Ok(affected_rows > 0)
+    }
+
+    async fn list_requests(&self) -> Result<Vec<Request>> {
+        let rows: Vec<RequestRow> = self
+            .query_many_with_error(
+                &format!("SELECT {REQUEST_COLUMNS} FROM request"),
+                params![],
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+
+        rows.into_iter().map(Request::try_from).collect()
+    }
+
+    async fn list_requests_by_user(&self, user_id: Uuid) -> Result<Vec<Request>> {
+        let rows: Vec<RequestRow> = self
+            .query_many_with_error(
+                &format!("SELECT {REQUEST_COLUMNS} FROM request WHERE requester_id = ?1"),
+                params![user_id.to_string()],
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+
+        rows.into_iter().map(Request::try_from).collect()
+    }
+
+    async fn list_requests_page(
+        &self,
+        filter: &RequestFilter,
+        page: &PageQuery,
+    ) -> Result<Page<Request>> {
+        let mut sql_filter = SqlFilter::new();
+        sql_filter
+            .push_if(
+                "requester_id = ?",
+                filter.requester_id.map(|id| id.to_string()),
+            )
+            .push_if(
+                "approver_id = ?",
+                filter.approver_id.map(|id| id.to_string()),
+            )
+            .push_if("state = ?", filter.state.map(|state| state.to_string()))
+            .push_if(
+                "requested_command = ?",
+                filter.kind.map(|kind| kind.to_string()),
+            );
+
+        let page: Page<RequestRow> = self
+            .query_page_with_error(
+                &REQUEST_PAGE_SQL,
+                &sql_filter,
+                page,
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+
+        page.try_map(Request::try_from)
+    }
+
+    async fn expire_requests(&self, now: NaiveDateTime) -> Result<u64> {
+        self.execute_returning_affected_with_error(
+            "UPDATE request SET state = ?1
+             WHERE state IN (?2, ?3) AND expires_at <= ?4 AND deleted = 0",
+            params![
+                RequestState::EXPIRED.to_string(),
+                RequestState::PENDING.to_string(),
+                RequestState::IN_REVIEW.to_string(),
+                format_datetime(now)
+            ],
             Error::UnknownDatabaseError,
         )
         .await
     }
 
-    async fn update_request(&self, request: &Request) -> Result<()> {
+    async fn update_request_step(&self, step: &RequestStep) -> Result<()> {
         self.execute_with_error(
-            "UPDATE request SET 
-                requester_id = ?1, 
-                requested_command = ?2, 
-                justification = ?3, 
-                approved = ?4, 
-                approver_id = ?5
-             WHERE request_id = ?6",
+            "UPDATE request_step SET approver_id = ?1, approved = ?2, decided_at = ?3
+             WHERE request_id = ?4 AND step = ?5",
             params![
-                request.requester_id.to_string(),
-                request.requested_command.clone(),
-                request.justification.clone(),
-                request.approved,
-                request.approver_id.map(|id| id.to_string()),
-                request.request_id.to_string()
+                step.approver_id.map(|id| id.to_string()),
+                step.approved,
+                step.decided_at.map(format_datetime),
+                step.request_id.to_string(),
+                step.step
             ],
             Error::UnknownDatabaseError,
         )
         .await
     }
 
-    async fn list_requests(&self) -> Result<Vec<Request>> {
+    async fn decide_request_step(
+        &self,
+        request: &Request,
+        step: &RequestStep,
+        state: RequestState,
+        current_step: u32,
+        event: Option<&OutboxEvent>,
+    ) -> Result<bool> {
+        let conn = self
+            .get_connection_with_error(Error::UnknownDatabaseError)
+            .await?;
+        let transaction = conn
+            .transaction()
+            .await
+            .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+
+        let decided: Result<bool> = async {
+            let request_rows = transaction
+                .execute(
+                    UPDATE_OPEN_REQUEST_SQL,
+                    update_open_request_params(request, state, current_step),
+                )
+                .await
+                .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+            if request_rows == 0 {
+                return Ok(false);
+            }
+
+            let step_rows = transaction
+                .execute(
+                    "UPDATE request_step SET approver_id = ?1, approved = ?2, decided_at = ?3
+                     WHERE request_id = ?4 AND step = ?5 AND approver_id IS NULL",
+                    params![
+                        step.approver_id.map(|id| id.to_string()),
+                        step.approved,
+                        step.decided_at.map(format_datetime),
+                        step.request_id.to_string(),
+                        step.step
+                    ],
+                )
+                .await
+                .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+            if step_rows == 0 {
+                return Ok(false);
+            }
+
+            if let Some(event) = event {
+                insert_outbox_event(&transaction, event)
+                    .await
+                    .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+            }
+            Ok(true)
+        }
+        .await;
+
+        match decided {
+            Ok(true) => {
+                transaction
+                    .commit()
+                    .await
+                    .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+                Ok(true)
+            }
+            Ok(false) | Err(_) => {
+                transaction
+                    .rollback()
+                    .await
+                    .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+                decided
+            }
+        }
+    }
+
+    async fn list_request_steps(&self, request_id: Uuid) -> Result<Vec<RequestStep>> {
         self.query_many_with_error(
-            "SELECT request_id, requester_id, requested_command, justification, approved, approver_id 
-             FROM request",
-            params![],
+            "SELECT request_id, step, approver_rol, approver_id, approved, decided_at
+             FROM request_step
+             WHERE request_id = ?1
+             ORDER BY step",
+            params![request_id.to_string()],
             Error::UnknownDatabaseError,
         )
         .await
     }
 
-    async fn list_requests_by_user(&self, user_id: Uuid) -> Result<Vec<Request>> {
+    async fn create_request_comment(&self, comment: &RequestComment) -> Result<()> {
+        self.execute_with_error(
+            "INSERT INTO request_comment (id_comment, request_id, author_id, body, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                comment.id_comment.to_string(),
+                comment.request_id.to_string(),
+                comment.author_id.to_string(),
+                comment.body.clone(),
+                format_datetime(comment.created_at)
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_request_comments(&self, request_id: Uuid) -> Result<Vec<RequestComment>> {
         self.query_many_with_error(
-            "SELECT request_id, requester_id, requested_command, justification, approved, approver_id 
-             FROM request 
-             WHERE requester_id = ?1",
-            params![user_id.to_string()],
+            "SELECT id_comment, request_id, author_id, body, created_at
+             FROM request_comment
+             WHERE request_id = ?1
+             ORDER BY created_at",
+            params![request_id.to_string()],
             Error::UnknownDatabaseError,
         )
         .await
@@ -87,10 +466,19 @@ mod test {
     use std::future::Future;
 
     use super::*;
-    use entities::request::Request;
+    use chrono::Duration;
+    use entities::audit::Actor;
+    use entities::event::DomainEvent;
+    use entities::request::{Request, RoleChange};
+    use entities::user::URol;
     use rstest::{fixture, rstest};
+    use use_cases::event_service::repository_trait::OutboxRepository;
     use uuid::Uuid;
 
+    fn test_datetime() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str("2025-01-01 10:00:00", "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
     #[fixture]
     async fn repository() -> TursoDb {
         let db = crate::TestDbBuilder::create()
@@ -118,13 +506,21 @@ mod test {
         let request = Request {
             request_id,
             requester_id,
-            requested_command: "Test Command".to_string(),
+            requested_command: RequestCommand::ROLE_CHANGE(RoleChange {
+                user_id: requester_id,
+                user_rol: URol::TRAINER,
+            }),
             justification: "Test Justification".to_string(),
-            approved: None,
+            state: RequestState::PENDING,
+            current_step: 0,
             approver_id: None,
+            created_at: test_datetime(),
+            expires_at: None,
+            executed_at: None,
+            execution_error: None,
         };
 
-        db.create_request(&request)
+        db.create_request(&request, &[])
             .await
             .expect("Failed to create request");
 
@@ -156,21 +552,28 @@ mod test {
         let mut request = Request {
             request_id,
             requester_id,
-            requested_command: "Test Command".to_string(),
+            requested_command: RequestCommand::EXPORT_USER_DATA,
             justification: "Test Justification".to_string(),
-            approved: None,
+            state: RequestState::PENDING,
+            current_step: 0,
             approver_id: None,
+            created_at: test_datetime(),
+            expires_at: None,
+            executed_at: None,
+            execution_error: None,
         };
 
-        db.create_request(&request)
+        db.create_request(&request, &[])
             .await
             .expect("Failed to create request");
 
         // Update request
-        request.approved = Some(true);
+        request.state = RequestState::APPROVED;
         request.approver_id = Some(approver_id);
+        request.executed_at = Some(test_datetime());
+        request.execution_error = Some("Test Error".to_string());
 
-        db.update_request(&request)
+        db.update_request(&request, None)
             .await
             .expect("Failed to update request");
 
@@ -198,13 +601,18 @@ mod test {
             let request = Request {
                 request_id: Uuid::new_v4(),
                 requester_id,
-                requested_command: format!("Command {i}"),
+                requested_command: RequestCommand::EXPORT_USER_DATA,
                 justification: format!("Justification {i}"),
-                approved: None,
+                state: RequestState::PENDING,
+                current_step: 0,
                 approver_id: None,
+                created_at: test_datetime(),
+                expires_at: None,
+                executed_at: None,
+                execution_error: None,
             };
 
-            db.create_request(&request)
+            db.create_request(&request, &[])
                 .await
                 .expect("Failed to create request");
         }
@@ -234,13 +642,18 @@ mod test {
             let request = Request {
                 request_id: Uuid::new_v4(),
                 requester_id: user1_id,
-                requested_command: format!("Command {i}"),
+                requested_command: RequestCommand::EXPORT_USER_DATA,
                 justification: format!("Justification {i}"),
-                approved: None,
+                state: RequestState::PENDING,
+                current_step: 0,
                 approver_id: None,
+                created_at: test_datetime(),
+                expires_at: None,
+                executed_at: None,
+                execution_error: None,
             };
 
-            db.create_request(&request)
+            db.create_request(&request, &[])
                 .await
                 .expect("Failed to create request");
         }
@@ -249,13 +662,18 @@ mod test {
         let request = Request {
             request_id: Uuid::new_v4(),
             requester_id: user2_id,
-            requested_command: "Command".to_string(),
+            requested_command: RequestCommand::ERASE_USER_ACCOUNT,
             justification: "Justification".to_string(),
-            approved: None,
+            state: RequestState::PENDING,
+            current_step: 0,
             approver_id: None,
+            created_at: test_datetime(),
+            expires_at: None,
+            executed_at: None,
+            execution_error: None,
         };
 
-        db.create_request(&request)
+        db.create_request(&request, &[])
             .await
             .expect("Failed to create request");
 
@@ -275,4 +693,300 @@ mod test {
 
         assert_eq!(user2_requests.len(), 1);
     }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_expire_requests(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let requester_id = Uuid::new_v4();
+
+        db.create_test_user(requester_id)
+            .await
+            .expect("Error creating test user");
+
+        let deadline = test_datetime();
+        let mut expired_id = Uuid::nil();
+        // Overdue and open, overdue but closed, and open with a future deadline
+        for (state, expires_at) in [
+            (RequestState::IN_REVIEW, deadline - Duration::days(1)),
+            (RequestState::APPROVED, deadline - Duration::days(1)),
+            (RequestState::PENDING, deadline + Duration::days(1)),
+        ] {
+            let request = Request {
+                request_id: Uuid::new_v4(),
+                requester_id,
+                requested_command: RequestCommand::EXPORT_USER_DATA,
+                justification: "Justification".to_string(),
+                state,
+                current_step: 0,
+                approver_id: None,
+                created_at: test_datetime() - Duration::days(30),
+                expires_at: Some(expires_at),
+                executed_at: None,
+                execution_error: None,
+            };
+            if state == RequestState::IN_REVIEW {
+                expired_id = request.request_id;
+            }
+
+            db.create_request(&request, &[])
+                .await
+                .expect("Failed to create request");
+        }
+
+        let expired = db
+            .expire_requests(deadline)
+            .await
+            .expect("Failed to expire requests");
+        assert_eq!(expired, 1);
+
+        let request = db
+            .get_request_by_id(expired_id)
+            .await
+            .expect("Failed to get request")
+            .expect("Request not found");
+        assert_eq!(request.state, RequestState::EXPIRED);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_request_steps_and_comments(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let request_id = Uuid::new_v4();
+        let requester_id = Uuid::new_v4();
+        let approver_id = Uuid::new_v4();
+
+        db.create_test_user(requester_id)
+            .await
+            .expect("Error creating test user");
+        db.create_test_user(approver_id)
+            .await
+            .expect("Error creating test user");
+
+        let request = Request {
+            request_id,
+            requester_id,
+            requested_command: RequestCommand::EXPORT_USER_DATA,
+            justification: "Justification".to_string(),
+            state: RequestState::PENDING,
+            current_step: 0,
+            approver_id: None,
+            created_at: test_datetime(),
+            expires_at: None,
+            executed_at: None,
+            execution_error: None,
+        };
+        let steps: Vec<RequestStep> = [URol::TRAINER, URol::ADMIN]
+            .into_iter()
+            .enumerate()
+            .map(|(step, approver_rol)| RequestStep {
+                request_id,
+                step: step as u32,
+                approver_rol,
+                approver_id: None,
+                approved: None,
+                decided_at: None,
+            })
+            .collect();
+        db.create_request(&request, &steps)
+            .await
+            .expect("Failed to create request");
+
+        let first_step = RequestStep {
+            request_id,
+            step: 0,
+            approver_rol: URol::TRAINER,
+            approver_id: Some(approver_id),
+            approved: Some(true),
+            decided_at: Some(test_datetime()),
+        };
+        db.update_request_step(&first_step)
+            .await
+            .expect("Failed to update request step");
+
+        let steps = db
+            .list_request_steps(request_id)
+            .await
+            .expect("Failed to list request steps");
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0], first_step);
+        assert_eq!(steps[1].approver_rol, URol::ADMIN);
+        assert_eq!(steps[1].approved, None);
+
+        let comment = RequestComment {
+            id_comment: Uuid::new_v4(),
+            request_id,
+            author_id: approver_id,
+            body: "Looks good to me".to_string(),
+            created_at: test_datetime(),
+        };
+        db.create_request_comment(&comment)
+            .await
+            .expect("Failed to create request comment");
+
+        let comments = db
+            .list_request_comments(request_id)
+            .await
+            .expect("Failed to list request comments");
+        assert_eq!(comments, vec![comment]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_request_is_not_stored_without_its_steps(
+        repository: impl Future<Output = TursoDb>,
+    ) {
+        let db = repository.await;
+        let request_id = Uuid::new_v4();
+        let requester_id = Uuid::new_v4();
+        db.create_test_user(requester_id)
+            .await
+            .expect("Error creating test user");
+
+        let request = Request {
+            request_id,
+            requester_id,
+            requested_command: RequestCommand::EXPORT_USER_DATA,
+            justification: "Justification".to_string(),
+            state: RequestState::PENDING,
+            current_step: 0,
+            approver_id: None,
+            created_at: test_datetime(),
+            expires_at: None,
+            executed_at: None,
+            execution_error: None,
+        };
+        let step = RequestStep {
+            request_id,
+            step: 0,
+            approver_rol: URol::ADMIN,
+            approver_id: None,
+            approved: None,
+            decided_at: None,
+        };
+
+        // The repeated step breaks the primary key after the request row was inserted
+        assert!(db
+            .create_request(&request, &[step.clone(), step])
+            .await
+            .is_err());
+
+        assert_eq!(db.get_request_by_id(request_id).await.unwrap(), None);
+        assert!(db.list_request_steps(request_id).await.unwrap().is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_request_step_is_decided_once(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let request_id = Uuid::new_v4();
+        let requester_id = Uuid::new_v4();
+        db.create_test_user(requester_id)
+            .await
+            .expect("Error creating test user");
+
+        let request = Request {
+            request_id,
+            requester_id,
+            requested_command: RequestCommand::ERASE_USER_ACCOUNT,
+            justification: "Justification".to_string(),
+            state: RequestState::PENDING,
+            current_step: 0,
+            approver_id: None,
+            created_at: test_datetime(),
+            expires_at: None,
+            executed_at: None,
+            execution_error: None,
+        };
+        let step = RequestStep {
+            request_id,
+            step: 0,
+            approver_rol: URol::ADMIN,
+            approver_id: None,
+            approved: None,
+            decided_at: None,
+        };
+        db.create_request(&request, &[step])
+            .await
+            .expect("Failed to create request");
+
+        // Two admins approve the same pending step
+        let admins = [Uuid::new_v4(), Uuid::new_v4()];
+        for admin in admins {
+            db.create_test_user(admin)
+                .await
+                .expect("Error creating test user");
+        }
+        let decisions = admins.map(|approver_id| {
+            let step = RequestStep {
+                request_id,
+                step: 0,
+                approver_rol: URol::ADMIN,
+                approver_id: Some(approver_id),
+                approved: Some(true),
+                decided_at: Some(test_datetime()),
+            };
+            let approved = Request {
+                state: RequestState::APPROVED,
+                approver_id: Some(approver_id),
+                ..request.clone()
+            };
+            let event = OutboxEvent {
+                id_event: Uuid::new_v4(),
+                event: DomainEvent::REQUEST_COMPLETED {
+                    request_id,
+                    requester_id,
+                    request_kind: approved.requested_command.kind(),
+                    state: RequestState::APPROVED,
+                },
+                actor: Actor {
+                    user_id: approver_id,
+                    user_rol: URol::ADMIN,
+                },
+                occurred_at: test_datetime(),
+                dispatched_at: None,
+                attempts: 0,
+                last_error: None,
+            };
+            (approved, step, event)
+        });
+
+        let mut decided = Vec::new();
+        for (approved, step, event) in &decisions {
+            decided.push(
+                db.decide_request_step(approved, step, RequestState::PENDING, 0, Some(event))
+                    .await
+                    .expect("Failed to decide request step"),
+            );
+        }
+        assert_eq!(decided, vec![true, false]);
+
+        // Only the decision that was recorded is published
+        let events = db
+            .list_pending_events(10, 5)
+            .await
+            .expect("Failed to list pending events");
+        assert_eq!(events, vec![decisions[0].2.clone()]);
+
+        let stored = db
+            .get_request_by_id(request_id)
+            .await
+            .expect("Failed to get request")
+            .expect("Request not found");
+        assert_eq!(stored, decisions[0].0);
+        let steps = db
+            .list_request_steps(request_id)
+            .await
+            .expect("Failed to list request steps");
+        assert_eq!(steps, vec![decisions[0].1.clone()]);
+
+        let withdrawn = Request {
+            state: RequestState::WITHDRAWN,
+            ..request.clone()
+        };
+        assert!(!db
+            .update_open_request(&withdrawn, RequestState::PENDING, 0)
+            .await
+            .expect("Failed to update request"));
+    }
 }
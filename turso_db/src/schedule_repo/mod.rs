@@ -0,0 +1,227 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use entities::schedule::ScheduleEntry;
+use libsql::params;
+use use_cases::schedule_service::{
+    err::{Error, Result},
+    repository_trait::ScheduleRepository,
+};
+use uuid::Uuid;
+
+use crate::TursoDb;
+
+#[async_trait]
+impl ScheduleRepository for TursoDb {
+    async fn list_user_schedule(
+        &self,
+        id_user: Uuid,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<Vec<ScheduleEntry>> {
+        self.query_many_with_error(
+            "SELECT 'TRAINING' AS kind, t.id_training AS id_source, t.name AS name,
+                    t.start_datetime AS start_datetime, t.end_datetime AS end_datetime
+             FROM training_registration tr
+             JOIN training t ON t.id_training = tr.id_training
+             WHERE tr.id_user = ?1 AND tr.deleted = 0 AND t.deleted = 0
+             AND t.start_datetime < ?3 AND t.end_datetime > ?2
+             UNION ALL
+             SELECT 'TRAINING_LED', t.id_training, t.name, t.start_datetime, t.end_datetime
+             FROM training t
+             WHERE t.trainer_id = ?1 AND t.deleted = 0
+             AND t.start_datetime < ?3 AND t.end_datetime > ?2
+             UNION ALL
+             SELECT 'TOURNAMENT', tt.id_tournament, tt.name, tt.start_datetime, tt.end_datetime
+             FROM tournament_registration treg
+             JOIN tournament tt ON tt.id_tournament = treg.id_tournament
+             WHERE treg.id_user = ?1 AND treg.deleted = 0 AND tt.deleted = 0
+             AND tt.start_datetime < ?3 AND tt.end_datetime > ?2
+             UNION ALL
+             SELECT 'COURT_BOOKING', r.id_court_reservation, c.court_name,
+                    r.start_reservation_datetime, r.end_reservation_datetime
+             FROM court_reservation r
+             JOIN court c ON c.id_court = r.id_court
+             WHERE r.deleted = 0
+             AND (r.id_owner = ?1 OR r.id_court_reservation IN (
+                 SELECT p.id_court_reservation FROM court_reservation_partner p
+                 WHERE p.id_user = ?1))
+             AND r.start_reservation_datetime < ?3 AND r.end_reservation_datetime > ?2
+             ORDER BY start_datetime",
+            params![
+                id_user.to_string(),
+                from.format("%Y-%m-%d %H:%M:%S").to_string(),
+                to.format("%Y-%m-%d %H:%M:%S").to_string()
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::future::Future;
+
+    use super::*;
+    use chrono::{Duration, Utc};
+    use entities::court::{Court, CourtReservation, CourtSurface};
+    use entities::schedule::ScheduleEntryKind;
+    use entities::tournament::{Tournament, TournamentRegistration};
+    use entities::training::{Training, TrainingRegistration};
+    use rstest::{fixture, rstest};
+    use use_cases::court_service::repository_trait::{CourtRepository, CourtReservationRepository};
+    use use_cases::tournament_service::repository_trait::{
+        TournamentRegistrationRepository, TournamentRepository,
+    };
+    use use_cases::training_service::repository_trait::{
+        TrainingRegistrationRepository, TrainingRepository,
+    };
+
+    #[fixture]
+    async fn repository() -> TursoDb {
+        crate::TestDbBuilder::create_full().await
+    }
+
+    fn test_datetime() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(
+            &Utc::now()
+                .naive_utc()
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+            "%Y-%m-%d %H:%M:%S",
+        )
+        .unwrap()
+    }
+
+    fn training(start: NaiveDateTime, id_category: Uuid, trainer_id: Uuid) -> Training {
+        Training {
+            id_training: Uuid::new_v4(),
+            name: "Juniors".to_string(),
+            id_category,
+            trainer_id,
+            start_datetime: start,
+            end_datetime: start + Duration::hours(2),
+            minimum_payment: 0.0,
+            max_participants: None,
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_user_schedule_merges_every_session(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let id_user = Uuid::new_v4();
+        let other_user = Uuid::new_v4();
+        let id_category = Uuid::new_v4();
+        db.create_test_user(id_user).await.unwrap();
+        db.create_test_user(other_user).await.unwrap();
+        db.create_test_category(id_category).await.unwrap();
+        let start = test_datetime() + Duration::days(1);
+
+        let registered = training(start, id_category, other_user);
+        db.create_training(&registered, None).await.unwrap();
+        db.register_user_for_training(
+            &TrainingRegistration {
+                id_training: registered.id_training,
+                id_user,
+                registration_datetime: test_datetime(),
+                attended: false,
+                attendance_datetime: None,
+                attendance_status: None,
+                attendance_submitted_by: None,
+                attendance_notes: None,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+        let led = training(start + Duration::hours(3), id_category, id_user);
+        db.create_training(&led, None).await.unwrap();
+
+        let tournament = Tournament {
+            id_tournament: Uuid::new_v4(),
+            name: "Open".to_string(),
+            id_category,
+            start_datetime: start + Duration::hours(6),
+            end_datetime: start + Duration::hours(8),
+        };
+        db.create_tournament(&tournament).await.unwrap();
+        db.register_user_for_tournament(
+            &TournamentRegistration {
+                id_tournament: tournament.id_tournament,
+                id_user,
+                registration_datetime: test_datetime(),
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+        let court = Court {
+            id_court: Uuid::new_v4(),
+            court_name: "Central".to_string(),
+            surface: CourtSurface::CLAY,
+            indoor: false,
+            has_lighting: true,
+            capacity: 4,
+            default_slot_minutes: 60,
+            active: true,
+        };
+        db.create_court(&court).await.unwrap();
+        let booking = CourtReservation {
+            id_court_reservation: Uuid::new_v4(),
+            id_court: court.id_court,
+            start_reservation_datetime: start + Duration::hours(9),
+            end_reservation_datetime: start + Duration::hours(10),
+            id_training: None,
+            id_tournament: None,
+            id_owner: Some(other_user),
+        };
+        db.create_booking(&booking, &[id_user], None).await.unwrap();
+        // Sessions of other users and deleted ones are left out
+        db.create_training(&training(start, id_category, other_user), None)
+            .await
+            .unwrap();
+        let deleted = training(start + Duration::hours(12), id_category, id_user);
+        db.create_training(&deleted, None).await.unwrap();
+        db.delete_training(deleted.id_training, None).await.unwrap();
+
+        let schedule = db
+            .list_user_schedule(id_user, start, start + Duration::days(1))
+            .await
+            .unwrap();
+        assert_eq!(
+            schedule
+                .iter()
+                .map(|entry| (entry.kind, entry.id_source))
+                .collect::<Vec<_>>(),
+            vec![
+                (ScheduleEntryKind::TRAINING, registered.id_training),
+                (ScheduleEntryKind::TRAINING_LED, led.id_training),
+                (ScheduleEntryKind::TOURNAMENT, tournament.id_tournament),
+                (
+                    ScheduleEntryKind::COURT_BOOKING,
+                    booking.id_court_reservation
+                ),
+            ]
+        );
+        assert_eq!(schedule[3].name, "Central");
+
+        // Only the sessions overlapping the window
+        let overlapping = db
+            .list_user_schedule(
+                id_user,
+                start + Duration::hours(1),
+                start + Duration::hours(4),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            overlapping
+                .iter()
+                .map(|entry| entry.id_source)
+                .collect::<Vec<_>>(),
+            vec![registered.id_training, led.id_training]
+        );
+    }
+}
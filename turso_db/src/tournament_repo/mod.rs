@@ -1,5 +1,9 @@
 use async_trait::async_trait;
-use entities::tournament::{Tournament, TournamentAttendance, TournamentRegistration};
+use entities::event::OutboxEvent;
+use entities::pagination::{Page, PageQuery};
+use entities::tournament::{
+    Tournament, TournamentAttendance, TournamentFilter, TournamentRegistration,
+};
 use libsql::{de, params};
 use use_cases::tournament_service::err::{Error, Result};
 use use_cases::tournament_service::repository_trait::{
@@ -7,8 +11,19 @@ use use_cases::tournament_service::repository_trait::{
 };
 use uuid::Uuid;
 
+use crate::pagination::{PageSql, SqlFilter};
 use crate::TursoDb;
 
+const TOURNAMENT_PAGE_SQL: PageSql<'static> = PageSql {
+    select: "SELECT id_tournament, name, id_category, start_datetime, end_datetime
+FROM tournament
+WHERE deleted = 0",
+    count: "SELECT COUNT(*) AS count FROM tournament WHERE deleted = 0",
+    sortable_columns: &["name", "start_datetime", "end_datetime"],
+    default_sort_column: "start_datetime",
+    primary_key: "id_tournament",
+};
+
 #[async_trait]
 impl TournamentRepository for TursoDb {
     async fn create_tournament(&self, tournament: &Tournament) -> Result<()> {
@@ -76,11 +91,12 @@ impl TournamentRepository for TursoDb {
         Ok(())
     }
 
-    async fn delete_tournament(&self, id: Uuid) -> Result<()> {
+    async fn delete_tournament(&self, id: Uuid, event: Option<&OutboxEvent>) -> Result<()> {
         let affected_rows = self
-            .execute_returning_affected_with_error(
+            .execute_with_event(
                 "UPDATE tournament SET deleted = 1 WHERE id_tournament = ?1 AND deleted = 0",
                 params![id.to_string()],
+                event,
                 Error::UnknownDatabaseError,
             )
             .await?;
@@ -100,6 +116,39 @@ impl TournamentRepository for TursoDb {
         )
         .await
     }
+
+    async fn list_tournaments_page(
+        &self,
+        filter: &TournamentFilter,
+        page: &PageQuery,
+    ) -> Result<Page<Tournament>> {
+        let mut sql_filter = SqlFilter::new();
+        sql_filter
+            .push_if(
+                "id_category = ?",
+                filter.id_category.map(|id| id.to_string()),
+            )
+            .push_if(
+                "start_datetime >= ?",
+                filter
+                    .start_datetime_from
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+            )
+            .push_if(
+                "start_datetime <= ?",
+                filter
+                    .start_datetime_to
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+            );
+
+        self.query_page_with_error(
+            &TOURNAMENT_PAGE_SQL,
+            &sql_filter,
+            page,
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
 }
 
 #[async_trait]
@@ -107,8 +156,9 @@ impl TournamentRegistrationRepository for TursoDb {
     async fn register_user_for_tournament(
         &self,
         registration: &TournamentRegistration,
+        event: Option<&OutboxEvent>,
     ) -> Result<()> {
-        self.execute_with_error(
+        self.execute_with_event(
             "INSERT INTO tournament_registration (
                 id_tournament, id_user, registration_datetime
             ) VALUES (?1, ?2, ?3)",
@@ -120,9 +170,11 @@ impl TournamentRegistrationRepository for TursoDb {
                     .format("%Y-%m-%d %H:%M:%S")
                     .to_string(),
             ],
+            event,
             Error::UnknownDatabaseError,
         )
-        .await
+        .await?;
+        Ok(())
     }
 
     async fn get_tournament_registrations(
@@ -198,8 +250,12 @@ impl TournamentAttendanceRepository for TursoDb {
         Ok(())
     }
 
-    async fn record_tournament_attendance(&self, attendance: &TournamentAttendance) -> Result<()> {
-        self.execute_with_error(
+    async fn record_tournament_attendance(
+        &self,
+        attendance: &TournamentAttendance,
+        event: Option<&OutboxEvent>,
+    ) -> Result<()> {
+        self.execute_with_event(
             "INSERT INTO tournament_attendance (
                 id_tournament, id_user, attendance_datetime, position
             ) VALUES (?1, ?2, ?3, ?4)",
@@ -212,9 +268,11 @@ impl TournamentAttendanceRepository for TursoDb {
                     .to_string(),
                 attendance.position,
             ],
+            event,
             Error::UnknownDatabaseError,
         )
-        .await
+        .await?;
+        Ok(())
     }
 
     async fn get_tournament_attendance(
@@ -251,12 +309,14 @@ impl TournamentAttendanceRepository for TursoDb {
         tournament_id: Uuid,
         user_id: Uuid,
         position: i32,
+        event: Option<&OutboxEvent>,
     ) -> Result<()> {
         let affected_rows = self
-            .execute_returning_affected_with_error(
+            .execute_with_event(
                 "UPDATE tournament_attendance SET position = ?1 
              WHERE id_tournament = ?2 AND id_user = ?3",
                 params![position, tournament_id.to_string(), user_id.to_string()],
+                event,
                 Error::UnknownDatabaseError,
             )
             .await?;
@@ -313,7 +373,7 @@ mod test {
             ..User::default()
         };
 
-        db.create_user(&user)
+        db.create_user(&user, None)
             .await
             .expect("Failed to create test user");
 
@@ -366,7 +426,7 @@ mod test {
             ..User::default()
         };
 
-        db.create_user(&user)
+        db.create_user(&user, None)
             .await
             .expect("Failed to create test user");
 
@@ -501,7 +561,7 @@ mod test {
             .await
             .expect("Error creating tournament");
 
-        db.delete_tournament(tournament_id)
+        db.delete_tournament(tournament_id, None)
             .await
             .expect("Error deleting tournament");
 
@@ -560,7 +620,7 @@ mod test {
         assert_eq!(tournaments_after_insert.len(), initial_count + 2);
 
         // Delete one tournament
-        db.delete_tournament(tournament1.id_tournament)
+        db.delete_tournament(tournament1.id_tournament, None)
             .await
             .expect("Error deleting tournament1");
 
@@ -596,7 +656,7 @@ mod test {
             registration_datetime: chrono::DateTime::from_timestamp(0, 0).unwrap().naive_utc(),
         };
 
-        db.register_user_for_tournament(&registration)
+        db.register_user_for_tournament(&registration, None)
             .await
             .expect("Error registering user for tournament");
 
@@ -629,7 +689,7 @@ mod test {
             position: 1,
         };
 
-        db.record_tournament_attendance(&attendance)
+        db.record_tournament_attendance(&attendance, None)
             .await
             .expect("Error recording tournament attendance");
 
@@ -656,11 +716,11 @@ mod test {
             position: 1,
         };
 
-        db.record_tournament_attendance(&attendance)
+        db.record_tournament_attendance(&attendance, None)
             .await
             .expect("Error recording tournament attendance");
 
-        db.update_tournament_position(tournament_id, user_id, 2)
+        db.update_tournament_position(tournament_id, user_id, 2, None)
             .await
             .expect("Error updating tournament position");
 
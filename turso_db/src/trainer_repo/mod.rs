@@ -0,0 +1,277 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use entities::trainer::{AvailableTrainer, TrainerAvailability, TrainerTimeOff};
+use libsql::params;
+use use_cases::trainer_service::{
+    err::{Error, Result},
+    repository_trait::TrainerRepository,
+};
+use uuid::Uuid;
+
+use crate::TursoDb;
+
+const TIME_OFF_COLUMNS: &str = "id_time_off, id_trainer, reason, start_datetime, end_datetime";
+
+#[async_trait]
+impl TrainerRepository for TursoDb {
+    async fn list_trainers(&self) -> Result<Vec<AvailableTrainer>> {
+        self.query_many_with_error(
+            "SELECT id_user AS id_trainer, first_name, last_name
+             FROM person
+             WHERE user_rol = 'TRAINER' AND deleted = 0
+             ORDER BY first_name, last_name",
+            params![],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn get_availability(&self, id_trainer: Uuid) -> Result<Vec<TrainerAvailability>> {
+        let mut availability: Vec<TrainerAvailability> = self
+            .query_many_with_error(
+                "SELECT day_of_week, start_time, end_time FROM trainer_availability WHERE id_trainer = ?1",
+                params![id_trainer.to_string()],
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+
+        availability.sort_by_key(|hours| hours.day_of_week);
+        Ok(availability)
+    }
+
+    async fn set_availability(
+        &self,
+        id_trainer: Uuid,
+        availability: &[TrainerAvailability],
+    ) -> Result<()> {
+        self.execute_with_error(
+            "DELETE FROM trainer_availability WHERE id_trainer = ?1",
+            params![id_trainer.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await?;
+
+        for hours in availability {
+            self.execute_with_error(
+                "INSERT INTO trainer_availability (id_trainer, day_of_week, start_time, end_time)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    id_trainer.to_string(),
+                    hours.day_of_week.to_string(),
+                    hours.start_time.format("%H:%M").to_string(),
+                    hours.end_time.format("%H:%M").to_string()
+                ],
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn create_time_off(&self, time_off: &TrainerTimeOff) -> Result<()> {
+        self.execute_with_error(
+            &format!("INSERT INTO trainer_time_off ({TIME_OFF_COLUMNS}, deleted) VALUES (?1, ?2, ?3, ?4, ?5, 0)"),
+            params![
+                time_off.id_time_off.to_string(),
+                time_off.id_trainer.to_string(),
+                time_off.reason.clone(),
+                time_off.start_datetime.format("%Y-%m-%d %H:%M:%S").to_string(),
+                time_off.end_datetime.format("%Y-%m-%d %H:%M:%S").to_string()
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn get_time_off_by_id(&self, id_time_off: Uuid) -> Result<Option<TrainerTimeOff>> {
+        self.query_one_with_error(
+            &format!(
+                "SELECT {TIME_OFF_COLUMNS} FROM trainer_time_off WHERE id_time_off = ?1 AND deleted = 0"
+            ),
+            params![id_time_off.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn get_time_off_in_range(
+        &self,
+        id_trainer: Uuid,
+        start_time: NaiveDateTime,
+        end_time: NaiveDateTime,
+    ) -> Result<Vec<TrainerTimeOff>> {
+        self.query_many_with_error(
+            &format!(
+                "SELECT {TIME_OFF_COLUMNS} FROM trainer_time_off
+                 WHERE deleted = 0 AND id_trainer = ?1
+                 AND start_datetime < ?3 AND end_datetime > ?2
+                 ORDER BY start_datetime"
+            ),
+            params![
+                id_trainer.to_string(),
+                start_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+                end_time.format("%Y-%m-%d %H:%M:%S").to_string()
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn delete_time_off(&self, id_time_off: Uuid) -> Result<()> {
+        self.execute_with_error(
+            "UPDATE trainer_time_off SET deleted = 1 WHERE id_time_off = ?1",
+            params![id_time_off.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::future::Future;
+
+    use super::*;
+    use chrono::{Duration, NaiveDate, NaiveTime};
+    use entities::court::DayOfWeek;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    async fn repository() -> TursoDb {
+        crate::TestDbBuilder::create_full().await
+    }
+
+    fn test_datetime() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 6, 2)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_list_trainers_only_returns_trainers(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let trainer_id = Uuid::new_v4();
+        let member_id = Uuid::new_v4();
+        for id_user in [trainer_id, member_id] {
+            db.create_test_user(id_user)
+                .await
+                .expect("Error creating user");
+        }
+        db.execute_with_error(
+            "UPDATE person SET user_rol = 'TRAINER' WHERE id_user = ?1",
+            params![trainer_id.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+        .expect("Error promoting trainer");
+
+        assert_eq!(
+            db.list_trainers().await.unwrap(),
+            vec![AvailableTrainer {
+                id_trainer: trainer_id,
+                first_name: "Test".to_string(),
+                last_name: "User".to_string(),
+            }]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_set_availability_replaces_week(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let trainer_id = Uuid::new_v4();
+        db.create_test_user(trainer_id)
+            .await
+            .expect("Error creating trainer");
+        let hours = |day_of_week, start, end| TrainerAvailability {
+            day_of_week,
+            start_time: NaiveTime::from_hms_opt(start, 0, 0).unwrap(),
+            end_time: NaiveTime::from_hms_opt(end, 0, 0).unwrap(),
+        };
+
+        db.set_availability(
+            trainer_id,
+            &[
+                hours(DayOfWeek::FRIDAY, 16, 20),
+                hours(DayOfWeek::MONDAY, 8, 12),
+            ],
+        )
+        .await
+        .expect("Error setting availability");
+        db.set_availability(
+            trainer_id,
+            &[
+                hours(DayOfWeek::WEDNESDAY, 16, 20),
+                hours(DayOfWeek::MONDAY, 14, 18),
+            ],
+        )
+        .await
+        .expect("Error replacing availability");
+
+        assert_eq!(
+            db.get_availability(trainer_id).await.unwrap(),
+            vec![
+                hours(DayOfWeek::MONDAY, 14, 18),
+                hours(DayOfWeek::WEDNESDAY, 16, 20)
+            ]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_time_off_in_range(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let trainer_id = Uuid::new_v4();
+        db.create_test_user(trainer_id)
+            .await
+            .expect("Error creating trainer");
+        let start = test_datetime();
+        let time_off = TrainerTimeOff {
+            id_time_off: Uuid::new_v4(),
+            id_trainer: trainer_id,
+            reason: "Holidays".to_string(),
+            start_datetime: start,
+            end_datetime: start + Duration::days(7),
+        };
+        db.create_time_off(&time_off)
+            .await
+            .expect("Error creating time off");
+
+        assert!(db
+            .get_time_off_in_range(trainer_id, start - Duration::hours(2), start)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            db.get_time_off_in_range(
+                trainer_id,
+                start + Duration::days(6),
+                start + Duration::days(8)
+            )
+            .await
+            .unwrap(),
+            vec![time_off.clone()]
+        );
+        assert!(db
+            .get_time_off_in_range(Uuid::new_v4(), start, start + Duration::days(1))
+            .await
+            .unwrap()
+            .is_empty());
+
+        db.delete_time_off(time_off.id_time_off)
+            .await
+            .expect("Error deleting time off");
+        assert_eq!(
+            db.get_time_off_by_id(time_off.id_time_off).await.unwrap(),
+            None
+        );
+        assert!(db
+            .get_time_off_in_range(trainer_id, start, start + Duration::days(1))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -1,22 +1,56 @@
 use async_trait::async_trait;
 use chrono::NaiveDateTime;
-use entities::training::{Training, TrainingRegistration};
-use libsql::params;
+use entities::event::OutboxEvent;
+use entities::pagination::{Page, PageQuery};
+use entities::training::{
+    AttendanceStatus, Training, TrainingFilter, TrainingRegistration, TrainingRosterEntry,
+    TrainingWaitlistEntry,
+};
+use libsql::{params, params::IntoParams};
+use serde::Deserialize;
 use use_cases::training_service::{
     err::{Error, Result},
-    repository_trait::{TrainingRegistrationRepository, TrainingRepository},
+    repository_trait::{
+        TrainingRegistrationRepository, TrainingRepository, TrainingWaitlistRepository,
+    },
 };
 use uuid::Uuid;
 
+use crate::pagination::{PageSql, SqlFilter};
 use crate::TursoDb;
 
+const TRAINING_PAGE_SQL: PageSql<'static> = PageSql {
+    select: "SELECT id_training, name, start_datetime, end_datetime, minimum_payment, id_category, trainer_id, max_participants
+FROM training WHERE deleted = 0",
+    count: "SELECT COUNT(*) AS count FROM training WHERE deleted = 0",
+    sortable_columns: &["name", "start_datetime", "end_datetime", "minimum_payment"],
+    default_sort_column: "start_datetime",
+    primary_key: "id_training",
+};
+
+const REGISTRATION_COLUMNS: &str =
+    "id_user, registration_datetime, attended, attendance_datetime, id_training,
+     attendance_status, attendance_submitted_by, attendance_notes";
+
+/// The queue order is the insertion order, joining again inserts a new row at the end
+const WAITLIST_COLUMNS: &str = "w.id_training, w.id_user, w.joined_datetime, w.status,
+     w.offered_datetime, w.confirm_before,
+     CASE WHEN w.status = 'WAITING' THEN
+         (SELECT COUNT(*) FROM training_waitlist q
+          WHERE q.id_training = w.id_training AND q.status = 'WAITING' AND q.rowid <= w.rowid)
+     END AS position";
+
 #[async_trait]
 impl TrainingRepository for TursoDb {
-    async fn create_training(&self, training: &Training) -> Result<()> {
-        self.execute_with_error(
+    async fn create_training(
+        &self,
+        training: &Training,
+        event: Option<&OutboxEvent>,
+    ) -> Result<()> {
+        self.execute_with_event(
             "INSERT INTO 
-training (id_training, name, start_datetime, end_datetime, minimum_payment, id_category, trainer_id, deleted) 
-VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0)",
+training (id_training, name, start_datetime, end_datetime, minimum_payment, id_category, trainer_id, max_participants, deleted) 
+VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 0)",
             params![
                 training.id_training.to_string(),
                 training.name.clone(),
@@ -30,16 +64,19 @@ VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0)",
                     .to_string(),
                 training.minimum_payment,
                 training.id_category.to_string(),
-                training.trainer_id.to_string()
+                training.trainer_id.to_string(),
+                training.max_participants
             ],
+            event,
             Error::UnknownDatabaseError,
         )
-        .await
+        .await?;
+        Ok(())
     }
 
     async fn get_training_by_id(&self, id: Uuid) -> Result<Option<Training>> {
         self.query_one_with_error(
-            "SELECT id_training, name, start_datetime, end_datetime, minimum_payment, id_category, trainer_id
+            "SELECT id_training, name, start_datetime, end_datetime, minimum_payment, id_category, trainer_id, max_participants
 FROM training WHERE id_training = ?1 AND deleted = 0",
             params![id.to_string()],
             Error::UnknownDatabaseError,
@@ -50,7 +87,8 @@ FROM training WHERE id_training = ?1 AND deleted = 0",
     async fn update_training(&self, training: &Training) -> Result<()> {
         self.execute_with_error(
             "UPDATE training SET name = ?1, start_datetime = ?2, end_datetime = ?3,
-minimum_payment = ?4, id_category = ?5, trainer_id = ?6 WHERE id_training = ?7 AND deleted = 0",
+minimum_payment = ?4, id_category = ?5, trainer_id = ?6, max_participants = ?7
+WHERE id_training = ?8 AND deleted = 0",
             params![
                 training.name.clone(),
                 training
@@ -64,6 +102,7 @@ minimum_payment = ?4, id_category = ?5, trainer_id = ?6 WHERE id_training = ?7 A
                 training.minimum_payment,
                 training.id_category.to_string(),
                 training.trainer_id.to_string(),
+                training.max_participants,
                 training.id_training.to_string(),
             ],
             Error::UnknownDatabaseError,
@@ -71,11 +110,12 @@ minimum_payment = ?4, id_category = ?5, trainer_id = ?6 WHERE id_training = ?7 A
         .await
     }
 
-    async fn delete_training(&self, id: Uuid) -> Result<()> {
+    async fn delete_training(&self, id: Uuid, event: Option<&OutboxEvent>) -> Result<()> {
         let affected_rows = self
-            .execute_returning_affected_with_error(
+            .execute_with_event(
                 "UPDATE training SET deleted = 1 WHERE id_training = ?1 AND deleted = 0",
                 params![id.to_string()],
+                event,
                 Error::UnknownDatabaseError,
             )
             .await?;
@@ -87,7 +127,7 @@ minimum_payment = ?4, id_category = ?5, trainer_id = ?6 WHERE id_training = ?7 A
 
     async fn list_trainings(&self) -> Result<Vec<Training>> {
         self.query_many_with_error(
-            "SELECT id_training, name, start_datetime, end_datetime, minimum_payment, id_category, trainer_id FROM
+            "SELECT id_training, name, start_datetime, end_datetime, minimum_payment, id_category, trainer_id, max_participants FROM
 training WHERE deleted = 0",
             params![],
             Error::UnknownDatabaseError,
@@ -97,7 +137,7 @@ training WHERE deleted = 0",
 
     async fn get_trainings_by_trainer_id(&self, trainer_id: Uuid) -> Result<Vec<Training>> {
         self.query_many_with_error(
-            "SELECT id_training, name, start_datetime, end_datetime, minimum_payment, id_category, trainer_id 
+            "SELECT id_training, name, start_datetime, end_datetime, minimum_payment, id_category, trainer_id, max_participants 
              FROM training 
              WHERE trainer_id = ?1 AND deleted = 0",
             params![trainer_id.to_string()],
@@ -105,6 +145,143 @@ training WHERE deleted = 0",
         )
         .await
     }
+
+    async fn list_trainings_page(
+        &self,
+        filter: &TrainingFilter,
+        page: &PageQuery,
+    ) -> Result<Page<Training>> {
+        let mut sql_filter = SqlFilter::new();
+        sql_filter
+            .push_if(
+                "id_category = ?",
+                filter.id_category.map(|id| id.to_string()),
+            )
+            .push_if("trainer_id = ?", filter.trainer_id.map(|id| id.to_string()))
+            .push_if(
+                "start_datetime >= ?",
+                filter
+                    .start_datetime_from
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+            )
+            .push_if(
+                "start_datetime <= ?",
+                filter
+                    .start_datetime_to
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+            );
+
+        self.query_page_with_error(
+            &TRAINING_PAGE_SQL,
+            &sql_filter,
+            page,
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_trainings_starting_between(
+        &self,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<Vec<Training>> {
+        self.query_many_with_error(
+            "SELECT id_training, name, start_datetime, end_datetime, minimum_payment, id_category, trainer_id, max_participants
+             FROM training
+             WHERE start_datetime >= ?1 AND start_datetime < ?2 AND deleted = 0",
+            params![
+                from.format("%Y-%m-%d %H:%M:%S").to_string(),
+                to.format("%Y-%m-%d %H:%M:%S").to_string()
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_trainings_with_open_attendance(
+        &self,
+        ended_before: NaiveDateTime,
+    ) -> Result<Vec<Training>> {
+        self.query_many_with_error(
+            "SELECT id_training, name, start_datetime, end_datetime, minimum_payment, id_category, trainer_id, max_participants
+             FROM training
+             WHERE end_datetime < ?1 AND attendance_closed = 0 AND deleted = 0",
+            params![ended_before.format("%Y-%m-%d %H:%M:%S").to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn close_training_attendance(&self, id: Uuid) -> Result<()> {
+        let conn = self
+            .get_connection_with_error(Error::UnknownDatabaseError)
+            .await?;
+        let transaction = conn
+            .transaction()
+            .await
+            .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+
+        let closed: Result<()> = async {
+            transaction
+                .execute(
+                    "UPDATE training_registration
+                     SET attendance_status = ?1
+                     WHERE id_training = ?2 AND attendance_status IS NULL AND attended = 0",
+                    params![AttendanceStatus::ABSENT.to_string(), id.to_string()],
+                )
+                .await
+                .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+            transaction
+                .execute(
+                    "UPDATE training SET attendance_closed = 1
+                     WHERE id_training = ?1 AND deleted = 0",
+                    params![id.to_string()],
+                )
+                .await
+                .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+            Ok(())
+        }
+        .await;
+
+        if let Err(err) = closed {
+            transaction
+                .rollback()
+                .await
+                .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+            return Err(err);
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|err| Error::UnknownDatabaseError(err.to_string()))
+    }
+
+    async fn is_training_attendance_closed(&self, id: Uuid) -> Result<bool> {
+        #[derive(Deserialize)]
+        struct Closed {
+            attendance_closed: bool,
+        }
+
+        let result: Option<Closed> = self
+            .query_one_with_error(
+                "SELECT attendance_closed FROM training WHERE id_training = ?1 AND deleted = 0",
+                params![id.to_string()],
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+
+        Ok(result.is_some_and(|training| training.attendance_closed))
+    }
+}
+
+/// The capacity trigger of `training_registration` aborts with `TRAINING_FULL`
+fn registration_error(message: String) -> Error {
+    if message.contains("TRAINING_FULL") {
+        Error::TrainingFull
+    } else {
+        Error::UnknownDatabaseError(message)
+    }
 }
 
 #[async_trait]
@@ -114,8 +291,7 @@ impl TrainingRegistrationRepository for TursoDb {
         user_id: Uuid,
     ) -> Result<Vec<TrainingRegistration>> {
         self.query_many_with_error(
-            "SELECT id_user, registration_datetime, attended, attendance_datetime, id_training
-             FROM training_registration WHERE id_user = ?1",
+            &format!("SELECT {REGISTRATION_COLUMNS} FROM training_registration WHERE id_user = ?1"),
             params![user_id.to_string()],
             Error::UnknownDatabaseError,
         )
@@ -136,19 +312,28 @@ impl TrainingRegistrationRepository for TursoDb {
         Ok(())
     }
 
-    async fn register_user_for_training(&self, registration: &TrainingRegistration) -> Result<()> {
-        self.execute_with_error(
-        "INSERT INTO training_registration (id_user, registration_datetime, attended, attendance_datetime, id_training)
-         VALUES (?1, ?2, ?3, ?4, ?5)",
+    async fn register_user_for_training(
+        &self,
+        registration: &TrainingRegistration,
+        event: Option<&OutboxEvent>,
+    ) -> Result<()> {
+        self.execute_with_event(
+        "INSERT INTO training_registration (id_user, registration_datetime, attended, attendance_datetime, id_training, attendance_status, attendance_submitted_by, attendance_notes)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
         params![
             registration.id_user.to_string(),
             registration.registration_datetime.format("%Y-%m-%d %H:%M:%S").to_string(),
             registration.attended,
             registration.attendance_datetime.map(|date_time| date_time.format("%Y-%m-%d %H:%M:%S").to_string()),
             registration.id_training.to_string(),
+            registration.attendance_status.map(|status| status.to_string()),
+            registration.attendance_submitted_by.map(|id_user| id_user.to_string()),
+            registration.attendance_notes.clone(),
         ],
-        Error::UnknownDatabaseError,
-    ).await
+        event,
+        registration_error,
+    ).await?;
+        Ok(())
     }
 
     async fn get_training_registrations(
@@ -157,8 +342,9 @@ impl TrainingRegistrationRepository for TursoDb {
     ) -> Result<Vec<TrainingRegistration>> {
         // No 'deleted' field
         self.query_many_with_error(
-            "SELECT id_user, registration_datetime, attended, attendance_datetime, id_training
-FROM training_registration WHERE id_training = ?1",
+            &format!(
+                "SELECT {REGISTRATION_COLUMNS} FROM training_registration WHERE id_training = ?1"
+            ),
             params![training_id.to_string()],
             Error::UnknownDatabaseError,
         )
@@ -171,35 +357,516 @@ FROM training_registration WHERE id_training = ?1",
         user_id: Uuid,
     ) -> Result<Option<TrainingRegistration>> {
         self.query_one_with_error(
-            "SELECT id_user, registration_datetime, attended, attendance_datetime, id_training
-             FROM training_registration WHERE id_training = ?1 AND id_user = ?2",
+            &format!(
+                "SELECT {REGISTRATION_COLUMNS} FROM training_registration
+                 WHERE id_training = ?1 AND id_user = ?2"
+            ),
             params![training_id.to_string(), user_id.to_string()],
             Error::UnknownDatabaseError,
         )
         .await
     }
 
-    async fn mark_training_attendance(
+    async fn get_training_roster(&self, training_id: Uuid) -> Result<Vec<TrainingRosterEntry>> {
+        self.query_many_with_error(
+            "SELECT tr.id_user, p.first_name, p.last_name, tr.registration_datetime,
+                    tr.attendance_status, tr.attendance_datetime, tr.attendance_submitted_by,
+                    tr.attendance_notes
+             FROM training_registration tr
+             JOIN person p ON p.id_user = tr.id_user
+             WHERE tr.id_training = ?1
+             ORDER BY p.last_name, p.first_name",
+            params![training_id.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn save_training_attendance(&self, registrations: &[TrainingRegistration]) -> Result<()> {
+        let conn = self
+            .get_connection_with_error(Error::UnknownDatabaseError)
+            .await?;
+        let transaction = conn
+            .transaction()
+            .await
+            .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+
+        let saved: Result<()> = async {
+            for registration in registrations {
+                let affected_rows = transaction
+                    .execute(
+                        "UPDATE training_registration
+                         SET attended = ?1, attendance_datetime = ?2, attendance_status = ?3,
+                             attendance_submitted_by = ?4, attendance_notes = ?5
+                         WHERE id_training = ?6 AND id_user = ?7",
+                        params![
+                            registration.attended,
+                            registration
+                                .attendance_datetime
+                                .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+                            registration
+                                .attendance_status
+                                .map(|status| status.to_string()),
+                            registration
+                                .attendance_submitted_by
+                                .map(|id_user| id_user.to_string()),
+                            registration.attendance_notes.clone(),
+                            registration.id_training.to_string(),
+                            registration.id_user.to_string()
+                        ],
+                    )
+                    .await
+                    .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+                if affected_rows == 0 {
+                    return Err(Error::RegistrationNotFound);
+                }
+            }
+            Ok(())
+        }
+        .await;
+
+        if let Err(err) = saved {
+            transaction
+                .rollback()
+                .await
+                .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+            return Err(err);
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|err| Error::UnknownDatabaseError(err.to_string()))
+    }
+}
+
+#[async_trait]
+impl TrainingWaitlistRepository for TursoDb {
+    async fn add_to_waitlist(&self, entry: &TrainingWaitlistEntry) -> Result<()> {
+        self.execute_with_error(
+            "INSERT OR REPLACE INTO training_waitlist
+             (id_training, id_user, joined_datetime, status, offered_datetime, confirm_before)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            waitlist_params(entry),
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn get_waitlist_entry(
         &self,
         training_id: Uuid,
         user_id: Uuid,
-        attended: bool,
-        attendance_date: Option<NaiveDateTime>,
+    ) -> Result<Option<TrainingWaitlistEntry>> {
+        self.query_one_with_error(
+            &format!(
+                "SELECT {WAITLIST_COLUMNS} FROM training_waitlist w
+                 WHERE w.id_training = ?1 AND w.id_user = ?2"
+            ),
+            params![training_id.to_string(), user_id.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_waitlist(&self, training_id: Uuid) -> Result<Vec<TrainingWaitlistEntry>> {
+        self.query_many_with_error(
+            &format!(
+                "SELECT {WAITLIST_COLUMNS} FROM training_waitlist w
+                 WHERE w.id_training = ?1 AND w.status IN ('WAITING', 'OFFERED')
+                 ORDER BY w.rowid"
+            ),
+            params![training_id.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn update_waitlist_entry(
+        &self,
+        entry: &TrainingWaitlistEntry,
+        event: Option<&OutboxEvent>,
     ) -> Result<()> {
-        let affected_rows = self.execute_returning_affected_with_error(
-        "UPDATE training_registration SET attended = ?1, attendance_datetime = ?2 WHERE id_training = ?3 AND id_user = ?4",
-        params![
-            attended,
-            attendance_date.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
-            training_id.to_string(),
-            user_id.to_string()
-        ],
-        Error::UnknownDatabaseError,
-    )
-    .await?;
-        if affected_rows == 0 {
-            return Err(Error::RegistrationNotFound);
-        }
+        self.execute_with_event(
+            "UPDATE training_waitlist SET joined_datetime = ?3, status = ?4,
+             offered_datetime = ?5, confirm_before = ?6
+             WHERE id_training = ?1 AND id_user = ?2",
+            waitlist_params(entry),
+            event,
+            Error::UnknownDatabaseError,
+        )
+        .await?;
         Ok(())
     }
+
+    async fn list_expired_offers(&self, now: NaiveDateTime) -> Result<Vec<TrainingWaitlistEntry>> {
+        self.query_many_with_error(
+            &format!(
+                "SELECT {WAITLIST_COLUMNS} FROM training_waitlist w
+                 WHERE w.status = 'OFFERED' AND w.confirm_before <= ?1
+                 ORDER BY w.rowid"
+            ),
+            params![now.format("%Y-%m-%d %H:%M:%S").to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+}
+
+fn waitlist_params(entry: &TrainingWaitlistEntry) -> impl IntoParams {
+    params![
+        entry.id_training.to_string(),
+        entry.id_user.to_string(),
+        entry
+            .joined_datetime
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string(),
+        entry.status.to_string(),
+        entry
+            .offered_datetime
+            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+        entry
+            .confirm_before
+            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use std::future::Future;
+
+    use super::*;
+    use chrono::{Duration, Utc};
+    use entities::training::{AttendanceStatus, WaitlistStatus};
+    use rstest::{fixture, rstest};
+    use tokio::task::JoinSet;
+
+    #[fixture]
+    async fn repository() -> TursoDb {
+        crate::TestDbBuilder::create_full().await
+    }
+
+    fn test_datetime() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(
+            &Utc::now()
+                .naive_utc()
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+            "%Y-%m-%d %H:%M:%S",
+        )
+        .unwrap()
+    }
+
+    async fn member(db: &TursoDb) -> Uuid {
+        let id_user = Uuid::new_v4();
+        db.create_test_user(id_user)
+            .await
+            .expect("Error creating member");
+        id_user
+    }
+
+    async fn stored_training(db: &TursoDb, max_participants: Option<u32>) -> Training {
+        let id_category = Uuid::new_v4();
+        db.create_test_category(id_category)
+            .await
+            .expect("Error creating category");
+        let start = test_datetime() + Duration::days(1);
+        let training = Training {
+            id_training: Uuid::new_v4(),
+            name: "Morning drills".to_string(),
+            id_category,
+            trainer_id: member(db).await,
+            start_datetime: start,
+            end_datetime: start + Duration::hours(2),
+            minimum_payment: 0.0,
+            max_participants,
+        };
+        db.create_training(&training, None)
+            .await
+            .expect("Error creating training");
+        training
+    }
+
+    fn waiting(id_training: Uuid, id_user: Uuid) -> TrainingWaitlistEntry {
+        TrainingWaitlistEntry {
+            id_training,
+            id_user,
+            joined_datetime: test_datetime(),
+            status: WaitlistStatus::WAITING,
+            offered_datetime: None,
+            confirm_before: None,
+            position: None,
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_waitlist_keeps_the_queue_order(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let id_training = stored_training(&db, None).await.id_training;
+        let first = waiting(id_training, member(&db).await);
+        let second = waiting(id_training, member(&db).await);
+        let third = waiting(id_training, member(&db).await);
+        for entry in [&first, &second, &third] {
+            db.add_to_waitlist(entry).await.unwrap();
+        }
+
+        let positions = |waitlist: Vec<TrainingWaitlistEntry>| {
+            waitlist
+                .into_iter()
+                .map(|entry| (entry.id_user, entry.position))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            positions(db.list_waitlist(id_training).await.unwrap()),
+            vec![
+                (first.id_user, Some(1)),
+                (second.id_user, Some(2)),
+                (third.id_user, Some(3))
+            ]
+        );
+
+        // The first one gets a spot, the ones behind move up
+        let now = test_datetime();
+        let offer = TrainingWaitlistEntry {
+            status: WaitlistStatus::OFFERED,
+            offered_datetime: Some(now),
+            confirm_before: Some(now + Duration::hours(1)),
+            ..first.clone()
+        };
+        db.update_waitlist_entry(&offer, None).await.unwrap();
+        assert_eq!(
+            db.get_waitlist_entry(id_training, second.id_user)
+                .await
+                .unwrap()
+                .and_then(|entry| entry.position),
+            Some(1)
+        );
+
+        // Leaving and joining again goes to the end of the queue
+        db.update_waitlist_entry(
+            &TrainingWaitlistEntry {
+                status: WaitlistStatus::LEFT,
+                ..second.clone()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+        db.add_to_waitlist(&second).await.unwrap();
+        assert_eq!(
+            positions(db.list_waitlist(id_training).await.unwrap()),
+            vec![
+                (first.id_user, None),
+                (third.id_user, Some(1)),
+                (second.id_user, Some(2))
+            ]
+        );
+
+        assert!(db.list_expired_offers(now).await.unwrap().is_empty());
+        assert_eq!(
+            db.list_expired_offers(now + Duration::hours(1))
+                .await
+                .unwrap(),
+            vec![offer]
+        );
+    }
+
+    fn registration(id_training: Uuid, id_user: Uuid) -> TrainingRegistration {
+        TrainingRegistration {
+            id_training,
+            id_user,
+            registration_datetime: test_datetime(),
+            attended: false,
+            attendance_datetime: None,
+            attendance_status: None,
+            attendance_submitted_by: None,
+            attendance_notes: None,
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_attendance_is_saved_all_or_none(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let training = stored_training(&db, None).await;
+        let id_training = training.id_training;
+        let trainer = training.trainer_id;
+        let present = registration(id_training, member(&db).await);
+        let excused = registration(id_training, member(&db).await);
+        for registered in [&present, &excused] {
+            db.register_user_for_training(registered, None)
+                .await
+                .unwrap();
+        }
+
+        let now = test_datetime();
+        let marked_present = TrainingRegistration {
+            attended: true,
+            attendance_datetime: Some(now),
+            attendance_status: Some(AttendanceStatus::PRESENT),
+            attendance_submitted_by: Some(trainer),
+            ..present.clone()
+        };
+        let marked_excused = TrainingRegistration {
+            attendance_status: Some(AttendanceStatus::EXCUSED),
+            attendance_notes: Some("School exam".to_string()),
+            attendance_submitted_by: Some(trainer),
+            ..excused.clone()
+        };
+
+        // A member who isn't registered rolls back the whole submission
+        let stranger = TrainingRegistration {
+            attendance_status: Some(AttendanceStatus::ABSENT),
+            ..registration(id_training, member(&db).await)
+        };
+        assert!(matches!(
+            db.save_training_attendance(&[marked_present.clone(), stranger])
+                .await,
+            Err(Error::RegistrationNotFound)
+        ));
+        assert_eq!(
+            db.get_training_registration(id_training, present.id_user)
+                .await
+                .unwrap()
+                .and_then(|registration| registration.attendance_status),
+            None
+        );
+
+        db.save_training_attendance(&[marked_present, marked_excused])
+            .await
+            .unwrap();
+        let roster = db.get_training_roster(id_training).await.unwrap();
+        assert_eq!(roster.len(), 2);
+        assert!(roster
+            .iter()
+            .all(|entry| entry.first_name == "Test"
+                && entry.attendance_submitted_by == Some(trainer)));
+        let status_of = |id_user: Uuid| {
+            roster
+                .iter()
+                .find(|entry| entry.id_user == id_user)
+                .and_then(|entry| entry.attendance_status)
+        };
+        assert_eq!(status_of(present.id_user), Some(AttendanceStatus::PRESENT));
+        assert_eq!(status_of(excused.id_user), Some(AttendanceStatus::EXCUSED));
+        assert!(roster
+            .iter()
+            .any(|entry| entry.attendance_notes.as_deref() == Some("School exam")));
+        let saved = db
+            .get_training_registration(id_training, present.id_user)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(saved.attended);
+        assert_eq!(saved.attendance_datetime, Some(now));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_closing_the_attendance_marks_the_rest_absent(
+        repository: impl Future<Output = TursoDb>,
+    ) {
+        let db = repository.await;
+        let training = stored_training(&db, None).await;
+        let marked = registration(training.id_training, member(&db).await);
+        let unmarked = registration(training.id_training, member(&db).await);
+        for registered in [&marked, &unmarked] {
+            db.register_user_for_training(registered, None)
+                .await
+                .unwrap();
+        }
+        db.save_training_attendance(&[TrainingRegistration {
+            attendance_status: Some(AttendanceStatus::EXCUSED),
+            attendance_submitted_by: Some(training.trainer_id),
+            ..marked.clone()
+        }])
+        .await
+        .unwrap();
+        assert!(!db
+            .is_training_attendance_closed(training.id_training)
+            .await
+            .unwrap());
+
+        db.close_training_attendance(training.id_training)
+            .await
+            .unwrap();
+
+        assert!(db
+            .is_training_attendance_closed(training.id_training)
+            .await
+            .unwrap());
+        let status_of = |id_user: Uuid| {
+            let db = db.clone();
+            async move {
+                db.get_training_registration(training.id_training, id_user)
+                    .await
+                    .unwrap()
+                    .and_then(|registration| registration.attendance_status)
+            }
+        };
+        assert_eq!(
+            status_of(marked.id_user).await,
+            Some(AttendanceStatus::EXCUSED)
+        );
+        assert_eq!(
+            status_of(unmarked.id_user).await,
+            Some(AttendanceStatus::ABSENT)
+        );
+        assert!(db
+            .list_trainings_with_open_attendance(training.end_datetime + Duration::days(1))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_parallel_registrations_respect_the_capacity(
+        repository: impl Future<Output = TursoDb>,
+    ) {
+        let db = repository.await;
+        let training = stored_training(&db, Some(3)).await;
+
+        // One of the spots is held for a member of the waitlist
+        let offered = TrainingWaitlistEntry {
+            status: WaitlistStatus::OFFERED,
+            offered_datetime: Some(test_datetime()),
+            confirm_before: Some(training.start_datetime),
+            ..waiting(training.id_training, member(&db).await)
+        };
+        db.add_to_waitlist(&offered).await.unwrap();
+
+        let mut tasks = JoinSet::new();
+        for _ in 0..10 {
+            let db = db.clone();
+            let registration = registration(training.id_training, member(&db).await);
+            tasks.spawn(async move { db.register_user_for_training(&registration, None).await });
+        }
+        let results = tasks.join_all().await;
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 2);
+        assert!(results
+            .iter()
+            .all(|result| matches!(result, Ok(()) | Err(Error::TrainingFull))));
+
+        // The member holding the offered spot can still take it
+        db.register_user_for_training(&registration(training.id_training, offered.id_user), None)
+            .await
+            .unwrap();
+        assert!(matches!(
+            db.register_user_for_training(
+                &registration(training.id_training, member(&db).await),
+                None
+            )
+            .await,
+            Err(Error::TrainingFull)
+        ));
+        assert_eq!(
+            db.get_training_registrations(training.id_training)
+                .await
+                .unwrap()
+                .len(),
+            3
+        );
+    }
 }
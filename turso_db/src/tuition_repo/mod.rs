@@ -1,17 +1,35 @@
 use async_trait::async_trait;
-use entities::tuition::Tuition;
+use chrono::NaiveDateTime;
+use entities::event::OutboxEvent;
+use entities::pagination::{Page, PageQuery};
+use entities::tuition::{Tuition, TuitionFilter};
 use libsql::params;
 use serde::Deserialize;
 use use_cases::tuition_service::err::{Error, Result};
 use use_cases::tuition_service::repository_trait::TuitionRepository;
 use uuid::Uuid;
 
+use crate::pagination::{PageSql, SqlFilter};
 use crate::TursoDb;
 
+const TUITION_PAGE_SQL: PageSql<'static> = PageSql {
+    select: "SELECT id_tuition, id_user, amount, payment_date
+FROM tuition
+WHERE deleted = 0",
+    count: "SELECT COUNT(*) AS count FROM tuition WHERE deleted = 0",
+    sortable_columns: &["amount", "payment_date"],
+    default_sort_column: "payment_date",
+    primary_key: "id_tuition",
+};
+
 #[async_trait]
 impl TuitionRepository for TursoDb {
-    async fn record_tuition_payment(&self, tuition: &Tuition) -> Result<()> {
-        self.execute_with_error(
+    async fn record_tuition_payment(
+        &self,
+        tuition: &Tuition,
+        event: Option<&OutboxEvent>,
+    ) -> Result<()> {
+        self.execute_with_event(
             "INSERT INTO tuition (
 id_tuition, id_user, amount, payment_date, deleted
 ) VALUES (?1, ?2, ?3, ?4, 0)", // deleted = 0
@@ -21,9 +39,11 @@ id_tuition, id_user, amount, payment_date, deleted
                 tuition.amount,
                 tuition.payment_date.format("%Y-%m-%d %H:%M:%S").to_string(),
             ],
+            event,
             Error::UnknownDatabaseError,
         )
-        .await
+        .await?;
+        Ok(())
     }
 
     async fn get_tuition_by_id(&self, id: Uuid) -> Result<Option<Tuition>> {
@@ -60,6 +80,36 @@ WHERE deleted = 0",
         .await
     }
 
+    async fn list_tuition_payments_page(
+        &self,
+        filter: &TuitionFilter,
+        page: &PageQuery,
+    ) -> Result<Page<Tuition>> {
+        let mut sql_filter = SqlFilter::new();
+        sql_filter
+            .push_if("id_user = ?", filter.id_user.map(|id| id.to_string()))
+            .push_if(
+                "payment_date >= ?",
+                filter
+                    .paid_from
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+            )
+            .push_if(
+                "payment_date <= ?",
+                filter
+                    .paid_to
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+            );
+
+        self.query_page_with_error(
+            &TUITION_PAGE_SQL,
+            &sql_filter,
+            page,
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
     async fn has_active_tuition(&self, user_id: Uuid) -> Result<bool> {
         #[derive(Deserialize)]
         struct Count {
@@ -111,6 +161,29 @@ AND payment_date >= date('now', '-30 days')", // Ensure date comparison works wi
             _ => Ok(false), // No active tuition or amount is NULL
         }
     }
+
+    async fn list_latest_tuitions_paid_between(
+        &self,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<Vec<Tuition>> {
+        self.query_many_with_error(
+            "SELECT id_tuition, id_user, amount, payment_date
+             FROM tuition t
+             WHERE deleted = 0
+             AND payment_date >= ?1 AND payment_date < ?2
+             AND payment_date = (
+                 SELECT MAX(payment_date) FROM tuition
+                 WHERE id_user = t.id_user AND deleted = 0
+             )",
+            params![
+                from.format("%Y-%m-%d %H:%M:%S").to_string(),
+                to.format("%Y-%m-%d %H:%M:%S").to_string()
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
 }
 
 #[cfg(test)]
@@ -118,7 +191,7 @@ mod test {
     use std::future::Future;
 
     use super::*;
-    use chrono::Utc;
+    use chrono::{Duration, Utc};
     use entities::tuition::Tuition;
     use rstest::{fixture, rstest};
     use uuid::Uuid;
@@ -164,7 +237,7 @@ mod test {
         };
 
         // Record the tuition payment
-        db.record_tuition_payment(&tuition)
+        db.record_tuition_payment(&tuition, None)
             .await
             .expect("Failed to record tuition");
 
@@ -201,7 +274,7 @@ mod test {
                 payment_date: Utc::now().naive_utc(),
             };
 
-            db.record_tuition_payment(&tuition)
+            db.record_tuition_payment(&tuition, None)
                 .await
                 .expect("Failed to record tuition");
         }
@@ -214,7 +287,7 @@ mod test {
             payment_date: Utc::now().naive_utc(),
         };
 
-        db.record_tuition_payment(&tuition)
+        db.record_tuition_payment(&tuition, None)
             .await
             .expect("Failed to record tuition");
 
@@ -259,7 +332,7 @@ mod test {
             payment_date: Utc::now().naive_utc(),
         };
 
-        db.record_tuition_payment(&tuition)
+        db.record_tuition_payment(&tuition, None)
             .await
             .expect("Failed to record tuition");
 
@@ -278,4 +351,53 @@ mod test {
         // Should no longer have active tuition
         assert!(!db.has_active_tuition(user_id).await.unwrap());
     }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_list_latest_tuitions_paid_between(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let renewed_user = Uuid::new_v4();
+        let expiring_user = Uuid::new_v4();
+        let now = Utc::now().naive_utc();
+        let month_ago = now - Duration::days(28);
+
+        for user_id in [renewed_user, expiring_user] {
+            db.create_test_user(user_id)
+                .await
+                .expect("Failed to create test user");
+            db.record_tuition_payment(
+                &Tuition {
+                    id_tuition: Uuid::new_v4(),
+                    id_user: user_id,
+                    amount: 100.0,
+                    payment_date: month_ago,
+                },
+                None,
+            )
+            .await
+            .expect("Failed to record tuition");
+        }
+        db.record_tuition_payment(
+            &Tuition {
+                id_tuition: Uuid::new_v4(),
+                id_user: renewed_user,
+                amount: 100.0,
+                payment_date: now,
+            },
+            None,
+        )
+        .await
+        .expect("Failed to record tuition");
+
+        let tuitions = db
+            .list_latest_tuitions_paid_between(
+                month_ago - Duration::hours(1),
+                month_ago + Duration::hours(1),
+            )
+            .await
+            .expect("Failed to list tuitions");
+
+        assert_eq!(tuitions.len(), 1);
+        assert_eq!(tuitions[0].id_user, expiring_user);
+    }
 }
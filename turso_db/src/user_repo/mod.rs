@@ -1,24 +1,41 @@
 use async_trait::async_trait;
-use entities::user::{IdType, User};
+use entities::event::OutboxEvent;
+use entities::pagination::{Page, PageQuery};
+use entities::user::{IdType, User, UserFilter};
 use libsql::{de, params};
 use tracing::{info, instrument};
 use use_cases::user_service::err::{Error, Result};
 use use_cases::user_service::repository_trait::UserRepository;
 use uuid::Uuid;
 
+use crate::pagination::{PageSql, SqlFilter};
 use crate::TursoDb;
 
+const USER_PAGE_SQL: PageSql<'static> = PageSql {
+    select: "SELECT id_user, first_name, last_name, birth_date, registration_date, email,
+email_verified, phone_number, country_code, password, identification_number,
+identification_type, user_rol
+FROM person
+WHERE deleted = 0",
+    count: "SELECT COUNT(*) AS count FROM person WHERE deleted = 0",
+    sortable_columns: &[
+        "first_name",
+        "last_name",
+        "email",
+        "birth_date",
+        "registration_date",
+        "user_rol",
+    ],
+    default_sort_column: "registration_date",
+    primary_key: "id_user",
+};
+
 #[async_trait]
 impl UserRepository for TursoDb {
-    async fn create_user(&self, user: &User) -> Result<()> {
+    async fn create_user(&self, user: &User, event: Option<&OutboxEvent>) -> Result<()> {
         info!("User is: {:?}", user.user_rol);
 
-        let conn = self
-            .get_connection()
-            .await
-            .map_err(|err| Error::UnknownDatabaseError(format!("{err}")))?;
-
-        conn.execute(
+        self.execute_with_event(
             "INSERT INTO person (
                 id_user, first_name, last_name, birth_date, registration_date, 
                 email, email_verified, phone_number, country_code, password, 
@@ -41,10 +58,10 @@ impl UserRepository for TursoDb {
                 user.identification_type.to_string(),
                 user.user_rol.to_string(),
             ],
+            event,
+            Error::UnknownDatabaseError,
         )
-        .await
-        .map_err(|err| Error::UnknownDatabaseError(format!("{err}")))?;
-
+        .await?;
         Ok(())
     }
 
@@ -240,6 +257,73 @@ WHERE id_user = ?13",
         Ok(())
     }
 
+    async fn anonymize_user(&self, user: &User) -> Result<()> {
+        let conn = self
+            .get_connection_with_error(Error::UnknownDatabaseError)
+            .await?;
+        let transaction = conn
+            .transaction()
+            .await
+            .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+
+        let anonymized: Result<()> = async {
+            transaction
+                .execute(
+                    "UPDATE person SET
+                        first_name = ?1,
+                        last_name = ?2,
+                        birth_date = ?3,
+                        email = ?4,
+                        email_verified = ?5,
+                        phone_number = ?6,
+                        country_code = ?7,
+                        password = ?8,
+                        identification_number = ?9,
+                        deleted = 1
+                     WHERE id_user = ?10",
+                    params![
+                        user.first_name.clone(),
+                        user.last_name.clone(),
+                        user.birth_date.format("%Y-%m-%d").to_string(),
+                        user.email.clone(),
+                        user.email_verified as i32,
+                        user.phone_number.clone(),
+                        user.country_code.clone(),
+                        user.password.clone(),
+                        user.identification_number.clone(),
+                        user.id_user.to_string()
+                    ],
+                )
+                .await
+                .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+
+            // Messages are addressed by name and the delivery log keeps their rendered content
+            transaction
+                .execute(
+                    "DELETE FROM notification WHERE id_user = ?1",
+                    params![user.id_user.to_string()],
+                )
+                .await
+                .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+
+            Ok(())
+        }
+        .await;
+
+        if let Err(err) = anonymized {
+            transaction
+                .rollback()
+                .await
+                .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+            return Err(err);
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|err| Error::UnknownDatabaseError(err.to_string()))
+    }
+
     async fn list_users(&self) -> Result<Vec<User>> {
         let conn = self
             .get_connection()
@@ -271,15 +355,55 @@ WHERE deleted = 0",
 
         Ok(users)
     }
+
+    async fn list_users_page(&self, filter: &UserFilter, page: &PageQuery) -> Result<Page<User>> {
+        let mut sql_filter = SqlFilter::new();
+        sql_filter
+            .push_if(
+                "user_rol = ?",
+                filter.user_rol.as_ref().map(|rol| rol.to_string()),
+            )
+            .push_if(
+                "registration_date >= ?",
+                filter
+                    .registered_from
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+            )
+            .push_if(
+                "registration_date <= ?",
+                filter
+                    .registered_to
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+            );
+        if let Some(search) = &filter.search {
+            sql_filter.push_repeated(
+                "first_name LIKE ? OR last_name LIKE ? OR email LIKE ?",
+                format!("%{search}%"),
+                3,
+            );
+        }
+
+        self.query_page_with_error(
+            &USER_PAGE_SQL,
+            &sql_filter,
+            page,
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
 }
 
 #[cfg(test)]
 mod test {
     use std::future::Future;
 
-    use entities::user::{IdType, User};
+    use entities::{
+        pagination::PageQuery,
+        user::{IdType, User, UserFilter},
+    };
+    use libsql::params;
     use rstest::{fixture, rstest};
-    use use_cases::user_service::repository_trait::UserRepository;
+    use use_cases::user_service::{err::Error, repository_trait::UserRepository};
     use uuid::Uuid;
 
     use crate::{TestDbBuilder, TursoDb};
@@ -310,7 +434,9 @@ mod test {
             ..User::default()
         };
 
-        db.create_user(&user).await.expect("Error creating user");
+        db.create_user(&user, None)
+            .await
+            .expect("Error creating user");
 
         let user_db = db
             .get_user_by_id(user_id)
@@ -334,7 +460,9 @@ mod test {
             ..User::default()
         };
 
-        db.create_user(&user).await.expect("Error creating user");
+        db.create_user(&user, None)
+            .await
+            .expect("Error creating user");
 
         let user_db = db
             .get_user_by_id(user_id)
@@ -367,7 +495,9 @@ mod test {
             ..User::default()
         };
 
-        db.create_user(&user).await.expect("Error creating user");
+        db.create_user(&user, None)
+            .await
+            .expect("Error creating user");
 
         let fetched_id = db
             .get_user_id_by_email(&email)
@@ -391,7 +521,9 @@ mod test {
             ..User::default()
         };
 
-        db.create_user(&user).await.expect("Error creating user");
+        db.create_user(&user, None)
+            .await
+            .expect("Error creating user");
 
         let fetched_id = db
             .get_user_id_by_phone(&phone_number)
@@ -417,7 +549,9 @@ mod test {
             ..User::default()
         };
 
-        db.create_user(&user).await.expect("Error creating user");
+        db.create_user(&user, None)
+            .await
+            .expect("Error creating user");
 
         let fetched_id = db
             .get_user_id_by_identification(&identification_number, &IdType::default())
@@ -441,7 +575,9 @@ mod test {
             ..User::default()
         };
 
-        db.create_user(&user).await.expect("Error creating user");
+        db.create_user(&user, None)
+            .await
+            .expect("Error creating user");
 
         // Update some fields.
         user.first_name = "Updated".to_string();
@@ -470,7 +606,9 @@ mod test {
             ..User::default()
         };
 
-        db.create_user(&user).await.expect("Error creating user");
+        db.create_user(&user, None)
+            .await
+            .expect("Error creating user");
 
         db.delete_user(user_id).await.expect("Error deleting user");
 
@@ -481,6 +619,68 @@ mod test {
         assert!(deleted_user.is_none());
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn test_anonymize_user(repository: impl Future<Output = TursoDb>) {
+        #[derive(serde::Deserialize)]
+        struct StoredPerson {
+            first_name: String,
+            email: String,
+            phone_number: String,
+            identification_number: String,
+            deleted: i64,
+        }
+
+        let user_id = Uuid::new_v4();
+        let db = repository.await;
+
+        let user = User {
+            id_user: user_id,
+            first_name: "Maria".to_string(),
+            email: "maria@example.com".to_string(),
+            phone_number: "3001234567".to_string(),
+            identification_number: "1020304050".to_string(),
+            ..User::default()
+        };
+        db.create_user(&user, None)
+            .await
+            .expect("Error creating user");
+
+        let anonymized = User {
+            first_name: "ANONYMIZED".to_string(),
+            email: "erased@erased.invalid".to_string(),
+            phone_number: "erased".to_string(),
+            identification_number: "erased".to_string(),
+            ..user.clone()
+        };
+        db.anonymize_user(&anonymized)
+            .await
+            .expect("Error anonymizing user");
+
+        assert!(db.get_user_by_id(user_id).await.unwrap().is_none());
+        assert!(db
+            .get_user_id_by_email("maria@example.com")
+            .await
+            .unwrap()
+            .is_none());
+
+        let stored: StoredPerson = db
+            .query_one_with_error(
+                "SELECT first_name, email, phone_number, identification_number, deleted
+                 FROM person WHERE id_user = ?1",
+                params![user_id.to_string()],
+                Error::UnknownDatabaseError,
+            )
+            .await
+            .unwrap()
+            .expect("Anonymized user was removed");
+        assert_eq!(stored.first_name, "ANONYMIZED");
+        assert_eq!(stored.email, "erased@erased.invalid");
+        assert_eq!(stored.phone_number, "erased");
+        assert_eq!(stored.identification_number, "erased");
+        assert_eq!(stored.deleted, 1);
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_list_users(repository: impl Future<Output = TursoDb>) {
@@ -506,8 +706,12 @@ mod test {
             ..User::default()
         };
 
-        db.create_user(&user1).await.expect("Error creating user1");
-        db.create_user(&user2).await.expect("Error creating user2");
+        db.create_user(&user1, None)
+            .await
+            .expect("Error creating user1");
+        db.create_user(&user2, None)
+            .await
+            .expect("Error creating user2");
 
         let users_after_insert = db
             .list_users()
@@ -531,4 +735,45 @@ mod test {
             assert_ne!(user.id_user, user1.id_user);
         }
     }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_list_users_page(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+
+        for i in 0..3 {
+            let user = User {
+                id_user: Uuid::new_v4(),
+                first_name: format!("Paged{i}"),
+                email: format!("paged{i}@example.com"),
+                phone_number: format!("33333{i}"),
+                identification_number: format!("ID_PAGE_{i}"),
+                ..User::default()
+            };
+            db.create_user(&user, None)
+                .await
+                .expect("Error creating user");
+        }
+
+        let filter = UserFilter {
+            search: Some("paged".to_string()),
+            ..UserFilter::default()
+        };
+        let page_query = PageQuery {
+            page: 2,
+            page_size: 2,
+            sort_by: Some("first_name".to_string()),
+            ..PageQuery::default()
+        };
+
+        let page = db
+            .list_users_page(&filter, &page_query)
+            .await
+            .expect("Error listing users page");
+
+        assert_eq!(page.total, 3);
+        assert_eq!(page.total_pages, 2);
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].first_name, "Paged2");
+    }
 }
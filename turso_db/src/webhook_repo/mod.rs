@@ -0,0 +1,383 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use entities::datetime_serde;
+use entities::event::EventKind;
+use entities::pagination::{Page, PageQuery};
+use entities::webhook::{Webhook, WebhookDelivery, WebhookDeliveryFilter};
+use libsql::params;
+use serde::Deserialize;
+use use_cases::webhook_service::{
+    err::{Error, Result},
+    repository_trait::WebhookRepository,
+};
+use uuid::Uuid;
+
+use crate::pagination::{PageSql, SqlFilter};
+use crate::TursoDb;
+
+const DELIVERY_COLUMNS: &str = "id_delivery, id_webhook, id_event, event_kind, payload, status,
+attempts, response_status, last_error, next_attempt_at, created_at, delivered_at";
+
+const DELIVERY_PAGE_SQL: PageSql<'static> = PageSql {
+    select: "SELECT id_delivery, id_webhook, id_event, event_kind, payload, status, attempts,
+response_status, last_error, next_attempt_at, created_at, delivered_at
+FROM webhook_delivery
+WHERE 1 = 1",
+    count: "SELECT COUNT(*) AS count FROM webhook_delivery WHERE 1 = 1",
+    sortable_columns: &["created_at", "next_attempt_at", "status", "event_kind"],
+    default_sort_column: "created_at",
+    primary_key: "id_delivery",
+};
+
+/// The subscribed event kinds are stored as a JSON array
+#[derive(Deserialize)]
+struct WebhookRow {
+    id_webhook: Uuid,
+    url: String,
+    description: String,
+    event_kinds: String,
+    secret: String,
+    active: bool,
+    #[serde(with = "datetime_serde")]
+    created_at: NaiveDateTime,
+}
+
+impl TryFrom<WebhookRow> for Webhook {
+    type Error = Error;
+
+    fn try_from(row: WebhookRow) -> Result<Self> {
+        let event_kinds: Vec<EventKind> = serde_json::from_str(&row.event_kinds)
+            .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+
+        Ok(Self {
+            id_webhook: row.id_webhook,
+            url: row.url,
+            description: row.description,
+            event_kinds,
+            secret: row.secret,
+            active: row.active,
+            created_at: row.created_at,
+        })
+    }
+}
+
+fn event_kinds(webhook: &Webhook) -> Result<String> {
+    serde_json::to_string(&webhook.event_kinds)
+        .map_err(|err| Error::UnknownDatabaseError(err.to_string()))
+}
+
+fn format_datetime(datetime: NaiveDateTime) -> String {
+    datetime.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+#[async_trait]
+impl WebhookRepository for TursoDb {
+    async fn create_webhook(&self, webhook: &Webhook) -> Result<()> {
+        self.execute_with_error(
+            "INSERT INTO webhook (
+                id_webhook, url, description, event_kinds, secret, active, created_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                webhook.id_webhook.to_string(),
+                webhook.url.clone(),
+                webhook.description.clone(),
+                event_kinds(webhook)?,
+                webhook.secret.clone(),
+                webhook.active,
+                format_datetime(webhook.created_at),
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn get_webhook(&self, id_webhook: Uuid) -> Result<Option<Webhook>> {
+        let row: Option<WebhookRow> = self
+            .query_one_with_error(
+                "SELECT id_webhook, url, description, event_kinds, secret, active, created_at
+                FROM webhook
+                WHERE id_webhook = ?1 AND deleted = 0",
+                params![id_webhook.to_string()],
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+
+        row.map(Webhook::try_from).transpose()
+    }
+
+    async fn list_webhooks(&self) -> Result<Vec<Webhook>> {
+        let rows: Vec<WebhookRow> = self
+            .query_many_with_error(
+                "SELECT id_webhook, url, description, event_kinds, secret, active, created_at
+                FROM webhook
+                WHERE deleted = 0
+                ORDER BY created_at",
+                params![],
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+
+        rows.into_iter().map(Webhook::try_from).collect()
+    }
+
+    async fn update_webhook(&self, webhook: &Webhook) -> Result<()> {
+        self.execute_with_error(
+            "UPDATE webhook SET url = ?1, description = ?2, event_kinds = ?3, active = ?4
+            WHERE id_webhook = ?5 AND deleted = 0",
+            params![
+                webhook.url.clone(),
+                webhook.description.clone(),
+                event_kinds(webhook)?,
+                webhook.active,
+                webhook.id_webhook.to_string(),
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn delete_webhook(&self, id_webhook: Uuid) -> Result<()> {
+        self.execute_with_error(
+            "UPDATE webhook SET deleted = 1, active = 0 WHERE id_webhook = ?1",
+            params![id_webhook.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn create_delivery(&self, delivery: &WebhookDelivery) -> Result<bool> {
+        let affected = self
+            .execute_returning_affected_with_error(
+                "INSERT OR IGNORE INTO webhook_delivery (
+                    id_delivery, id_webhook, id_event, event_kind, payload, status, attempts,
+                    response_status, last_error, next_attempt_at, created_at, delivered_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    delivery.id_delivery.to_string(),
+                    delivery.id_webhook.to_string(),
+                    delivery.id_event.to_string(),
+                    delivery.event_kind.to_string(),
+                    delivery.payload.clone(),
+                    delivery.status.to_string(),
+                    delivery.attempts,
+                    delivery.response_status,
+                    delivery.last_error.clone(),
+                    delivery.next_attempt_at.map(format_datetime),
+                    format_datetime(delivery.created_at),
+                    delivery.delivered_at.map(format_datetime),
+                ],
+                Error::UnknownDatabaseError,
+            )
+            .await?;
+
+        Ok(affected > 0)
+    }
+
+    async fn get_delivery(&self, id_delivery: Uuid) -> Result<Option<WebhookDelivery>> {
+        self.query_one_with_error(
+            &format!("SELECT {DELIVERY_COLUMNS} FROM webhook_delivery WHERE id_delivery = ?1"),
+            params![id_delivery.to_string()],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn update_delivery(&self, delivery: &WebhookDelivery) -> Result<()> {
+        self.execute_with_error(
+            "UPDATE webhook_delivery SET status = ?1, attempts = ?2, response_status = ?3,
+            last_error = ?4, next_attempt_at = ?5, delivered_at = ?6
+            WHERE id_delivery = ?7",
+            params![
+                delivery.status.to_string(),
+                delivery.attempts,
+                delivery.response_status,
+                delivery.last_error.clone(),
+                delivery.next_attempt_at.map(format_datetime),
+                delivery.delivered_at.map(format_datetime),
+                delivery.id_delivery.to_string(),
+            ],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_deliveries_page(
+        &self,
+        filter: &WebhookDeliveryFilter,
+        page: &PageQuery,
+    ) -> Result<Page<WebhookDelivery>> {
+        let mut sql_filter = SqlFilter::new();
+        sql_filter
+            .push_if("id_webhook = ?", filter.id_webhook.map(|id| id.to_string()))
+            .push_if(
+                "event_kind = ?",
+                filter.event_kind.map(|kind| kind.to_string()),
+            )
+            .push_if("status = ?", filter.status.map(|status| status.to_string()));
+
+        self.query_page_with_error(
+            &DELIVERY_PAGE_SQL,
+            &sql_filter,
+            page,
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+
+    async fn list_due_deliveries(
+        &self,
+        now: NaiveDateTime,
+        limit: u32,
+    ) -> Result<Vec<WebhookDelivery>> {
+        self.query_many_with_error(
+            &format!(
+                "SELECT {DELIVERY_COLUMNS} FROM webhook_delivery
+                WHERE status = 'PENDING' AND next_attempt_at <= ?1
+                ORDER BY next_attempt_at
+                LIMIT ?2"
+            ),
+            params![format_datetime(now), limit],
+            Error::UnknownDatabaseError,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::future::Future;
+
+    use super::*;
+    use chrono::{Duration, Utc};
+    use entities::webhook::WebhookDeliveryStatus;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    async fn repository() -> TursoDb {
+        crate::TestDbBuilder::create().await.build()
+    }
+
+    fn test_datetime() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(
+            &Utc::now()
+                .naive_utc()
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+            "%Y-%m-%d %H:%M:%S",
+        )
+        .unwrap()
+    }
+
+    fn webhook() -> Webhook {
+        Webhook {
+            id_webhook: Uuid::new_v4(),
+            url: "https://example.com/hooks/club".to_string(),
+            description: "Ranking spreadsheet".to_string(),
+            event_kinds: vec![
+                EventKind::TOURNAMENT_RESULT_RECORDED,
+                EventKind::TUITION_PAID,
+            ],
+            secret: "whsec_test".to_string(),
+            active: true,
+            created_at: test_datetime(),
+        }
+    }
+
+    fn delivery(id_webhook: Uuid, next_attempt_at: NaiveDateTime) -> WebhookDelivery {
+        WebhookDelivery {
+            id_delivery: Uuid::new_v4(),
+            id_webhook,
+            id_event: Uuid::new_v4(),
+            event_kind: EventKind::TUITION_PAID,
+            payload: "{}".to_string(),
+            status: WebhookDeliveryStatus::PENDING,
+            attempts: 0,
+            response_status: None,
+            last_error: None,
+            next_attempt_at: Some(next_attempt_at),
+            created_at: test_datetime(),
+            delivered_at: None,
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_webhook_lifecycle(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let mut webhook = webhook();
+
+        db.create_webhook(&webhook)
+            .await
+            .expect("Error creating webhook");
+        assert_eq!(
+            db.get_webhook(webhook.id_webhook).await.unwrap(),
+            Some(webhook.clone())
+        );
+
+        webhook.active = false;
+        db.update_webhook(&webhook)
+            .await
+            .expect("Error updating webhook");
+        assert_eq!(db.list_webhooks().await.unwrap(), vec![webhook.clone()]);
+
+        db.delete_webhook(webhook.id_webhook)
+            .await
+            .expect("Error deleting webhook");
+        assert_eq!(db.get_webhook(webhook.id_webhook).await.unwrap(), None);
+        assert!(db.list_webhooks().await.unwrap().is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_deliveries(repository: impl Future<Output = TursoDb>) {
+        let db = repository.await;
+        let webhook = webhook();
+        db.create_webhook(&webhook)
+            .await
+            .expect("Error creating webhook");
+
+        let now = test_datetime();
+        let due = delivery(webhook.id_webhook, now - Duration::minutes(1));
+        let later = delivery(webhook.id_webhook, now + Duration::minutes(5));
+        assert!(db.create_delivery(&due).await.unwrap());
+        assert!(db.create_delivery(&later).await.unwrap());
+
+        // A second delivery of the same event to the same webhook is ignored
+        let duplicate = WebhookDelivery {
+            id_delivery: Uuid::new_v4(),
+            ..due.clone()
+        };
+        assert!(!db.create_delivery(&duplicate).await.unwrap());
+
+        assert_eq!(
+            db.list_due_deliveries(now, 10).await.unwrap(),
+            vec![due.clone()]
+        );
+
+        let succeeded = WebhookDelivery {
+            status: WebhookDeliveryStatus::SUCCEEDED,
+            attempts: 1,
+            response_status: Some(204),
+            next_attempt_at: None,
+            delivered_at: Some(now),
+            ..due.clone()
+        };
+        db.update_delivery(&succeeded).await.unwrap();
+        assert!(db.list_due_deliveries(now, 10).await.unwrap().is_empty());
+        assert_eq!(
+            db.get_delivery(due.id_delivery).await.unwrap(),
+            Some(succeeded)
+        );
+
+        let filter = WebhookDeliveryFilter {
+            status: Some(WebhookDeliveryStatus::PENDING),
+            ..WebhookDeliveryFilter::default()
+        };
+        let page = db
+            .list_deliveries_page(&filter, &PageQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(page.total, 1);
+        assert_eq!(page.items, vec![later]);
+    }
+}
@@ -0,0 +1,9 @@
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Error {
+    #[error("Database error: {0}")]
+    UnknownDatabaseError(String),
+}
@@ -0,0 +1,117 @@
+pub mod err;
+pub mod repository_trait;
+
+use std::{fmt::Display, sync::Arc};
+
+use chrono::Utc;
+use entities::{
+    audit::{Actor, AuditEntity, AuditEntry, AuditFilter},
+    pagination::{Page, PageQuery},
+};
+use repository_trait::AuditRepository;
+use serde::Serialize;
+use serde_json::Value;
+use tracing::error;
+use uuid::Uuid;
+
+use self::err::Result;
+
+#[derive(Clone)]
+pub struct AuditService {
+    audit_repo: Arc<dyn AuditRepository>,
+}
+
+impl AuditService {
+    pub fn new(audit_repo: Arc<dyn AuditRepository>) -> Self {
+        Self { audit_repo }
+    }
+
+    pub async fn record_creation(
+        &self,
+        actor: &Actor,
+        action: &str,
+        entity: AuditEntity,
+        entity_id: impl Display,
+        after: &impl Serialize,
+    ) {
+        self.record(actor, action, entity, entity_id, None, snapshot(after))
+            .await
+    }
+
+    pub async fn record_update(
+        &self,
+        actor: &Actor,
+        action: &str,
+        entity: AuditEntity,
+        entity_id: impl Display,
+        before: &impl Serialize,
+        after: &impl Serialize,
+    ) {
+        self.record(
+            actor,
+            action,
+            entity,
+            entity_id,
+            snapshot(before),
+            snapshot(after),
+        )
+        .await
+    }
+
+    pub async fn record_deletion(
+        &self,
+        actor: &Actor,
+        action: &str,
+        entity: AuditEntity,
+        entity_id: impl Display,
+        before: &impl Serialize,
+    ) {
+        self.record(actor, action, entity, entity_id, snapshot(before), None)
+            .await
+    }
+
+    pub async fn list_entries_page(
+        &self,
+        filter: &AuditFilter,
+        page: &PageQuery,
+    ) -> Result<Page<AuditEntry>> {
+        self.audit_repo.list_audit_entries_page(filter, page).await
+    }
+
+    /// The mutation has already been applied when this runs, so a failure to store the entry is
+    /// logged instead of being reported to the caller
+    async fn record(
+        &self,
+        actor: &Actor,
+        action: &str,
+        entity: AuditEntity,
+        entity_id: impl Display,
+        before: Option<Value>,
+        after: Option<Value>,
+    ) {
+        let entry = AuditEntry {
+            id_audit: Uuid::new_v4(),
+            actor_id: actor.user_id,
+            actor_rol: actor.user_rol.clone(),
+            action: action.to_string(),
+            entity,
+            entity_id: entity_id.to_string(),
+            before,
+            after,
+            created_at: Utc::now().naive_utc(),
+        };
+
+        if let Err(err) = self.audit_repo.create_audit_entry(&entry).await {
+            error!(
+                "Error recording audit entry {} for {} {}: {err}",
+                entry.action, entry.entity, entry.entity_id
+            );
+        }
+    }
+}
+
+fn snapshot(value: &impl Serialize) -> Option<Value> {
+    serde_json::to_value(value)
+        .inspect_err(|err| error!("Error serializing audit snapshot: {err}"))
+        .ok()
+}
@@ -0,0 +1,15 @@
+use super::err::Result;
+use async_trait::async_trait;
+use entities::audit::{AuditEntry, AuditFilter};
+use entities::pagination::{Page, PageQuery};
+
+/// Audit entries can only be appended and read, never updated nor deleted
+#[async_trait]
+pub trait AuditRepository: Send + Sync {
+    async fn create_audit_entry(&self, entry: &AuditEntry) -> Result<()>;
+    async fn list_audit_entries_page(
+        &self,
+        filter: &AuditFilter,
+        page: &PageQuery,
+    ) -> Result<Page<AuditEntry>>;
+}
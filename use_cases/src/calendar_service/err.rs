@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Database error: {0}")]
+    UnknownDatabaseError(String),
+    #[error("Calendar feed not found")]
+    FeedNotFound,
+    #[error("Only whoever created the feed can revoke it")]
+    NotFeedOwner,
+    #[error("A court feed needs the court it follows")]
+    MissingSubject,
+    #[error("Only admins can follow the schedule of someone else")]
+    SubjectNotAllowed,
+    #[error("User is not a trainer")]
+    NotATrainer,
+    #[error("Error in court service: {0}")]
+    CourtServiceError(#[from] crate::court_service::err::Error),
+    #[error("Error in user service: {0}")]
+    UserServiceError(#[from] crate::user_service::err::Error),
+}
@@ -0,0 +1,167 @@
+use chrono::NaiveDateTime;
+use chrono_tz::Tz;
+use entities::calendar::{CalendarEntry, CalendarEntryKind};
+
+/// Longest line RFC 5545 allows, in octets without the line break
+const MAX_LINE_OCTETS: usize = 75;
+const PRODUCT_ID: &str = "-//Club API//Calendar feeds//EN";
+const UID_DOMAIN: &str = "club-api";
+
+/// iCalendar document of the entries. Times are sent in UTC, as they are stored, and the club
+/// timezone tells calendar apps how to show them. Cancelled entries stay in the document so the
+/// apps that already imported them mark them as cancelled
+pub fn render_calendar(
+    name: &str,
+    timezone: Tz,
+    entries: &[CalendarEntry],
+    now: NaiveDateTime,
+) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{PRODUCT_ID}"),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(name)),
+        format!("X-WR-TIMEZONE:{}", timezone.name()),
+    ];
+    for entry in entries {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", uid(entry)));
+        lines.push(format!("DTSTAMP:{}", utc(now)));
+        lines.push(format!("DTSTART:{}", utc(entry.start_datetime)));
+        lines.push(format!("DTEND:{}", utc(entry.end_datetime)));
+        lines.push(format!("SUMMARY:{}", escape_text(&summary(entry))));
+        if let Some(location) = &entry.location {
+            lines.push(format!("LOCATION:{}", escape_text(location)));
+        }
+        // Some apps only apply a cancellation with a higher sequence than the one they have
+        if entry.cancelled {
+            lines.push("STATUS:CANCELLED".to_string());
+            lines.push("SEQUENCE:1".to_string());
+        } else {
+            lines.push("STATUS:CONFIRMED".to_string());
+            lines.push("SEQUENCE:0".to_string());
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold(line)).collect()
+}
+
+/// Derived from the source id, so the apps update the same event on every refresh
+fn uid(entry: &CalendarEntry) -> String {
+    let kind = match entry.kind {
+        CalendarEntryKind::COURT_RESERVATION => "court-reservation",
+        CalendarEntryKind::TRAINING => "training",
+        CalendarEntryKind::TOURNAMENT => "tournament",
+    };
+    format!("{kind}-{}@{UID_DOMAIN}", entry.id_source)
+}
+
+fn summary(entry: &CalendarEntry) -> String {
+    let default = match entry.kind {
+        CalendarEntryKind::COURT_RESERVATION => "Court booking",
+        CalendarEntryKind::TRAINING => "Training",
+        CalendarEntryKind::TOURNAMENT => "Tournament",
+    };
+    entry.name.clone().unwrap_or_else(|| default.to_string())
+}
+
+fn utc(datetime: NaiveDateTime) -> String {
+    datetime.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Splits the line in lines of at most 75 octets without breaking a character, the following
+/// ones start with a space
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 2);
+    let mut line_octets = 0;
+    for character in line.chars() {
+        if line_octets + character.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            line_octets = 1;
+        }
+        folded.push(character);
+        line_octets += character.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn at(hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 6, 7)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_render_calendar() {
+        let id_training = Uuid::new_v4();
+        let entries = [
+            CalendarEntry {
+                kind: CalendarEntryKind::TRAINING,
+                id_source: id_training,
+                name: Some("Juniors, advanced".to_string()),
+                location: Some("Central".to_string()),
+                start_datetime: at(18),
+                end_datetime: at(20),
+                cancelled: false,
+            },
+            CalendarEntry {
+                kind: CalendarEntryKind::COURT_RESERVATION,
+                id_source: Uuid::new_v4(),
+                name: None,
+                location: None,
+                start_datetime: at(9),
+                end_datetime: at(10),
+                cancelled: true,
+            },
+        ];
+
+        let calendar = render_calendar(
+            "Coach",
+            Tz::America__Argentina__Buenos_Aires,
+            &entries,
+            at(8),
+        );
+
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+        assert!(calendar.contains("X-WR-TIMEZONE:America/Argentina/Buenos_Aires\r\n"));
+        assert!(calendar.contains(&format!("UID:training-{id_training}@club-api\r\n")));
+        assert!(calendar.contains("DTSTART:20250607T180000Z\r\nDTEND:20250607T200000Z\r\n"));
+        assert!(calendar.contains("SUMMARY:Juniors\\, advanced\r\n"));
+        assert!(calendar.contains("SUMMARY:Court booking\r\nSTATUS:CANCELLED\r\nSEQUENCE:1\r\n"));
+        assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 2);
+    }
+
+    #[test]
+    fn test_long_lines_are_folded() {
+        let line = format!("SUMMARY:{}", "ñ".repeat(60));
+
+        let folded = fold(&line);
+
+        assert!(folded
+            .split("\r\n")
+            .all(|physical_line| physical_line.len() <= MAX_LINE_OCTETS));
+        assert_eq!(folded.replace("\r\n ", ""), format!("{line}\r\n"));
+    }
+}
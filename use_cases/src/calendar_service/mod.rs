@@ -0,0 +1,174 @@
+pub mod err;
+mod ics;
+pub mod repository_trait;
+
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use chrono_tz::Tz;
+use entities::audit::{Actor, AuditEntity};
+use entities::calendar::{CalendarFeed, CalendarFeedCreation, CalendarFeedKind};
+use entities::user::URol;
+use repository_trait::CalendarRepository;
+use uuid::Uuid;
+
+use self::err::{Error, Result};
+use crate::{audit_service::AuditService, court_service::CourtService, user_service::UserService};
+
+/// How long ended sessions stay in the feeds
+const FEED_HISTORY_DAYS: i64 = 30;
+
+/// Read-only iCalendar feeds of the court reservations, the trainings of a trainer and the
+/// schedule of a member, reached with the token of the feed instead of a login
+#[derive(Clone)]
+pub struct CalendarService {
+    calendar_repo: Arc<dyn CalendarRepository>,
+    court_service: CourtService,
+    user_service: UserService,
+    audit_service: AuditService,
+    timezone: Tz,
+}
+
+impl CalendarService {
+    pub fn new(
+        calendar_repo: Arc<dyn CalendarRepository>,
+        court_service: CourtService,
+        user_service: UserService,
+        audit_service: AuditService,
+        timezone: Tz,
+    ) -> Self {
+        Self {
+            calendar_repo,
+            court_service,
+            user_service,
+            audit_service,
+            timezone,
+        }
+    }
+
+    /// Anyone can follow a court, trainers and members follow their own schedule and admins
+    /// any of them
+    pub async fn create_feed(
+        &self,
+        actor: &Actor,
+        creation: CalendarFeedCreation,
+    ) -> Result<CalendarFeed> {
+        let id_subject = match (creation.kind, creation.id_subject) {
+            (_, Some(id_subject)) => id_subject,
+            (CalendarFeedKind::COURT, None) => return Err(Error::MissingSubject),
+            (_, None) => actor.user_id,
+        };
+        match creation.kind {
+            CalendarFeedKind::COURT => {
+                self.court_service.get_court(id_subject).await?;
+            }
+            CalendarFeedKind::TRAINER | CalendarFeedKind::MEMBER => {
+                if id_subject != actor.user_id && actor.user_rol != URol::ADMIN {
+                    return Err(Error::SubjectNotAllowed);
+                }
+                let user = self.user_service.get_user_by_id(id_subject).await?;
+                if creation.kind == CalendarFeedKind::TRAINER && user.user_rol != URol::TRAINER {
+                    return Err(Error::NotATrainer);
+                }
+            }
+        }
+
+        let feed = CalendarFeed {
+            token: Uuid::new_v4().simple().to_string(),
+            id_user: actor.user_id,
+            kind: creation.kind,
+            id_subject,
+            created_datetime: Utc::now().naive_utc(),
+        };
+        self.calendar_repo.create_feed(&feed).await?;
+
+        self.audit_service
+            .record_creation(
+                actor,
+                "CREATE_CALENDAR_FEED",
+                AuditEntity::CALENDAR_FEED,
+                &feed.token,
+                &feed,
+            )
+            .await;
+
+        Ok(feed)
+    }
+
+    pub async fn list_user_feeds(&self, id_user: Uuid) -> Result<Vec<CalendarFeed>> {
+        self.calendar_repo.list_user_feeds(id_user).await
+    }
+
+    /// The link stops working for every app subscribed to it
+    pub async fn revoke_feed(&self, actor: &Actor, token: &str) -> Result<()> {
+        let feed = self
+            .calendar_repo
+            .get_feed(token)
+            .await?
+            .ok_or(Error::FeedNotFound)?;
+        if feed.id_user != actor.user_id && actor.user_rol != URol::ADMIN {
+            return Err(Error::NotFeedOwner);
+        }
+
+        self.calendar_repo.revoke_feed(token).await?;
+
+        self.audit_service
+            .record_deletion(
+                actor,
+                "REVOKE_CALENDAR_FEED",
+                AuditEntity::CALENDAR_FEED,
+                token,
+                &feed,
+            )
+            .await;
+
+        Ok(())
+    }
+
+    /// iCalendar document of the feed, with the sessions that ended in the last days and every
+    /// upcoming one
+    pub async fn render_feed(&self, token: &str) -> Result<String> {
+        let feed = self
+            .calendar_repo
+            .get_feed(token)
+            .await?
+            .ok_or(Error::FeedNotFound)?;
+        let now = Utc::now().naive_utc();
+        let from = now - Duration::days(FEED_HISTORY_DAYS);
+
+        let (name, entries) = match feed.kind {
+            CalendarFeedKind::COURT => {
+                let court = self.court_service.get_court(feed.id_subject).await?;
+                let entries = self
+                    .calendar_repo
+                    .list_court_entries(feed.id_subject, from)
+                    .await?;
+                (court.court_name, entries)
+            }
+            CalendarFeedKind::TRAINER => {
+                let trainer = self.user_service.get_user_by_id(feed.id_subject).await?;
+                let entries = self
+                    .calendar_repo
+                    .list_trainer_entries(feed.id_subject, from)
+                    .await?;
+                (
+                    format!("Trainings of {} {}", trainer.first_name, trainer.last_name),
+                    entries,
+                )
+            }
+            CalendarFeedKind::MEMBER => {
+                let member = self.user_service.get_user_by_id(feed.id_subject).await?;
+                let entries = self
+                    .calendar_repo
+                    .list_member_entries(feed.id_subject, from)
+                    .await?;
+                (
+                    format!("Schedule of {} {}", member.first_name, member.last_name),
+                    entries,
+                )
+            }
+        };
+
+        Ok(ics::render_calendar(&name, self.timezone, &entries, now))
+    }
+}
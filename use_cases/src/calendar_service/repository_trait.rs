@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use entities::calendar::{CalendarEntry, CalendarFeed};
+use uuid::Uuid;
+
+use super::err::Result;
+
+#[async_trait]
+pub trait CalendarRepository: Send + Sync {
+    async fn create_feed(&self, feed: &CalendarFeed) -> Result<()>;
+    /// Revoked feeds are left out
+    async fn get_feed(&self, token: &str) -> Result<Option<CalendarFeed>>;
+    /// Feeds the user created, newest first
+    async fn list_user_feeds(&self, id_user: Uuid) -> Result<Vec<CalendarFeed>>;
+    async fn revoke_feed(&self, token: &str) -> Result<()>;
+
+    /// The entries below end after `from`, deleted ones are returned as cancelled
+    async fn list_court_entries(
+        &self,
+        id_court: Uuid,
+        from: NaiveDateTime,
+    ) -> Result<Vec<CalendarEntry>>;
+    async fn list_trainer_entries(
+        &self,
+        trainer_id: Uuid,
+        from: NaiveDateTime,
+    ) -> Result<Vec<CalendarEntry>>;
+    /// Trainings and tournaments the member is registered to
+    async fn list_member_entries(
+        &self,
+        id_user: Uuid,
+        from: NaiveDateTime,
+    ) -> Result<Vec<CalendarEntry>>;
+}
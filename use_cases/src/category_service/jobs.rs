@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use entities::audit::Actor;
+
+use super::CategoryService;
+use crate::job_service::{
+    err::{Error, Result},
+    job_trait::Job,
+};
+
+/// Takes the members out of the categories they outgrew. Joining the next category still goes
+/// through the usual eligibility checks
+pub struct CategoryAgeRolloverJob(pub CategoryService);
+
+#[async_trait]
+impl Job for CategoryAgeRolloverJob {
+    fn name(&self) -> &'static str {
+        "category_age_rollover"
+    }
+
+    fn schedule(&self) -> &'static str {
+        "0 3 * * *"
+    }
+
+    async fn run(&self, actor: &Actor) -> Result<String> {
+        let removed = self
+            .0
+            .remove_overage_members(actor)
+            .await
+            .map_err(|err| Error::JobFailed(err.to_string()))?;
+
+        Ok(format!(
+            "{removed} members removed from categories they outgrew"
+        ))
+    }
+}
@@ -1,5 +1,6 @@
 use chrono::Utc;
 use entities::{
+    audit::{Actor, AuditEntity},
     category::{Category, CategoryCreation, CategoryRequirement, Level, LevelName},
     user::UserCategory,
 };
@@ -9,10 +10,12 @@ use std::sync::Arc;
 use tracing::{debug, info};
 use uuid::Uuid;
 
-use crate::user_service::UserService;
+use crate::{audit_service::AuditService, user_service::UserService};
 
 pub mod err;
+pub mod jobs;
 pub mod repository_trait;
+mod request_executor;
 
 #[derive(Clone)]
 pub struct CategoryService {
@@ -20,6 +23,7 @@ pub struct CategoryService {
     requirement_repo: Arc<dyn CategoryRequirementRepository>,
     user_category_repo: Arc<dyn UserCategoryRepository>,
     user_service: UserService,
+    audit_service: AuditService,
 }
 
 impl CategoryService {
@@ -28,27 +32,57 @@ impl CategoryService {
         requirement_repo: Arc<dyn CategoryRequirementRepository>,
         user_category_repo: Arc<dyn UserCategoryRepository>,
         user_service: UserService,
+        audit_service: AuditService,
     ) -> Self {
         Self {
             category_repo,
             requirement_repo,
             user_category_repo,
             user_service,
+            audit_service,
         }
     }
 
-    pub async fn delete_user_from_category(&self, user_id: Uuid, category_id: Uuid) -> Result<()> {
+    pub async fn delete_user_from_category(
+        &self,
+        actor: &Actor,
+        user_id: Uuid,
+        category_id: Uuid,
+    ) -> Result<()> {
+        let before = self
+            .user_category_repo
+            .get_user_category(user_id, category_id)
+            .await?;
+
         self.user_category_repo
             .delete_user_category(user_id, category_id)
-            .await
+            .await?;
+
+        self.audit_service
+            .record_deletion(
+                actor,
+                "DELETE_USER_FROM_CATEGORY",
+                AuditEntity::USER_CATEGORY,
+                format!("{user_id}/{category_id}"),
+                &before,
+            )
+            .await;
+
+        Ok(())
     }
 
     pub async fn update_user_category_level(
         &self,
+        actor: &Actor,
         user_id: Uuid,
         category_id: Uuid,
         new_level: LevelName,
     ) -> Result<()> {
+        let before = self
+            .user_category_repo
+            .get_user_category(user_id, category_id)
+            .await?;
+
         self.user_category_repo
             .update_user_category(
                 user_id,
@@ -57,25 +91,47 @@ impl CategoryService {
                     level_name: new_level,
                 },
             )
-            .await
+            .await?;
+
+        let after = UserCategory {
+            id_user: user_id,
+            id_category: category_id,
+            user_level: new_level,
+        };
+        self.audit_service
+            .record_update(
+                actor,
+                "UPDATE_USER_CATEGORY_LEVEL",
+                AuditEntity::USER_CATEGORY,
+                format!("{user_id}/{category_id}"),
+                &before,
+                &after,
+            )
+            .await;
+
+        Ok(())
     }
 
     //  delete_category
-    pub async fn delete_category(&self, id: Uuid) -> Result<()> {
+    pub async fn delete_category(&self, actor: &Actor, id: Uuid) -> Result<()> {
+        let before = self.category_repo.get_category_by_id(id).await?;
+
         self.category_repo.delete_category(id).await?;
+
+        self.audit_service
+            .record_deletion(actor, "DELETE_CATEGORY", AuditEntity::CATEGORY, id, &before)
+            .await;
+
         Ok(())
     }
 
-    pub async fn update_category(&self, category: &Category) -> Result<()> {
+    pub async fn update_category(&self, actor: &Actor, category: &Category) -> Result<()> {
         // Validate category exists
-        if self
+        let before = self
             .category_repo
             .get_category_by_id(category.id_category)
             .await?
-            .is_none()
-        {
-            return Err(Error::CategoryNotFound);
-        }
+            .ok_or(Error::CategoryNotFound)?;
 
         // Validate category name
         if category.name.trim().is_empty() {
@@ -88,6 +144,18 @@ impl CategoryService {
         }
 
         self.category_repo.update_category(category).await?;
+
+        self.audit_service
+            .record_update(
+                actor,
+                "UPDATE_CATEGORY",
+                AuditEntity::CATEGORY,
+                category.id_category,
+                &before,
+                category,
+            )
+            .await;
+
         Ok(())
     }
 
@@ -102,7 +170,11 @@ impl CategoryService {
         self.category_repo.list_categories().await
     }
 
-    pub async fn add_category(&self, category_creation: CategoryCreation) -> Result<()> {
+    pub async fn add_category(
+        &self,
+        actor: &Actor,
+        category_creation: CategoryCreation,
+    ) -> Result<()> {
         if self
             .category_repo
             .get_category_by_name(&category_creation.name)
@@ -116,23 +188,71 @@ impl CategoryService {
 
         self.category_repo.create_category(&category).await?;
 
+        self.audit_service
+            .record_creation(
+                actor,
+                "ADD_CATEGORY",
+                AuditEntity::CATEGORY,
+                category.id_category,
+                &category,
+            )
+            .await;
+
         Ok(())
     }
 
-    pub async fn add_category_requirement(&self, category_req: &CategoryRequirement) -> Result<()> {
+    pub async fn add_category_requirement(
+        &self,
+        actor: &Actor,
+        category_req: &CategoryRequirement,
+    ) -> Result<()> {
         self.requirement_repo
             .create_category_requirement(category_req)
-            .await
+            .await?;
+
+        self.audit_service
+            .record_creation(
+                actor,
+                "ADD_CATEGORY_REQUIREMENT",
+                AuditEntity::CATEGORY_REQUIREMENT,
+                format!(
+                    "{}/{}",
+                    category_req.id_category, category_req.id_category_requirement
+                ),
+                category_req,
+            )
+            .await;
+
+        Ok(())
     }
 
     pub async fn delete_category_requirement(
         &self,
+        actor: &Actor,
         category_req_id: &Uuid,
         category_id: &Uuid,
     ) -> Result<()> {
+        let before = self
+            .get_category_requirements(*category_id)
+            .await?
+            .into_iter()
+            .find(|req| req.id_category_requirement == *category_req_id);
+
         self.requirement_repo
             .delete_category_requirement(category_req_id, category_id)
-            .await
+            .await?;
+
+        self.audit_service
+            .record_deletion(
+                actor,
+                "DELETE_CATEGORY_REQUIREMENT",
+                AuditEntity::CATEGORY_REQUIREMENT,
+                format!("{category_id}/{category_req_id}"),
+                &before,
+            )
+            .await;
+
+        Ok(())
     }
 
     pub async fn get_category_requirements(
@@ -166,7 +286,12 @@ impl CategoryService {
         self.user_category_repo.get_user_categories(user_id).await
     }
 
-    pub async fn add_user_to_category(&self, user_id: Uuid, category_id: Uuid) -> Result<()> {
+    pub async fn add_user_to_category(
+        &self,
+        actor: &Actor,
+        user_id: Uuid,
+        category_id: Uuid,
+    ) -> Result<()> {
         if self.user_has_category(user_id, category_id).await? {
             return Err(Error::UserAlreadyHasCategory);
         }
@@ -184,9 +309,57 @@ impl CategoryService {
             .create_user_category(&user_category)
             .await?;
 
+        self.audit_service
+            .record_creation(
+                actor,
+                "ADD_USER_TO_CATEGORY",
+                AuditEntity::USER_CATEGORY,
+                format!("{user_id}/{category_id}"),
+                &user_category,
+            )
+            .await;
+
         Ok(())
     }
 
+    /// Removes the members who grew older than the maximum age of their categories and returns
+    /// how many memberships were removed. The removals are audited as done by `actor`
+    pub async fn remove_overage_members(&self, actor: &Actor) -> Result<usize> {
+        let current_date = Utc::now().naive_utc().date();
+
+        let mut removed = 0;
+        for category in self.category_repo.list_categories().await? {
+            for member in self
+                .user_category_repo
+                .list_category_members(category.id_category)
+                .await?
+            {
+                let user = self.user_service.get_user_by_id(member.id_user).await?;
+                let user_age = current_date.years_since(user.birth_date).unwrap_or(0);
+                if user_age <= category.max_age as u32 {
+                    continue;
+                }
+
+                self.user_category_repo
+                    .delete_user_category(member.id_user, member.id_category)
+                    .await?;
+
+                self.audit_service
+                    .record_deletion(
+                        actor,
+                        "AGE_ROLLOVER",
+                        AuditEntity::USER_CATEGORY,
+                        format!("{}/{}", member.id_user, member.id_category),
+                        &member,
+                    )
+                    .await;
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+
     pub async fn is_user_eligible_for_category(
         &self,
         user_id: Uuid,
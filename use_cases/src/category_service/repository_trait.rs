@@ -51,6 +51,8 @@ pub trait UserCategoryRepository: Send + Sync {
 
     async fn get_user_categories(&self, user_id: Uuid) -> Result<Vec<UserCategory>>;
 
+    async fn list_category_members(&self, id_category: Uuid) -> Result<Vec<UserCategory>>;
+
     async fn update_user_category(
         &self,
         user_id: Uuid,
@@ -0,0 +1,46 @@
+use async_trait::async_trait;
+use entities::audit::Actor;
+use entities::request::{Request, RequestCommand};
+use uuid::Uuid;
+
+use super::CategoryService;
+use crate::request_service::{
+    err::{Error, Result},
+    executor::RequestExecutor,
+};
+
+#[async_trait]
+impl RequestExecutor for CategoryService {
+    fn handles(&self, command: &RequestCommand) -> bool {
+        matches!(command, RequestCommand::CATEGORY_LEVEL_CHANGE(_))
+    }
+
+    async fn validate(&self, _requester_id: Uuid, command: &RequestCommand) -> Result<()> {
+        let RequestCommand::CATEGORY_LEVEL_CHANGE(change) = command else {
+            return Ok(());
+        };
+
+        let has_category = self
+            .user_has_category(change.user_id, change.category_id)
+            .await
+            .map_err(|err| Error::InvalidRequestPayload(err.to_string()))?;
+
+        if !has_category {
+            return Err(Error::InvalidRequestPayload(
+                "The user doesn't belong to the category".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn execute(&self, approver: &Actor, request: &Request) -> Result<()> {
+        let RequestCommand::CATEGORY_LEVEL_CHANGE(change) = &request.requested_command else {
+            return Ok(());
+        };
+
+        self.update_user_category_level(approver, change.user_id, change.category_id, change.level)
+            .await
+            .map_err(|err| Error::ExecutionFailed(err.to_string()))
+    }
+}
@@ -0,0 +1,333 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+use entities::audit::{Actor, AuditEntity};
+use entities::court_attendance::{
+    CourtCancellation, CourtCancellationRequest, CourtCheckIn, CourtNoShow, MemberNoShows,
+};
+use entities::event::DomainEvent;
+use entities::user::URol;
+use uuid::Uuid;
+
+use super::err::{Error, Result};
+use super::CourtService;
+
+/// How far back the no-show job looks for bookings nobody checked in to
+const NO_SHOW_LOOKBACK_DAYS: i64 = 1;
+
+impl CourtService {
+    /// Owners cancel their bookings until they start, paying for them when they cancel after the
+    /// free cancellation period. Admins cancel any reservation for the club, the players of the
+    /// bookings they cancel are notified
+    pub async fn cancel_reservation(
+        &self,
+        actor: &Actor,
+        id_reservation: Uuid,
+        request: CourtCancellationRequest,
+    ) -> Result<CourtCancellation> {
+        let reason = request.reason.trim();
+        if reason.is_empty() {
+            return Err(Error::CancellationReasonMissing);
+        }
+        let reservation = self.get_reservation(id_reservation).await?;
+        let now = Utc::now().naive_utc();
+        if reservation.start_reservation_datetime <= now {
+            return Err(Error::BookingAlreadyStarted);
+        }
+
+        let by_owner = reservation.id_owner == Some(actor.user_id);
+        if !by_owner && actor.user_rol != URol::ADMIN {
+            return Err(match reservation.id_owner {
+                Some(_) => Error::NotBookingOwner,
+                None => Error::NotABooking,
+            });
+        }
+
+        let cancellation = CourtCancellation {
+            id_court_reservation: id_reservation,
+            cancelled_by: actor.user_id,
+            reason: reason.to_string(),
+            cancelled_datetime: now,
+            late: by_owner
+                && is_late_cancellation(
+                    reservation.start_reservation_datetime,
+                    now,
+                    self.booking_policy.free_cancellation_notice,
+                ),
+        };
+        // The players are told when somebody else cancels their booking
+        let event = match reservation.id_owner {
+            Some(_) if !by_owner => {
+                let booking = self.to_booking(reservation.clone()).await?;
+                let court = self.get_court(reservation.id_court).await?;
+                Some(
+                    self.event_service.event(
+                        actor,
+                        DomainEvent::COURT_BOOKING_CANCELLED {
+                            id_court_reservation: id_reservation,
+                            id_court: court.id_court,
+                            court_name: court.court_name,
+                            start_datetime: reservation.start_reservation_datetime,
+                            users: reservation
+                                .id_owner
+                                .into_iter()
+                                .chain(booking.partners)
+                                .collect(),
+                        },
+                    ),
+                )
+            }
+            _ => None,
+        };
+        self.attendance_repo
+            .cancel_reservation(&cancellation, event.as_ref())
+            .await?;
+
+        self.audit_service
+            .record_update(
+                actor,
+                "CANCEL_RESERVATION",
+                AuditEntity::COURT_RESERVATION,
+                id_reservation,
+                &reservation,
+                &cancellation,
+            )
+            .await;
+
+        Ok(cancellation)
+    }
+
+    /// Admins and whoever cancelled the reservation can see why
+    pub async fn get_cancellation(
+        &self,
+        actor: &Actor,
+        id_reservation: Uuid,
+    ) -> Result<CourtCancellation> {
+        let cancellation = self
+            .attendance_repo
+            .get_cancellation(id_reservation)
+            .await?
+            .ok_or(Error::CancellationNotFound)?;
+        if cancellation.cancelled_by != actor.user_id && actor.user_rol != URol::ADMIN {
+            return Err(Error::NotBookingOwner);
+        }
+        Ok(cancellation)
+    }
+
+    /// Any player of the booking checks everyone in, admins can check in for them. Checking in
+    /// twice keeps the first check-in
+    pub async fn check_in(&self, actor: &Actor, id_reservation: Uuid) -> Result<CourtCheckIn> {
+        let reservation = self.get_reservation(id_reservation).await?;
+        if reservation.id_owner.is_none() {
+            return Err(Error::NotABooking);
+        }
+        let booking = self.to_booking(reservation).await?;
+        let is_player = booking.reservation.id_owner == Some(actor.user_id)
+            || booking.partners.contains(&actor.user_id);
+        if !is_player && actor.user_rol != URol::ADMIN {
+            return Err(Error::NotBookingPlayer);
+        }
+
+        let now = Utc::now().naive_utc();
+        let opening =
+            booking.reservation.start_reservation_datetime - self.booking_policy.check_in_opening;
+        if now < opening || now >= booking.reservation.end_reservation_datetime {
+            return Err(Error::CheckInClosed);
+        }
+        if let Some(check_in) = self.attendance_repo.get_check_in(id_reservation).await? {
+            return Ok(check_in);
+        }
+
+        let check_in = CourtCheckIn {
+            id_court_reservation: id_reservation,
+            id_user: actor.user_id,
+            checked_in_datetime: now,
+        };
+        self.attendance_repo.create_check_in(&check_in).await?;
+
+        self.audit_service
+            .record_creation(
+                actor,
+                "CHECK_IN",
+                AuditEntity::COURT_RESERVATION,
+                id_reservation,
+                &check_in,
+            )
+            .await;
+
+        Ok(check_in)
+    }
+
+    /// Counts the bookings that ended without a check-in against their owners
+    pub async fn record_no_shows(&self, actor: &Actor) -> Result<usize> {
+        let now = Utc::now().naive_utc();
+        let unattended = self
+            .attendance_repo
+            .list_unattended_bookings(now - Duration::days(NO_SHOW_LOOKBACK_DAYS), now)
+            .await?;
+
+        let mut recorded = 0;
+        for reservation in unattended {
+            let Some(id_owner) = reservation.id_owner else {
+                continue;
+            };
+            let no_show = CourtNoShow {
+                id_court_reservation: reservation.id_court_reservation,
+                id_user: id_owner,
+                start_datetime: reservation.start_reservation_datetime,
+            };
+            self.attendance_repo.create_no_show(&no_show).await?;
+
+            self.audit_service
+                .record_creation(
+                    actor,
+                    "RECORD_NO_SHOW",
+                    AuditEntity::COURT_RESERVATION,
+                    no_show.id_court_reservation,
+                    &no_show,
+                )
+                .await;
+            recorded += 1;
+        }
+
+        Ok(recorded)
+    }
+
+    pub async fn get_member_no_shows(&self, id_user: Uuid) -> Result<MemberNoShows> {
+        let no_shows = self.attendance_repo.list_no_shows(id_user).await?;
+        let now = Utc::now().naive_utc();
+        let recent = no_shows
+            .iter()
+            .filter(|no_show| no_show.start_datetime > now - self.booking_policy.no_show_window)
+            .count();
+
+        Ok(MemberNoShows {
+            id_user,
+            restricted_until: restricted_until(
+                &no_shows,
+                now,
+                self.booking_policy.no_show_limit,
+                self.booking_policy.no_show_window,
+            ),
+            recent_no_shows: recent as u32,
+            no_shows,
+        })
+    }
+
+    /// Admins forgive a no-show, for example when the court couldn't be played
+    pub async fn waive_no_show(&self, actor: &Actor, id_reservation: Uuid) -> Result<()> {
+        let no_show = self
+            .attendance_repo
+            .get_no_show(id_reservation)
+            .await?
+            .ok_or(Error::NoShowNotFound)?;
+
+        self.attendance_repo.waive_no_show(id_reservation).await?;
+
+        self.audit_service
+            .record_deletion(
+                actor,
+                "WAIVE_NO_SHOW",
+                AuditEntity::COURT_RESERVATION,
+                id_reservation,
+                &no_show,
+            )
+            .await;
+
+        Ok(())
+    }
+
+    pub(super) async fn booking_restriction(
+        &self,
+        id_user: Uuid,
+        now: NaiveDateTime,
+    ) -> Result<Option<NaiveDateTime>> {
+        if self.booking_policy.no_show_limit == 0 {
+            return Ok(None);
+        }
+        let no_shows = self.attendance_repo.list_no_shows(id_user).await?;
+        Ok(restricted_until(
+            &no_shows,
+            now,
+            self.booking_policy.no_show_limit,
+            self.booking_policy.no_show_window,
+        ))
+    }
+}
+
+fn is_late_cancellation(start: NaiveDateTime, now: NaiveDateTime, free_notice: Duration) -> bool {
+    start - now < free_notice
+}
+
+/// When the member gets below the no-show limit again, empty when they already are. The
+/// no-shows are sorted newest first
+fn restricted_until(
+    no_shows: &[CourtNoShow],
+    now: NaiveDateTime,
+    limit: u32,
+    window: Duration,
+) -> Option<NaiveDateTime> {
+    let limit = usize::try_from(limit).ok().filter(|limit| *limit > 0)?;
+    let recent: Vec<NaiveDateTime> = no_shows
+        .iter()
+        .map(|no_show| no_show.start_datetime)
+        .filter(|start| *start > now - window)
+        .collect();
+
+    recent.get(limit - 1).map(|start| *start + window)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn day(day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 6, day)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap()
+    }
+
+    fn no_show(start_datetime: NaiveDateTime) -> CourtNoShow {
+        CourtNoShow {
+            id_court_reservation: Uuid::new_v4(),
+            id_user: Uuid::new_v4(),
+            start_datetime,
+        }
+    }
+
+    #[test]
+    fn test_late_cancellation() {
+        let notice = Duration::hours(24);
+
+        assert!(!is_late_cancellation(day(3), day(1), notice));
+        assert!(!is_late_cancellation(day(2), day(1), notice));
+        assert!(is_late_cancellation(
+            day(2),
+            day(1) + Duration::minutes(1),
+            notice
+        ));
+    }
+
+    #[test]
+    fn test_restriction_lifts_when_the_oldest_no_show_leaves_the_window() {
+        let window = Duration::days(30);
+        let no_shows = [no_show(day(20)), no_show(day(10)), no_show(day(5))];
+
+        assert_eq!(
+            restricted_until(&no_shows, day(21), 3, window),
+            Some(day(5) + window)
+        );
+        assert_eq!(restricted_until(&no_shows, day(21), 4, window), None);
+        assert_eq!(restricted_until(&no_shows, day(21), 0, window), None);
+        // Once the first one is older than the window the member can book again
+        assert_eq!(
+            restricted_until(&no_shows, day(5) + window, 3, window),
+            None
+        );
+        assert_eq!(
+            restricted_until(&no_shows, day(5) + window, 2, window),
+            Some(day(10) + window)
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use chrono::Duration;
+
+const DEFAULT_MAX_DURATION_MINUTES: i64 = 90;
+const DEFAULT_MAX_ADVANCE_DAYS: i64 = 14;
+const DEFAULT_MAX_ACTIVE_BOOKINGS: u32 = 2;
+const DEFAULT_MAX_PARTNERS: usize = 3;
+const DEFAULT_EVENT_PRIORITY_NOTICE_HOURS: i64 = 48;
+const DEFAULT_PAYMENT_TIMEOUT_MINUTES: i64 = 30;
+const DEFAULT_FREE_CANCELLATION_HOURS: i64 = 24;
+const DEFAULT_CHECK_IN_OPENING_MINUTES: i64 = 15;
+const DEFAULT_NO_SHOW_LIMIT: u32 = 3;
+const DEFAULT_NO_SHOW_WINDOW_DAYS: i64 = 30;
+
+/// Rules members have to follow when they book a court on their own, and how reservations are
+/// spaced on a court
+#[derive(Debug, Clone)]
+pub struct BookingPolicy {
+    /// Longest a single booking can last
+    pub max_duration: Duration,
+    /// How far ahead a court can be booked
+    pub max_advance: Duration,
+    /// Upcoming bookings a member can own at the same time
+    pub max_active_bookings: u32,
+    pub max_partners: usize,
+    /// Club events displace the member bookings on their court only when they are created at
+    /// least this long before they start, closer to the start the booking is kept
+    pub event_priority_notice: Duration,
+    /// Time kept free around every reservation of a court, for changeovers and maintenance.
+    /// Applies to club events too
+    pub reservation_buffer: Duration,
+    /// How long a booking with a price is held before it has to be paid. Bookings starting
+    /// sooner have to be paid before they start
+    pub payment_timeout: Duration,
+    /// Owners cancelling closer to the start than this keep paying for the booking
+    pub free_cancellation_notice: Duration,
+    /// How long before the start players can check in, they can until the booking ends
+    pub check_in_opening: Duration,
+    /// No-shows within the window that keep a member from booking, zero disables the
+    /// restriction. The member can book again once the oldest of them leaves the window
+    pub no_show_limit: u32,
+    pub no_show_window: Duration,
+}
+
+impl Default for BookingPolicy {
+    fn default() -> Self {
+        Self {
+            max_duration: Duration::minutes(DEFAULT_MAX_DURATION_MINUTES),
+            max_advance: Duration::days(DEFAULT_MAX_ADVANCE_DAYS),
+            max_active_bookings: DEFAULT_MAX_ACTIVE_BOOKINGS,
+            max_partners: DEFAULT_MAX_PARTNERS,
+            event_priority_notice: Duration::hours(DEFAULT_EVENT_PRIORITY_NOTICE_HOURS),
+            reservation_buffer: Duration::zero(),
+            payment_timeout: Duration::minutes(DEFAULT_PAYMENT_TIMEOUT_MINUTES),
+            free_cancellation_notice: Duration::hours(DEFAULT_FREE_CANCELLATION_HOURS),
+            check_in_opening: Duration::minutes(DEFAULT_CHECK_IN_OPENING_MINUTES),
+            no_show_limit: DEFAULT_NO_SHOW_LIMIT,
+            no_show_window: Duration::days(DEFAULT_NO_SHOW_WINDOW_DAYS),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use chrono::NaiveDateTime;
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -22,4 +23,68 @@ pub enum Error {
     ReservationPurposeConflict,
     #[error("Can't delete a court if reservation already exists")]
     ReservationExists,
+    #[error("Member bookings can't be linked to a training or a tournament")]
+    BookingPurposeConflict,
+    #[error("Court can't be booked in the past or that far ahead")]
+    BookingOutsideWindow,
+    #[error("Booking is longer than allowed")]
+    BookingTooLong,
+    #[error("Invalid partners: {0}")]
+    InvalidPartners(String),
+    #[error("Member already has the maximum number of upcoming bookings")]
+    BookingQuotaExceeded,
+    #[error("Reservation is not a member booking")]
+    NotABooking,
+    #[error("Only the owner of the booking can cancel it")]
+    NotBookingOwner,
+    #[error("Booking already started")]
+    BookingAlreadyStarted,
+    #[error("Court is not open at the given time")]
+    OutsideOpeningHours,
+    #[error("Court is closed: {0}")]
+    CourtClosed(String),
+    #[error("Invalid opening hours: {0}")]
+    InvalidOpeningHours(String),
+    #[error("Closure not found")]
+    ClosureNotFound,
+    #[error("Court is reserved for a training or a tournament during the closure")]
+    ClosureConflict,
+    #[error("Invalid slot search: {0}")]
+    InvalidSlotSearch(String),
+    #[error("Court is not active")]
+    CourtInactive,
+    #[error("No court matching the requirements is available")]
+    NoMatchingCourtAvailable,
+    #[error("Invalid court blocks: {0}")]
+    InvalidCourtBlocks(String),
+    #[error("Invalid price rule: {0}")]
+    InvalidPriceRule(String),
+    #[error("Price rule overlaps another one of the same court and day")]
+    PriceRuleOverlap,
+    #[error("Price rule not found")]
+    PriceRuleNotFound,
+    #[error("Court has no lighting")]
+    CourtWithoutLights,
+    #[error("Charge not found")]
+    ChargeNotFound,
+    #[error("Only the member who owes the charge can pay it")]
+    NotChargeOwner,
+    #[error("Charge is not pending")]
+    ChargeNotPending,
+    #[error("Charge was due before it was paid")]
+    ChargeExpired,
+    #[error("A reason is needed to cancel a reservation")]
+    CancellationReasonMissing,
+    #[error("Cancellation not found")]
+    CancellationNotFound,
+    #[error("Only the players of the booking can check in")]
+    NotBookingPlayer,
+    #[error("Check-in is only open around the booking")]
+    CheckInClosed,
+    #[error("No-show not found")]
+    NoShowNotFound,
+    #[error("Member can't book until {0} after repeated no-shows")]
+    BookingRestricted(NaiveDateTime),
+    #[error("Error in tuition service: {0}")]
+    TuitionServiceError(#[from] crate::tuition_service::err::Error),
 }
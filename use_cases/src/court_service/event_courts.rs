@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use entities::audit::{Actor, AuditEntity};
+use entities::court::{
+    CourtReservation, CourtReservationCreation, CourtSelection, EventCourtBlock,
+};
+use entities::event::OutboxEvent;
+use uuid::Uuid;
+
+use super::err::{Error, Result};
+use super::CourtService;
+
+/// Court and time range of a reservation
+type Block = (Uuid, NaiveDateTime, NaiveDateTime);
+
+impl CourtService {
+    /// Every court the event holds, sorted by start. The event type is "training" or
+    /// "tournament"
+    pub async fn get_reservations_for_event(
+        &self,
+        event_id: Uuid,
+        event_type: &str,
+    ) -> Result<Vec<CourtReservation>> {
+        match event_type {
+            "training" => self.get_reservations_for_training(event_id).await,
+            "tournament" => self.get_reservations_for_tournament(event_id).await,
+            _ => Err(Error::ReservationPurposeMissing),
+        }
+    }
+
+    /// Replaces the courts of a training or a tournament with the selected ones as a single
+    /// operation, without a selection the event releases them. Nothing changes when the event
+    /// already holds the same blocks
+    pub async fn set_reservations_for_event(
+        &self,
+        actor: &Actor,
+        event_id: Uuid,
+        event_type: &str,
+        event_start: NaiveDateTime,
+        event_end: NaiveDateTime,
+        selection: Option<&CourtSelection>,
+    ) -> Result<Vec<CourtReservation>> {
+        let (id_training, id_tournament) = match event_type {
+            "training" => (Some(event_id), None),
+            "tournament" => (None, Some(event_id)),
+            _ => return Err(Error::ReservationPurposeMissing),
+        };
+        let current = self
+            .get_reservations_for_event(event_id, event_type)
+            .await?;
+
+        let blocks = match selection {
+            None => Vec::new(),
+            Some(CourtSelection::ById(id_court)) => vec![(*id_court, event_start, event_end)],
+            Some(CourtSelection::ByRequirements(requirements)) => {
+                let id_court = self
+                    .select_court(requirements, event_start, event_end, &current)
+                    .await?;
+                vec![(id_court, event_start, event_end)]
+            }
+            Some(CourtSelection::ByBlocks(blocks)) => {
+                resolve_blocks(blocks, event_start, event_end)?
+            }
+        };
+
+        let mut current_blocks: Vec<Block> = current
+            .iter()
+            .map(|r| {
+                (
+                    r.id_court,
+                    r.start_reservation_datetime,
+                    r.end_reservation_datetime,
+                )
+            })
+            .collect();
+        current_blocks.sort_by_key(|&(id_court, start, _)| (start, id_court));
+        if current_blocks == blocks {
+            return Ok(current);
+        }
+
+        let current_ids: Vec<Uuid> = current.iter().map(|r| r.id_court_reservation).collect();
+        let mut reservations = Vec::with_capacity(blocks.len());
+        let mut displaced = HashMap::new();
+        for (id_court, start, end) in blocks {
+            let reservation_creation = CourtReservationCreation {
+                id_court,
+                start_reservation_datetime: start,
+                end_reservation_datetime: end,
+                id_training,
+                id_tournament,
+                id_owner: None,
+            };
+            let (court, bookings) = self
+                .plan_event_reservation(&reservation_creation, &current_ids)
+                .await?;
+            for booking in bookings {
+                displaced
+                    .entry(booking.reservation.id_court_reservation)
+                    .or_insert((court.clone(), booking));
+            }
+            reservations.push(reservation_creation.to_court_reservation(Uuid::new_v4()));
+        }
+
+        let replaced: Vec<Uuid> = current_ids
+            .iter()
+            .chain(displaced.keys())
+            .copied()
+            .collect();
+        let events: Vec<OutboxEvent> = displaced
+            .values()
+            .map(|(court, booking)| self.displaced_booking_event(actor, court, booking))
+            .collect();
+        self.reservation_repo
+            .create_reservations_replacing(&reservations, &replaced, &events)
+            .await?;
+
+        for reservation in &current {
+            self.audit_service
+                .record_deletion(
+                    actor,
+                    "DELETE_RESERVATION",
+                    AuditEntity::COURT_RESERVATION,
+                    reservation.id_court_reservation,
+                    reservation,
+                )
+                .await;
+        }
+        for reservation in &reservations {
+            self.audit_service
+                .record_creation(
+                    actor,
+                    "CREATE_RESERVATION",
+                    AuditEntity::COURT_RESERVATION,
+                    reservation.id_court_reservation,
+                    reservation,
+                )
+                .await;
+        }
+        for (_, booking) in displaced.values() {
+            self.audit_displaced_booking(actor, booking).await;
+        }
+
+        Ok(reservations)
+    }
+}
+
+/// Fills the blocks with the event times, sorted by start. Blocks stay within the event and
+/// the ones of the same court can't overlap
+fn resolve_blocks(
+    blocks: &[EventCourtBlock],
+    event_start: NaiveDateTime,
+    event_end: NaiveDateTime,
+) -> Result<Vec<Block>> {
+    let mut resolved = Vec::with_capacity(blocks.len());
+    for block in blocks {
+        let start = block.start_reservation_datetime.unwrap_or(event_start);
+        let end = block.end_reservation_datetime.unwrap_or(event_end);
+        if start >= end {
+            return Err(Error::InvalidReservationTime);
+        }
+        if start < event_start || end > event_end {
+            return Err(Error::InvalidCourtBlocks(
+                "blocks must be within the event".to_string(),
+            ));
+        }
+        resolved.push((block.id_court, start, end));
+    }
+    resolved.sort_by_key(|&(id_court, start, _)| (start, id_court));
+
+    for (i, &(id_court, _, end)) in resolved.iter().enumerate() {
+        if resolved[i + 1..]
+            .iter()
+            .any(|&(other_court, other_start, _)| other_court == id_court && other_start < end)
+        {
+            return Err(Error::InvalidCourtBlocks(
+                "blocks of the same court overlap".to_string(),
+            ));
+        }
+    }
+
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDate};
+
+    use super::*;
+
+    fn at(hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 6, 7)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    fn block(id_court: Uuid, start: Option<u32>, end: Option<u32>) -> EventCourtBlock {
+        EventCourtBlock {
+            id_court,
+            start_reservation_datetime: start.map(at),
+            end_reservation_datetime: end.map(at),
+        }
+    }
+
+    #[test]
+    fn test_resolve_blocks_defaults_to_the_event() {
+        let (court_1, court_2) = (Uuid::new_v4(), Uuid::new_v4());
+        let blocks = [
+            block(court_2, Some(12), None),
+            block(court_1, None, None),
+            block(court_2, Some(10), Some(12)),
+        ];
+
+        assert_eq!(
+            resolve_blocks(&blocks, at(9), at(14)),
+            Ok(vec![
+                (court_1, at(9), at(14)),
+                (court_2, at(10), at(12)),
+                (court_2, at(12), at(14)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_resolve_blocks_rejects_invalid_blocks() {
+        let id_court = Uuid::new_v4();
+
+        let outside = [block(id_court, Some(8), None)];
+        assert!(matches!(
+            resolve_blocks(&outside, at(9), at(14)),
+            Err(Error::InvalidCourtBlocks(_))
+        ));
+
+        let overlapping = [
+            block(id_court, None, Some(11)),
+            block(id_court, Some(10), None),
+        ];
+        assert!(matches!(
+            resolve_blocks(&overlapping, at(9), at(14)),
+            Err(Error::InvalidCourtBlocks(_))
+        ));
+
+        let reversed = [block(id_court, Some(12), Some(10))];
+        assert_eq!(
+            resolve_blocks(&reversed, at(9), at(14)),
+            Err(Error::InvalidReservationTime)
+        );
+        assert_eq!(
+            resolve_blocks(&[], at(9), at(9) + Duration::hours(1)),
+            Ok(vec![])
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use entities::event::{DomainEvent, EventKind, OutboxEvent};
+
+use super::CourtService;
+use crate::event_service::{
+    err::{Error, Result},
+    subscriber_trait::EventSubscriber,
+};
+
+/// Releases the court reserved for an event once the event is cancelled
+#[async_trait]
+impl EventSubscriber for CourtService {
+    fn name(&self) -> &'static str {
+        "court_reservations"
+    }
+
+    fn handles(&self, kind: EventKind) -> bool {
+        matches!(
+            kind,
+            EventKind::TRAINING_CANCELLED | EventKind::TOURNAMENT_CANCELLED
+        )
+    }
+
+    async fn handle(&self, event: &OutboxEvent) -> Result<()> {
+        let (event_id, event_type) = match &event.event {
+            DomainEvent::TRAINING_CANCELLED { id_training, .. } => (*id_training, "training"),
+            DomainEvent::TOURNAMENT_CANCELLED { id_tournament } => (*id_tournament, "tournament"),
+            _ => return Ok(()),
+        };
+
+        self.delete_reservation_for_event(&event.actor, event_id, event_type)
+            .await
+            .map_err(|err| Error::SubscriberFailed(err.to_string()))
+    }
+}
@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use entities::audit::Actor;
+
+use super::CourtService;
+use crate::job_service::{
+    err::{Error, Result},
+    job_trait::Job,
+};
+
+/// Releases the bookings that weren't paid in time, so other members can book the court
+pub struct CourtPaymentExpiryJob(pub CourtService);
+
+#[async_trait]
+impl Job for CourtPaymentExpiryJob {
+    fn name(&self) -> &'static str {
+        "court_payment_expiry"
+    }
+
+    fn schedule(&self) -> &'static str {
+        "*/5 * * * *"
+    }
+
+    async fn run(&self, actor: &Actor) -> Result<String> {
+        let released = self
+            .0
+            .release_unpaid_bookings(actor)
+            .await
+            .map_err(|err| Error::JobFailed(err.to_string()))?;
+
+        Ok(format!("{released} unpaid bookings released"))
+    }
+}
+
+/// Counts the bookings nobody checked in to against their owners
+pub struct CourtNoShowJob(pub CourtService);
+
+#[async_trait]
+impl Job for CourtNoShowJob {
+    fn name(&self) -> &'static str {
+        "court_no_shows"
+    }
+
+    fn schedule(&self) -> &'static str {
+        "*/15 * * * *"
+    }
+
+    async fn run(&self, actor: &Actor) -> Result<String> {
+        let recorded = self
+            .0
+            .record_no_shows(actor)
+            .await
+            .map_err(|err| Error::JobFailed(err.to_string()))?;
+
+        Ok(format!("{recorded} no-shows recorded"))
+    }
+}
@@ -1,32 +1,82 @@
+mod attendance;
+pub mod booking_policy;
 pub mod err;
+mod event_courts;
+mod event_subscriber;
+pub mod jobs;
+mod pricing;
 pub mod repository_trait;
+mod request_executor;
+mod schedule;
+mod slots;
 
-use chrono::NaiveDateTime;
-use entities::court::{Court, CourtCreation, CourtReservation, CourtReservationCreation};
-use repository_trait::{CourtRepository, CourtReservationRepository};
+use booking_policy::BookingPolicy;
+use chrono::{NaiveDateTime, Utc};
+use entities::audit::{Actor, AuditEntity};
+use entities::court::{
+    Court, CourtBooking, CourtBookingCreation, CourtCreation, CourtFilter, CourtRequirements,
+    CourtReservation, CourtReservationCreation,
+};
+use entities::event::{DomainEvent, OutboxEvent};
+use entities::pagination::{Page, PageQuery};
+use repository_trait::{
+    CourtAttendanceRepository, CourtPricingRepository, CourtRepository, CourtReservationRepository,
+    CourtScheduleRepository,
+};
+use std::collections::HashSet;
+use std::slice;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use self::err::{Error, Result};
+use crate::{
+    audit_service::AuditService, event_service::EventService, tuition_service::TuitionService,
+};
 
 #[derive(Clone)]
 pub struct CourtService {
     court_repo: Arc<dyn CourtRepository>,
     reservation_repo: Arc<dyn CourtReservationRepository>,
+    schedule_repo: Arc<dyn CourtScheduleRepository>,
+    pricing_repo: Arc<dyn CourtPricingRepository>,
+    attendance_repo: Arc<dyn CourtAttendanceRepository>,
+    tuition_service: TuitionService,
+    event_service: EventService,
+    audit_service: AuditService,
+    booking_policy: BookingPolicy,
 }
 
 impl CourtService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         court_repo: Arc<dyn CourtRepository>,
         reservation_repo: Arc<dyn CourtReservationRepository>,
+        schedule_repo: Arc<dyn CourtScheduleRepository>,
+        pricing_repo: Arc<dyn CourtPricingRepository>,
+        attendance_repo: Arc<dyn CourtAttendanceRepository>,
+        tuition_service: TuitionService,
+        event_service: EventService,
+        audit_service: AuditService,
+        booking_policy: BookingPolicy,
     ) -> Self {
         Self {
             court_repo,
             reservation_repo,
+            schedule_repo,
+            pricing_repo,
+            attendance_repo,
+            tuition_service,
+            event_service,
+            audit_service,
+            booking_policy,
         }
     }
 
-    pub async fn create_court(&self, court_creation: CourtCreation) -> Result<Court> {
+    pub async fn create_court(
+        &self,
+        actor: &Actor,
+        court_creation: CourtCreation,
+    ) -> Result<Court> {
         if self
             .court_repo
             .get_court_by_name(&court_creation.court_name)
@@ -38,9 +88,93 @@ impl CourtService {
         let court_id = Uuid::new_v4();
         let court = court_creation.to_court(court_id);
         self.court_repo.create_court(&court).await?;
+
+        self.audit_service
+            .record_creation(
+                actor,
+                "CREATE_COURT",
+                AuditEntity::COURT,
+                court.id_court,
+                &court,
+            )
+            .await;
+
+        Ok(court)
+    }
+
+    pub async fn update_court(
+        &self,
+        actor: &Actor,
+        id_court: Uuid,
+        court_update: CourtCreation,
+    ) -> Result<Court> {
+        let before = self.get_court(id_court).await?;
+
+        if let Some(existing) = self
+            .court_repo
+            .get_court_by_name(&court_update.court_name)
+            .await?
+        {
+            if existing.id_court != id_court {
+                return Err(Error::CourtNameExists);
+            }
+        }
+
+        let court = court_update.to_court(id_court);
+        self.court_repo.update_court(&court).await?;
+
+        self.audit_service
+            .record_update(
+                actor,
+                "UPDATE_COURT",
+                AuditEntity::COURT,
+                id_court,
+                &before,
+                &court,
+            )
+            .await;
+
         Ok(court)
     }
 
+    /// Active court to reserve for an event. With requirements, a court the event already
+    /// holds is kept when it still matches and is free
+    pub async fn select_court(
+        &self,
+        requirements: &CourtRequirements,
+        start_time: NaiveDateTime,
+        end_time: NaiveDateTime,
+        current_reservations: &[CourtReservation],
+    ) -> Result<Uuid> {
+        let mut candidates: Vec<Court> = self
+            .court_repo
+            .list_courts()
+            .await?
+            .into_iter()
+            .filter(|court| court.active && requirements.matches(court))
+            .collect();
+        candidates.sort_by_key(|court| {
+            !current_reservations
+                .iter()
+                .any(|r| r.id_court == court.id_court)
+        });
+
+        let excluded: Vec<Uuid> = current_reservations
+            .iter()
+            .map(|r| r.id_court_reservation)
+            .collect();
+        for court in candidates {
+            if self
+                .is_court_available(court.id_court, start_time, end_time, &excluded)
+                .await?
+            {
+                return Ok(court.id_court);
+            }
+        }
+
+        Err(Error::NoMatchingCourtAvailable)
+    }
+
     pub async fn get_court(&self, id_court: Uuid) -> Result<Court> {
         self.court_repo
             .get_court_by_id(id_court)
@@ -52,7 +186,15 @@ impl CourtService {
         self.court_repo.list_courts().await
     }
 
-    pub async fn delete_court(&self, id_court: Uuid) -> Result<()> {
+    pub async fn list_courts_page(
+        &self,
+        filter: &CourtFilter,
+        page: &PageQuery,
+    ) -> Result<Page<Court>> {
+        self.court_repo.list_courts_page(filter, page).await
+    }
+
+    pub async fn delete_court(&self, actor: &Actor, id_court: Uuid) -> Result<()> {
         let reservation_exists = self
             .reservation_repo
             .court_has_reservations(id_court)
@@ -62,24 +204,83 @@ impl CourtService {
             return Err(Error::ReservationExists);
         }
 
-        self.court_repo.delete_court(id_court).await
+        let before = self.court_repo.get_court_by_id(id_court).await?;
+
+        self.court_repo.delete_court(id_court).await?;
+
+        self.audit_service
+            .record_deletion(actor, "DELETE_COURT", AuditEntity::COURT, id_court, &before)
+            .await;
+
+        Ok(())
     }
 
     pub async fn create_reservation(
         &self,
+        actor: &Actor,
         reservation_creation: CourtReservationCreation,
     ) -> Result<CourtReservation> {
+        let (court, displaced_bookings) = self
+            .plan_event_reservation(&reservation_creation, &[])
+            .await?;
+        let displaced_ids: Vec<Uuid> = displaced_bookings
+            .iter()
+            .map(|booking| booking.reservation.id_court_reservation)
+            .collect();
+
+        let events: Vec<OutboxEvent> = displaced_bookings
+            .iter()
+            .map(|booking| self.displaced_booking_event(actor, &court, booking))
+            .collect();
+
+        let reservation_id = Uuid::new_v4();
+        let reservation = reservation_creation.to_court_reservation(reservation_id);
+        self.reservation_repo
+            .create_reservations_replacing(slice::from_ref(&reservation), &displaced_ids, &events)
+            .await?;
+
+        self.audit_service
+            .record_creation(
+                actor,
+                "CREATE_RESERVATION",
+                AuditEntity::COURT_RESERVATION,
+                reservation.id_court_reservation,
+                &reservation,
+            )
+            .await;
+
+        for booking in &displaced_bookings {
+            self.audit_displaced_booking(actor, booking).await;
+        }
+
+        Ok(reservation)
+    }
+
+    /// Checks the reservation of a club event and finds the member bookings it takes over.
+    /// The excluded reservations are the ones the event gives up at the same time
+    async fn plan_event_reservation(
+        &self,
+        reservation_creation: &CourtReservationCreation,
+        excluded: &[Uuid],
+    ) -> Result<(Court, Vec<CourtBooking>)> {
         if reservation_creation.start_reservation_datetime
             >= reservation_creation.end_reservation_datetime
         {
             return Err(Error::InvalidReservationTime);
         }
 
-        let _ = self
+        if reservation_creation.id_owner.is_some() {
+            return Err(Error::BookingPurposeConflict);
+        }
+
+        let court = self
             .court_repo
             .get_court_by_id(reservation_creation.id_court)
             .await?
             .ok_or(Error::CourtNotFound)?;
+        if !court.active {
+            return Err(Error::CourtInactive);
+        }
 
         match (
             reservation_creation.id_training,
@@ -87,46 +288,231 @@ impl CourtService {
         ) {
             (Some(_), Some(_)) => return Err(Error::ReservationPurposeConflict),
             (None, None) => return Err(Error::ReservationPurposeMissing),
-            (Some(training_id), None) => {
-                if self
-                    .reservation_repo
-                    .get_reservation_for_training(training_id)
-                    .await?
-                    .is_some()
-                {
-                    return Err(Error::CourtUnavailable);
-                }
-            }
-            (None, Some(tournament_id)) => {
-                if self
-                    .reservation_repo
-                    .get_reservation_for_tournament(tournament_id)
-                    .await?
-                    .is_some()
-                {
-                    return Err(Error::CourtUnavailable);
-                }
-            }
+            _ => {}
         }
 
-        if !self
-            .is_court_available(
+        self.ensure_court_open(
+            reservation_creation.id_court,
+            reservation_creation.start_reservation_datetime,
+            reservation_creation.end_reservation_datetime,
+        )
+        .await?;
+
+        // Club events take the court over member bookings when they are planned with enough
+        // notice, never over another club event
+        let overlapping = self
+            .overlapping_reservations(
                 reservation_creation.id_court,
                 reservation_creation.start_reservation_datetime,
                 reservation_creation.end_reservation_datetime,
-                None,
+                excluded,
             )
+            .await?;
+        let priority_deadline = Utc::now().naive_utc() + self.booking_policy.event_priority_notice;
+        let displaces_bookings =
+            reservation_creation.start_reservation_datetime >= priority_deadline;
+        if overlapping
+            .iter()
+            .any(|r| r.id_owner.is_none() || !displaces_bookings)
+        {
+            return Err(Error::CourtUnavailable);
+        }
+
+        let mut displaced_bookings = Vec::with_capacity(overlapping.len());
+        for booking in overlapping {
+            displaced_bookings.push(self.to_booking(booking).await?);
+        }
+
+        Ok((court, displaced_bookings))
+    }
+
+    /// Cancels a member booking in favour of a closure and lets its players know
+    async fn displace_booking(
+        &self,
+        actor: &Actor,
+        court: &Court,
+        reservation: CourtReservation,
+    ) -> Result<()> {
+        let booking = self.to_booking(reservation).await?;
+        let event = self.displaced_booking_event(actor, court, &booking);
+        self.reservation_repo
+            .delete_reservation(booking.reservation.id_court_reservation, Some(&event))
+            .await?;
+
+        self.audit_displaced_booking(actor, &booking).await;
+        Ok(())
+    }
+
+    /// Lets the players of a booking taken over by a closure or a club event know
+    fn displaced_booking_event(
+        &self,
+        actor: &Actor,
+        court: &Court,
+        booking: &CourtBooking,
+    ) -> OutboxEvent {
+        let users = booking
+            .reservation
+            .id_owner
+            .into_iter()
+            .chain(booking.partners.iter().copied())
+            .collect();
+        self.event_service.event(
+            actor,
+            DomainEvent::COURT_BOOKING_CANCELLED {
+                id_court_reservation: booking.reservation.id_court_reservation,
+                id_court: court.id_court,
+                court_name: court.court_name.clone(),
+                start_datetime: booking.reservation.start_reservation_datetime,
+                users,
+            },
+        )
+    }
+
+    async fn audit_displaced_booking(&self, actor: &Actor, booking: &CourtBooking) {
+        self.audit_service
+            .record_deletion(
+                actor,
+                "DISPLACE_BOOKING",
+                AuditEntity::COURT_RESERVATION,
+                booking.reservation.id_court_reservation,
+                booking,
+            )
+            .await;
+    }
+
+    /// Books a court for the actor, following the booking policy. Members book for themselves,
+    /// the partners only play along
+    pub async fn create_booking(
+        &self,
+        actor: &Actor,
+        booking_creation: CourtBookingCreation,
+    ) -> Result<CourtBooking> {
+        let start = booking_creation.start_reservation_datetime;
+        let end = booking_creation.end_reservation_datetime;
+        if start >= end {
+            return Err(Error::InvalidReservationTime);
+        }
+
+        let now = Utc::now().naive_utc();
+        if start < now || start > now + self.booking_policy.max_advance {
+            return Err(Error::BookingOutsideWindow);
+        }
+        if end - start > self.booking_policy.max_duration {
+            return Err(Error::BookingTooLong);
+        }
+        self.validate_partners(actor.user_id, &booking_creation.partners)?;
+
+        let court = self.get_court(booking_creation.id_court).await?;
+        if !court.active {
+            return Err(Error::CourtInactive);
+        }
+        if booking_creation.lights && !court.has_lighting {
+            return Err(Error::CourtWithoutLights);
+        }
+
+        if let Some(restricted_until) = self.booking_restriction(actor.user_id, now).await? {
+            return Err(Error::BookingRestricted(restricted_until));
+        }
+        let active_bookings = self
+            .reservation_repo
+            .count_active_bookings(actor.user_id, now)
+            .await?;
+        if active_bookings >= self.booking_policy.max_active_bookings {
+            return Err(Error::BookingQuotaExceeded);
+        }
+
+        self.ensure_court_open(booking_creation.id_court, start, end)
+            .await?;
+        if !self
+            .overlapping_reservations(booking_creation.id_court, start, end, &[])
             .await?
+            .is_empty()
         {
             return Err(Error::CourtUnavailable);
         }
 
-        let reservation_id = Uuid::new_v4();
-        let reservation = reservation_creation.to_court_reservation(reservation_id);
+        let reservation = CourtReservation {
+            id_court_reservation: Uuid::new_v4(),
+            id_court: booking_creation.id_court,
+            start_reservation_datetime: start,
+            end_reservation_datetime: end,
+            id_training: None,
+            id_tournament: None,
+            id_owner: Some(actor.user_id),
+        };
+        let charge = self
+            .charge_for_booking(&reservation, booking_creation.lights, now)
+            .await?;
         self.reservation_repo
-            .create_reservation(&reservation)
+            .create_booking(&reservation, &booking_creation.partners, charge.as_ref())
             .await?;
-        Ok(reservation)
+
+        let booking = CourtBooking {
+            reservation,
+            partners: booking_creation.partners,
+            charge,
+        };
+
+        self.audit_service
+            .record_creation(
+                actor,
+                "CREATE_BOOKING",
+                AuditEntity::COURT_RESERVATION,
+                booking.reservation.id_court_reservation,
+                &booking,
+            )
+            .await;
+
+        Ok(booking)
+    }
+
+    fn validate_partners(&self, id_owner: Uuid, partners: &[Uuid]) -> Result<()> {
+        if partners.len() > self.booking_policy.max_partners {
+            return Err(Error::InvalidPartners(format!(
+                "at most {} partners are allowed",
+                self.booking_policy.max_partners
+            )));
+        }
+        if partners.contains(&id_owner) {
+            return Err(Error::InvalidPartners(
+                "the owner can't be a partner".to_string(),
+            ));
+        }
+        let unique_partners: HashSet<&Uuid> = partners.iter().collect();
+        if unique_partners.len() != partners.len() {
+            return Err(Error::InvalidPartners("partners are repeated".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Upcoming bookings the member owns or plays in
+    pub async fn list_member_bookings(&self, id_user: Uuid) -> Result<Vec<CourtBooking>> {
+        let reservations = self
+            .reservation_repo
+            .list_member_bookings(id_user, Utc::now().naive_utc())
+            .await?;
+
+        let mut bookings = Vec::with_capacity(reservations.len());
+        for reservation in reservations {
+            bookings.push(self.to_booking(reservation).await?);
+        }
+        Ok(bookings)
+    }
+
+    async fn to_booking(&self, reservation: CourtReservation) -> Result<CourtBooking> {
+        let partners = self
+            .reservation_repo
+            .get_booking_partners(reservation.id_court_reservation)
+            .await?;
+        let charge = self
+            .pricing_repo
+            .get_charge_for_reservation(reservation.id_court_reservation)
+            .await?;
+        Ok(CourtBooking {
+            reservation,
+            partners,
+            charge,
+        })
     }
 
     pub async fn get_reservation(&self, id_reservation: Uuid) -> Result<CourtReservation> {
@@ -154,54 +540,95 @@ impl CourtService {
             .await
     }
 
+    /// Open and free of other reservations for the whole range
     pub async fn is_court_available(
         &self,
         id_court: Uuid,
         start_time: NaiveDateTime,
         end_time: NaiveDateTime,
-        exclude_reservation_id: Option<Uuid>,
+        excluded: &[Uuid],
     ) -> Result<bool> {
-        let overlapping_reservations = self
+        match self.ensure_court_open(id_court, start_time, end_time).await {
+            Ok(()) => {}
+            Err(Error::OutsideOpeningHours | Error::CourtClosed(_)) => return Ok(false),
+            Err(err) => return Err(err),
+        }
+
+        Ok(self
+            .overlapping_reservations(id_court, start_time, end_time, excluded)
+            .await?
+            .is_empty())
+    }
+
+    /// Reservations closer to the range than the reservation buffer
+    async fn overlapping_reservations(
+        &self,
+        id_court: Uuid,
+        start_time: NaiveDateTime,
+        end_time: NaiveDateTime,
+        excluded: &[Uuid],
+    ) -> Result<Vec<CourtReservation>> {
+        let start_time = start_time - self.booking_policy.reservation_buffer;
+        let end_time = end_time + self.booking_policy.reservation_buffer;
+        let reservations = self
             .reservation_repo
             .get_reservations_for_court_in_range(id_court, start_time, end_time)
             .await?;
 
-        for r in overlapping_reservations {
-            if Some(r.id_court_reservation) == exclude_reservation_id {
-                continue;
-            }
-            if start_time < r.end_reservation_datetime && end_time > r.start_reservation_datetime {
-                return Ok(false);
-            }
-        }
-        Ok(true)
+        Ok(reservations
+            .into_iter()
+            .filter(|r| !excluded.contains(&r.id_court_reservation))
+            .filter(|r| {
+                start_time < r.end_reservation_datetime && end_time > r.start_reservation_datetime
+            })
+            .collect())
     }
 
+    /// Releases every court of the event
     pub async fn delete_reservation_for_event(
         &self,
+        actor: &Actor,
         event_id: Uuid,
         event_type: &str,
     ) -> Result<()> {
+        let before = self
+            .get_reservations_for_event(event_id, event_type)
+            .await?;
+
         self.reservation_repo
             .delete_reservation_by_event_id(event_id, event_type)
-            .await
+            .await?;
+
+        for reservation in before {
+            self.audit_service
+                .record_deletion(
+                    actor,
+                    "DELETE_RESERVATION",
+                    AuditEntity::COURT_RESERVATION,
+                    reservation.id_court_reservation,
+                    &reservation,
+                )
+                .await;
+        }
+
+        Ok(())
     }
 
-    pub async fn get_reservation_for_training(
+    pub async fn get_reservations_for_training(
         &self,
         training_id: Uuid,
-    ) -> Result<Option<CourtReservation>> {
+    ) -> Result<Vec<CourtReservation>> {
         self.reservation_repo
-            .get_reservation_for_training(training_id)
+            .get_reservations_for_training(training_id)
             .await
     }
 
-    pub async fn get_reservation_for_tournament(
+    pub async fn get_reservations_for_tournament(
         &self,
         tournament_id: Uuid,
-    ) -> Result<Option<CourtReservation>> {
+    ) -> Result<Vec<CourtReservation>> {
         self.reservation_repo
-            .get_reservation_for_tournament(tournament_id)
+            .get_reservations_for_tournament(tournament_id)
             .await
     }
 }
@@ -0,0 +1,346 @@
+use chrono::{Datelike, Duration, NaiveDateTime, NaiveTime, Utc};
+use entities::audit::{Actor, AuditEntity};
+use entities::court::{CourtReservation, DayOfWeek};
+use entities::court_pricing::{ChargeStatus, CourtCharge, CourtPriceRule, CourtPriceRuleCreation};
+use entities::event::DomainEvent;
+use entities::user::URol;
+use uuid::Uuid;
+
+use super::err::{Error, Result};
+use super::CourtService;
+
+impl CourtService {
+    pub async fn create_price_rule(
+        &self,
+        actor: &Actor,
+        rule_creation: CourtPriceRuleCreation,
+    ) -> Result<CourtPriceRule> {
+        if rule_creation.name.trim().is_empty() {
+            return Err(Error::InvalidPriceRule("the name is empty".to_string()));
+        }
+        if rule_creation.start_time >= rule_creation.end_time {
+            return Err(Error::InvalidPriceRule(
+                "the band ends before it starts".to_string(),
+            ));
+        }
+        let prices = [
+            rule_creation.member_price,
+            rule_creation.guest_price,
+            rule_creation.lights_surcharge,
+        ];
+        if prices
+            .iter()
+            .any(|price| !price.is_finite() || *price < 0.0)
+        {
+            return Err(Error::InvalidPriceRule(
+                "prices can't be negative".to_string(),
+            ));
+        }
+        if let Some(id_court) = rule_creation.id_court {
+            self.get_court(id_court).await?;
+        }
+
+        let overlaps = self
+            .pricing_repo
+            .list_price_rules()
+            .await?
+            .iter()
+            .any(|rule| {
+                rule.id_court == rule_creation.id_court
+                    && rule.day_of_week == rule_creation.day_of_week
+                    && rule.start_time < rule_creation.end_time
+                    && rule_creation.start_time < rule.end_time
+            });
+        if overlaps {
+            return Err(Error::PriceRuleOverlap);
+        }
+
+        let rule = rule_creation.to_court_price_rule(Uuid::new_v4());
+        self.pricing_repo.create_price_rule(&rule).await?;
+
+        self.audit_service
+            .record_creation(
+                actor,
+                "CREATE_PRICE_RULE",
+                AuditEntity::COURT_PRICE_RULE,
+                rule.id_price_rule,
+                &rule,
+            )
+            .await;
+
+        Ok(rule)
+    }
+
+    pub async fn list_price_rules(&self) -> Result<Vec<CourtPriceRule>> {
+        self.pricing_repo.list_price_rules().await
+    }
+
+    /// Bookings keep the price they were charged
+    pub async fn delete_price_rule(&self, actor: &Actor, id_price_rule: Uuid) -> Result<()> {
+        let rule = self
+            .pricing_repo
+            .get_price_rule_by_id(id_price_rule)
+            .await?
+            .ok_or(Error::PriceRuleNotFound)?;
+
+        self.pricing_repo.delete_price_rule(id_price_rule).await?;
+
+        self.audit_service
+            .record_deletion(
+                actor,
+                "DELETE_PRICE_RULE",
+                AuditEntity::COURT_PRICE_RULE,
+                id_price_rule,
+                &rule,
+            )
+            .await;
+
+        Ok(())
+    }
+
+    /// Pending charge of a new booking, members with an active tuition pay the member price.
+    /// Free bookings have no charge
+    pub(super) async fn charge_for_booking(
+        &self,
+        reservation: &CourtReservation,
+        lights: bool,
+        now: NaiveDateTime,
+    ) -> Result<Option<CourtCharge>> {
+        let Some(id_owner) = reservation.id_owner else {
+            return Ok(None);
+        };
+        let rules = self
+            .pricing_repo
+            .list_price_rules_for_court(reservation.id_court)
+            .await?;
+        let member = self.tuition_service.has_active_tuition(id_owner).await?;
+
+        let amount = price_of(
+            &rules,
+            reservation.start_reservation_datetime,
+            reservation.end_reservation_datetime,
+            member,
+            lights,
+        );
+        if amount <= 0.0 {
+            return Ok(None);
+        }
+
+        Ok(Some(CourtCharge {
+            id_charge: Uuid::new_v4(),
+            id_court_reservation: reservation.id_court_reservation,
+            id_user: id_owner,
+            amount,
+            status: ChargeStatus::PENDING,
+            created_datetime: now,
+            due_datetime: (now + self.booking_policy.payment_timeout)
+                .min(reservation.start_reservation_datetime),
+            paid_datetime: None,
+        }))
+    }
+
+    /// Members pay their own charges, admins can record the payment of any of them
+    pub async fn pay_charge(&self, actor: &Actor, id_charge: Uuid) -> Result<CourtCharge> {
+        let before = self
+            .pricing_repo
+            .get_charge_by_id(id_charge)
+            .await?
+            .ok_or(Error::ChargeNotFound)?;
+        if before.id_user != actor.user_id && actor.user_rol != URol::ADMIN {
+            return Err(Error::NotChargeOwner);
+        }
+        if before.status != ChargeStatus::PENDING {
+            return Err(Error::ChargeNotPending);
+        }
+        let now = Utc::now().naive_utc();
+        if before.due_datetime <= now {
+            return Err(Error::ChargeExpired);
+        }
+
+        let charge = CourtCharge {
+            status: ChargeStatus::PAID,
+            paid_datetime: Some(now),
+            ..before.clone()
+        };
+        self.pricing_repo.update_charge(&charge).await?;
+
+        self.audit_service
+            .record_update(
+                actor,
+                "PAY_COURT_CHARGE",
+                AuditEntity::COURT_CHARGE,
+                id_charge,
+                &before,
+                &charge,
+            )
+            .await;
+
+        Ok(charge)
+    }
+
+    pub async fn list_user_charges(&self, id_user: Uuid) -> Result<Vec<CourtCharge>> {
+        self.pricing_repo.list_user_charges(id_user).await
+    }
+
+    /// Releases the bookings whose charge is past due and lets their owners know
+    pub async fn release_unpaid_bookings(&self, actor: &Actor) -> Result<usize> {
+        let overdue = self
+            .pricing_repo
+            .list_overdue_charges(Utc::now().naive_utc())
+            .await?;
+
+        for before in &overdue {
+            let reservation = self
+                .reservation_repo
+                .get_reservation_by_id(before.id_court_reservation)
+                .await?;
+            if let Some(reservation) = &reservation {
+                let court = self.get_court(reservation.id_court).await?;
+                let event = self.event_service.event(
+                    actor,
+                    DomainEvent::COURT_BOOKING_RELEASED {
+                        id_court_reservation: reservation.id_court_reservation,
+                        id_court: court.id_court,
+                        court_name: court.court_name,
+                        start_datetime: reservation.start_reservation_datetime,
+                        id_user: before.id_user,
+                    },
+                );
+                self.reservation_repo
+                    .delete_reservation(before.id_court_reservation, Some(&event))
+                    .await?;
+            }
+
+            let charge = CourtCharge {
+                status: ChargeStatus::EXPIRED,
+                ..before.clone()
+            };
+            self.pricing_repo.update_charge(&charge).await?;
+
+            self.audit_service
+                .record_update(
+                    actor,
+                    "RELEASE_UNPAID_BOOKING",
+                    AuditEntity::COURT_CHARGE,
+                    charge.id_charge,
+                    before,
+                    &charge,
+                )
+                .await;
+        }
+
+        Ok(overdue.len())
+    }
+}
+
+/// Price of the court time in the range, following the band that covers each part of it.
+/// Rounded to cents
+fn price_of(
+    rules: &[CourtPriceRule],
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    member: bool,
+    lights: bool,
+) -> f64 {
+    let mut total = 0.0;
+    let mut cursor = start;
+    while cursor < end {
+        let day = DayOfWeek::from(cursor.weekday());
+        let time = cursor.time();
+        let day_rules: Vec<&CourtPriceRule> = rules
+            .iter()
+            .filter(|rule| rule.day_of_week.is_none_or(|rule_day| rule_day == day))
+            .collect();
+
+        let midnight = (cursor.date() + Duration::days(1)).and_time(NaiveTime::MIN);
+        let segment_end = day_rules
+            .iter()
+            .flat_map(|rule| [rule.start_time, rule.end_time])
+            .filter(|boundary| *boundary > time)
+            .map(|boundary| cursor.date().and_time(boundary))
+            .fold(midnight.min(end), NaiveDateTime::min);
+
+        let band = day_rules
+            .iter()
+            .filter(|rule| rule.start_time <= time && time < rule.end_time)
+            .max_by_key(|rule| (rule.id_court.is_some(), rule.day_of_week.is_some()));
+        if let Some(band) = band {
+            let hourly_price = if member {
+                band.member_price
+            } else {
+                band.guest_price
+            };
+            let surcharge = if lights { band.lights_surcharge } else { 0.0 };
+            let hours = (segment_end - cursor).num_seconds() as f64 / 3600.0;
+            total += (hourly_price + surcharge) * hours;
+        }
+
+        cursor = segment_end;
+    }
+
+    (total * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    /// Saturday
+    fn at(hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 6, 7)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn rule(
+        id_court: Option<Uuid>,
+        day_of_week: Option<DayOfWeek>,
+        hours: (u32, u32),
+        member_price: f64,
+        guest_price: f64,
+    ) -> CourtPriceRule {
+        CourtPriceRule {
+            id_price_rule: Uuid::new_v4(),
+            name: "Band".to_string(),
+            id_court,
+            day_of_week,
+            start_time: NaiveTime::from_hms_opt(hours.0, 0, 0).unwrap(),
+            end_time: NaiveTime::from_hms_opt(hours.1, 0, 0).unwrap(),
+            member_price,
+            guest_price,
+            lights_surcharge: 4.0,
+        }
+    }
+
+    #[test]
+    fn test_price_follows_the_bands() {
+        let rules = [
+            rule(None, None, (8, 18), 0.0, 10.0),
+            rule(None, None, (18, 22), 5.0, 20.0),
+        ];
+
+        // Half an hour off-peak and an hour at peak
+        assert_eq!(price_of(&rules, at(17, 30), at(19, 0), false, false), 25.0);
+        assert_eq!(price_of(&rules, at(17, 30), at(19, 0), true, false), 5.0);
+        assert_eq!(price_of(&rules, at(17, 30), at(19, 0), true, true), 11.0);
+        // Outside every band the court is free
+        assert_eq!(price_of(&rules, at(22, 0), at(23, 0), false, true), 0.0);
+    }
+
+    #[test]
+    fn test_most_specific_band_wins() {
+        let id_court = Uuid::new_v4();
+        let rules = [
+            rule(None, None, (8, 22), 0.0, 10.0),
+            rule(None, Some(DayOfWeek::SATURDAY), (8, 22), 0.0, 12.0),
+            rule(Some(id_court), None, (10, 11), 0.0, 30.0),
+            rule(None, Some(DayOfWeek::SUNDAY), (8, 22), 0.0, 50.0),
+        ];
+
+        assert_eq!(price_of(&rules, at(9, 0), at(10, 0), false, false), 12.0);
+        assert_eq!(price_of(&rules, at(9, 30), at(11, 30), false, false), 42.0);
+    }
+}
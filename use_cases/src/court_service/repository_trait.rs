@@ -1,6 +1,10 @@
 use async_trait::async_trait;
 use chrono::NaiveDateTime;
-use entities::court::{Court, CourtReservation};
+use entities::court::{Court, CourtClosure, CourtFilter, CourtOpeningHours, CourtReservation};
+use entities::court_attendance::{CourtCancellation, CourtCheckIn, CourtNoShow};
+use entities::court_pricing::{CourtCharge, CourtPriceRule};
+use entities::event::OutboxEvent;
+use entities::pagination::{Page, PageQuery};
 use uuid::Uuid;
 
 use super::err::Result;
@@ -8,15 +12,28 @@ use super::err::Result;
 #[async_trait]
 pub trait CourtRepository: Send + Sync {
     async fn create_court(&self, court: &Court) -> Result<()>;
+    async fn update_court(&self, court: &Court) -> Result<()>;
     async fn get_court_by_id(&self, id_court: Uuid) -> Result<Option<Court>>;
     async fn get_court_by_name(&self, court_name: &str) -> Result<Option<Court>>;
     async fn list_courts(&self) -> Result<Vec<Court>>;
+    async fn list_courts_page(&self, filter: &CourtFilter, page: &PageQuery)
+        -> Result<Page<Court>>;
     async fn delete_court(&self, id_court: Uuid) -> Result<()>;
 }
 
 #[async_trait]
 pub trait CourtReservationRepository: Send + Sync {
+    /// Fails with `CourtUnavailable` when another reservation overlaps, even if both are created
+    /// at the same time
     async fn create_reservation(&self, reservation: &CourtReservation) -> Result<()>;
+    /// Cancels the replaced reservations, creates the new ones and stores the events in the
+    /// outbox as a single operation
+    async fn create_reservations_replacing(
+        &self,
+        reservations: &[CourtReservation],
+        replaced: &[Uuid],
+        events: &[OutboxEvent],
+    ) -> Result<()>;
     async fn get_reservations_for_court_in_range(
         &self,
         id_court: Uuid,
@@ -26,15 +43,108 @@ pub trait CourtReservationRepository: Send + Sync {
     async fn get_reservation_by_id(&self, id_reservation: Uuid)
         -> Result<Option<CourtReservation>>;
     async fn delete_reservation_by_event_id(&self, event_id: Uuid, event_type: &str) -> Result<()>;
-    async fn get_reservation_for_training(
+    /// Every court the training holds, sorted by start
+    async fn get_reservations_for_training(
         &self,
         training_id: Uuid,
-    ) -> Result<Option<CourtReservation>>;
-
-    async fn get_reservation_for_tournament(
+    ) -> Result<Vec<CourtReservation>>;
+    /// Every court the tournament holds, sorted by start
+    async fn get_reservations_for_tournament(
         &self,
         tournament_id: Uuid,
-    ) -> Result<Option<CourtReservation>>;
+    ) -> Result<Vec<CourtReservation>>;
 
     async fn court_has_reservations(&self, id_court: Uuid) -> Result<bool>;
+
+    /// Stores a member booking with its partners and, when it has a price, its charge
+    async fn create_booking(
+        &self,
+        reservation: &CourtReservation,
+        partners: &[Uuid],
+        charge: Option<&CourtCharge>,
+    ) -> Result<()>;
+    async fn get_booking_partners(&self, id_reservation: Uuid) -> Result<Vec<Uuid>>;
+    /// Bookings ending after `from` the user owns or plays in, sorted by start
+    async fn list_member_bookings(
+        &self,
+        id_user: Uuid,
+        from: NaiveDateTime,
+    ) -> Result<Vec<CourtReservation>>;
+    /// Bookings of the owner ending after `from`
+    async fn count_active_bookings(&self, id_owner: Uuid, from: NaiveDateTime) -> Result<u32>;
+    /// Cancels the pending charge of the reservation and refunds the paid one. `event` is stored
+    /// in the outbox in the same transaction
+    async fn delete_reservation(
+        &self,
+        id_reservation: Uuid,
+        event: Option<&OutboxEvent>,
+    ) -> Result<()>;
+}
+
+#[async_trait]
+pub trait CourtScheduleRepository: Send + Sync {
+    async fn get_opening_hours(&self, id_court: Uuid) -> Result<Vec<CourtOpeningHours>>;
+    /// Replaces the whole week of the court
+    async fn set_opening_hours(
+        &self,
+        id_court: Uuid,
+        opening_hours: &[CourtOpeningHours],
+    ) -> Result<()>;
+    async fn create_closure(&self, closure: &CourtClosure) -> Result<()>;
+    async fn get_closure_by_id(&self, id_closure: Uuid) -> Result<Option<CourtClosure>>;
+    /// Closures of the court, including the ones of every court, overlapping the range
+    async fn get_closures_for_court_in_range(
+        &self,
+        id_court: Uuid,
+        start_time: NaiveDateTime,
+        end_time: NaiveDateTime,
+    ) -> Result<Vec<CourtClosure>>;
+    async fn delete_closure(&self, id_closure: Uuid) -> Result<()>;
+}
+
+#[async_trait]
+pub trait CourtPricingRepository: Send + Sync {
+    async fn create_price_rule(&self, rule: &CourtPriceRule) -> Result<()>;
+    async fn get_price_rule_by_id(&self, id_price_rule: Uuid) -> Result<Option<CourtPriceRule>>;
+    async fn list_price_rules(&self) -> Result<Vec<CourtPriceRule>>;
+    /// Rules of the court and of every court
+    async fn list_price_rules_for_court(&self, id_court: Uuid) -> Result<Vec<CourtPriceRule>>;
+    async fn delete_price_rule(&self, id_price_rule: Uuid) -> Result<()>;
+    async fn get_charge_by_id(&self, id_charge: Uuid) -> Result<Option<CourtCharge>>;
+    async fn get_charge_for_reservation(&self, id_reservation: Uuid)
+        -> Result<Option<CourtCharge>>;
+    /// Newest first
+    async fn list_user_charges(&self, id_user: Uuid) -> Result<Vec<CourtCharge>>;
+    /// Pending charges due before `now`
+    async fn list_overdue_charges(&self, now: NaiveDateTime) -> Result<Vec<CourtCharge>>;
+    /// Updates the status and the payment date
+    async fn update_charge(&self, charge: &CourtCharge) -> Result<()>;
+}
+
+#[async_trait]
+pub trait CourtAttendanceRepository: Send + Sync {
+    /// Stores the cancellation and deletes the reservation. The pending charge is cancelled, the
+    /// paid one is refunded unless the cancellation is late. `event` is stored in the outbox in the
+    /// same transaction
+    async fn cancel_reservation(
+        &self,
+        cancellation: &CourtCancellation,
+        event: Option<&OutboxEvent>,
+    ) -> Result<()>;
+    async fn get_cancellation(&self, id_reservation: Uuid) -> Result<Option<CourtCancellation>>;
+    async fn create_check_in(&self, check_in: &CourtCheckIn) -> Result<()>;
+    async fn get_check_in(&self, id_reservation: Uuid) -> Result<Option<CourtCheckIn>>;
+    /// Bookings ending in the range nobody checked in to and without a no-show yet
+    async fn list_unattended_bookings(
+        &self,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<Vec<CourtReservation>>;
+    async fn create_no_show(&self, no_show: &CourtNoShow) -> Result<()>;
+    /// Waived no-shows are left out
+    async fn get_no_show(&self, id_reservation: Uuid) -> Result<Option<CourtNoShow>>;
+    /// Newest first, waived no-shows are left out
+    async fn list_no_shows(&self, id_user: Uuid) -> Result<Vec<CourtNoShow>>;
+    /// The no-show stops counting, and isn't recorded again
+    async fn waive_no_show(&self, id_reservation: Uuid) -> Result<()>;
 }
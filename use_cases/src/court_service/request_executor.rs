@@ -0,0 +1,57 @@
+use async_trait::async_trait;
+use entities::audit::Actor;
+use entities::request::{Request, RequestCommand};
+use uuid::Uuid;
+
+use super::{err::Error as CourtError, CourtService};
+use crate::request_service::{
+    err::{Error, Result},
+    executor::RequestExecutor,
+};
+
+#[async_trait]
+impl RequestExecutor for CourtService {
+    fn handles(&self, command: &RequestCommand) -> bool {
+        matches!(command, RequestCommand::COURT_BOOKING(_))
+    }
+
+    /// Availability is only checked on execution, the court may be free by the time of approval
+    async fn validate(&self, _requester_id: Uuid, command: &RequestCommand) -> Result<()> {
+        let RequestCommand::COURT_BOOKING(booking) = command else {
+            return Ok(());
+        };
+
+        let invalid_booking =
+            if booking.start_reservation_datetime >= booking.end_reservation_datetime {
+                Some(CourtError::InvalidReservationTime)
+            } else if booking.id_owner.is_some() {
+                Some(CourtError::BookingPurposeConflict)
+            } else {
+                match (booking.id_training, booking.id_tournament) {
+                    (Some(_), Some(_)) => Some(CourtError::ReservationPurposeConflict),
+                    (None, None) => Some(CourtError::ReservationPurposeMissing),
+                    _ => None,
+                }
+            };
+        if let Some(err) = invalid_booking {
+            return Err(Error::InvalidRequestPayload(err.to_string()));
+        }
+
+        self.get_court(booking.id_court)
+            .await
+            .map_err(|err| Error::InvalidRequestPayload(err.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn execute(&self, approver: &Actor, request: &Request) -> Result<()> {
+        let RequestCommand::COURT_BOOKING(booking) = &request.requested_command else {
+            return Ok(());
+        };
+
+        self.create_reservation(approver, booking.clone())
+            .await
+            .map(|_| ())
+            .map_err(|err| Error::ExecutionFailed(err.to_string()))
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::HashSet;
+
+use chrono::{Datelike, NaiveDateTime};
+use entities::audit::{Actor, AuditEntity};
+use entities::court::{CourtClosure, CourtClosureCreation, CourtOpeningHours, DayOfWeek};
+use uuid::Uuid;
+
+use super::err::{Error, Result};
+use super::CourtService;
+
+impl CourtService {
+    pub async fn get_opening_hours(&self, id_court: Uuid) -> Result<Vec<CourtOpeningHours>> {
+        self.get_court(id_court).await?;
+        self.schedule_repo.get_opening_hours(id_court).await
+    }
+
+    /// Replaces the opening hours of the court, an empty week leaves the court always open
+    pub async fn set_opening_hours(
+        &self,
+        actor: &Actor,
+        id_court: Uuid,
+        mut opening_hours: Vec<CourtOpeningHours>,
+    ) -> Result<Vec<CourtOpeningHours>> {
+        self.get_court(id_court).await?;
+
+        let mut days = HashSet::new();
+        for hours in &opening_hours {
+            if hours.open_time >= hours.close_time {
+                return Err(Error::InvalidOpeningHours(format!(
+                    "{} closes before it opens",
+                    hours.day_of_week
+                )));
+            }
+            if !days.insert(hours.day_of_week) {
+                return Err(Error::InvalidOpeningHours(format!(
+                    "{} is repeated",
+                    hours.day_of_week
+                )));
+            }
+        }
+        opening_hours.sort_by_key(|hours| hours.day_of_week);
+
+        let before = self.schedule_repo.get_opening_hours(id_court).await?;
+        self.schedule_repo
+            .set_opening_hours(id_court, &opening_hours)
+            .await?;
+
+        self.audit_service
+            .record_update(
+                actor,
+                "UPDATE_OPENING_HOURS",
+                AuditEntity::COURT,
+                id_court,
+                &before,
+                &opening_hours,
+            )
+            .await;
+
+        Ok(opening_hours)
+    }
+
+    /// Closes a court, or every court, for the window. Member bookings in the window are
+    /// cancelled, the reservations of trainings and tournaments have to be moved first
+    pub async fn create_closure(
+        &self,
+        actor: &Actor,
+        closure_creation: CourtClosureCreation,
+    ) -> Result<CourtClosure> {
+        if closure_creation.start_datetime >= closure_creation.end_datetime {
+            return Err(Error::InvalidReservationTime);
+        }
+
+        let courts = match closure_creation.id_court {
+            Some(id_court) => vec![self.get_court(id_court).await?],
+            None => self.list_courts().await?,
+        };
+
+        let mut bookings = Vec::new();
+        for court in courts {
+            let overlapping = self
+                .overlapping_reservations(
+                    court.id_court,
+                    closure_creation.start_datetime,
+                    closure_creation.end_datetime,
+                    &[],
+                )
+                .await?;
+            if overlapping.iter().any(|r| r.id_owner.is_none()) {
+                return Err(Error::ClosureConflict);
+            }
+            bookings.extend(overlapping.into_iter().map(|r| (court.clone(), r)));
+        }
+
+        let closure = closure_creation.to_court_closure(Uuid::new_v4());
+        self.schedule_repo.create_closure(&closure).await?;
+
+        self.audit_service
+            .record_creation(
+                actor,
+                "CREATE_CLOSURE",
+                AuditEntity::COURT_CLOSURE,
+                closure.id_closure,
+                &closure,
+            )
+            .await;
+
+        for (court, booking) in bookings {
+            self.displace_booking(actor, &court, booking).await?;
+        }
+
+        Ok(closure)
+    }
+
+    pub async fn get_closures_for_court(
+        &self,
+        id_court: Uuid,
+        start_datetime_filter: Option<NaiveDateTime>,
+        end_datetime_filter: Option<NaiveDateTime>,
+    ) -> Result<Vec<CourtClosure>> {
+        let start = start_datetime_filter.unwrap_or_else(|| {
+            NaiveDateTime::parse_from_str("1970-01-01 00:00:00", "%Y-%m-%d %H:%M:%S").unwrap()
+        });
+        let end = end_datetime_filter.unwrap_or_else(|| {
+            NaiveDateTime::parse_from_str("9999-12-31 23:59:59", "%Y-%m-%d %H:%M:%S").unwrap()
+        });
+
+        self.schedule_repo
+            .get_closures_for_court_in_range(id_court, start, end)
+            .await
+    }
+
+    pub async fn delete_closure(&self, actor: &Actor, id_closure: Uuid) -> Result<()> {
+        let closure = self
+            .schedule_repo
+            .get_closure_by_id(id_closure)
+            .await?
+            .ok_or(Error::ClosureNotFound)?;
+
+        self.schedule_repo.delete_closure(id_closure).await?;
+
+        self.audit_service
+            .record_deletion(
+                actor,
+                "DELETE_CLOSURE",
+                AuditEntity::COURT_CLOSURE,
+                id_closure,
+                &closure,
+            )
+            .await;
+
+        Ok(())
+    }
+
+    /// Fails when the range overlaps a closure or falls outside the opening hours. Ranges spanning
+    /// several days, like tournaments, only have to start and end while the court is open
+    pub(super) async fn ensure_court_open(
+        &self,
+        id_court: Uuid,
+        start_time: NaiveDateTime,
+        end_time: NaiveDateTime,
+    ) -> Result<()> {
+        let closures = self
+            .schedule_repo
+            .get_closures_for_court_in_range(id_court, start_time, end_time)
+            .await?;
+        if let Some(closure) = closures.into_iter().next() {
+            return Err(Error::CourtClosed(closure.reason));
+        }
+
+        let opening_hours = self.schedule_repo.get_opening_hours(id_court).await?;
+        if opening_hours.is_empty() {
+            return Ok(());
+        }
+
+        let hours_of = |datetime: NaiveDateTime| {
+            let day_of_week = DayOfWeek::from(datetime.weekday());
+            opening_hours
+                .iter()
+                .find(|hours| hours.day_of_week == day_of_week)
+        };
+        let opens_at_start = hours_of(start_time).is_some_and(|hours| {
+            hours.open_time <= start_time.time() && start_time.time() < hours.close_time
+        });
+        let open_until_end = hours_of(end_time).is_some_and(|hours| {
+            hours.open_time < end_time.time() && end_time.time() <= hours.close_time
+        });
+
+        if opens_at_start && open_until_end {
+            Ok(())
+        } else {
+            Err(Error::OutsideOpeningHours)
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use chrono::{Datelike, Duration, NaiveDateTime};
+use entities::court::{
+    CourtFilter, CourtFreeSlots, CourtOpeningHours, CourtSlotsQuery, DayOfWeek, TimeSlot,
+};
+
+use super::err::{Error, Result};
+use super::CourtService;
+
+const MAX_SEARCH_DAYS: i64 = 31;
+
+type Interval = (NaiveDateTime, NaiveDateTime);
+
+impl CourtService {
+    /// Free slots of every active court matching the filter, leaving out the closures, the time outside
+    /// the opening hours and the reservations with their buffer. Each free window is split into
+    /// consecutive slots of the given duration starting at the beginning of the window
+    pub async fn find_free_slots(
+        &self,
+        query: &CourtSlotsQuery,
+        filter: &CourtFilter,
+    ) -> Result<Vec<CourtFreeSlots>> {
+        let (start, end) = (query.start_datetime, query.end_datetime);
+        if start >= end {
+            return Err(Error::InvalidReservationTime);
+        }
+        if end - start > Duration::days(MAX_SEARCH_DAYS) {
+            return Err(Error::InvalidSlotSearch(format!(
+                "the range can't be longer than {MAX_SEARCH_DAYS} days"
+            )));
+        }
+        if query.duration_minutes == 0 {
+            return Err(Error::InvalidSlotSearch(
+                "the duration must be positive".to_string(),
+            ));
+        }
+        let duration = Duration::minutes(query.duration_minutes.into());
+        let buffer = self.booking_policy.reservation_buffer;
+
+        let courts = self.list_courts().await?;
+        let mut free_slots = Vec::new();
+        let courts = courts
+            .into_iter()
+            .filter(|court| court.active && filter.matches(court));
+        for court in courts {
+            let opening_hours = self.schedule_repo.get_opening_hours(court.id_court).await?;
+            let mut free = open_intervals(&opening_hours, start, end);
+
+            let closures = self
+                .schedule_repo
+                .get_closures_for_court_in_range(court.id_court, start, end)
+                .await?;
+            for closure in closures {
+                free = subtract(free, (closure.start_datetime, closure.end_datetime));
+            }
+
+            let reservations = self
+                .reservation_repo
+                .get_reservations_for_court_in_range(court.id_court, start - buffer, end + buffer)
+                .await?;
+            for r in reservations {
+                free = subtract(
+                    free,
+                    (
+                        r.start_reservation_datetime - buffer,
+                        r.end_reservation_datetime + buffer,
+                    ),
+                );
+            }
+
+            free_slots.push(CourtFreeSlots {
+                court,
+                slots: split_into_slots(&free, duration),
+            });
+        }
+
+        Ok(free_slots)
+    }
+}
+
+/// Parts of the range inside the opening hours, the whole range for courts that are always open
+fn open_intervals(
+    opening_hours: &[CourtOpeningHours],
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+) -> Vec<Interval> {
+    if opening_hours.is_empty() {
+        return vec![(start, end)];
+    }
+
+    start
+        .date()
+        .iter_days()
+        .take_while(|day| *day <= end.date())
+        .filter_map(|day| {
+            let day_of_week = DayOfWeek::from(day.weekday());
+            let hours = opening_hours
+                .iter()
+                .find(|hours| hours.day_of_week == day_of_week)?;
+            let open = day.and_time(hours.open_time).max(start);
+            let close = day.and_time(hours.close_time).min(end);
+            (open < close).then_some((open, close))
+        })
+        .collect()
+}
+
+fn subtract(intervals: Vec<Interval>, (blocked_start, blocked_end): Interval) -> Vec<Interval> {
+    intervals
+        .into_iter()
+        .flat_map(|(start, end)| {
+            [
+                (start, end.min(blocked_start)),
+                (start.max(blocked_end), end),
+            ]
+        })
+        .filter(|(start, end)| start < end)
+        .collect()
+}
+
+fn split_into_slots(intervals: &[Interval], duration: Duration) -> Vec<TimeSlot> {
+    let mut slots = Vec::new();
+    for (start, end) in intervals {
+        let mut slot_start = *start;
+        while slot_start + duration <= *end {
+            slots.push(TimeSlot {
+                start_datetime: slot_start,
+                end_datetime: slot_start + duration,
+            });
+            slot_start += duration;
+        }
+    }
+    slots
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveTime;
+
+    use super::*;
+
+    fn datetime(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_open_intervals_follow_opening_hours() {
+        // 2025-03-10 is a Monday
+        let opening_hours = [CourtOpeningHours {
+            day_of_week: DayOfWeek::MONDAY,
+            open_time: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            close_time: NaiveTime::from_hms_opt(20, 0, 0).unwrap(),
+        }];
+
+        let intervals = open_intervals(
+            &opening_hours,
+            datetime("2025-03-10 10:00:00"),
+            datetime("2025-03-17 09:00:00"),
+        );
+
+        assert_eq!(
+            intervals,
+            vec![
+                (
+                    datetime("2025-03-10 10:00:00"),
+                    datetime("2025-03-10 20:00:00")
+                ),
+                (
+                    datetime("2025-03-17 08:00:00"),
+                    datetime("2025-03-17 09:00:00")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_free_slots_skip_blocked_time() {
+        let free = subtract(
+            vec![(
+                datetime("2025-03-10 08:00:00"),
+                datetime("2025-03-10 12:00:00"),
+            )],
+            (
+                datetime("2025-03-10 09:30:00"),
+                datetime("2025-03-10 10:00:00"),
+            ),
+        );
+
+        let slots = split_into_slots(&free, Duration::hours(1));
+
+        let starts: Vec<_> = slots.iter().map(|slot| slot.start_datetime).collect();
+        assert_eq!(
+            starts,
+            vec![
+                datetime("2025-03-10 08:00:00"),
+                datetime("2025-03-10 10:00:00"),
+                datetime("2025-03-10 11:00:00"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,11 @@
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Database error: {0}")]
+    UnknownDatabaseError(String),
+    #[error("Subscriber failed: {0}")]
+    SubscriberFailed(String),
+}
@@ -0,0 +1,131 @@
+pub mod err;
+pub mod repository_trait;
+pub mod subscriber_trait;
+
+use std::sync::{Arc, PoisonError, RwLock};
+
+use chrono::Utc;
+use entities::audit::Actor;
+use entities::event::{DomainEvent, OutboxEvent};
+use repository_trait::OutboxRepository;
+use subscriber_trait::EventSubscriber;
+use tracing::warn;
+use uuid::Uuid;
+
+use self::err::{Error, Result};
+
+/// Events failing this many dispatch rounds stay in the outbox for inspection
+const MAX_DISPATCH_ATTEMPTS: u32 = 5;
+
+#[derive(Clone)]
+pub struct EventService {
+    outbox_repo: Arc<dyn OutboxRepository>,
+    subscribers: Arc<RwLock<Vec<Arc<dyn EventSubscriber>>>>,
+}
+
+impl EventService {
+    pub fn new(outbox_repo: Arc<dyn OutboxRepository>) -> Self {
+        Self {
+            outbox_repo,
+            subscribers: Arc::new(RwLock::new(Vec::new())),
+        }
+    }
+
+    /// Subscribers are often services that publish events as well, so they are registered once
+    /// every service has been built
+    pub fn subscribe(&self, subscriber: Arc<dyn EventSubscriber>) {
+        self.subscribers
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(subscriber);
+    }
+
+    /// Builds the outbox event of an operation. The repository applying the operation stores it
+    /// in the same transaction, so the event is published only if the operation is applied
+    pub fn event(&self, actor: &Actor, event: DomainEvent) -> OutboxEvent {
+        OutboxEvent {
+            id_event: Uuid::new_v4(),
+            event,
+            actor: actor.clone(),
+            occurred_at: Utc::now().naive_utc(),
+            dispatched_at: None,
+            attempts: 0,
+            last_error: None,
+        }
+    }
+
+    /// Appends an event that no stored operation produced, like a reminder, to the outbox. The
+    /// dispatcher delivers it afterwards
+    pub async fn publish(&self, actor: &Actor, event: DomainEvent) -> Result<()> {
+        self.outbox_repo
+            .append_event(&self.event(actor, event))
+            .await
+    }
+
+    /// Delivers a batch of pending events to their subscribers and returns how many were fully
+    /// dispatched. Events with failing subscribers are retried on the next call
+    pub async fn dispatch_pending(&self, batch_size: u32) -> Result<usize> {
+        let events = self
+            .outbox_repo
+            .list_pending_events(batch_size, MAX_DISPATCH_ATTEMPTS)
+            .await?;
+
+        let mut dispatched = 0;
+        for event in events {
+            match self.dispatch(&event).await {
+                Ok(()) => {
+                    self.outbox_repo
+                        .mark_event_dispatched(event.id_event, Utc::now().naive_utc())
+                        .await?;
+                    dispatched += 1;
+                }
+                Err(err) => {
+                    warn!("Error dispatching event {}: {err}", event.id_event);
+                    self.outbox_repo
+                        .mark_event_failed(event.id_event, &err.to_string())
+                        .await?;
+                }
+            }
+        }
+
+        Ok(dispatched)
+    }
+
+    /// Subscribers that handled the event in a previous round are skipped
+    async fn dispatch(&self, event: &OutboxEvent) -> Result<()> {
+        let kind = event.event.kind();
+        let subscribers: Vec<Arc<dyn EventSubscriber>> = self
+            .subscribers
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .filter(|subscriber| subscriber.handles(kind))
+            .cloned()
+            .collect();
+        let delivered = self
+            .outbox_repo
+            .list_event_deliveries(event.id_event)
+            .await?;
+
+        let mut failures = Vec::new();
+        for subscriber in subscribers
+            .iter()
+            .filter(|subscriber| !delivered.iter().any(|name| name == subscriber.name()))
+        {
+            match subscriber.handle(event).await {
+                Ok(()) => {
+                    self.outbox_repo
+                        .record_event_delivery(event.id_event, subscriber.name())
+                        .await?
+                }
+                Err(err) => failures.push(format!("{}: {err}", subscriber.name())),
+            }
+        }
+
+        if !failures.is_empty() {
+            return Err(Error::SubscriberFailed(failures.join("; ")));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use super::err::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use entities::event::OutboxEvent;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait OutboxRepository: Send + Sync {
+    async fn append_event(&self, event: &OutboxEvent) -> Result<()>;
+    /// Undispatched events with less than `max_attempts` failed rounds, oldest first
+    async fn list_pending_events(&self, limit: u32, max_attempts: u32) -> Result<Vec<OutboxEvent>>;
+    async fn mark_event_dispatched(
+        &self,
+        id_event: Uuid,
+        dispatched_at: NaiveDateTime,
+    ) -> Result<()>;
+    /// Increments the attempts of the event and stores why the round failed
+    async fn mark_event_failed(&self, id_event: Uuid, error: &str) -> Result<()>;
+
+    /// Subscribers that already handled the event, they are skipped when it is retried
+    async fn list_event_deliveries(&self, id_event: Uuid) -> Result<Vec<String>>;
+    async fn record_event_delivery(&self, id_event: Uuid, subscriber: &str) -> Result<()>;
+}
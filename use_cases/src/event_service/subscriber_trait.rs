@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use entities::event::{EventKind, OutboxEvent};
+
+use super::err::Result;
+
+/// In-process reaction to domain events. Events are delivered at least once, so handlers must
+/// tolerate seeing the same event again
+#[async_trait]
+pub trait EventSubscriber: Send + Sync {
+    /// Unique name, used to remember which subscribers already handled an event
+    fn name(&self) -> &'static str;
+
+    fn handles(&self, kind: EventKind) -> bool;
+
+    async fn handle(&self, event: &OutboxEvent) -> Result<()>;
+}
@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Database error: {0}")]
+    UnknownDatabaseError(String),
+    #[error("Job not found")]
+    JobNotFound,
+    #[error("The job is already running")]
+    JobAlreadyRunning,
+    #[error("Invalid schedule: {0}")]
+    InvalidSchedule(String),
+    #[error("Job failed: {0}")]
+    JobFailed(String),
+}
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use entities::audit::Actor;
+
+use super::err::Result;
+
+/// Periodic club task. Jobs are implemented next to the service owning the data they touch
+#[async_trait]
+pub trait Job: Send + Sync {
+    /// Unique name, the persisted state of the job is keyed by it
+    fn name(&self) -> &'static str;
+
+    /// Cron expression with the five usual fields, evaluated in UTC
+    fn schedule(&self) -> &'static str;
+
+    /// Returns a short summary of what the run did
+    async fn run(&self, actor: &Actor) -> Result<String>;
+}
@@ -0,0 +1,203 @@
+pub mod err;
+pub mod job_trait;
+pub mod repository_trait;
+pub mod schedule;
+
+use std::sync::Arc;
+
+use chrono::{Duration, NaiveDateTime, Utc};
+use entities::audit::{Actor, AuditEntity};
+use entities::job::{JobRun, JobRunFilter, JobRunStatus, ScheduledJob};
+use entities::pagination::{Page, PageQuery};
+use job_trait::Job;
+use repository_trait::JobRepository;
+use schedule::CronSchedule;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use self::err::{Error, Result};
+use crate::audit_service::AuditService;
+
+/// A run holding the lock for longer than this is considered dead, e.g. the process crashed
+const STALE_LOCK_MINUTES: i64 = 60;
+
+#[derive(Clone)]
+pub struct JobService {
+    job_repo: Arc<dyn JobRepository>,
+    jobs: Vec<Arc<dyn Job>>,
+    audit_service: AuditService,
+}
+
+impl JobService {
+    pub fn new(
+        job_repo: Arc<dyn JobRepository>,
+        jobs: Vec<Arc<dyn Job>>,
+        audit_service: AuditService,
+    ) -> Self {
+        Self {
+            job_repo,
+            jobs,
+            audit_service,
+        }
+    }
+
+    /// Persists the registered jobs, rescheduling the ones whose schedule changed. Must run
+    /// before the first `run_due_jobs`
+    pub async fn sync_jobs(&self) -> Result<()> {
+        let now = Utc::now().naive_utc();
+
+        for job in &self.jobs {
+            let next_run_at = next_run_after(job.as_ref(), now)?;
+            match self.job_repo.get_job(job.name()).await? {
+                None => {
+                    self.job_repo
+                        .create_job(&ScheduledJob {
+                            name: job.name().to_string(),
+                            schedule: job.schedule().to_string(),
+                            next_run_at,
+                            running_since: None,
+                            last_run_at: None,
+                            last_status: None,
+                        })
+                        .await?
+                }
+                Some(stored) if stored.schedule != job.schedule() => {
+                    self.job_repo
+                        .update_job_schedule(job.name(), job.schedule(), next_run_at)
+                        .await?
+                }
+                Some(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs every job whose next run is due and returns how many ran. Jobs locked by another
+    /// instance are skipped, a failed job is retried on its next scheduled time
+    pub async fn run_due_jobs(&self) -> Result<usize> {
+        let now = Utc::now().naive_utc();
+        let due_jobs = self.job_repo.list_jobs().await?;
+
+        let mut executed = 0;
+        for stored in due_jobs.iter().filter(|stored| stored.next_run_at <= now) {
+            let Some(job) = self.find_job(&stored.name) else {
+                continue;
+            };
+
+            match self.execute(job, &Actor::system(), None).await {
+                Ok(_) => executed += 1,
+                Err(Error::JobAlreadyRunning) => {}
+                Err(err) => error!("Error running job {}: {err}", stored.name),
+            }
+        }
+
+        Ok(executed)
+    }
+
+    /// Runs the job right away, outside of its schedule
+    pub async fn trigger_job(&self, actor: &Actor, name: &str) -> Result<JobRun> {
+        let job = self.find_job(name).ok_or(Error::JobNotFound)?;
+        let run = self.execute(job, actor, Some(actor.user_id)).await?;
+
+        self.audit_service
+            .record_creation(actor, "TRIGGER_JOB", AuditEntity::JOB, run.id_run, &run)
+            .await;
+
+        Ok(run)
+    }
+
+    pub async fn list_jobs(&self) -> Result<Vec<ScheduledJob>> {
+        self.job_repo.list_jobs().await
+    }
+
+    pub async fn list_job_runs_page(
+        &self,
+        filter: &JobRunFilter,
+        page: &PageQuery,
+    ) -> Result<Page<JobRun>> {
+        self.job_repo.list_job_runs_page(filter, page).await
+    }
+
+    fn find_job(&self, name: &str) -> Option<&Arc<dyn Job>> {
+        self.jobs.iter().find(|job| job.name() == name)
+    }
+
+    /// Runs the job under its lock and records the run. A failure of the job itself is stored
+    /// in the run instead of being returned
+    async fn execute(
+        &self,
+        job: &Arc<dyn Job>,
+        actor: &Actor,
+        triggered_by: Option<Uuid>,
+    ) -> Result<JobRun> {
+        let started_at = Utc::now().naive_utc();
+        let stale_before = started_at - Duration::minutes(STALE_LOCK_MINUTES);
+        // Scheduled runs only take the lock while the job is due, manual ones any time
+        if !self
+            .job_repo
+            .lock_job(job.name(), started_at, stale_before, triggered_by.is_none())
+            .await?
+        {
+            return Err(Error::JobAlreadyRunning);
+        }
+
+        let mut run = JobRun {
+            id_run: Uuid::new_v4(),
+            job_name: job.name().to_string(),
+            started_at,
+            finished_at: None,
+            status: JobRunStatus::RUNNING,
+            output: None,
+            triggered_by,
+        };
+        if let Err(err) = self.job_repo.create_job_run(&run).await {
+            // Nothing ran, so the lock is released for the next tick to try again
+            let failed_at = Utc::now().naive_utc();
+            let next_run_at = next_run_after(job.as_ref(), failed_at).unwrap_or(failed_at);
+            if let Err(unlock_err) = self
+                .job_repo
+                .unlock_job(job.name(), failed_at, JobRunStatus::FAILED, next_run_at)
+                .await
+            {
+                error!(
+                    "Couldn't release the lock of job {}: {unlock_err}",
+                    job.name()
+                );
+            }
+            return Err(err);
+        }
+
+        let (status, output) = match job.run(actor).await {
+            Ok(output) => (JobRunStatus::SUCCEEDED, output),
+            Err(err) => (JobRunStatus::FAILED, err.to_string()),
+        };
+        info!("Job {} finished with {status}: {output}", job.name());
+
+        let finished_at = Utc::now().naive_utc();
+        run.finished_at = Some(finished_at);
+        run.status = status;
+        run.output = Some(output);
+        // The lock is released even when the run can't be stored, so the job isn't held until
+        // the lock goes stale
+        let stored = self.job_repo.update_job_run(&run).await;
+
+        self.job_repo
+            .unlock_job(
+                job.name(),
+                finished_at,
+                status,
+                next_run_after(job.as_ref(), finished_at)?,
+            )
+            .await?;
+        stored?;
+
+        Ok(run)
+    }
+}
+
+fn next_run_after(job: &dyn Job, after: NaiveDateTime) -> Result<NaiveDateTime> {
+    CronSchedule::parse(job.schedule())?
+        .next_after(after)
+        .ok_or_else(|| Error::InvalidSchedule(format!("'{}' never runs", job.schedule())))
+}
@@ -0,0 +1,44 @@
+use super::err::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use entities::job::{JobRun, JobRunFilter, JobRunStatus, ScheduledJob};
+use entities::pagination::{Page, PageQuery};
+
+#[async_trait]
+pub trait JobRepository: Send + Sync {
+    async fn create_job(&self, job: &ScheduledJob) -> Result<()>;
+    async fn get_job(&self, name: &str) -> Result<Option<ScheduledJob>>;
+    async fn list_jobs(&self) -> Result<Vec<ScheduledJob>>;
+    async fn update_job_schedule(
+        &self,
+        name: &str,
+        schedule: &str,
+        next_run_at: NaiveDateTime,
+    ) -> Result<()>;
+    /// Takes the lock of the job unless another run holds it since after `stale_before`. With
+    /// `due_only` the job must also be due at `now`, so a scheduled run another instance just
+    /// finished isn't repeated. Returns false when the lock wasn't taken
+    async fn lock_job(
+        &self,
+        name: &str,
+        now: NaiveDateTime,
+        stale_before: NaiveDateTime,
+        due_only: bool,
+    ) -> Result<bool>;
+    /// Releases the lock and stores the outcome of the run
+    async fn unlock_job(
+        &self,
+        name: &str,
+        last_run_at: NaiveDateTime,
+        last_status: JobRunStatus,
+        next_run_at: NaiveDateTime,
+    ) -> Result<()>;
+
+    async fn create_job_run(&self, run: &JobRun) -> Result<()>;
+    async fn update_job_run(&self, run: &JobRun) -> Result<()>;
+    async fn list_job_runs_page(
+        &self,
+        filter: &JobRunFilter,
+        page: &PageQuery,
+    ) -> Result<Page<JobRun>>;
+}
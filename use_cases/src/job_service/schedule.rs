@@ -0,0 +1,179 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Timelike};
+
+use super::err::{Error, Result};
+
+/// Searching further than this means the expression can't match, like `0 0 31 2 *`
+const MAX_SEARCH_DAYS: i64 = 366 * 5;
+
+/// Cron expression with the fields minute, hour, day of month, month and day of week.
+/// Every field accepts `*`, numbers, ranges `a-b`, lists `a,b` and steps `*/n` or `a-b/n`.
+/// Sunday is both 0 and 7 in the day of week
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<Self> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let &[minute, hour, day_of_month, month, day_of_week] = fields.as_slice() else {
+            return Err(Error::InvalidSchedule(format!(
+                "'{expression}' must have 5 fields"
+            )));
+        };
+
+        let mut days_of_week = parse_field(day_of_week, 0, 7)?;
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week |= 1;
+        }
+
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59)?,
+            hours: parse_field(hour, 0, 23)?,
+            days_of_month: parse_field(day_of_month, 1, 31)?,
+            months: parse_field(month, 1, 12)?,
+            days_of_week,
+            any_day_of_month: day_of_month == "*",
+            any_day_of_week: day_of_week == "*",
+        })
+    }
+
+    /// First minute strictly after `after` matching the expression
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = start + Duration::days(MAX_SEARCH_DAYS);
+
+        let mut candidate = start;
+        while candidate < limit {
+            if !contains(self.months, candidate.month()) {
+                let (year, month) = match candidate.month() {
+                    12 => (candidate.year() + 1, 1),
+                    month => (candidate.year(), month + 1),
+                };
+                candidate = NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)?;
+            } else if !self.matches_day(candidate.date()) {
+                candidate = candidate.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+            } else if !contains(self.hours, candidate.hour()) {
+                candidate = candidate.with_minute(0)? + Duration::hours(1);
+            } else if !contains(self.minutes, candidate.minute()) {
+                candidate += Duration::minutes(1);
+            } else {
+                return Some(candidate);
+            }
+        }
+
+        None
+    }
+
+    /// When both day fields are restricted a day matching either of them is enough, as in cron
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day_of_month = contains(self.days_of_month, date.day());
+        let day_of_week = contains(self.days_of_week, date.weekday().num_days_from_sunday());
+
+        match (self.any_day_of_month, self.any_day_of_week) {
+            (false, false) => day_of_month || day_of_week,
+            _ => day_of_month && day_of_week,
+        }
+    }
+}
+
+fn contains(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+/// Bitset with the values of the field between `min` and `max`
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64> {
+    let invalid = || Error::InvalidSchedule(format!("invalid field '{field}'"));
+    let number = |value: &str| -> Result<u32> {
+        value
+            .parse::<u32>()
+            .ok()
+            .filter(|value| (min..=max).contains(value))
+            .ok_or_else(invalid)
+    };
+
+    let mut set = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, number(step).ok().filter(|step| *step > 0)),
+            None => (part, Some(1)),
+        };
+        let step = step.ok_or_else(invalid)?;
+
+        let (from, to) = match range {
+            "*" => (min, max),
+            range => match range.split_once('-') {
+                Some((from, to)) => (number(from)?, number(to)?),
+                // `a/n` goes from `a` to the end of the field
+                None if step > 1 => (number(range)?, max),
+                None => (number(range)?, number(range)?),
+            },
+        };
+        if from > to {
+            return Err(invalid());
+        }
+
+        for value in (from..=to).step_by(step as usize) {
+            set |= 1 << value;
+        }
+    }
+
+    Ok(set)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datetime(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_next_after() {
+        let cases = [
+            ("*/15 * * * *", "2025-03-10 10:07", "2025-03-10 10:15"),
+            ("0 * * * *", "2025-03-10 10:00", "2025-03-10 11:00"),
+            ("30 6 * * *", "2025-03-10 07:00", "2025-03-11 06:30"),
+            ("0 8 * * 1-5", "2025-03-14 09:00", "2025-03-17 08:00"),
+            ("0 3 1 1 *", "2025-03-10 10:00", "2026-01-01 03:00"),
+            ("0 0 * * 7", "2025-03-10 10:00", "2025-03-16 00:00"),
+            ("0 0 29 2 *", "2025-03-10 10:00", "2028-02-29 00:00"),
+        ];
+
+        for (expression, after, expected) in cases {
+            let schedule = CronSchedule::parse(expression).unwrap();
+            assert_eq!(
+                schedule.next_after(datetime(after)),
+                Some(datetime(expected)),
+                "{expression}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_expressions() {
+        for expression in [
+            "* * * *",
+            "60 * * * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "a * * * *",
+        ] {
+            assert!(CronSchedule::parse(expression).is_err(), "{expression}");
+        }
+    }
+
+    #[test]
+    fn test_impossible_schedule_never_runs() {
+        let schedule = CronSchedule::parse("0 0 31 2 *").unwrap();
+
+        assert_eq!(schedule.next_after(datetime("2025-03-10 10:00")), None);
+    }
+}
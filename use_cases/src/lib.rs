@@ -1,8 +1,17 @@
+pub mod audit_service;
+pub mod calendar_service;
 pub mod category_service;
 pub mod court_service;
+pub mod event_service;
+pub mod job_service;
+pub mod notification_service;
+pub mod privacy_service;
 pub mod report_service;
 pub mod request_service;
+pub mod schedule_service;
 pub mod tournament_service;
+pub mod trainer_service;
 pub mod training_service;
 pub mod tuition_service;
 pub mod user_service;
+pub mod webhook_service;
@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Database error: {0}")]
+    UnknownDatabaseError(String),
+    #[error("User service error: {0}")]
+    UserServiceError(#[from] crate::user_service::err::Error),
+    #[error("Notification not found")]
+    NotificationNotFound,
+    #[error("No notifier registered for the {0} channel")]
+    ChannelUnavailable(String),
+    #[error("Delivery failed: {0}")]
+    DeliveryFailed(String),
+    #[error("No provider configured for the {0} channel, the message wasn't sent")]
+    ProviderNotConfigured(String),
+}
@@ -0,0 +1,174 @@
+use async_trait::async_trait;
+use entities::event::{DomainEvent, EventKind, OutboxEvent};
+use entities::notification::NotificationKind;
+use entities::request::RequestState;
+
+use super::NotificationService;
+use crate::event_service::{err::Result, subscriber_trait::EventSubscriber};
+
+/// Delivery failures are already logged per channel, so handling never fails and an event
+/// doesn't notify members twice
+#[async_trait]
+impl EventSubscriber for NotificationService {
+    fn name(&self) -> &'static str {
+        "notifications"
+    }
+
+    fn handles(&self, kind: EventKind) -> bool {
+        matches!(
+            kind,
+            EventKind::TRAINING_CANCELLED
+                | EventKind::TRAINING_REMINDER
+                | EventKind::TRAINING_SPOT_OFFERED
+                | EventKind::TOURNAMENT_RESULT_RECORDED
+                | EventKind::TUITION_EXPIRING
+                | EventKind::REQUEST_COMPLETED
+                | EventKind::COURT_BOOKING_CANCELLED
+                | EventKind::COURT_BOOKING_RELEASED
+        )
+    }
+
+    async fn handle(&self, event: &OutboxEvent) -> Result<()> {
+        match &event.event {
+            DomainEvent::TRAINING_CANCELLED {
+                name,
+                start_datetime,
+                registered_users,
+                ..
+            }
+            | DomainEvent::TRAINING_REMINDER {
+                name,
+                start_datetime,
+                registered_users,
+                ..
+            } => {
+                let kind = match event.event.kind() {
+                    EventKind::TRAINING_CANCELLED => NotificationKind::TRAINING_CANCELLED,
+                    _ => NotificationKind::TRAINING_REMINDER,
+                };
+                self.notify_many(
+                    registered_users.iter().copied(),
+                    kind,
+                    &[
+                        ("training_name", name.clone()),
+                        (
+                            "start_datetime",
+                            start_datetime.format("%Y-%m-%d %H:%M").to_string(),
+                        ),
+                    ],
+                )
+                .await
+            }
+            DomainEvent::TRAINING_SPOT_OFFERED {
+                name,
+                start_datetime,
+                id_user,
+                confirm_before,
+                ..
+            } => {
+                self.notify(
+                    *id_user,
+                    NotificationKind::TRAINING_SPOT_OFFERED,
+                    &[
+                        ("training_name", name.clone()),
+                        (
+                            "start_datetime",
+                            start_datetime.format("%Y-%m-%d %H:%M").to_string(),
+                        ),
+                        (
+                            "confirm_before",
+                            confirm_before.format("%Y-%m-%d %H:%M").to_string(),
+                        ),
+                    ],
+                )
+                .await
+            }
+            DomainEvent::TOURNAMENT_RESULT_RECORDED {
+                tournament_name,
+                id_user,
+                position,
+                ..
+            } => {
+                self.notify(
+                    *id_user,
+                    NotificationKind::TOURNAMENT_RESULT,
+                    &[
+                        ("tournament_name", tournament_name.clone()),
+                        ("position", position.to_string()),
+                    ],
+                )
+                .await
+            }
+            DomainEvent::TUITION_EXPIRING {
+                id_user,
+                expires_at,
+            } => {
+                self.notify(
+                    *id_user,
+                    NotificationKind::TUITION_EXPIRING,
+                    &[("expires_on", expires_at.format("%Y-%m-%d").to_string())],
+                )
+                .await
+            }
+            DomainEvent::REQUEST_COMPLETED {
+                requester_id,
+                request_kind,
+                state,
+                ..
+            } => {
+                let kind = match state {
+                    RequestState::APPROVED => NotificationKind::REQUEST_APPROVED,
+                    RequestState::REJECTED => NotificationKind::REQUEST_REJECTED,
+                    _ => return Ok(()),
+                };
+                self.notify(
+                    *requester_id,
+                    kind,
+                    &[("request_kind", request_kind.to_string())],
+                )
+                .await
+            }
+            DomainEvent::COURT_BOOKING_CANCELLED {
+                court_name,
+                start_datetime,
+                users,
+                ..
+            } => {
+                self.notify_many(
+                    users.iter().copied(),
+                    NotificationKind::COURT_BOOKING_CANCELLED,
+                    &[
+                        ("court_name", court_name.clone()),
+                        (
+                            "start_datetime",
+                            start_datetime.format("%Y-%m-%d %H:%M").to_string(),
+                        ),
+                    ],
+                )
+                .await
+            }
+            DomainEvent::COURT_BOOKING_RELEASED {
+                court_name,
+                start_datetime,
+                id_user,
+                ..
+            } => {
+                self.notify(
+                    *id_user,
+                    NotificationKind::COURT_BOOKING_RELEASED,
+                    &[
+                        ("court_name", court_name.clone()),
+                        (
+                            "start_datetime",
+                            start_datetime.format("%Y-%m-%d %H:%M").to_string(),
+                        ),
+                    ],
+                )
+                .await
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
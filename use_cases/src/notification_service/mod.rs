@@ -0,0 +1,193 @@
+pub mod err;
+mod event_subscriber;
+pub mod notifier_trait;
+pub mod repository_trait;
+mod templates;
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use entities::audit::{Actor, AuditEntity};
+use entities::notification::{
+    DeliveryStatus, Notification, NotificationChannel, NotificationKind, NotificationMessage,
+    NotificationPreferences, NotificationPreferencesUpdate, Recipient,
+};
+use entities::pagination::{Page, PageQuery};
+use notifier_trait::Notifier;
+use repository_trait::NotificationRepository;
+use tracing::error;
+use uuid::Uuid;
+
+use self::err::{Error, Result};
+use crate::{audit_service::AuditService, user_service::UserService};
+
+#[derive(Clone)]
+pub struct NotificationService {
+    notification_repo: Arc<dyn NotificationRepository>,
+    notifiers: Vec<Arc<dyn Notifier>>,
+    user_service: UserService,
+    audit_service: AuditService,
+}
+
+impl NotificationService {
+    pub fn new(
+        notification_repo: Arc<dyn NotificationRepository>,
+        notifiers: Vec<Arc<dyn Notifier>>,
+        user_service: UserService,
+        audit_service: AuditService,
+    ) -> Self {
+        Self {
+            notification_repo,
+            notifiers,
+            user_service,
+            audit_service,
+        }
+    }
+
+    /// Sends the templated message through every channel the member enabled and records each
+    /// delivery. Notifications are a side effect of an operation that already succeeded, so
+    /// errors are logged instead of being reported to the caller
+    pub async fn notify(&self, user_id: Uuid, kind: NotificationKind, params: &[(&str, String)]) {
+        if let Err(err) = self.try_notify(user_id, kind, params).await {
+            error!("Error sending {kind} notification to user {user_id}: {err}");
+        }
+    }
+
+    pub async fn notify_many(
+        &self,
+        user_ids: impl IntoIterator<Item = Uuid>,
+        kind: NotificationKind,
+        params: &[(&str, String)],
+    ) {
+        for user_id in user_ids {
+            self.notify(user_id, kind, params).await;
+        }
+    }
+
+    pub async fn get_preferences(&self, user_id: Uuid) -> Result<NotificationPreferences> {
+        Ok(self
+            .notification_repo
+            .get_preferences(user_id)
+            .await?
+            .unwrap_or_else(|| NotificationPreferences::default_for(user_id)))
+    }
+
+    pub async fn update_preferences(
+        &self,
+        actor: &Actor,
+        user_id: Uuid,
+        update: NotificationPreferencesUpdate,
+    ) -> Result<NotificationPreferences> {
+        let before = self.get_preferences(user_id).await?;
+        let preferences = update.to_notification_preferences(user_id);
+
+        self.notification_repo
+            .save_preferences(&preferences)
+            .await?;
+
+        self.audit_service
+            .record_update(
+                actor,
+                "UPDATE_NOTIFICATION_PREFERENCES",
+                AuditEntity::NOTIFICATION_PREFERENCES,
+                user_id,
+                &before,
+                &preferences,
+            )
+            .await;
+
+        Ok(preferences)
+    }
+
+    pub async fn list_inbox_page(
+        &self,
+        user_id: Uuid,
+        page: &PageQuery,
+    ) -> Result<Page<Notification>> {
+        self.notification_repo.list_inbox_page(user_id, page).await
+    }
+
+    pub async fn list_user_notifications(&self, user_id: Uuid) -> Result<Vec<Notification>> {
+        self.notification_repo.list_user_notifications(user_id).await
+    }
+
+    pub async fn mark_as_read(&self, user_id: Uuid, id_notification: Uuid) -> Result<()> {
+        let updated = self
+            .notification_repo
+            .mark_notification_read(id_notification, user_id, Utc::now().naive_utc())
+            .await?;
+
+        if !updated {
+            return Err(Error::NotificationNotFound);
+        }
+
+        Ok(())
+    }
+
+    async fn try_notify(
+        &self,
+        user_id: Uuid,
+        kind: NotificationKind,
+        params: &[(&str, String)],
+    ) -> Result<()> {
+        let user = self.user_service.get_user_by_id(user_id).await?;
+        let preferences = self.get_preferences(user_id).await?;
+
+        let recipient = Recipient {
+            id_user: user.id_user,
+            first_name: user.first_name,
+            email: user.email,
+            phone_number: user.phone_number,
+            country_code: user.country_code,
+        };
+        let mut params = params.to_vec();
+        params.push(("first_name", recipient.first_name.clone()));
+        let message = templates::render(kind, preferences.language, &params);
+
+        for channel in preferences.enabled_channels() {
+            let delivery = self.deliver(channel, &recipient, &message).await;
+            let notification = Notification {
+                id_notification: Uuid::new_v4(),
+                id_user: user_id,
+                kind,
+                channel,
+                subject: message.subject.clone(),
+                body: message.body.clone(),
+                status: match delivery {
+                    Ok(()) => DeliveryStatus::SENT,
+                    Err(Error::ProviderNotConfigured(_)) => DeliveryStatus::SKIPPED,
+                    Err(_) => DeliveryStatus::FAILED,
+                },
+                error: delivery.err().map(|err| err.to_string()),
+                created_at: Utc::now().naive_utc(),
+                read_at: None,
+            };
+
+            self.notification_repo
+                .create_notification(&notification)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// In-app messages are delivered by storing them, the inbox reads them from the log
+    async fn deliver(
+        &self,
+        channel: NotificationChannel,
+        recipient: &Recipient,
+        message: &NotificationMessage,
+    ) -> Result<()> {
+        if channel == NotificationChannel::IN_APP {
+            return Ok(());
+        }
+
+        let notifier = self
+            .notifiers
+            .iter()
+            .find(|notifier| notifier.channel() == channel)
+            .ok_or(Error::ChannelUnavailable(channel.to_string()))?;
+
+        notifier.send(recipient, message).await
+    }
+}
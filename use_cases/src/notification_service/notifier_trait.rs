@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use entities::notification::{NotificationChannel, NotificationMessage, Recipient};
+
+use super::err::Result;
+
+/// Delivers rendered messages through one channel, e.g. an email provider or an SMS gateway
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    fn channel(&self) -> NotificationChannel;
+
+    async fn send(&self, recipient: &Recipient, message: &NotificationMessage) -> Result<()>;
+}
@@ -0,0 +1,25 @@
+use super::err::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use entities::notification::{Notification, NotificationPreferences};
+use entities::pagination::{Page, PageQuery};
+use uuid::Uuid;
+
+#[async_trait]
+pub trait NotificationRepository: Send + Sync {
+    async fn get_preferences(&self, user_id: Uuid) -> Result<Option<NotificationPreferences>>;
+    /// Inserts or replaces the preferences of the member
+    async fn save_preferences(&self, preferences: &NotificationPreferences) -> Result<()>;
+
+    async fn create_notification(&self, notification: &Notification) -> Result<()>;
+    async fn list_inbox_page(&self, user_id: Uuid, page: &PageQuery) -> Result<Page<Notification>>;
+    /// Every delivery log entry of the member, whatever its channel
+    async fn list_user_notifications(&self, user_id: Uuid) -> Result<Vec<Notification>>;
+    /// Returns false when the notification doesn't exist or belongs to another member
+    async fn mark_notification_read(
+        &self,
+        id_notification: Uuid,
+        user_id: Uuid,
+        read_at: NaiveDateTime,
+    ) -> Result<bool>;
+}
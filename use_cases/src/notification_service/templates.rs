@@ -0,0 +1,139 @@
+use entities::notification::{Language, NotificationKind, NotificationMessage};
+
+struct Template {
+    subject: &'static str,
+    body: &'static str,
+}
+
+fn template(kind: NotificationKind, language: Language) -> Template {
+    use Language::*;
+    use NotificationKind::*;
+
+    match (kind, language) {
+        (TRAINING_CANCELLED, ES) => Template {
+            subject: "Entrenamiento cancelado",
+            body: "Hola {first_name}, el entrenamiento {training_name} del {start_datetime} fue cancelado.",
+        },
+        (TRAINING_CANCELLED, EN) => Template {
+            subject: "Training cancelled",
+            body: "Hi {first_name}, the training {training_name} on {start_datetime} has been cancelled.",
+        },
+        (TRAINING_REMINDER, ES) => Template {
+            subject: "Recordatorio de entrenamiento",
+            body: "Hola {first_name}, te recordamos el entrenamiento {training_name} del {start_datetime}.",
+        },
+        (TRAINING_REMINDER, EN) => Template {
+            subject: "Training reminder",
+            body: "Hi {first_name}, this is a reminder of the training {training_name} on {start_datetime}.",
+        },
+        (TRAINING_SPOT_OFFERED, ES) => Template {
+            subject: "Se liberó un lugar en el entrenamiento",
+            body: "Hola {first_name}, se liberó un lugar en el entrenamiento {training_name} del {start_datetime}. Confirmalo antes del {confirm_before}.",
+        },
+        (TRAINING_SPOT_OFFERED, EN) => Template {
+            subject: "A training spot is available",
+            body: "Hi {first_name}, a spot freed up in the training {training_name} on {start_datetime}. Confirm it before {confirm_before}.",
+        },
+        (TOURNAMENT_RESULT, ES) => Template {
+            subject: "Resultado del torneo",
+            body: "Hola {first_name}, quedaste en la posición {position} del torneo {tournament_name}.",
+        },
+        (TOURNAMENT_RESULT, EN) => Template {
+            subject: "Tournament result",
+            body: "Hi {first_name}, you finished in position {position} of the tournament {tournament_name}.",
+        },
+        (REQUEST_APPROVED, ES) => Template {
+            subject: "Solicitud aprobada",
+            body: "Hola {first_name}, tu solicitud {request_kind} fue aprobada.",
+        },
+        (REQUEST_APPROVED, EN) => Template {
+            subject: "Request approved",
+            body: "Hi {first_name}, your {request_kind} request has been approved.",
+        },
+        (REQUEST_REJECTED, ES) => Template {
+            subject: "Solicitud rechazada",
+            body: "Hola {first_name}, tu solicitud {request_kind} fue rechazada.",
+        },
+        (REQUEST_REJECTED, EN) => Template {
+            subject: "Request rejected",
+            body: "Hi {first_name}, your {request_kind} request has been rejected.",
+        },
+        (TUITION_EXPIRING, ES) => Template {
+            subject: "Tu mensualidad está por vencer",
+            body: "Hola {first_name}, tu mensualidad vence el {expires_on}.",
+        },
+        (TUITION_EXPIRING, EN) => Template {
+            subject: "Your tuition is about to expire",
+            body: "Hi {first_name}, your tuition expires on {expires_on}.",
+        },
+        (COURT_BOOKING_CANCELLED, ES) => Template {
+            subject: "Reserva de cancha cancelada",
+            body: "Hola {first_name}, tu reserva de la cancha {court_name} del {start_datetime} fue cancelada por el club.",
+        },
+        (COURT_BOOKING_CANCELLED, EN) => Template {
+            subject: "Court booking cancelled",
+            body: "Hi {first_name}, your booking of the court {court_name} on {start_datetime} has been cancelled by the club.",
+        },
+        (COURT_BOOKING_RELEASED, ES) => Template {
+            subject: "Reserva de cancha liberada",
+            body: "Hola {first_name}, tu reserva de la cancha {court_name} del {start_datetime} fue liberada porque no se pagó a tiempo.",
+        },
+        (COURT_BOOKING_RELEASED, EN) => Template {
+            subject: "Court booking released",
+            body: "Hi {first_name}, your booking of the court {court_name} on {start_datetime} has been released because it wasn't paid in time.",
+        },
+    }
+}
+
+/// Fills the `{placeholders}` of the template with the given params, unknown ones are kept as is
+pub fn render(
+    kind: NotificationKind,
+    language: Language,
+    params: &[(&str, String)],
+) -> NotificationMessage {
+    let template = template(kind, language);
+    let fill = |text: &str| {
+        params.iter().fold(text.to_string(), |text, (key, value)| {
+            text.replace(&format!("{{{key}}}"), value)
+        })
+    };
+
+    NotificationMessage {
+        subject: fill(template.subject),
+        body: fill(template.body),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_fills_params() {
+        let message = render(
+            NotificationKind::TOURNAMENT_RESULT,
+            Language::EN,
+            &[
+                ("first_name", "Ana".to_string()),
+                ("position", "2".to_string()),
+                ("tournament_name", "Open".to_string()),
+            ],
+        );
+
+        assert_eq!(message.subject, "Tournament result");
+        assert_eq!(
+            message.body,
+            "Hi Ana, you finished in position 2 of the tournament Open."
+        );
+    }
+
+    #[test]
+    fn test_render_keeps_missing_params() {
+        let message = render(NotificationKind::TUITION_EXPIRING, Language::ES, &[]);
+
+        assert_eq!(
+            message.body,
+            "Hola {first_name}, tu mensualidad vence el {expires_on}."
+        );
+    }
+}
@@ -0,0 +1,36 @@
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("User service error: {0}")]
+    UserServiceError(#[from] crate::user_service::err::Error),
+
+    #[error("Category service error: {0}")]
+    CategoryServiceError(#[from] crate::category_service::err::Error),
+
+    #[error("Training service error: {0}")]
+    TrainingServiceError(#[from] crate::training_service::err::Error),
+
+    #[error("Tournament service error: {0}")]
+    TournamentServiceError(#[from] crate::tournament_service::err::Error),
+
+    #[error("Tuition service error: {0}")]
+    TuitionServiceError(#[from] crate::tuition_service::err::Error),
+
+    #[error("Request service error: {0}")]
+    RequestServiceError(#[from] crate::request_service::err::Error),
+
+    #[error("Notification service error: {0}")]
+    NotificationServiceError(#[from] crate::notification_service::err::Error),
+
+    #[error("The request is not a {0} request")]
+    InvalidRequestCommand(String),
+
+    #[error("The request has not been approved")]
+    RequestNotApproved,
+
+    #[error("Only the requester can access the result of this request")]
+    NotRequestOwner,
+}
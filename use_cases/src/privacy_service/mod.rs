@@ -0,0 +1,193 @@
+pub mod err;
+
+use chrono::Utc;
+use entities::{
+    audit::Actor,
+    privacy::UserDataExport,
+    request::{Request, RequestCommand, RequestKind, RequestState},
+};
+use err::{Error, Result};
+use uuid::Uuid;
+
+use crate::{
+    category_service::CategoryService,
+    notification_service::NotificationService,
+    request_service::{err::Error as RequestError, RequestService},
+    tournament_service::TournamentService,
+    training_service::TrainingService,
+    tuition_service::TuitionService,
+    user_service::UserService,
+};
+
+#[derive(Clone)]
+pub struct PrivacyService {
+    user_service: UserService,
+    category_service: CategoryService,
+    training_service: TrainingService,
+    tournament_service: TournamentService,
+    tuition_service: TuitionService,
+    request_service: RequestService,
+    notification_service: NotificationService,
+}
+
+impl PrivacyService {
+    pub fn new(
+        user_service: UserService,
+        category_service: CategoryService,
+        training_service: TrainingService,
+        tournament_service: TournamentService,
+        tuition_service: TuitionService,
+        request_service: RequestService,
+        notification_service: NotificationService,
+    ) -> Self {
+        Self {
+            user_service,
+            category_service,
+            training_service,
+            tournament_service,
+            tuition_service,
+            request_service,
+            notification_service,
+        }
+    }
+
+    pub async fn request_data_export(
+        &self,
+        actor: &Actor,
+        justification: String,
+    ) -> Result<Request> {
+        let _ = self.user_service.get_user_by_id(actor.user_id).await?;
+
+        let request = self
+            .request_service
+            .create_request(actor, RequestCommand::EXPORT_USER_DATA, justification)
+            .await?;
+
+        Ok(request)
+    }
+
+    /// The account is anonymized by the user service as soon as the request is approved
+    pub async fn request_account_erasure(
+        &self,
+        actor: &Actor,
+        justification: String,
+    ) -> Result<Request> {
+        let _ = self.user_service.get_user_by_id(actor.user_id).await?;
+
+        let request = self
+            .request_service
+            .create_request(actor, RequestCommand::ERASE_USER_ACCOUNT, justification)
+            .await?;
+
+        Ok(request)
+    }
+
+    /// Builds the data bundle of an approved export request. Only the requester can download it
+    pub async fn export_user_data(
+        &self,
+        request_id: Uuid,
+        requester_id: Uuid,
+    ) -> Result<UserDataExport> {
+        let request = self
+            .get_approved_request(request_id, RequestKind::EXPORT_USER_DATA)
+            .await?;
+
+        if request.requester_id != requester_id {
+            return Err(Error::NotRequestOwner);
+        }
+
+        self.collect_user_data(request.requester_id).await
+    }
+
+    async fn get_approved_request(&self, request_id: Uuid, kind: RequestKind) -> Result<Request> {
+        let request = self
+            .request_service
+            .get_request_by_id(request_id)
+            .await?
+            .ok_or(RequestError::RequestNotFound)?;
+
+        if request.requested_command.kind() != kind {
+            return Err(Error::InvalidRequestCommand(kind.to_string()));
+        }
+
+        if request.state != RequestState::APPROVED {
+            return Err(Error::RequestNotApproved);
+        }
+
+        Ok(request)
+    }
+
+    async fn collect_user_data(&self, user_id: Uuid) -> Result<UserDataExport> {
+        let (
+            profile,
+            categories,
+            training_registrations,
+            tournament_registrations,
+            tournament_attendances,
+            tuitions,
+            requests,
+            notifications,
+        ) = tokio::try_join!(
+            async {
+                self.user_service
+                    .get_user_by_id(user_id)
+                    .await
+                    .map_err(Error::from)
+            },
+            async {
+                self.category_service
+                    .get_user_categories(user_id)
+                    .await
+                    .map_err(Error::from)
+            },
+            async {
+                self.training_service
+                    .get_user_training_registrations(user_id)
+                    .await
+                    .map_err(Error::from)
+            },
+            async {
+                self.tournament_service
+                    .get_user_registrations(user_id)
+                    .await
+                    .map_err(Error::from)
+            },
+            async {
+                self.tournament_service
+                    .get_user_attendance(user_id)
+                    .await
+                    .map_err(Error::from)
+            },
+            async {
+                self.tuition_service
+                    .get_user_tuitions(user_id)
+                    .await
+                    .map_err(Error::from)
+            },
+            async {
+                self.request_service
+                    .list_user_requests(user_id)
+                    .await
+                    .map_err(Error::from)
+            },
+            async {
+                self.notification_service
+                    .list_user_notifications(user_id)
+                    .await
+                    .map_err(Error::from)
+            },
+        )?;
+
+        Ok(UserDataExport {
+            generated_at: Utc::now().naive_utc(),
+            profile,
+            categories,
+            training_registrations,
+            tournament_registrations,
+            tournament_attendances,
+            tuitions,
+            requests,
+            notifications,
+        })
+    }
+}
@@ -115,14 +115,7 @@ impl ReportService {
                 ReportError::ReportServiceError("Error getting the categories names".to_string())
             })?;
 
-        let training_summary = TrainingSummary {
-            total_registrations: training_registrations.len() as u32,
-            total_attendances: training_registrations.iter().filter(|r| r.attended).count() as u32,
-            most_recent_attendance: training_registrations
-                .iter()
-                .filter_map(|r| r.attendance_datetime.map(|dt| dt.date()))
-                .max(),
-        };
+        let training_summary = TrainingSummary::from_registrations(&training_registrations);
 
         let tournament_summary = TournamentSummary {
             total_registrations: tournament_registrations.len() as u32,
@@ -157,12 +150,8 @@ impl ReportService {
             .into_iter()
             .map(|r| UserRequest {
                 request_id: r.request_id,
-                requested_command: r.requested_command,
-                state: match r.approved {
-                    Some(true) => "APPROVED".to_string(),
-                    Some(false) => "REJECTED".to_string(),
-                    None => "PENDING".to_string(),
-                },
+                requested_command: r.requested_command.kind().to_string(),
+                state: r.state.to_string(),
             })
             .collect();
 
@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+
+use chrono::Duration;
+use entities::request::RequestKind;
+use entities::user::URol;
+
+const DEFAULT_VALIDITY_DAYS: i64 = 30;
+
+/// Roles that have to approve each kind of request, in order, and how long a request stays open
+#[derive(Debug, Clone)]
+pub struct ApprovalPolicy {
+    chains: HashMap<RequestKind, Vec<URol>>,
+    default_chain: Vec<URol>,
+    validity: Duration,
+}
+
+impl ApprovalPolicy {
+    pub fn new(default_chain: Vec<URol>, validity: Duration) -> Self {
+        Self {
+            chains: HashMap::new(),
+            default_chain,
+            validity,
+        }
+    }
+
+    pub fn with_chain(mut self, kind: RequestKind, chain: Vec<URol>) -> Self {
+        self.chains.insert(kind, chain);
+        self
+    }
+
+    /// Kinds without a chain of their own, or with an empty one, use the default chain
+    pub fn chain_for(&self, kind: RequestKind) -> &[URol] {
+        self.chains
+            .get(&kind)
+            .filter(|chain| !chain.is_empty())
+            .unwrap_or(&self.default_chain)
+    }
+
+    pub fn validity(&self) -> Duration {
+        self.validity
+    }
+}
+
+/// Trainers review what happens on the field before an admin signs it off
+impl Default for ApprovalPolicy {
+    fn default() -> Self {
+        Self::new(vec![URol::ADMIN], Duration::days(DEFAULT_VALIDITY_DAYS))
+            .with_chain(
+                RequestKind::CATEGORY_LEVEL_CHANGE,
+                vec![URol::TRAINER, URol::ADMIN],
+            )
+            .with_chain(
+                RequestKind::LATE_REGISTRATION,
+                vec![URol::TRAINER, URol::ADMIN],
+            )
+            .with_chain(RequestKind::COURT_BOOKING, vec![URol::TRAINER])
+    }
+}
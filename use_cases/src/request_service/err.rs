@@ -10,8 +10,24 @@ pub enum Error {
     RequestNotFound,
     #[error("Request already completed")]
     RequestAlreadyCompleted,
+    #[error("Request expired")]
+    RequestExpired,
     #[error("Cannot approve/reject your own request")]
     SelfApprovalNotAllowed,
-    #[error("Invalid approver ID")]
-    InvalidApprover,
+    #[error("The current approval step requires the {0} role")]
+    ApproverRoleNotAllowed(String),
+    #[error("The approver already decided another step of this request")]
+    ApproverAlreadyDecided,
+    #[error("The request was changed by someone else meanwhile, reload it")]
+    RequestChangedConcurrently,
+    #[error("Only the requester can withdraw the request")]
+    NotRequester,
+    #[error("Only the requester and its approvers can comment the request")]
+    CommentNotAllowed,
+    #[error("The comment is empty")]
+    EmptyComment,
+    #[error("Invalid request payload: {0}")]
+    InvalidRequestPayload(String),
+    #[error("Request execution failed: {0}")]
+    ExecutionFailed(String),
 }
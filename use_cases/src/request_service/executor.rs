@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+use entities::audit::Actor;
+use entities::request::{Request, RequestCommand};
+use uuid::Uuid;
+
+use super::err::Result;
+
+/// Implemented by the service that owns a kind of request. Executors are handed to the
+/// `RequestService` at start up so it never depends on the owning services directly
+#[async_trait]
+pub trait RequestExecutor: Send + Sync {
+    fn handles(&self, command: &RequestCommand) -> bool;
+
+    /// Checks the payload before the request is stored
+    async fn validate(&self, requester_id: Uuid, command: &RequestCommand) -> Result<()>;
+
+    /// Applies an approved request on behalf of its approver
+    async fn execute(&self, approver: &Actor, request: &Request) -> Result<()>;
+}
@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+use entities::audit::Actor;
+
+use super::RequestService;
+use crate::job_service::{
+    err::{Error, Result},
+    job_trait::Job,
+};
+
+/// Sweeps the open requests past their deadline
+pub struct RequestExpiryJob(pub RequestService);
+
+#[async_trait]
+impl Job for RequestExpiryJob {
+    fn name(&self) -> &'static str {
+        "request_expiry"
+    }
+
+    fn schedule(&self) -> &'static str {
+        "*/15 * * * *"
+    }
+
+    async fn run(&self, _actor: &Actor) -> Result<String> {
+        let expired = self
+            .0
+            .expire_overdue_requests()
+            .await
+            .map_err(|err| Error::JobFailed(err.to_string()))?;
+
+        Ok(format!("{expired} requests expired"))
+    }
+}
@@ -1,8 +1,21 @@
+pub mod approval_policy;
 pub mod err;
+pub mod executor;
+pub mod jobs;
 pub mod repository_trait;
 
 use self::err::{Error, Result};
-use entities::request::Request;
+use crate::{audit_service::AuditService, event_service::EventService};
+use approval_policy::ApprovalPolicy;
+use chrono::Utc;
+use entities::audit::{Actor, AuditEntity};
+use entities::event::{DomainEvent, OutboxEvent};
+use entities::pagination::{Page, PageQuery};
+use entities::request::{
+    Request, RequestCommand, RequestComment, RequestFilter, RequestState, RequestStep,
+};
+use entities::user::URol;
+use executor::RequestExecutor;
 use repository_trait::RequestRepository;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -10,61 +23,299 @@ use uuid::Uuid;
 #[derive(Clone)]
 pub struct RequestService {
     request_repo: Arc<dyn RequestRepository + Send + Sync>,
+    event_service: EventService,
+    audit_service: AuditService,
+    executors: Vec<Arc<dyn RequestExecutor>>,
+    approval_policy: ApprovalPolicy,
 }
 
 impl RequestService {
-    pub fn new(request_repo: Arc<dyn RequestRepository + Send + Sync>) -> Self {
-        Self { request_repo }
+    pub fn new(
+        request_repo: Arc<dyn RequestRepository + Send + Sync>,
+        event_service: EventService,
+        audit_service: AuditService,
+        executors: Vec<Arc<dyn RequestExecutor>>,
+        approval_policy: ApprovalPolicy,
+    ) -> Self {
+        Self {
+            request_repo,
+            event_service,
+            audit_service,
+            executors,
+            approval_policy,
+        }
     }
 
+    /// Stores the request of the actor together with the approval chain configured for its kind.
+    /// Requests are always filed in the actor's own name, the command says who it applies to
     pub async fn create_request(
         &self,
-        requester_id: Uuid,
-        requested_command: String,
+        actor: &Actor,
+        requested_command: RequestCommand,
         justification: String,
-    ) -> Result<()> {
+    ) -> Result<Request> {
+        let requester_id = actor.user_id;
+        if let Some(executor) = self.executor_for(&requested_command) {
+            executor.validate(requester_id, &requested_command).await?;
+        }
+
+        let created_at = Utc::now().naive_utc();
         let request = Request {
             request_id: Uuid::new_v4(),
             requester_id,
             requested_command,
             justification,
-            approved: None,
+            state: RequestState::PENDING,
+            current_step: 0,
             approver_id: None,
+            created_at,
+            expires_at: Some(created_at + self.approval_policy.validity()),
+            executed_at: None,
+            execution_error: None,
         };
 
-        self.request_repo.create_request(&request).await
+        let steps: Vec<RequestStep> = self
+            .approval_policy
+            .chain_for(request.requested_command.kind())
+            .iter()
+            .enumerate()
+            .map(|(step, approver_rol)| RequestStep {
+                request_id: request.request_id,
+                step: step as u32,
+                approver_rol: approver_rol.clone(),
+                approver_id: None,
+                approved: None,
+                decided_at: None,
+            })
+            .collect();
+        self.request_repo.create_request(&request, &steps).await?;
+
+        self.audit_service
+            .record_creation(
+                actor,
+                "CREATE_REQUEST",
+                AuditEntity::REQUEST,
+                request.request_id,
+                &request,
+            )
+            .await;
+
+        Ok(request)
     }
 
+    /// Decides the current approval step. The actor needs the role of the step, admins can decide
+    /// any step, and nobody decides two steps of the same request. A rejection closes the
+    /// request, approving the last step applies it through its owning service and a failure to
+    /// apply it is stored on the request
     pub async fn complete_request(
         &self,
+        actor: &Actor,
         request_id: Uuid,
-        approver_id: Uuid,
         approved: bool,
-    ) -> Result<()> {
-        let mut request = self
-            .request_repo
-            .get_request_by_id(request_id)
-            .await?
-            .ok_or(Error::RequestNotFound)?;
+    ) -> Result<Request> {
+        let approver_id = actor.user_id;
+        let mut request = self.get_open_request(actor, request_id).await?;
 
         if request.requester_id == approver_id {
             return Err(Error::SelfApprovalNotAllowed);
         }
 
-        if request.approved.is_some() {
-            return Err(Error::RequestAlreadyCompleted);
+        let steps = self.request_repo.list_request_steps(request_id).await?;
+        if steps
+            .iter()
+            .any(|step| step.approver_id == Some(approver_id))
+        {
+            return Err(Error::ApproverAlreadyDecided);
+        }
+
+        let mut step = steps
+            .iter()
+            .find(|step| step.step == request.current_step)
+            .cloned()
+            .ok_or_else(|| {
+                Error::UnknownDatabaseError(format!(
+                    "Request {request_id} has no approval step {}",
+                    request.current_step
+                ))
+            })?;
+
+        if actor.user_rol != step.approver_rol && actor.user_rol != URol::ADMIN {
+            return Err(Error::ApproverRoleNotAllowed(step.approver_rol.to_string()));
+        }
+
+        step.approver_id = Some(approver_id);
+        step.approved = Some(approved);
+        step.decided_at = Some(Utc::now().naive_utc());
+
+        let before = request.clone();
+        let is_last_step = step.step as usize + 1 >= steps.len();
+        if !approved {
+            request.state = RequestState::REJECTED;
+            request.approver_id = Some(approver_id);
+        } else if is_last_step {
+            request.state = RequestState::APPROVED;
+            request.approver_id = Some(approver_id);
+        } else {
+            request.state = RequestState::IN_REVIEW;
+            request.current_step += 1;
+        }
+
+        // A rejection completes the request with the decision, an approval once its command ran
+        let rejected_event = (request.state == RequestState::REJECTED)
+            .then(|| self.completed_event(actor, &request));
+
+        // Only one of several approvers deciding at once moves the request on, so the command
+        // is executed once
+        if !self
+            .request_repo
+            .decide_request_step(
+                &request,
+                &step,
+                before.state,
+                before.current_step,
+                rejected_event.as_ref(),
+            )
+            .await?
+        {
+            return Err(Error::RequestChangedConcurrently);
+        }
+
+        if request.state == RequestState::APPROVED {
+            let approved_event = self.completed_event(actor, &request);
+            self.execute(actor, &mut request).await;
+            self.request_repo
+                .update_request(&request, Some(&approved_event))
+                .await?;
+        }
+
+        self.audit_service
+            .record_update(
+                actor,
+                "COMPLETE_REQUEST",
+                AuditEntity::REQUEST,
+                request_id,
+                &before,
+                &request,
+            )
+            .await;
+
+        Ok(request)
+    }
+
+    fn completed_event(&self, actor: &Actor, request: &Request) -> OutboxEvent {
+        self.event_service.event(
+            actor,
+            DomainEvent::REQUEST_COMPLETED {
+                request_id: request.request_id,
+                requester_id: request.requester_id,
+                request_kind: request.requested_command.kind(),
+                state: request.state,
+            },
+        )
+    }
+
+    /// Only the requester can withdraw, and only while the request is still open
+    pub async fn withdraw_request(&self, actor: &Actor, request_id: Uuid) -> Result<Request> {
+        let mut request = self.get_open_request(actor, request_id).await?;
+
+        if request.requester_id != actor.user_id {
+            return Err(Error::NotRequester);
+        }
+
+        let before = request.clone();
+        request.state = RequestState::WITHDRAWN;
+        if !self
+            .request_repo
+            .update_open_request(&request, before.state, before.current_step)
+            .await?
+        {
+            return Err(Error::RequestChangedConcurrently);
+        }
+
+        self.audit_service
+            .record_update(
+                actor,
+                "WITHDRAW_REQUEST",
+                AuditEntity::REQUEST,
+                request_id,
+                &before,
+                &request,
+            )
+            .await;
+
+        Ok(request)
+    }
+
+    /// The requester, admins and the roles of the approval chain can comment an open request
+    pub async fn add_comment(
+        &self,
+        actor: &Actor,
+        request_id: Uuid,
+        body: String,
+    ) -> Result<RequestComment> {
+        let body = body.trim().to_string();
+        if body.is_empty() {
+            return Err(Error::EmptyComment);
         }
 
-        request.approved = Some(approved);
-        request.approver_id = Some(approver_id);
+        let request = self.get_open_request(actor, request_id).await?;
+        if request.requester_id != actor.user_id && actor.user_rol != URol::ADMIN {
+            let steps = self.request_repo.list_request_steps(request_id).await?;
+            if !steps.iter().any(|step| step.approver_rol == actor.user_rol) {
+                return Err(Error::CommentNotAllowed);
+            }
+        }
+
+        let comment = RequestComment {
+            id_comment: Uuid::new_v4(),
+            request_id,
+            author_id: actor.user_id,
+            body,
+            created_at: Utc::now().naive_utc(),
+        };
+        self.request_repo.create_request_comment(&comment).await?;
 
-        self.request_repo.update_request(&request).await
+        self.audit_service
+            .record_creation(
+                actor,
+                "COMMENT_REQUEST",
+                AuditEntity::REQUEST_COMMENT,
+                comment.id_comment,
+                &comment,
+            )
+            .await;
+
+        Ok(comment)
+    }
+
+    pub async fn list_comments(&self, request_id: Uuid) -> Result<Vec<RequestComment>> {
+        self.request_repo.list_request_comments(request_id).await
+    }
+
+    pub async fn list_steps(&self, request_id: Uuid) -> Result<Vec<RequestStep>> {
+        self.request_repo.list_request_steps(request_id).await
+    }
+
+    /// Expires every open request past its deadline. Requests touched through the service are
+    /// expired on access as well, this sweeps the ones nobody looks at
+    pub async fn expire_overdue_requests(&self) -> Result<u64> {
+        self.request_repo
+            .expire_requests(Utc::now().naive_utc())
+            .await
     }
 
     pub async fn list_requests(&self) -> Result<Vec<Request>> {
         self.request_repo.list_requests().await
     }
 
+    pub async fn list_requests_page(
+        &self,
+        filter: &RequestFilter,
+        page: &PageQuery,
+    ) -> Result<Page<Request>> {
+        self.request_repo.list_requests_page(filter, page).await
+    }
+
     pub async fn list_user_requests(&self, user_id: Uuid) -> Result<Vec<Request>> {
         self.request_repo.list_requests_by_user(user_id).await
     }
@@ -72,4 +323,55 @@ impl RequestService {
     pub async fn get_request_by_id(&self, id: Uuid) -> Result<Option<Request>> {
         self.request_repo.get_request_by_id(id).await
     }
+
+    /// Loads a request that still accepts changes, expiring it first if its deadline passed
+    async fn get_open_request(&self, actor: &Actor, request_id: Uuid) -> Result<Request> {
+        let mut request = self
+            .request_repo
+            .get_request_by_id(request_id)
+            .await?
+            .ok_or(Error::RequestNotFound)?;
+
+        let now = Utc::now().naive_utc();
+        if request.state.is_open() && request.expires_at.is_some_and(|deadline| deadline <= now) {
+            let before = request.clone();
+            request.state = RequestState::EXPIRED;
+            self.request_repo.update_request(&request, None).await?;
+
+            self.audit_service
+                .record_update(
+                    actor,
+                    "EXPIRE_REQUEST",
+                    AuditEntity::REQUEST,
+                    request_id,
+                    &before,
+                    &request,
+                )
+                .await;
+        }
+
+        match request.state {
+            RequestState::EXPIRED => Err(Error::RequestExpired),
+            state if !state.is_open() => Err(Error::RequestAlreadyCompleted),
+            _ => Ok(request),
+        }
+    }
+
+    async fn execute(&self, approver: &Actor, request: &mut Request) {
+        let Some(executor) = self.executor_for(&request.requested_command) else {
+            return;
+        };
+
+        match executor.execute(approver, request).await {
+            Ok(()) => request.executed_at = Some(Utc::now().naive_utc()),
+            Err(Error::ExecutionFailed(reason)) => request.execution_error = Some(reason),
+            Err(err) => request.execution_error = Some(err.to_string()),
+        }
+    }
+
+    fn executor_for(&self, command: &RequestCommand) -> Option<&Arc<dyn RequestExecutor>> {
+        self.executors
+            .iter()
+            .find(|executor| executor.handles(command))
+    }
 }
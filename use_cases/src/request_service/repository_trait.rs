@@ -1,13 +1,50 @@
 use super::err::Result;
 use async_trait::async_trait;
-use entities::request::Request;
+use chrono::NaiveDateTime;
+use entities::event::OutboxEvent;
+use entities::pagination::{Page, PageQuery};
+use entities::request::{Request, RequestComment, RequestFilter, RequestState, RequestStep};
 use uuid::Uuid;
 
 #[async_trait]
 pub trait RequestRepository: Send + Sync {
-    async fn create_request(&self, request: &Request) -> Result<()>;
+    /// Stores the request together with its approval chain, all or none
+    async fn create_request(&self, request: &Request, steps: &[RequestStep]) -> Result<()>;
     async fn get_request_by_id(&self, id: Uuid) -> Result<Option<Request>>;
-    async fn update_request(&self, request: &Request) -> Result<()>;
+    /// `event` is stored in the outbox along with the request
+    async fn update_request(&self, request: &Request, event: Option<&OutboxEvent>) -> Result<()>;
+    /// Saves the request only while it is still in `state` at `current_step`. False when someone
+    /// else changed it first
+    async fn update_open_request(
+        &self,
+        request: &Request,
+        state: RequestState,
+        current_step: u32,
+    ) -> Result<bool>;
     async fn list_requests(&self) -> Result<Vec<Request>>;
+    async fn list_requests_page(
+        &self,
+        filter: &RequestFilter,
+        page: &PageQuery,
+    ) -> Result<Page<Request>>;
     async fn list_requests_by_user(&self, user_id: Uuid) -> Result<Vec<Request>>;
+    /// Moves every open request whose deadline is before `now` to EXPIRED, returns how many
+    async fn expire_requests(&self, now: NaiveDateTime) -> Result<u64>;
+
+    async fn update_request_step(&self, step: &RequestStep) -> Result<()>;
+    /// Records the decision of the step and moves the request on in one transaction, under the
+    /// same condition as `update_open_request`. A step already decided is left untouched. `event`
+    /// is stored in the outbox only if the decision is recorded
+    async fn decide_request_step(
+        &self,
+        request: &Request,
+        step: &RequestStep,
+        state: RequestState,
+        current_step: u32,
+        event: Option<&OutboxEvent>,
+    ) -> Result<bool>;
+    async fn list_request_steps(&self, request_id: Uuid) -> Result<Vec<RequestStep>>;
+
+    async fn create_request_comment(&self, comment: &RequestComment) -> Result<()>;
+    async fn list_request_comments(&self, request_id: Uuid) -> Result<Vec<RequestComment>>;
 }
@@ -0,0 +1,32 @@
+use entities::schedule::ScheduleEntry;
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum Error {
+    #[error("Database error: {0}")]
+    UnknownDatabaseError(String),
+    #[error("The schedule window must end after it starts and last at most a year")]
+    InvalidWindow,
+    #[error("Only admins can see the schedule of someone else")]
+    NotAllowed,
+    #[error("The user already has sessions at that time: {}", describe_conflicts(.0))]
+    ScheduleConflict(Vec<ScheduleEntry>),
+}
+
+fn describe_conflicts(conflicts: &[ScheduleEntry]) -> String {
+    conflicts
+        .iter()
+        .map(|entry| {
+            format!(
+                "{} {} ({} - {})",
+                entry.kind,
+                entry.name,
+                entry.start_datetime.format("%Y-%m-%d %H:%M"),
+                entry.end_datetime.format("%Y-%m-%d %H:%M")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,173 @@
+pub mod err;
+pub mod repository_trait;
+
+use std::sync::Arc;
+
+use chrono::{Duration, NaiveDateTime, Utc};
+use entities::audit::Actor;
+use entities::schedule::{ScheduleConflictQuery, ScheduleEntry, ScheduleQuery};
+use entities::user::URol;
+use repository_trait::ScheduleRepository;
+use uuid::Uuid;
+
+use self::err::{Error, Result};
+
+/// Days shown when the window has no end
+const DEFAULT_WINDOW_DAYS: i64 = 30;
+const MAX_WINDOW_DAYS: i64 = 366;
+
+/// Timeline of a user across trainings, tournaments and court bookings, used to keep the
+/// registrations of a user from overlapping
+#[derive(Clone)]
+pub struct ScheduleService {
+    schedule_repo: Arc<dyn ScheduleRepository>,
+}
+
+impl ScheduleService {
+    pub fn new(schedule_repo: Arc<dyn ScheduleRepository>) -> Self {
+        Self { schedule_repo }
+    }
+
+    /// Users see their own schedule, admins anyone's
+    pub async fn get_user_schedule(
+        &self,
+        actor: &Actor,
+        id_user: Uuid,
+        query: &ScheduleQuery,
+    ) -> Result<Vec<ScheduleEntry>> {
+        check_access(actor, id_user)?;
+        let (from, to) = schedule_window(query, Utc::now().naive_utc())?;
+        self.schedule_repo
+            .list_user_schedule(id_user, from, to)
+            .await
+    }
+
+    /// Sessions the user would miss, so clients can warn before registering
+    pub async fn get_conflicts(
+        &self,
+        actor: &Actor,
+        id_user: Uuid,
+        query: &ScheduleConflictQuery,
+    ) -> Result<Vec<ScheduleEntry>> {
+        check_access(actor, id_user)?;
+        if query.start_datetime >= query.end_datetime {
+            return Err(Error::InvalidWindow);
+        }
+        self.find_conflicts(
+            id_user,
+            query.start_datetime,
+            query.end_datetime,
+            query.id_source,
+        )
+        .await
+    }
+
+    /// Fails with the sessions of the user overlapping the window. The session `id_source`
+    /// doesn't conflict with itself, e.g. when a trainer's training is moved
+    pub async fn ensure_free(
+        &self,
+        id_user: Uuid,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+        id_source: Uuid,
+    ) -> Result<()> {
+        let conflicts = self
+            .find_conflicts(id_user, start, end, Some(id_source))
+            .await?;
+        if !conflicts.is_empty() {
+            return Err(Error::ScheduleConflict(conflicts));
+        }
+        Ok(())
+    }
+
+    /// Sessions of the user overlapping the window, leaving out the session `id_source`
+    pub async fn find_conflicts(
+        &self,
+        id_user: Uuid,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+        id_source: Option<Uuid>,
+    ) -> Result<Vec<ScheduleEntry>> {
+        let entries = self
+            .schedule_repo
+            .list_user_schedule(id_user, start, end)
+            .await?;
+        Ok(entries
+            .into_iter()
+            .filter(|entry| Some(entry.id_source) != id_source)
+            .collect())
+    }
+}
+
+fn check_access(actor: &Actor, id_user: Uuid) -> Result<()> {
+    if actor.user_id != id_user && actor.user_rol != URol::ADMIN {
+        return Err(Error::NotAllowed);
+    }
+    Ok(())
+}
+
+fn schedule_window(
+    query: &ScheduleQuery,
+    now: NaiveDateTime,
+) -> Result<(NaiveDateTime, NaiveDateTime)> {
+    let from = query.from.unwrap_or(now);
+    let to = query
+        .to
+        .unwrap_or(from + Duration::days(DEFAULT_WINDOW_DAYS));
+    if to <= from || to - from > Duration::days(MAX_WINDOW_DAYS) {
+        return Err(Error::InvalidWindow);
+    }
+    Ok((from, to))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn day(day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 6, day)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_schedule_window() {
+        assert_eq!(
+            schedule_window(&ScheduleQuery::default(), day(1)),
+            Ok((day(1), day(1) + Duration::days(30)))
+        );
+        assert_eq!(
+            schedule_window(
+                &ScheduleQuery {
+                    from: Some(day(2)),
+                    to: Some(day(9)),
+                },
+                day(1)
+            ),
+            Ok((day(2), day(9)))
+        );
+        assert_eq!(
+            schedule_window(
+                &ScheduleQuery {
+                    from: None,
+                    to: Some(day(1)),
+                },
+                day(1)
+            ),
+            Err(Error::InvalidWindow)
+        );
+        assert_eq!(
+            schedule_window(
+                &ScheduleQuery {
+                    from: Some(day(1)),
+                    to: Some(day(1) + Duration::days(400)),
+                },
+                day(1)
+            ),
+            Err(Error::InvalidWindow)
+        );
+    }
+}
@@ -0,0 +1,18 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use entities::schedule::ScheduleEntry;
+use uuid::Uuid;
+
+use super::err::Result;
+
+#[async_trait]
+pub trait ScheduleRepository: Send + Sync {
+    /// Trainings and tournaments the user is registered to, trainings the user leads and court
+    /// bookings the user plays in, overlapping `from` until `to` and sorted by start
+    async fn list_user_schedule(
+        &self,
+        id_user: Uuid,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<Vec<ScheduleEntry>>;
+}
@@ -1,18 +1,25 @@
 pub mod err;
 pub mod repository_trait;
+mod request_executor;
 
 use crate::{
+    audit_service::AuditService,
     category_service::CategoryService,
     court_service::{self, CourtService}, // Added CourtService
+    event_service::EventService,
+    schedule_service::ScheduleService,
 };
 
 use self::err::{Error, Result};
 use chrono::{Duration, NaiveDateTime, Utc};
 use entities::{
-    court::CourtReservationCreation, // Added
+    audit::{Actor, AuditEntity},
+    court::CourtSelection,
+    event::{DomainEvent, OutboxEvent},
+    pagination::{Page, PageQuery},
     tournament::{
         Tournament, TournamentAttendance, TournamentAttendanceRequest, TournamentCreation,
-        TournamentRegistration, TournamentRegistrationRequest,
+        TournamentFilter, TournamentRegistration, TournamentRegistrationRequest,
     },
 };
 use repository_trait::{
@@ -31,15 +38,22 @@ pub struct TournamentService {
     attendance_repo: Arc<dyn TournamentAttendanceRepository>,
     category_service: CategoryService,
     court_service: CourtService, // Added
+    schedule_service: ScheduleService,
+    event_service: EventService,
+    audit_service: AuditService,
 }
 
 impl TournamentService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         tournament_repo: Arc<dyn TournamentRepository>,
         registration_repo: Arc<dyn TournamentRegistrationRepository>,
         attendance_repo: Arc<dyn TournamentAttendanceRepository>,
         category_service: CategoryService,
         court_service: CourtService, // Added
+        schedule_service: ScheduleService,
+        event_service: EventService,
+        audit_service: AuditService,
     ) -> Self {
         Self {
             tournament_repo,
@@ -47,13 +61,17 @@ impl TournamentService {
             attendance_repo,
             category_service,
             court_service, // Added
+            schedule_service,
+            event_service,
+            audit_service,
         }
     }
 
     pub async fn create_tournament(
         &self,
+        actor: &Actor,
         tournament_creation: TournamentCreation,
-        id_court_to_reserve: Option<Uuid>,
+        court_selection: Option<CourtSelection>,
     ) -> Result<Tournament> {
         validate_event_duration(
             tournament_creation.start_datetime,
@@ -71,36 +89,49 @@ impl TournamentService {
 
         self.tournament_repo.create_tournament(&tournament).await?;
 
-        if let Some(id_court) = id_court_to_reserve {
-            let reservation_creation = CourtReservationCreation {
-                id_court,
-                start_reservation_datetime: tournament.start_datetime,
-                end_reservation_datetime: tournament.end_datetime,
-                id_training: None,
-                id_tournament: Some(tournament.id_tournament),
-            };
+        if court_selection.is_some() {
             if let Err(e) = self
                 .court_service
-                .create_reservation(reservation_creation)
+                .set_reservations_for_event(
+                    actor,
+                    tournament.id_tournament,
+                    "tournament",
+                    tournament.start_datetime,
+                    tournament.end_datetime,
+                    court_selection.as_ref(),
+                )
                 .await
             {
                 // Rollback or inform. For now, delete tournament.
-                self.tournament_repo.delete_tournament(tournament.id_tournament).await.unwrap_or_else(|del_err| {
+                self.tournament_repo.delete_tournament(tournament.id_tournament, None).await.unwrap_or_else(|del_err| {
                     tracing::error!("Failed to rollback tournament creation after court reservation failure: {}", del_err);
                 });
                 return Err(Error::CourtServiceError(e));
             }
         }
+
+        self.audit_service
+            .record_creation(
+                actor,
+                "CREATE_TOURNAMENT",
+                AuditEntity::TOURNAMENT,
+                tournament.id_tournament,
+                &tournament,
+            )
+            .await;
+
         Ok(tournament)
     }
 
     pub async fn update_tournament(
         &self,
+        actor: &Actor,
         tournament_id: Uuid,
         tournament_update_payload: TournamentCreation,
-        id_court_to_reserve: Option<Uuid>,
+        court_selection: Option<CourtSelection>,
     ) -> Result<Tournament> {
         let mut tournament = self.get_tournament(tournament_id).await?;
+        let before = tournament.clone();
 
         validate_event_duration(
             tournament_update_payload.start_datetime,
@@ -119,52 +150,31 @@ impl TournamentService {
         tournament.start_datetime = tournament_update_payload.start_datetime;
         tournament.end_datetime = tournament_update_payload.end_datetime;
 
-        // Handle court reservation change
-        if let Some(existing_res) = self
-            .court_service
-            .get_reservation_for_tournament(tournament_id)
-            .await // Now returns Option
-            .map_err(Error::CourtServiceError)?
-        {
-            let times_changed = existing_res.start_reservation_datetime
-                != tournament.start_datetime
-                || existing_res.end_reservation_datetime != tournament.end_datetime;
-            let court_changed =
-                id_court_to_reserve.is_some() && Some(existing_res.id_court) != id_court_to_reserve;
-
-            if times_changed || court_changed || id_court_to_reserve.is_none() {
-                self.court_service
-                    .delete_reservation_for_event(tournament_id, "tournament")
-                    .await
-                    .map_err(Error::CourtServiceError)?;
-            }
-        }
-
-        if let Some(id_court) = id_court_to_reserve {
-            if self
-                .court_service
-                .get_reservation_for_tournament(tournament_id)
-                .await?
-                .is_none()
-            {
-                let reservation_creation = CourtReservationCreation {
-                    id_court,
-                    start_reservation_datetime: tournament.start_datetime,
-                    end_reservation_datetime: tournament.end_datetime,
-                    id_training: None,
-                    id_tournament: Some(tournament.id_tournament),
-                };
-                if let Err(e) = self
-                    .court_service
-                    .create_reservation(reservation_creation)
-                    .await
-                {
-                    return Err(Error::CourtServiceError(e));
-                }
-            }
-        }
+        // Without a selection the tournament releases its courts
+        self.court_service
+            .set_reservations_for_event(
+                actor,
+                tournament_id,
+                "tournament",
+                tournament.start_datetime,
+                tournament.end_datetime,
+                court_selection.as_ref(),
+            )
+            .await?;
 
         self.tournament_repo.update_tournament(&tournament).await?;
+
+        self.audit_service
+            .record_update(
+                actor,
+                "UPDATE_TOURNAMENT",
+                AuditEntity::TOURNAMENT,
+                tournament_id,
+                &before,
+                &tournament,
+            )
+            .await;
+
         Ok(tournament)
     }
 
@@ -175,37 +185,78 @@ impl TournamentService {
             .ok_or(Error::TournamentNotFound)
     }
 
-    pub async fn delete_tournament(&self, id: Uuid) -> Result<()> {
-        let _ = self.get_tournament(id).await?; // Ensures tournament exists
+    pub async fn delete_tournament(&self, actor: &Actor, id: Uuid) -> Result<()> {
+        let tournament = self.get_tournament(id).await?; // Ensures tournament exists
 
-        if let Err(e) = self
-            .court_service
-            .delete_reservation_for_event(id, "tournament")
-            .await
-        {
-            tracing::warn!(
-                "Could not delete court reservation for tournament {}: {}",
+        // The court reservation is released by its subscriber
+        let event = self.event_service.event(
+            actor,
+            DomainEvent::TOURNAMENT_CANCELLED { id_tournament: id },
+        );
+        self.tournament_repo
+            .delete_tournament(id, Some(&event))
+            .await?;
+
+        self.audit_service
+            .record_deletion(
+                actor,
+                "DELETE_TOURNAMENT",
+                AuditEntity::TOURNAMENT,
                 id,
-                e
-            );
-        }
-        self.tournament_repo.delete_tournament(id).await
+                &tournament,
+            )
+            .await;
+
+        Ok(())
     }
 
     pub async fn list_tournaments(&self) -> Result<Vec<Tournament>> {
         self.tournament_repo.list_tournaments().await
     }
 
+    pub async fn list_tournaments_page(
+        &self,
+        filter: &TournamentFilter,
+        page: &PageQuery,
+    ) -> Result<Page<Tournament>> {
+        self.tournament_repo
+            .list_tournaments_page(filter, page)
+            .await
+    }
+
     pub async fn register_user(
         &self,
+        actor: &Actor,
+        registration_payload: TournamentRegistrationRequest,
+        tournament_id: Uuid,
+    ) -> Result<TournamentRegistration> {
+        self.register(actor, registration_payload, tournament_id, false)
+            .await
+    }
+
+    /// Registers the user after the tournament has started, granted by an approved request
+    pub async fn register_user_late(
+        &self,
+        actor: &Actor,
+        registration_payload: TournamentRegistrationRequest,
+        tournament_id: Uuid,
+    ) -> Result<TournamentRegistration> {
+        self.register(actor, registration_payload, tournament_id, true)
+            .await
+    }
+
+    async fn register(
+        &self,
+        actor: &Actor,
         registration_payload: TournamentRegistrationRequest,
         tournament_id: Uuid,
+        late: bool,
     ) -> Result<TournamentRegistration> {
         let tournament = self.get_tournament(tournament_id).await?;
 
         let now = Utc::now().naive_utc();
 
-        if now >= tournament.start_datetime {
+        if !late && now >= tournament.start_datetime {
             return Err(Error::InvalidRegistrationDate);
         }
 
@@ -226,6 +277,15 @@ impl TournamentService {
             return Err(Error::UserAlreadyRegistered);
         }
 
+        self.schedule_service
+            .ensure_free(
+                registration_payload.id_user,
+                tournament.start_datetime,
+                tournament.end_datetime,
+                tournament_id,
+            )
+            .await?;
+
         // Use registration_payload directly as it now contains all necessary fields including id_tournament
         let registration_to_create = TournamentRegistration {
             id_tournament: tournament_id,
@@ -233,15 +293,33 @@ impl TournamentService {
             registration_datetime: Utc::now().naive_utc(), // Set current time
         };
 
+        let event = self.event_service.event(
+            actor,
+            DomainEvent::TOURNAMENT_REGISTERED {
+                id_tournament: tournament_id,
+                id_user: registration_to_create.id_user,
+            },
+        );
         self.registration_repo
-            .register_user_for_tournament(&registration_to_create) // Corrected variable name
+            .register_user_for_tournament(&registration_to_create, Some(&event)) // Corrected variable name
             .await?;
 
+        self.audit_service
+            .record_creation(
+                actor,
+                "REGISTER_USER_FOR_TOURNAMENT",
+                AuditEntity::TOURNAMENT_REGISTRATION,
+                format!("{tournament_id}/{}", registration_to_create.id_user),
+                &registration_to_create,
+            )
+            .await;
+
         Ok(registration_to_create) // Return the object that was actually created
     }
 
     pub async fn record_attendance(
         &self,
+        actor: &Actor,
         attendance_payload: TournamentAttendanceRequest,
         tournament_id: Uuid,
     ) -> Result<TournamentAttendance> {
@@ -299,10 +377,21 @@ impl TournamentService {
             position: attendance_payload.position,
         };
 
+        let event = self.result_event(actor, &tournament, attendance.id_user, attendance.position);
         self.attendance_repo
-            .record_tournament_attendance(&attendance)
+            .record_tournament_attendance(&attendance, Some(&event))
             .await?;
 
+        self.audit_service
+            .record_creation(
+                actor,
+                "RECORD_TOURNAMENT_ATTENDANCE",
+                AuditEntity::TOURNAMENT_ATTENDANCE,
+                format!("{tournament_id}/{}", attendance.id_user),
+                &attendance,
+            )
+            .await;
+
         Ok(attendance)
     }
 
@@ -324,14 +413,15 @@ impl TournamentService {
 
     pub async fn update_position(
         &self,
+        actor: &Actor,
         tournament_id: Uuid,
         user_id: Uuid,
         new_position: i32,
     ) -> Result<()> {
-        let _ = self.get_tournament(tournament_id).await?;
+        let tournament = self.get_tournament(tournament_id).await?;
 
         // Ensure user attended
-        let _ = self
+        let before = self
             .attendance_repo
             .get_tournament_attendance_by_user(tournament_id, user_id)
             .await?
@@ -353,9 +443,45 @@ impl TournamentService {
             return Err(Error::PositionAlreadyTaken);
         }
 
+        let event = self.result_event(actor, &tournament, user_id, new_position);
         self.attendance_repo
-            .update_tournament_position(tournament_id, user_id, new_position)
-            .await
+            .update_tournament_position(tournament_id, user_id, new_position, Some(&event))
+            .await?;
+
+        let after = TournamentAttendance {
+            position: new_position,
+            ..before.clone()
+        };
+        self.audit_service
+            .record_update(
+                actor,
+                "UPDATE_TOURNAMENT_POSITION",
+                AuditEntity::TOURNAMENT_ATTENDANCE,
+                format!("{tournament_id}/{user_id}"),
+                &before,
+                &after,
+            )
+            .await;
+
+        Ok(())
+    }
+
+    fn result_event(
+        &self,
+        actor: &Actor,
+        tournament: &Tournament,
+        id_user: Uuid,
+        position: i32,
+    ) -> OutboxEvent {
+        self.event_service.event(
+            actor,
+            DomainEvent::TOURNAMENT_RESULT_RECORDED {
+                id_tournament: tournament.id_tournament,
+                tournament_name: tournament.name.clone(),
+                id_user,
+                position,
+            },
+        )
     }
 
     pub async fn get_eligible_tournaments(&self, user_id: Uuid) -> Result<Vec<Tournament>> {
@@ -384,30 +510,64 @@ impl TournamentService {
             .await
     }
 
-    pub async fn delete_attendance(&self, tournament_id: Uuid, user_id: Uuid) -> Result<()> {
+    pub async fn delete_attendance(
+        &self,
+        actor: &Actor,
+        tournament_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<()> {
         let _ = self.get_tournament(tournament_id).await?;
         // Check if attendance exists before deleting
-        let _ = self
+        let attendance = self
             .attendance_repo
             .get_tournament_attendance_by_user(tournament_id, user_id)
             .await?
             .ok_or(Error::UserDidNotAttend)?;
         self.attendance_repo
             .delete_attendance(tournament_id, user_id)
-            .await
+            .await?;
+
+        self.audit_service
+            .record_deletion(
+                actor,
+                "DELETE_TOURNAMENT_ATTENDANCE",
+                AuditEntity::TOURNAMENT_ATTENDANCE,
+                format!("{tournament_id}/{user_id}"),
+                &attendance,
+            )
+            .await;
+
+        Ok(())
     }
 
-    pub async fn delete_registration(&self, tournament_id: Uuid, user_id: Uuid) -> Result<()> {
+    pub async fn delete_registration(
+        &self,
+        actor: &Actor,
+        tournament_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<()> {
         let _ = self.get_tournament(tournament_id).await?;
         // Check if registration exists before deleting
-        let _ = self
+        let registration = self
             .registration_repo
             .get_tournament_registration(tournament_id, user_id)
             .await?
             .ok_or(Error::UserNotRegistered)?;
         self.registration_repo
             .delete_registration(tournament_id, user_id)
-            .await
+            .await?;
+
+        self.audit_service
+            .record_deletion(
+                actor,
+                "DELETE_TOURNAMENT_REGISTRATION",
+                AuditEntity::TOURNAMENT_REGISTRATION,
+                format!("{tournament_id}/{user_id}"),
+                &registration,
+            )
+            .await;
+
+        Ok(())
     }
 
     pub async fn get_user_attendance(&self, user_id: Uuid) -> Result<Vec<TournamentAttendance>> {
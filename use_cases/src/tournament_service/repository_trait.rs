@@ -1,16 +1,26 @@
 use super::err::Result;
 use async_trait::async_trait;
-use entities::tournament::{Tournament, TournamentAttendance, TournamentRegistration};
+use entities::event::OutboxEvent;
+use entities::pagination::{Page, PageQuery};
+use entities::tournament::{
+    Tournament, TournamentAttendance, TournamentFilter, TournamentRegistration,
+};
 use uuid::Uuid;
 
-/// Trait defining tournament-related operations
+/// Trait defining tournament-related operations. The given `event` is stored in the outbox in
+/// the transaction of the operation
 #[async_trait]
 pub trait TournamentRepository: Send + Sync {
     async fn create_tournament(&self, tournament: &Tournament) -> Result<()>;
     async fn get_tournament_by_id(&self, id: Uuid) -> Result<Option<Tournament>>;
     async fn update_tournament(&self, tournament: &Tournament) -> Result<()>;
-    async fn delete_tournament(&self, id: Uuid) -> Result<()>;
+    async fn delete_tournament(&self, id: Uuid, event: Option<&OutboxEvent>) -> Result<()>;
     async fn list_tournaments(&self) -> Result<Vec<Tournament>>;
+    async fn list_tournaments_page(
+        &self,
+        filter: &TournamentFilter,
+        page: &PageQuery,
+    ) -> Result<Page<Tournament>>;
 }
 
 #[async_trait]
@@ -18,6 +28,7 @@ pub trait TournamentRegistrationRepository: Send + Sync {
     async fn register_user_for_tournament(
         &self,
         registration: &TournamentRegistration,
+        event: Option<&OutboxEvent>,
     ) -> Result<()>;
     async fn get_tournament_registrations(
         &self,
@@ -35,7 +46,11 @@ pub trait TournamentRegistrationRepository: Send + Sync {
 
 #[async_trait]
 pub trait TournamentAttendanceRepository: Send + Sync {
-    async fn record_tournament_attendance(&self, attendance: &TournamentAttendance) -> Result<()>;
+    async fn record_tournament_attendance(
+        &self,
+        attendance: &TournamentAttendance,
+        event: Option<&OutboxEvent>,
+    ) -> Result<()>;
     async fn get_tournament_attendance(
         &self,
         tournament_id: Uuid,
@@ -51,6 +66,7 @@ pub trait TournamentAttendanceRepository: Send + Sync {
         tournament_id: Uuid,
         user_id: Uuid,
         position: i32,
+        event: Option<&OutboxEvent>,
     ) -> Result<()>;
     async fn delete_attendance(&self, tournament_id: Uuid, user_id: Uuid) -> Result<()>;
 }
@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use entities::audit::Actor;
+use entities::request::{Request, RequestCommand};
+use entities::tournament::TournamentRegistrationRequest;
+use uuid::Uuid;
+
+use super::TournamentService;
+use crate::request_service::{
+    err::{Error, Result},
+    executor::RequestExecutor,
+};
+
+#[async_trait]
+impl RequestExecutor for TournamentService {
+    fn handles(&self, command: &RequestCommand) -> bool {
+        matches!(
+            command,
+            RequestCommand::LATE_REGISTRATION(registration)
+                if registration.id_tournament.is_some() && registration.id_training.is_none()
+        )
+    }
+
+    async fn validate(&self, _requester_id: Uuid, command: &RequestCommand) -> Result<()> {
+        let RequestCommand::LATE_REGISTRATION(registration) = command else {
+            return Ok(());
+        };
+        let Some(id_tournament) = registration.id_tournament else {
+            return Ok(());
+        };
+
+        self.get_tournament(id_tournament)
+            .await
+            .map_err(|err| Error::InvalidRequestPayload(err.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn execute(&self, approver: &Actor, request: &Request) -> Result<()> {
+        let RequestCommand::LATE_REGISTRATION(registration) = &request.requested_command else {
+            return Ok(());
+        };
+        let Some(id_tournament) = registration.id_tournament else {
+            return Ok(());
+        };
+
+        let registration_payload = TournamentRegistrationRequest {
+            id_user: registration.user_id,
+        };
+        self.register_user_late(approver, registration_payload, id_tournament)
+            .await
+            .map(|_| ())
+            .map_err(|err| Error::ExecutionFailed(err.to_string()))
+    }
+}
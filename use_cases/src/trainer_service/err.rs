@@ -0,0 +1,30 @@
+use thiserror::Error;
+
+use crate::schedule_service;
+use crate::user_service;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum Error {
+    #[error("Database error: {0}")]
+    UnknownDatabaseError(String),
+    #[error("The user is not a trainer")]
+    NotATrainer,
+    #[error("Only the trainer or an admin can manage the availability of a trainer")]
+    NotAllowed,
+    #[error("Invalid availability: {0}")]
+    InvalidAvailability(String),
+    #[error("Invalid window: end must be after start")]
+    InvalidWindow,
+    #[error("Time off not found")]
+    TimeOffNotFound,
+    #[error("The trainer is off at that time: {0}")]
+    TrainerOnTimeOff(String),
+    #[error("The training is outside the weekly availability of the trainer")]
+    OutsideAvailability,
+    #[error("Error in user service: {0}")]
+    UserServiceError(#[from] user_service::err::Error),
+    #[error("Error in schedule service: {0}")]
+    ScheduleServiceError(#[from] schedule_service::err::Error),
+}
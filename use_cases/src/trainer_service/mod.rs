@@ -0,0 +1,354 @@
+pub mod err;
+pub mod repository_trait;
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::{Datelike, NaiveDateTime};
+use entities::audit::{Actor, AuditEntity};
+use entities::court::DayOfWeek;
+use entities::trainer::{
+    AvailableTrainer, AvailableTrainersQuery, TrainerAvailability, TrainerTimeOff,
+    TrainerTimeOffCreation,
+};
+use entities::user::URol;
+use repository_trait::TrainerRepository;
+use uuid::Uuid;
+
+use self::err::{Error, Result};
+use crate::audit_service::AuditService;
+use crate::schedule_service::ScheduleService;
+use crate::user_service::UserService;
+
+/// Weekly availability and time off of the trainers, checked before a training is assigned to
+/// one of them
+#[derive(Clone)]
+pub struct TrainerService {
+    trainer_repo: Arc<dyn TrainerRepository>,
+    user_service: UserService,
+    schedule_service: ScheduleService,
+    audit_service: AuditService,
+}
+
+impl TrainerService {
+    pub fn new(
+        trainer_repo: Arc<dyn TrainerRepository>,
+        user_service: UserService,
+        schedule_service: ScheduleService,
+        audit_service: AuditService,
+    ) -> Self {
+        Self {
+            trainer_repo,
+            user_service,
+            schedule_service,
+            audit_service,
+        }
+    }
+
+    pub async fn get_availability(&self, id_trainer: Uuid) -> Result<Vec<TrainerAvailability>> {
+        self.check_trainer(id_trainer).await?;
+        self.trainer_repo.get_availability(id_trainer).await
+    }
+
+    /// Replaces the weekly availability of the trainer, an empty week leaves the trainer
+    /// available at any time
+    pub async fn set_availability(
+        &self,
+        actor: &Actor,
+        id_trainer: Uuid,
+        mut availability: Vec<TrainerAvailability>,
+    ) -> Result<Vec<TrainerAvailability>> {
+        check_access(actor, id_trainer)?;
+        self.check_trainer(id_trainer).await?;
+
+        validate_availability(&availability)?;
+        availability.sort_by_key(|hours| hours.day_of_week);
+
+        let before = self.trainer_repo.get_availability(id_trainer).await?;
+        self.trainer_repo
+            .set_availability(id_trainer, &availability)
+            .await?;
+
+        self.audit_service
+            .record_update(
+                actor,
+                "UPDATE_TRAINER_AVAILABILITY",
+                AuditEntity::USER,
+                id_trainer,
+                &before,
+                &availability,
+            )
+            .await;
+
+        Ok(availability)
+    }
+
+    /// Trainings already assigned in the window keep their trainer, they have to be moved or
+    /// reassigned apart
+    pub async fn create_time_off(
+        &self,
+        actor: &Actor,
+        id_trainer: Uuid,
+        time_off_creation: TrainerTimeOffCreation,
+    ) -> Result<TrainerTimeOff> {
+        check_access(actor, id_trainer)?;
+        self.check_trainer(id_trainer).await?;
+        if time_off_creation.start_datetime >= time_off_creation.end_datetime {
+            return Err(Error::InvalidWindow);
+        }
+
+        let time_off = TrainerTimeOff {
+            id_time_off: Uuid::new_v4(),
+            id_trainer,
+            reason: time_off_creation.reason,
+            start_datetime: time_off_creation.start_datetime,
+            end_datetime: time_off_creation.end_datetime,
+        };
+        self.trainer_repo.create_time_off(&time_off).await?;
+
+        self.audit_service
+            .record_creation(
+                actor,
+                "CREATE_TRAINER_TIME_OFF",
+                AuditEntity::TRAINER_TIME_OFF,
+                time_off.id_time_off,
+                &time_off,
+            )
+            .await;
+
+        Ok(time_off)
+    }
+
+    /// The reasons of a time off are only shown to the trainer and the admins
+    pub async fn get_time_off(
+        &self,
+        actor: &Actor,
+        id_trainer: Uuid,
+        start_datetime_filter: Option<NaiveDateTime>,
+        end_datetime_filter: Option<NaiveDateTime>,
+    ) -> Result<Vec<TrainerTimeOff>> {
+        check_access(actor, id_trainer)?;
+        let start = start_datetime_filter.unwrap_or_else(|| {
+            NaiveDateTime::parse_from_str("1970-01-01 00:00:00", "%Y-%m-%d %H:%M:%S").unwrap()
+        });
+        let end = end_datetime_filter.unwrap_or_else(|| {
+            NaiveDateTime::parse_from_str("9999-12-31 23:59:59", "%Y-%m-%d %H:%M:%S").unwrap()
+        });
+
+        self.trainer_repo
+            .get_time_off_in_range(id_trainer, start, end)
+            .await
+    }
+
+    pub async fn delete_time_off(&self, actor: &Actor, id_time_off: Uuid) -> Result<()> {
+        let time_off = self
+            .trainer_repo
+            .get_time_off_by_id(id_time_off)
+            .await?
+            .ok_or(Error::TimeOffNotFound)?;
+        check_access(actor, time_off.id_trainer)?;
+
+        self.trainer_repo.delete_time_off(id_time_off).await?;
+
+        self.audit_service
+            .record_deletion(
+                actor,
+                "DELETE_TRAINER_TIME_OFF",
+                AuditEntity::TRAINER_TIME_OFF,
+                id_time_off,
+                &time_off,
+            )
+            .await;
+
+        Ok(())
+    }
+
+    /// Fails when the trainer is off during the range or it falls outside the weekly
+    /// availability. Sessions the trainer already has are checked by the schedule service
+    pub async fn ensure_available(
+        &self,
+        id_trainer: Uuid,
+        start_time: NaiveDateTime,
+        end_time: NaiveDateTime,
+    ) -> Result<()> {
+        let time_off = self
+            .trainer_repo
+            .get_time_off_in_range(id_trainer, start_time, end_time)
+            .await?;
+        if let Some(time_off) = time_off.into_iter().next() {
+            return Err(Error::TrainerOnTimeOff(time_off.reason));
+        }
+
+        let availability = self.trainer_repo.get_availability(id_trainer).await?;
+        if within_availability(&availability, start_time, end_time) {
+            Ok(())
+        } else {
+            Err(Error::OutsideAvailability)
+        }
+    }
+
+    /// Trainers who could lead a training in the slot: available, not off and without other
+    /// sessions at that time
+    pub async fn get_available_trainers(
+        &self,
+        query: &AvailableTrainersQuery,
+    ) -> Result<Vec<AvailableTrainer>> {
+        if query.start_datetime >= query.end_datetime {
+            return Err(Error::InvalidWindow);
+        }
+
+        let mut available = Vec::new();
+        for trainer in self.trainer_repo.list_trainers().await? {
+            match self
+                .ensure_available(trainer.id_trainer, query.start_datetime, query.end_datetime)
+                .await
+            {
+                Ok(()) => {}
+                Err(Error::TrainerOnTimeOff(_) | Error::OutsideAvailability) => continue,
+                Err(err) => return Err(err),
+            }
+
+            let conflicts = self
+                .schedule_service
+                .find_conflicts(
+                    trainer.id_trainer,
+                    query.start_datetime,
+                    query.end_datetime,
+                    query.id_training,
+                )
+                .await?;
+            if conflicts.is_empty() {
+                available.push(trainer);
+            }
+        }
+
+        Ok(available)
+    }
+
+    async fn check_trainer(&self, id_trainer: Uuid) -> Result<()> {
+        let trainer = self.user_service.get_user_by_id(id_trainer).await?;
+        if trainer.user_rol != URol::TRAINER {
+            return Err(Error::NotATrainer);
+        }
+        Ok(())
+    }
+}
+
+/// The trainer, or an admin
+fn check_access(actor: &Actor, id_trainer: Uuid) -> Result<()> {
+    if actor.user_id != id_trainer && actor.user_rol != URol::ADMIN {
+        return Err(Error::NotAllowed);
+    }
+    Ok(())
+}
+
+fn validate_availability(availability: &[TrainerAvailability]) -> Result<()> {
+    let mut days = HashSet::new();
+    for hours in availability {
+        if hours.start_time >= hours.end_time {
+            return Err(Error::InvalidAvailability(format!(
+                "{} ends before it starts",
+                hours.day_of_week
+            )));
+        }
+        if !days.insert(hours.day_of_week) {
+            return Err(Error::InvalidAvailability(format!(
+                "{} is repeated",
+                hours.day_of_week
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Same rule as the opening hours of the courts, the range has to start and end while the
+/// trainer is available
+fn within_availability(
+    availability: &[TrainerAvailability],
+    start_time: NaiveDateTime,
+    end_time: NaiveDateTime,
+) -> bool {
+    if availability.is_empty() {
+        return true;
+    }
+
+    let hours_of = |datetime: NaiveDateTime| {
+        let day_of_week = DayOfWeek::from(datetime.weekday());
+        availability
+            .iter()
+            .find(|hours| hours.day_of_week == day_of_week)
+    };
+    let available_at_start = hours_of(start_time).is_some_and(|hours| {
+        hours.start_time <= start_time.time() && start_time.time() < hours.end_time
+    });
+    let available_until_end = hours_of(end_time).is_some_and(|hours| {
+        hours.start_time < end_time.time() && end_time.time() <= hours.end_time
+    });
+
+    available_at_start && available_until_end
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveTime};
+
+    use super::*;
+
+    fn hours(day_of_week: DayOfWeek, start: u32, end: u32) -> TrainerAvailability {
+        TrainerAvailability {
+            day_of_week,
+            start_time: NaiveTime::from_hms_opt(start, 0, 0).unwrap(),
+            end_time: NaiveTime::from_hms_opt(end, 0, 0).unwrap(),
+        }
+    }
+
+    /// 2025-06-02 is a monday
+    fn monday_at(hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 6, 2)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_within_availability() {
+        assert!(within_availability(&[], monday_at(3), monday_at(5)));
+
+        let week = [
+            hours(DayOfWeek::MONDAY, 16, 20),
+            hours(DayOfWeek::WEDNESDAY, 8, 12),
+        ];
+        assert!(within_availability(&week, monday_at(16), monday_at(18)));
+        assert!(within_availability(&week, monday_at(18), monday_at(20)));
+        assert!(!within_availability(&week, monday_at(15), monday_at(17)));
+        assert!(!within_availability(&week, monday_at(19), monday_at(21)));
+        // Tuesday is off once the trainer declares a week
+        assert!(!within_availability(
+            &week,
+            monday_at(16) + chrono::Duration::days(1),
+            monday_at(18) + chrono::Duration::days(1)
+        ));
+    }
+
+    #[test]
+    fn test_validate_availability() {
+        assert_eq!(
+            validate_availability(&[
+                hours(DayOfWeek::MONDAY, 16, 20),
+                hours(DayOfWeek::FRIDAY, 8, 12)
+            ]),
+            Ok(())
+        );
+        assert!(matches!(
+            validate_availability(&[hours(DayOfWeek::MONDAY, 20, 16)]),
+            Err(Error::InvalidAvailability(_))
+        ));
+        assert!(matches!(
+            validate_availability(&[
+                hours(DayOfWeek::MONDAY, 8, 12),
+                hours(DayOfWeek::MONDAY, 16, 20)
+            ]),
+            Err(Error::InvalidAvailability(_))
+        ));
+    }
+}
@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use entities::trainer::{AvailableTrainer, TrainerAvailability, TrainerTimeOff};
+use uuid::Uuid;
+
+use super::err::Result;
+
+#[async_trait]
+pub trait TrainerRepository: Send + Sync {
+    /// Users with the trainer role, sorted by name
+    async fn list_trainers(&self) -> Result<Vec<AvailableTrainer>>;
+    async fn get_availability(&self, id_trainer: Uuid) -> Result<Vec<TrainerAvailability>>;
+    /// Replaces the whole week of the trainer
+    async fn set_availability(
+        &self,
+        id_trainer: Uuid,
+        availability: &[TrainerAvailability],
+    ) -> Result<()>;
+    async fn create_time_off(&self, time_off: &TrainerTimeOff) -> Result<()>;
+    async fn get_time_off_by_id(&self, id_time_off: Uuid) -> Result<Option<TrainerTimeOff>>;
+    /// Time off of the trainer overlapping the range, sorted by start
+    async fn get_time_off_in_range(
+        &self,
+        id_trainer: Uuid,
+        start_time: NaiveDateTime,
+        end_time: NaiveDateTime,
+    ) -> Result<Vec<TrainerTimeOff>>;
+    async fn delete_time_off(&self, id_time_off: Uuid) -> Result<()>;
+}
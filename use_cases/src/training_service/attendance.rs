@@ -0,0 +1,222 @@
+use std::collections::HashSet;
+
+use chrono::{NaiveDateTime, Utc};
+use entities::audit::{Actor, AuditEntity};
+use entities::training::{
+    AttendanceStatus, AttendanceSubmission, Training, TrainingRegistration, TrainingRosterEntry,
+};
+use entities::user::URol;
+use uuid::Uuid;
+
+use super::err::{Error, Result};
+use super::TrainingService;
+
+const MAX_ATTENDANCE_NOTES_CHARS: usize = 500;
+
+impl TrainingService {
+    /// Members of the training with their attendance, for the trainer to take it
+    pub async fn get_training_roster(
+        &self,
+        actor: &Actor,
+        training_id: Uuid,
+    ) -> Result<Vec<TrainingRosterEntry>> {
+        let training = self.get_training(training_id).await?;
+        check_training_staff(actor, &training)?;
+
+        self.registration_repo
+            .get_training_roster(training_id)
+            .await
+    }
+
+    /// Saves the attendance of several members at once, all or none. Once the training ends
+    /// it can be corrected until the grace period passes
+    pub async fn submit_attendance(
+        &self,
+        actor: &Actor,
+        training_id: Uuid,
+        submission: &AttendanceSubmission,
+    ) -> Result<Vec<TrainingRegistration>> {
+        let training = self.get_training(training_id).await?;
+        check_training_staff(actor, &training)?;
+
+        let now = Utc::now().naive_utc();
+        if now <= training.start_datetime {
+            return Err(Error::InvalidAssistanceDate);
+        }
+        if now >= training.end_datetime + self.attendance_grace_period
+            || self
+                .training_repo
+                .is_training_attendance_closed(training_id)
+                .await?
+        {
+            return Err(Error::AttendanceClosed);
+        }
+
+        let mut submitted = HashSet::new();
+        if let Some(record) = submission
+            .records
+            .iter()
+            .find(|record| !submitted.insert(record.id_user))
+        {
+            return Err(Error::DuplicatedAttendanceRecord(record.id_user));
+        }
+
+        let registrations = self
+            .registration_repo
+            .get_training_registrations(training_id)
+            .await?;
+        let mut before = Vec::with_capacity(submission.records.len());
+        let mut after = Vec::with_capacity(submission.records.len());
+        for record in &submission.records {
+            let registration = registrations
+                .iter()
+                .find(|registration| registration.id_user == record.id_user)
+                .ok_or(Error::UserNotRegistered)?;
+            before.push(registration.clone());
+            after.push(record_attendance(
+                registration,
+                record.status,
+                normalize_notes(record.notes.clone())?,
+                actor.user_id,
+                now,
+            ));
+        }
+
+        self.registration_repo
+            .save_training_attendance(&after)
+            .await?;
+
+        self.audit_service
+            .record_update(
+                actor,
+                "SUBMIT_TRAINING_ATTENDANCE",
+                AuditEntity::TRAINING,
+                training_id,
+                &before,
+                &after,
+            )
+            .await;
+
+        Ok(after)
+    }
+}
+
+/// The trainer leading the training, or an admin
+pub(super) fn check_training_staff(actor: &Actor, training: &Training) -> Result<()> {
+    if actor.user_id != training.trainer_id && actor.user_rol != URol::ADMIN {
+        return Err(Error::NotTrainingTrainer);
+    }
+    Ok(())
+}
+
+/// The registration with its new attendance. Members still attending keep the time they were
+/// first marked
+pub(super) fn record_attendance(
+    registration: &TrainingRegistration,
+    status: AttendanceStatus,
+    notes: Option<String>,
+    submitted_by: Uuid,
+    now: NaiveDateTime,
+) -> TrainingRegistration {
+    let attendance_datetime = match (status.attended(), registration.attended) {
+        (false, _) => None,
+        (true, true) => registration.attendance_datetime.or(Some(now)),
+        (true, false) => Some(now),
+    };
+
+    TrainingRegistration {
+        attended: status.attended(),
+        attendance_datetime,
+        attendance_status: Some(status),
+        attendance_submitted_by: Some(submitted_by),
+        attendance_notes: notes,
+        ..registration.clone()
+    }
+}
+
+/// Blank notes are left empty
+pub(super) fn normalize_notes(notes: Option<String>) -> Result<Option<String>> {
+    let notes = notes
+        .map(|notes| notes.trim().to_string())
+        .filter(|notes| !notes.is_empty());
+    if notes
+        .as_ref()
+        .is_some_and(|notes| notes.chars().count() > MAX_ATTENDANCE_NOTES_CHARS)
+    {
+        return Err(Error::AttendanceNotesTooLong(MAX_ATTENDANCE_NOTES_CHARS));
+    }
+    Ok(notes)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDate};
+
+    use super::*;
+
+    fn at(hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 6, 7)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_record_attendance() {
+        let trainer = Uuid::new_v4();
+        let registration = TrainingRegistration {
+            id_training: Uuid::new_v4(),
+            id_user: Uuid::new_v4(),
+            registration_datetime: at(8),
+            attended: false,
+            attendance_datetime: None,
+            attendance_status: None,
+            attendance_submitted_by: None,
+            attendance_notes: None,
+        };
+
+        let late = record_attendance(&registration, AttendanceStatus::LATE, None, trainer, at(18));
+        assert!(late.attended);
+        assert_eq!(late.attendance_datetime, Some(at(18)));
+        assert_eq!(late.attendance_status, Some(AttendanceStatus::LATE));
+        assert_eq!(late.attendance_submitted_by, Some(trainer));
+
+        // A correction keeps the time the member was first marked
+        let corrected = record_attendance(
+            &late,
+            AttendanceStatus::PRESENT,
+            None,
+            trainer,
+            at(18) + Duration::hours(3),
+        );
+        assert_eq!(corrected.attendance_datetime, Some(at(18)));
+
+        let excused = record_attendance(
+            &late,
+            AttendanceStatus::EXCUSED,
+            Some("School exam".to_string()),
+            trainer,
+            at(20),
+        );
+        assert!(!excused.attended);
+        assert_eq!(excused.attendance_datetime, None);
+        assert_eq!(excused.attendance_status, Some(AttendanceStatus::EXCUSED));
+        assert_eq!(excused.attendance_notes.as_deref(), Some("School exam"));
+    }
+
+    #[test]
+    fn test_normalize_notes() {
+        assert!(matches!(normalize_notes(None), Ok(None)));
+        assert!(matches!(normalize_notes(Some("  ".to_string())), Ok(None)));
+        assert_eq!(
+            normalize_notes(Some(" Sprained ankle ".to_string()))
+                .unwrap()
+                .as_deref(),
+            Some("Sprained ankle")
+        );
+        assert!(matches!(
+            normalize_notes(Some("a".repeat(MAX_ATTENDANCE_NOTES_CHARS + 1))),
+            Err(Error::AttendanceNotesTooLong(_))
+        ));
+    }
+}
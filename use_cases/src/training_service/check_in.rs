@@ -0,0 +1,179 @@
+use chrono::{DateTime, Duration, NaiveDateTime, Timelike, Utc};
+use entities::audit::{Actor, AuditEntity};
+use entities::training::{AttendanceStatus, TrainingCheckInToken, TrainingRegistration};
+use uuid::Uuid;
+
+use super::attendance::{check_training_staff, record_attendance};
+use super::err::{Error, Result};
+use super::TrainingService;
+
+/// Lifetime of a check-in token. A screenshot sent around stops working before it's useful,
+/// the screen showing the code fetches a new one before it expires
+const CHECK_IN_TOKEN_SECONDS: i64 = 30;
+/// Keeps the signatures of the check-in tokens apart from anything else signed with the key
+const CHECK_IN_SIGNATURE_SCOPE: &str = "training-check-in";
+
+impl TrainingService {
+    /// Token for the QR code the trainer shows while the training runs
+    pub async fn issue_check_in_token(
+        &self,
+        actor: &Actor,
+        training_id: Uuid,
+    ) -> Result<TrainingCheckInToken> {
+        let training = self.get_training(training_id).await?;
+        check_training_staff(actor, &training)?;
+
+        let now = Utc::now().naive_utc();
+        if now <= training.start_datetime || now >= training.end_datetime {
+            return Err(Error::InvalidAssistanceDate);
+        }
+
+        // Whole seconds, as written in the token
+        let expires_datetime = (now + Duration::seconds(CHECK_IN_TOKEN_SECONDS))
+            .with_nanosecond(0)
+            .unwrap_or(now);
+        let payload = check_in_payload(training_id, expires_datetime);
+        let signature = self.check_in_signer.sign(&signed_content(&payload))?;
+
+        Ok(TrainingCheckInToken {
+            id_training: training_id,
+            token: format!("{payload}.{signature}"),
+            expires_datetime,
+        })
+    }
+
+    /// Marks the logged in member present with a scanned token
+    pub async fn check_in(&self, actor: &Actor, token: &str) -> Result<TrainingRegistration> {
+        let (training_id, expires_datetime, signature) = parse_check_in_token(token)?;
+        let payload = check_in_payload(training_id, expires_datetime);
+        if !self
+            .check_in_signer
+            .verify(&signed_content(&payload), signature)?
+        {
+            return Err(Error::InvalidCheckInToken);
+        }
+        if Utc::now().naive_utc() > expires_datetime {
+            return Err(Error::CheckInTokenExpired);
+        }
+
+        self.check_in_member(actor, training_id).await
+    }
+
+    /// Marks the logged in member present while the training runs. Notes left by the trainer
+    /// are kept
+    async fn check_in_member(
+        &self,
+        actor: &Actor,
+        training_id: Uuid,
+    ) -> Result<TrainingRegistration> {
+        let training = self.get_training(training_id).await?;
+
+        let now = Utc::now().naive_utc();
+        if now <= training.start_datetime || now >= training.end_datetime {
+            return Err(Error::InvalidAssistanceDate);
+        }
+
+        let before = self
+            .registration_repo
+            .get_training_registration(training_id, actor.user_id)
+            .await?
+            .ok_or(Error::UserNotRegistered)?;
+
+        let after = record_attendance(
+            &before,
+            AttendanceStatus::PRESENT,
+            before.attendance_notes.clone(),
+            actor.user_id,
+            now,
+        );
+
+        self.registration_repo
+            .save_training_attendance(std::slice::from_ref(&after))
+            .await?;
+
+        self.audit_service
+            .record_update(
+                actor,
+                "CHECK_IN_TRAINING",
+                AuditEntity::TRAINING_REGISTRATION,
+                format!("{training_id}/{}", actor.user_id),
+                &before,
+                &after,
+            )
+            .await;
+
+        Ok(after)
+    }
+}
+
+fn check_in_payload(training_id: Uuid, expires_datetime: NaiveDateTime) -> String {
+    format!(
+        "{}.{}",
+        training_id.simple(),
+        expires_datetime.and_utc().timestamp()
+    )
+}
+
+fn signed_content(payload: &str) -> String {
+    format!("{CHECK_IN_SIGNATURE_SCOPE}.{payload}")
+}
+
+/// Splits `<training>.<expiration timestamp>.<signature>`
+fn parse_check_in_token(token: &str) -> Result<(Uuid, NaiveDateTime, &str)> {
+    let mut parts = token.trim().split('.');
+    let (Some(training_id), Some(expires), Some(signature), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(Error::InvalidCheckInToken);
+    };
+
+    let training_id = Uuid::parse_str(training_id).map_err(|_| Error::InvalidCheckInToken)?;
+    let expires_datetime = expires
+        .parse::<i64>()
+        .ok()
+        .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0))
+        .ok_or(Error::InvalidCheckInToken)?
+        .naive_utc();
+
+    Ok((training_id, expires_datetime, signature))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    #[test]
+    fn test_check_in_token_round_trip() {
+        let training_id = Uuid::new_v4();
+        let expires = NaiveDate::from_ymd_opt(2025, 6, 7)
+            .unwrap()
+            .and_hms_opt(18, 30, 15)
+            .unwrap();
+        let token = format!("{}.abc123", check_in_payload(training_id, expires));
+
+        let (parsed_id, parsed_expires, signature) = parse_check_in_token(&token).unwrap();
+        assert_eq!(parsed_id, training_id);
+        assert_eq!(parsed_expires, expires);
+        assert_eq!(signature, "abc123");
+    }
+
+    #[test]
+    fn test_malformed_check_in_tokens() {
+        let training_id = Uuid::new_v4().simple();
+        for token in [
+            "",
+            "garbage",
+            &format!("{training_id}.1749321015"),
+            &format!("{training_id}.1749321015.abc.extra"),
+            &format!("{training_id}.tomorrow.abc"),
+            "not-a-uuid.1749321015.abc",
+        ] {
+            assert!(
+                matches!(parse_check_in_token(token), Err(Error::InvalidCheckInToken)),
+                "{token}"
+            );
+        }
+    }
+}
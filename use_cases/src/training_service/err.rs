@@ -29,6 +29,50 @@ pub enum Error {
     #[error("Training registration not found")]
     RegistrationNotFound,
 
+    #[error("Training is full, join its waitlist to get the next free spot")]
+    TrainingFull,
+
+    #[error("Training has free spots or no participant limit, register instead")]
+    TrainingNotFull,
+
+    #[error("User already on the waitlist of this training")]
+    AlreadyOnWaitlist,
+
+    #[error("User not on the waitlist of this training")]
+    WaitlistEntryNotFound,
+
+    #[error("No spot is held for the user, or its confirmation deadline passed")]
+    NoSpotOffered,
+
+    #[error(
+        "Only the member, the trainer of the training or an admin can manage its waitlist entry"
+    )]
+    NotWaitlistMember,
+
+    #[error("The training has {0} registered members, its capacity can't be lower")]
+    CapacityBelowRegistrations(usize),
+
+    #[error("Only the trainer of the training or an admin can take its attendance")]
+    NotTrainingTrainer,
+
+    #[error("The attendance of the training is closed, its grace period for corrections passed")]
+    AttendanceClosed,
+
+    #[error("The attendance of the user {0} was submitted more than once")]
+    DuplicatedAttendanceRecord(uuid::Uuid),
+
+    #[error("Attendance notes can't be longer than {0} characters")]
+    AttendanceNotesTooLong(usize),
+
+    #[error("Invalid check-in code")]
+    InvalidCheckInToken,
+
+    #[error("The check-in code expired, scan the current one")]
+    CheckInTokenExpired,
+
+    #[error("Error signing the check-in code: {0}")]
+    CheckInSigningError(String),
+
     #[error("Error in category service: {0}")]
     CategoryServiceError(#[from] crate::category_service::err::Error),
 
@@ -38,6 +82,12 @@ pub enum Error {
     #[error("Error in user service: {0}")]
     UserServiceError(#[from] user_service::err::Error),
 
+    #[error("Error in schedule service: {0}")]
+    ScheduleServiceError(#[from] crate::schedule_service::err::Error),
+
+    #[error("Error in trainer service: {0}")]
+    TrainerServiceError(#[from] crate::trainer_service::err::Error),
+
     #[error("Error in tuition service: {0}")]
     TuitionServiceError(#[from] tuition_service::err::Error),
 
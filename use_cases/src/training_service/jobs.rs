@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use chrono::{Duration, Timelike, Utc};
+use entities::audit::Actor;
+
+use super::TrainingService;
+use crate::job_service::{
+    err::{Error, Result},
+    job_trait::Job,
+};
+
+/// Members are reminded of a training this long before it starts
+const REMINDER_HOURS: i64 = 24;
+
+/// Closes the attendance of the trainings whose grace period for corrections passed, marked or
+/// not
+pub struct TrainingAttendanceCloseJob(pub TrainingService);
+
+#[async_trait]
+impl Job for TrainingAttendanceCloseJob {
+    fn name(&self) -> &'static str {
+        "training_attendance_close"
+    }
+
+    fn schedule(&self) -> &'static str {
+        "30 * * * *"
+    }
+
+    async fn run(&self, actor: &Actor) -> Result<String> {
+        let ended_before = Utc::now().naive_utc() - self.0.attendance_grace_period;
+        let closed = self
+            .0
+            .close_overdue_attendance(actor, ended_before)
+            .await
+            .map_err(|err| Error::JobFailed(err.to_string()))?;
+
+        Ok(format!("{closed} trainings closed"))
+    }
+}
+
+/// Reminds the registered members of the trainings starting in a day. Every hourly run covers
+/// the trainings starting during one hour, so each training is reminded once
+pub struct TrainingReminderJob(pub TrainingService);
+
+#[async_trait]
+impl Job for TrainingReminderJob {
+    fn name(&self) -> &'static str {
+        "training_reminder"
+    }
+
+    fn schedule(&self) -> &'static str {
+        "0 * * * *"
+    }
+
+    async fn run(&self, actor: &Actor) -> Result<String> {
+        let now = Utc::now().naive_utc();
+        let from = now
+            .with_minute(0)
+            .and_then(|hour| hour.with_second(0))
+            .and_then(|hour| hour.with_nanosecond(0))
+            .unwrap_or(now)
+            + Duration::hours(REMINDER_HOURS);
+
+        let reminded = self
+            .0
+            .send_training_reminders(actor, from, from + Duration::hours(1))
+            .await
+            .map_err(|err| Error::JobFailed(err.to_string()))?;
+
+        Ok(format!("{reminded} trainings reminded"))
+    }
+}
+
+/// Passes the waitlist offers nobody confirmed in time to the next members
+pub struct TrainingWaitlistExpiryJob(pub TrainingService);
+
+#[async_trait]
+impl Job for TrainingWaitlistExpiryJob {
+    fn name(&self) -> &'static str {
+        "training_waitlist_expiry"
+    }
+
+    fn schedule(&self) -> &'static str {
+        "*/10 * * * *"
+    }
+
+    async fn run(&self, actor: &Actor) -> Result<String> {
+        let expired = self
+            .0
+            .expire_waitlist_offers(actor)
+            .await
+            .map_err(|err| Error::JobFailed(err.to_string()))?;
+
+        Ok(format!("{expired} offers expired"))
+    }
+}
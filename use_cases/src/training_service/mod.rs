@@ -1,19 +1,37 @@
+mod attendance;
+mod check_in;
 pub mod err;
+pub mod jobs;
 pub mod repository_trait;
+mod request_executor;
+pub mod signer_trait;
+mod waitlist;
 
 use chrono::{Duration, NaiveDateTime, Utc};
 use err::{Error, Result};
-use repository_trait::{TrainingRegistrationRepository, TrainingRepository};
+use repository_trait::{
+    TrainingRegistrationRepository, TrainingRepository, TrainingWaitlistRepository,
+};
+use signer_trait::CheckInSigner;
 
 use crate::{
+    audit_service::AuditService,
     category_service::CategoryService,
     court_service::CourtService,
+    event_service::EventService,
+    schedule_service::ScheduleService,
+    trainer_service::TrainerService,
     tuition_service::TuitionService,
     user_service::{err::Error as UserError, UserService},
 };
 use entities::{
-    court::CourtReservationCreation,
-    training::{Training, TrainingCreation, TrainingRegistration},
+    audit::{Actor, AuditEntity},
+    court::CourtSelection,
+    event::DomainEvent,
+    pagination::{Page, PageQuery},
+    training::{
+        AttendanceStatus, Training, TrainingCreation, TrainingFilter, TrainingRegistration,
+    },
     user::URol,
 };
 use std::sync::Arc;
@@ -21,33 +39,58 @@ use uuid::Uuid;
 
 const MIN_EVENT_DURATION_MINUTES: i64 = 10;
 const MAX_EVENT_DURATION_HOURS: i64 = 5;
+/// Attendance stays open for corrections this long after the training ends, unless configured
+pub const DEFAULT_ATTENDANCE_GRACE_HOURS: i64 = 24;
 
 #[derive(Clone)]
 pub struct TrainingService {
     training_repo: Arc<dyn TrainingRepository>,
     registration_repo: Arc<dyn TrainingRegistrationRepository>,
+    waitlist_repo: Arc<dyn TrainingWaitlistRepository>,
+    check_in_signer: Arc<dyn CheckInSigner>,
+    /// How long after the end trainers can still correct the attendance
+    attendance_grace_period: Duration,
     category_service: CategoryService,
     court_service: CourtService,
     user_service: UserService,
     tuition_service: TuitionService,
+    schedule_service: ScheduleService,
+    trainer_service: TrainerService,
+    event_service: EventService,
+    audit_service: AuditService,
 }
 
 impl TrainingService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         training_repo: Arc<dyn TrainingRepository>,
         registration_repo: Arc<dyn TrainingRegistrationRepository>,
+        waitlist_repo: Arc<dyn TrainingWaitlistRepository>,
+        check_in_signer: Arc<dyn CheckInSigner>,
+        attendance_grace_period: Duration,
         category_service: CategoryService,
         court_service: CourtService,
         user_service: UserService,
         tuition_service: TuitionService,
+        schedule_service: ScheduleService,
+        trainer_service: TrainerService,
+        event_service: EventService,
+        audit_service: AuditService,
     ) -> Self {
         Self {
             training_repo,
             registration_repo,
+            waitlist_repo,
+            check_in_signer,
+            attendance_grace_period,
             category_service,
             court_service,
             user_service,
             tuition_service,
+            schedule_service,
+            trainer_service,
+            event_service,
+            audit_service,
         }
     }
 
@@ -71,26 +114,42 @@ impl TrainingService {
 
     pub async fn delete_training_registration(
         &self,
+        actor: &Actor,
         training_id: Uuid,
         user_id: Uuid,
     ) -> Result<()> {
-        // Check if training exists
-        let _ = self.get_training(training_id).await?;
+        let training = self.get_training(training_id).await?;
         // Check if registration exists
-        self.registration_repo
+        let registration = self
+            .registration_repo
             .get_training_registration(training_id, user_id)
             .await?
             .ok_or(Error::RegistrationNotFound)?;
 
         self.registration_repo
             .delete_training_registration(training_id, user_id)
-            .await
+            .await?;
+
+        self.audit_service
+            .record_deletion(
+                actor,
+                "DELETE_TRAINING_REGISTRATION",
+                AuditEntity::TRAINING_REGISTRATION,
+                format!("{training_id}/{user_id}"),
+                &registration,
+            )
+            .await;
+
+        self.offer_open_spots(actor, &training).await?;
+
+        Ok(())
     }
 
     pub async fn create_training(
         &self,
+        actor: &Actor,
         training_creation: TrainingCreation,
-        id_court_to_reserve: Option<Uuid>, // Added optional court ID for reservation
+        court_selection: Option<CourtSelection>,
     ) -> Result<Training> {
         validate_event_duration(
             training_creation.start_datetime,
@@ -124,28 +183,75 @@ impl TrainingService {
         let training_id = Uuid::new_v4();
         let training = training_creation.to_training(training_id);
 
-        self.training_repo.create_training(&training).await?;
+        // The trainer can't lead two sessions, or play in one, at the same time
+        self.schedule_service
+            .ensure_free(
+                training.trainer_id,
+                training.start_datetime,
+                training.end_datetime,
+                training_id,
+            )
+            .await?;
+        // Nor be assigned while off or outside the hours the trainer declared
+        self.trainer_service
+            .ensure_available(
+                training.trainer_id,
+                training.start_datetime,
+                training.end_datetime,
+            )
+            .await?;
 
-        if let Some(id_court) = id_court_to_reserve {
-            let reservation_creation = CourtReservationCreation {
-                id_court,
-                start_reservation_datetime: training.start_datetime,
-                end_reservation_datetime: training.end_datetime,
-                id_training: Some(training.id_training),
-                id_tournament: None,
-            };
+        let event = self.event_service.event(
+            actor,
+            DomainEvent::TRAINING_CREATED {
+                id_training: training.id_training,
+            },
+        );
+        self.training_repo
+            .create_training(&training, Some(&event))
+            .await?;
+
+        if court_selection.is_some() {
             if let Err(e) = self
                 .court_service
-                .create_reservation(reservation_creation)
+                .set_reservations_for_event(
+                    actor,
+                    training.id_training,
+                    "training",
+                    training.start_datetime,
+                    training.end_datetime,
+                    court_selection.as_ref(),
+                )
                 .await
             {
-                self.training_repo.delete_training(training.id_training).await.unwrap_or_else(|del_err| {
+                // The creation was already published, so the rollback is published as a cancellation
+                let event = self.event_service.event(
+                    actor,
+                    DomainEvent::TRAINING_CANCELLED {
+                        id_training: training.id_training,
+                        name: training.name.clone(),
+                        start_datetime: training.start_datetime,
+                        registered_users: Vec::new(),
+                    },
+                );
+                self.training_repo.delete_training(training.id_training, Some(&event)).await.unwrap_or_else(|del_err| {
                     tracing::error!("Failed to rollback training creation after court reservation failure: {}", del_err);
                 });
                 // Directly use the CourtServiceError variant from TrainingService::Error
                 return Err(Error::CourtServiceError(e));
             }
         }
+
+        self.audit_service
+            .record_creation(
+                actor,
+                "CREATE_TRAINING",
+                AuditEntity::TRAINING,
+                training.id_training,
+                &training,
+            )
+            .await;
+
         Ok(training)
     }
 
@@ -158,11 +264,13 @@ impl TrainingService {
 
     pub async fn update_training(
         &self,
+        actor: &Actor,
         training_id: Uuid,
         training_update_payload: TrainingCreation,
-        id_court_to_reserve: Option<Uuid>,
+        court_selection: Option<CourtSelection>,
     ) -> Result<Training> {
         let mut training = self.get_training(training_id).await?;
+        let before = training.clone();
 
         validate_event_duration(
             training_update_payload.start_datetime,
@@ -202,92 +310,202 @@ impl TrainingService {
         training.start_datetime = training_update_payload.start_datetime;
         training.end_datetime = training_update_payload.end_datetime;
         training.minimum_payment = training_update_payload.minimum_payment;
+        training.max_participants = training_update_payload.max_participants;
 
-        // Handle court reservation change
-        // 1. Delete existing reservation for this training if any
-        if let Some(existing_res) = self
-            .court_service
-            .get_reservation_for_training(training_id)
-            .await // Now returns Option
-            .map_err(Error::CourtServiceError)?
-        {
-            // Check if the court or time is changing, or if no new court is specified
-            let times_changed = existing_res.start_reservation_datetime != training.start_datetime
-                || existing_res.end_reservation_datetime != training.end_datetime;
-            let court_changed =
-                id_court_to_reserve.is_some() && Some(existing_res.id_court) != id_court_to_reserve;
-
-            if times_changed || court_changed || id_court_to_reserve.is_none() {
-                self.court_service
-                    .delete_reservation_for_event(training_id, "training")
-                    .await
-                    .map_err(Error::CourtServiceError)?;
+        // Registered members keep their spot, so the capacity can't drop below them
+        if let Some(max_participants) = training.max_participants {
+            let registered = self
+                .registration_repo
+                .get_training_registrations(training_id)
+                .await?
+                .len();
+            if (max_participants as usize) < registered {
+                return Err(Error::CapacityBelowRegistrations(registered));
             }
         }
 
-        // 2. Create new reservation if id_court_to_reserve is Some AND (it's a new court OR times changed for existing court)
-        if let Some(id_court) = id_court_to_reserve {
-            // Re-check if a reservation for this court/time already exists from previous step to avoid race if delete was slow
-            // Or simply attempt to create; create_reservation should handle conflicts.
-            let reservation_creation = CourtReservationCreation {
-                id_court,
-                start_reservation_datetime: training.start_datetime,
-                end_reservation_datetime: training.end_datetime,
-                id_training: Some(training.id_training),
-                id_tournament: None,
-            };
-            // Only create if no existing reservation for this new configuration,
-            // or if the existing one was for a different court/time and got deleted.
-            // This logic can be tricky. The `create_reservation` should be idempotent or check availability.
-            // For simplicity, we assume `create_reservation` handles `CourtUnavailable` correctly.
-            if self
-                .court_service
-                .get_reservation_for_training(training_id)
-                .await?
-                .is_none()
-            {
-                // If no reservation exists now
-                if let Err(e) = self
-                    .court_service
-                    .create_reservation(reservation_creation)
-                    .await
-                {
-                    return Err(Error::CourtServiceError(e));
-                }
-            }
+        self.schedule_service
+            .ensure_free(
+                training.trainer_id,
+                training.start_datetime,
+                training.end_datetime,
+                training_id,
+            )
+            .await?;
+        // Trainings kept at their time and trainer aren't held to availability declared later
+        if training.trainer_id != before.trainer_id
+            || training.start_datetime != before.start_datetime
+            || training.end_datetime != before.end_datetime
+        {
+            self.trainer_service
+                .ensure_available(
+                    training.trainer_id,
+                    training.start_datetime,
+                    training.end_datetime,
+                )
+                .await?;
         }
 
+        // Without a selection the training releases its courts
+        self.court_service
+            .set_reservations_for_event(
+                actor,
+                training_id,
+                "training",
+                training.start_datetime,
+                training.end_datetime,
+                court_selection.as_ref(),
+            )
+            .await?;
+
         self.training_repo.update_training(&training).await?;
+
+        self.audit_service
+            .record_update(
+                actor,
+                "UPDATE_TRAINING",
+                AuditEntity::TRAINING,
+                training_id,
+                &before,
+                &training,
+            )
+            .await;
+
+        // A bigger capacity frees spots for the waitlist
+        self.offer_open_spots(actor, &training).await?;
+
         Ok(training)
     }
 
-    pub async fn delete_training(&self, id: Uuid) -> Result<()> {
-        let _ = self.get_training(id).await?; // Ensures training exists before attempting delete
+    pub async fn delete_training(&self, actor: &Actor, id: Uuid) -> Result<()> {
+        let training = self.get_training(id).await?; // Ensures training exists before attempting delete
 
-        // Soft delete associated court reservations
-        // This might be better handled by ON DELETE CASCADE in DB if reservations are hard-deleted,
-        // or explicitly here if soft-deleted.
-        // For now, assuming CourtService handles this or it's done via DB constraints.
-        if let Err(e) = self
-            .court_service
-            .delete_reservation_for_event(id, "training")
-            .await
-        {
-            tracing::warn!(
-                "Could not delete court reservation for training {}: {}",
+        // Registrations are read before deleting so subscribers can reach the registered members
+        let registrations = self
+            .registration_repo
+            .get_training_registrations(id)
+            .await?;
+
+        // The court reservation is released by its subscriber
+        let event = self.event_service.event(
+            actor,
+            DomainEvent::TRAINING_CANCELLED {
+                id_training: id,
+                name: training.name.clone(),
+                start_datetime: training.start_datetime,
+                registered_users: registrations
+                    .iter()
+                    .map(|registration| registration.id_user)
+                    .collect(),
+            },
+        );
+        self.training_repo.delete_training(id, Some(&event)).await?;
+
+        self.audit_service
+            .record_deletion(
+                actor,
+                "DELETE_TRAINING",
+                AuditEntity::TRAINING,
                 id,
-                e
-            );
-            // Depending on requirements, you might want to fail here or just log.
+                &training,
+            )
+            .await;
+
+        Ok(())
+    }
+
+    /// Publishes `TRAINING_REMINDER` for the trainings with registered members starting from
+    /// `from` until `to` (excluded)
+    pub async fn send_training_reminders(
+        &self,
+        actor: &Actor,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<usize> {
+        let trainings = self
+            .training_repo
+            .list_trainings_starting_between(from, to)
+            .await?;
+
+        let mut reminded = 0;
+        for training in trainings {
+            let registrations = self
+                .registration_repo
+                .get_training_registrations(training.id_training)
+                .await?;
+            if registrations.is_empty() {
+                continue;
+            }
+
+            self.event_service
+                .publish(
+                    actor,
+                    DomainEvent::TRAINING_REMINDER {
+                        id_training: training.id_training,
+                        name: training.name,
+                        start_datetime: training.start_datetime,
+                        registered_users: registrations
+                            .iter()
+                            .map(|registration| registration.id_user)
+                            .collect(),
+                    },
+                )
+                .await
+                .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+            reminded += 1;
         }
 
-        self.training_repo.delete_training(id).await
+        Ok(reminded)
+    }
+
+    /// Closes the attendance of the trainings ended before `ended_before`. Registrations nobody
+    /// marked are saved as absences and the final roster is recorded in the audit log
+    pub async fn close_overdue_attendance(
+        &self,
+        actor: &Actor,
+        ended_before: NaiveDateTime,
+    ) -> Result<usize> {
+        let trainings = self
+            .training_repo
+            .list_trainings_with_open_attendance(ended_before)
+            .await?;
+
+        for training in &trainings {
+            self.training_repo
+                .close_training_attendance(training.id_training)
+                .await?;
+
+            let registrations = self
+                .registration_repo
+                .get_training_registrations(training.id_training)
+                .await?;
+
+            self.audit_service
+                .record_creation(
+                    actor,
+                    "CLOSE_TRAINING_ATTENDANCE",
+                    AuditEntity::TRAINING,
+                    training.id_training,
+                    &registrations,
+                )
+                .await;
+        }
+
+        Ok(trainings.len())
     }
 
     pub async fn list_trainings(&self) -> Result<Vec<Training>> {
         self.training_repo.list_trainings().await
     }
 
+    pub async fn list_trainings_page(
+        &self,
+        filter: &TrainingFilter,
+        page: &PageQuery,
+    ) -> Result<Page<Training>> {
+        self.training_repo.list_trainings_page(filter, page).await
+    }
+
     pub async fn get_trainings_by_trainer(&self, trainer_id: Uuid) -> Result<Vec<Training>> {
         // Validate trainer_id exists and is a TRAINER
         let trainer = self
@@ -312,8 +530,29 @@ impl TrainingService {
 
     pub async fn register_user(
         &self,
+        actor: &Actor,
+        training_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<TrainingRegistration> {
+        self.register(actor, training_id, user_id, false).await
+    }
+
+    /// Registers the user after the training has started, granted by an approved request
+    pub async fn register_user_late(
+        &self,
+        actor: &Actor,
+        training_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<TrainingRegistration> {
+        self.register(actor, training_id, user_id, true).await
+    }
+
+    async fn register(
+        &self,
+        actor: &Actor,
         training_id: Uuid,
         user_id: Uuid,
+        late: bool,
     ) -> Result<TrainingRegistration> {
         let training = self
             .training_repo
@@ -323,7 +562,7 @@ impl TrainingService {
 
         let now = Utc::now().naive_utc();
 
-        if now > training.start_datetime {
+        if !late && now > training.start_datetime {
             return Err(Error::InvalidRegistrationDate);
         }
 
@@ -343,6 +582,24 @@ impl TrainingService {
         {
             return Err(Error::UserAlreadyRegistered);
         }
+
+        let waitlist_entry = self
+            .waitlist_repo
+            .get_waitlist_entry(training_id, user_id)
+            .await?;
+        if self.free_spots(&training, Some(user_id)).await? == Some(0) {
+            return Err(Error::TrainingFull);
+        }
+
+        self.schedule_service
+            .ensure_free(
+                user_id,
+                training.start_datetime,
+                training.end_datetime,
+                training_id,
+            )
+            .await?;
+
         fn fun_name(e: crate::tuition_service::err::Error) -> Error {
             Error::TuitionServiceError(e)
         }
@@ -365,44 +622,89 @@ impl TrainingService {
             registration_datetime: Utc::now().naive_utc(),
             attended: false,
             attendance_datetime: None,
+            attendance_status: None,
+            attendance_submitted_by: None,
+            attendance_notes: None,
         };
 
+        let event = self.event_service.event(
+            actor,
+            DomainEvent::TRAINING_REGISTERED {
+                id_training: training_id,
+                id_user: user_id,
+            },
+        );
         self.registration_repo
-            .register_user_for_training(&registration_to_create)
+            .register_user_for_training(&registration_to_create, Some(&event))
             .await?;
 
+        self.audit_service
+            .record_creation(
+                actor,
+                "REGISTER_USER_FOR_TRAINING",
+                AuditEntity::TRAINING_REGISTRATION,
+                format!("{training_id}/{user_id}"),
+                &registration_to_create,
+            )
+            .await;
+
+        if let Some(entry) = waitlist_entry.filter(|entry| entry.is_queued()) {
+            self.confirm_waitlist_entry(actor, entry).await?;
+        }
+
         Ok(registration_to_create)
     }
 
+    /// Marks the attendance of a member while the training runs, only its trainer or an admin can
     pub async fn mark_attendance(
         &self,
+        actor: &Actor,
         training_id: Uuid,
         user_id: Uuid,
-        attended: bool,
+        status: AttendanceStatus,
+        notes: Option<String>,
     ) -> Result<()> {
         let training = self.get_training(training_id).await?;
+        attendance::check_training_staff(actor, &training)?;
+        let notes = attendance::normalize_notes(notes)?;
 
         let now = Utc::now().naive_utc();
 
         if now <= training.start_datetime || now >= training.end_datetime {
             return Err(Error::InvalidAssistanceDate);
         }
+        if self
+            .training_repo
+            .is_training_attendance_closed(training_id)
+            .await?
+        {
+            return Err(Error::AttendanceClosed);
+        }
 
-        let _ = self
+        let before = self
             .registration_repo
             .get_training_registration(training_id, user_id)
             .await?
             .ok_or(Error::UserNotRegistered)?;
 
-        let attendance_datetime = if attended {
-            Some(Utc::now().naive_utc())
-        } else {
-            None
-        };
+        let after = attendance::record_attendance(&before, status, notes, actor.user_id, now);
 
         self.registration_repo
-            .mark_training_attendance(training_id, user_id, attended, attendance_datetime)
-            .await
+            .save_training_attendance(std::slice::from_ref(&after))
+            .await?;
+
+        self.audit_service
+            .record_update(
+                actor,
+                "MARK_TRAINING_ATTENDANCE",
+                AuditEntity::TRAINING_REGISTRATION,
+                format!("{training_id}/{user_id}"),
+                &before,
+                &after,
+            )
+            .await;
+
+        Ok(())
     }
 
     pub async fn get_eligible_trainings(&self, user_id: Uuid) -> Result<Vec<Training>> {
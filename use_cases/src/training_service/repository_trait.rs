@@ -1,22 +1,52 @@
 use super::err::Result;
 use async_trait::async_trait;
 use chrono::NaiveDateTime;
-use entities::training::{Training, TrainingRegistration};
+use entities::event::OutboxEvent;
+use entities::pagination::{Page, PageQuery};
+use entities::training::{
+    Training, TrainingFilter, TrainingRegistration, TrainingRosterEntry, TrainingWaitlistEntry,
+};
 use uuid::Uuid;
 
+/// The given `event` is stored in the outbox in the transaction of the operation
 #[async_trait]
 pub trait TrainingRepository: Send + Sync {
-    async fn create_training(&self, training: &Training) -> Result<()>;
+    async fn create_training(&self, training: &Training, event: Option<&OutboxEvent>)
+        -> Result<()>;
     async fn get_training_by_id(&self, id: Uuid) -> Result<Option<Training>>;
     async fn update_training(&self, training: &Training) -> Result<()>;
-    async fn delete_training(&self, id: Uuid) -> Result<()>;
+    async fn delete_training(&self, id: Uuid, event: Option<&OutboxEvent>) -> Result<()>;
     async fn list_trainings(&self) -> Result<Vec<Training>>;
+    async fn list_trainings_page(
+        &self,
+        filter: &TrainingFilter,
+        page: &PageQuery,
+    ) -> Result<Page<Training>>;
     async fn get_trainings_by_trainer_id(&self, trainer_id: Uuid) -> Result<Vec<Training>>;
+    /// Trainings starting from `from` until `to` (excluded)
+    async fn list_trainings_starting_between(
+        &self,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<Vec<Training>>;
+    /// Trainings ended before `ended_before` whose attendance wasn't closed yet
+    async fn list_trainings_with_open_attendance(
+        &self,
+        ended_before: NaiveDateTime,
+    ) -> Result<Vec<Training>>;
+    /// Marks the registrations nobody took as absent and closes the attendance, in one
+    /// transaction
+    async fn close_training_attendance(&self, id: Uuid) -> Result<()>;
+    async fn is_training_attendance_closed(&self, id: Uuid) -> Result<bool>;
 }
 
 #[async_trait]
 pub trait TrainingRegistrationRepository: Send + Sync {
-    async fn register_user_for_training(&self, registration: &TrainingRegistration) -> Result<()>;
+    async fn register_user_for_training(
+        &self,
+        registration: &TrainingRegistration,
+        event: Option<&OutboxEvent>,
+    ) -> Result<()>;
     async fn get_training_registrations(
         &self,
         training_id: Uuid,
@@ -26,16 +56,34 @@ pub trait TrainingRegistrationRepository: Send + Sync {
         training_id: Uuid,
         user_id: Uuid,
     ) -> Result<Option<TrainingRegistration>>;
-    async fn mark_training_attendance(
-        &self,
-        training_id: Uuid,
-        user_id: Uuid,
-        attended: bool,
-        attendance_date: Option<NaiveDateTime>,
-    ) -> Result<()>;
+    /// Registrations of the training with the names of the members, sorted by last name
+    async fn get_training_roster(&self, training_id: Uuid) -> Result<Vec<TrainingRosterEntry>>;
+    /// Saves the attendance of every registration in one transaction, none is saved when one
+    /// of them fails
+    async fn save_training_attendance(&self, registrations: &[TrainingRegistration]) -> Result<()>;
     async fn get_user_training_registrations(
         &self,
         user_id: Uuid,
     ) -> Result<Vec<TrainingRegistration>>;
     async fn delete_training_registration(&self, training_id: Uuid, user_id: Uuid) -> Result<()>;
 }
+
+#[async_trait]
+pub trait TrainingWaitlistRepository: Send + Sync {
+    /// Joining again puts the member back at the end of the queue
+    async fn add_to_waitlist(&self, entry: &TrainingWaitlistEntry) -> Result<()>;
+    async fn get_waitlist_entry(
+        &self,
+        training_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<TrainingWaitlistEntry>>;
+    /// Waiting members and the ones holding an offered spot, in queue order
+    async fn list_waitlist(&self, training_id: Uuid) -> Result<Vec<TrainingWaitlistEntry>>;
+    async fn update_waitlist_entry(
+        &self,
+        entry: &TrainingWaitlistEntry,
+        event: Option<&OutboxEvent>,
+    ) -> Result<()>;
+    /// Offered spots nobody confirmed before `now`
+    async fn list_expired_offers(&self, now: NaiveDateTime) -> Result<Vec<TrainingWaitlistEntry>>;
+}
@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use entities::audit::Actor;
+use entities::request::{Request, RequestCommand};
+use uuid::Uuid;
+
+use super::TrainingService;
+use crate::request_service::{
+    err::{Error, Result},
+    executor::RequestExecutor,
+};
+
+/// Owns every late registration that isn't exclusively for a tournament, so malformed payloads
+/// are still validated here
+#[async_trait]
+impl RequestExecutor for TrainingService {
+    fn handles(&self, command: &RequestCommand) -> bool {
+        matches!(
+            command,
+            RequestCommand::LATE_REGISTRATION(registration)
+                if registration.id_tournament.is_none() || registration.id_training.is_some()
+        )
+    }
+
+    async fn validate(&self, _requester_id: Uuid, command: &RequestCommand) -> Result<()> {
+        let RequestCommand::LATE_REGISTRATION(registration) = command else {
+            return Ok(());
+        };
+
+        let id_training = match (registration.id_training, registration.id_tournament) {
+            (Some(id_training), None) => id_training,
+            _ => {
+                return Err(Error::InvalidRequestPayload(
+                    "A late registration must target either a training or a tournament".to_string(),
+                ))
+            }
+        };
+
+        self.get_training(id_training)
+            .await
+            .map_err(|err| Error::InvalidRequestPayload(err.to_string()))?;
+        self.user_service
+            .get_user_by_id(registration.user_id)
+            .await
+            .map_err(|err| Error::InvalidRequestPayload(err.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn execute(&self, approver: &Actor, request: &Request) -> Result<()> {
+        let RequestCommand::LATE_REGISTRATION(registration) = &request.requested_command else {
+            return Ok(());
+        };
+        let Some(id_training) = registration.id_training else {
+            return Ok(());
+        };
+
+        self.register_user_late(approver, id_training, registration.user_id)
+            .await
+            .map(|_| ())
+            .map_err(|err| Error::ExecutionFailed(err.to_string()))
+    }
+}
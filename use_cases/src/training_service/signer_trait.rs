@@ -0,0 +1,8 @@
+use super::err::Result;
+
+/// Signs the check-in tokens of the trainings so members can't make their own
+pub trait CheckInSigner: Send + Sync {
+    fn sign(&self, payload: &str) -> Result<String>;
+
+    fn verify(&self, payload: &str, signature: &str) -> Result<bool>;
+}
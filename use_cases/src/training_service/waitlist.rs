@@ -0,0 +1,438 @@
+use std::collections::HashSet;
+
+use chrono::{Duration, NaiveDateTime, Utc};
+use entities::audit::{Actor, AuditEntity};
+use entities::event::DomainEvent;
+use entities::training::{Training, TrainingRegistration, TrainingWaitlistEntry, WaitlistStatus};
+use entities::user::URol;
+use uuid::Uuid;
+
+use super::err::{Error, Result};
+use super::TrainingService;
+
+/// How long a member has to confirm an offered spot, never past the start of the training
+const WAITLIST_CONFIRMATION_HOURS: i64 = 12;
+
+impl TrainingService {
+    /// Queues the member for a spot of a full training, with the same requirements as a
+    /// registration
+    pub async fn join_waitlist(
+        &self,
+        actor: &Actor,
+        training_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<TrainingWaitlistEntry> {
+        let training = self.get_training(training_id).await?;
+        check_waitlist_access(actor, &training, user_id)?;
+        let now = Utc::now().naive_utc();
+        if now > training.start_datetime {
+            return Err(Error::InvalidRegistrationDate);
+        }
+        if !self
+            .category_service
+            .user_has_category(user_id, training.id_category)
+            .await?
+        {
+            return Err(Error::UserDoesNotMeetCategoryRequirements);
+        }
+        if self
+            .registration_repo
+            .get_training_registration(training_id, user_id)
+            .await?
+            .is_some()
+        {
+            return Err(Error::UserAlreadyRegistered);
+        }
+        if let Some(entry) = self
+            .waitlist_repo
+            .get_waitlist_entry(training_id, user_id)
+            .await?
+        {
+            if entry.is_queued() {
+                return Err(Error::AlreadyOnWaitlist);
+            }
+        }
+        if self.free_spots(&training, Some(user_id)).await? != Some(0) {
+            return Err(Error::TrainingNotFull);
+        }
+
+        let entry = TrainingWaitlistEntry {
+            id_training: training_id,
+            id_user: user_id,
+            joined_datetime: now,
+            status: WaitlistStatus::WAITING,
+            offered_datetime: None,
+            confirm_before: None,
+            position: None,
+        };
+        self.waitlist_repo.add_to_waitlist(&entry).await?;
+
+        self.audit_service
+            .record_creation(
+                actor,
+                "JOIN_TRAINING_WAITLIST",
+                AuditEntity::TRAINING_WAITLIST,
+                format!("{training_id}/{user_id}"),
+                &entry,
+            )
+            .await;
+
+        self.find_waitlist_entry(training_id, user_id).await
+    }
+
+    /// The entry of the member, with its place in the queue
+    pub async fn get_waitlist_entry(
+        &self,
+        actor: &Actor,
+        training_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<TrainingWaitlistEntry> {
+        let training = self.get_training(training_id).await?;
+        check_waitlist_access(actor, &training, user_id)?;
+        self.find_waitlist_entry(training_id, user_id).await
+    }
+
+    async fn find_waitlist_entry(
+        &self,
+        training_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<TrainingWaitlistEntry> {
+        self.waitlist_repo
+            .get_waitlist_entry(training_id, user_id)
+            .await?
+            .ok_or(Error::WaitlistEntryNotFound)
+    }
+
+    pub async fn list_waitlist(&self, training_id: Uuid) -> Result<Vec<TrainingWaitlistEntry>> {
+        self.get_training(training_id).await?;
+        self.waitlist_repo.list_waitlist(training_id).await
+    }
+
+    /// Leaving with an offered spot passes it to the next member
+    pub async fn leave_waitlist(
+        &self,
+        actor: &Actor,
+        training_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<()> {
+        let training = self.get_training(training_id).await?;
+        check_waitlist_access(actor, &training, user_id)?;
+        let entry = self.find_waitlist_entry(training_id, user_id).await?;
+        if !entry.is_queued() {
+            return Err(Error::WaitlistEntryNotFound);
+        }
+
+        let left = TrainingWaitlistEntry {
+            status: WaitlistStatus::LEFT,
+            position: None,
+            ..entry.clone()
+        };
+        self.waitlist_repo
+            .update_waitlist_entry(&left, None)
+            .await?;
+
+        self.audit_service
+            .record_update(
+                actor,
+                "LEAVE_TRAINING_WAITLIST",
+                AuditEntity::TRAINING_WAITLIST,
+                format!("{training_id}/{user_id}"),
+                &entry,
+                &left,
+            )
+            .await;
+
+        if entry.status == WaitlistStatus::OFFERED {
+            self.offer_open_spots(actor, &training).await?;
+        }
+        Ok(())
+    }
+
+    /// Takes the spot held for the member, registering them like a regular registration
+    pub async fn confirm_waitlist_spot(
+        &self,
+        actor: &Actor,
+        training_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<TrainingRegistration> {
+        let training = self.get_training(training_id).await?;
+        check_waitlist_access(actor, &training, user_id)?;
+        let entry = self.find_waitlist_entry(training_id, user_id).await?;
+        let now = Utc::now().naive_utc();
+        let held = entry.status == WaitlistStatus::OFFERED
+            && entry.confirm_before.is_some_and(|deadline| now < deadline);
+        if !held {
+            return Err(Error::NoSpotOffered);
+        }
+
+        self.register_user(actor, training_id, user_id).await
+    }
+
+    /// Gives the offered spots nobody confirmed in time to the next members of each waitlist
+    pub async fn expire_waitlist_offers(&self, actor: &Actor) -> Result<usize> {
+        let now = Utc::now().naive_utc();
+        let expired = self.waitlist_repo.list_expired_offers(now).await?;
+
+        let mut trainings = HashSet::new();
+        for entry in &expired {
+            let expired_entry = TrainingWaitlistEntry {
+                status: WaitlistStatus::EXPIRED,
+                ..entry.clone()
+            };
+            self.waitlist_repo
+                .update_waitlist_entry(&expired_entry, None)
+                .await?;
+
+            self.audit_service
+                .record_update(
+                    actor,
+                    "EXPIRE_TRAINING_SPOT_OFFER",
+                    AuditEntity::TRAINING_WAITLIST,
+                    format!("{}/{}", entry.id_training, entry.id_user),
+                    entry,
+                    &expired_entry,
+                )
+                .await;
+            trainings.insert(entry.id_training);
+        }
+
+        for training_id in trainings {
+            // Deleted trainings have nothing left to offer
+            match self.get_training(training_id).await {
+                Ok(training) => {
+                    self.offer_open_spots(actor, &training).await?;
+                }
+                Err(Error::TrainingNotFound) => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(expired.len())
+    }
+
+    /// Spots left in the training, not counting the spot held for `for_user`. Empty for
+    /// trainings without a limit
+    pub(super) async fn free_spots(
+        &self,
+        training: &Training,
+        for_user: Option<Uuid>,
+    ) -> Result<Option<usize>> {
+        let Some(max_participants) = training.max_participants else {
+            return Ok(None);
+        };
+        let registrations = self
+            .registration_repo
+            .get_training_registrations(training.id_training)
+            .await?;
+        let waitlist = self
+            .waitlist_repo
+            .list_waitlist(training.id_training)
+            .await?;
+
+        Ok(Some(count_free_spots(
+            max_participants,
+            registrations.len(),
+            &waitlist,
+            for_user,
+        )))
+    }
+
+    /// Holds the free spots for the first waiting members and lets them know
+    pub(super) async fn offer_open_spots(&self, actor: &Actor, training: &Training) -> Result<()> {
+        let now = Utc::now().naive_utc();
+        if now >= training.start_datetime {
+            return Ok(());
+        }
+        let Some(free) = self.free_spots(training, None).await? else {
+            return Ok(());
+        };
+        if free == 0 {
+            return Ok(());
+        }
+
+        let confirm_before = confirmation_deadline(now, training.start_datetime);
+        let waiting = self
+            .waitlist_repo
+            .list_waitlist(training.id_training)
+            .await?
+            .into_iter()
+            .filter(|entry| entry.status == WaitlistStatus::WAITING)
+            .take(free);
+        for entry in waiting {
+            let offer = TrainingWaitlistEntry {
+                status: WaitlistStatus::OFFERED,
+                offered_datetime: Some(now),
+                confirm_before: Some(confirm_before),
+                position: None,
+                ..entry.clone()
+            };
+            let event = self.event_service.event(
+                actor,
+                DomainEvent::TRAINING_SPOT_OFFERED {
+                    id_training: training.id_training,
+                    name: training.name.clone(),
+                    start_datetime: training.start_datetime,
+                    id_user: entry.id_user,
+                    confirm_before,
+                },
+            );
+            self.waitlist_repo
+                .update_waitlist_entry(&offer, Some(&event))
+                .await?;
+
+            self.audit_service
+                .record_update(
+                    actor,
+                    "OFFER_TRAINING_SPOT",
+                    AuditEntity::TRAINING_WAITLIST,
+                    format!("{}/{}", entry.id_training, entry.id_user),
+                    &entry,
+                    &offer,
+                )
+                .await;
+        }
+
+        Ok(())
+    }
+
+    /// Closes the entry of a member that got registered
+    pub(super) async fn confirm_waitlist_entry(
+        &self,
+        actor: &Actor,
+        entry: TrainingWaitlistEntry,
+    ) -> Result<()> {
+        let confirmed = TrainingWaitlistEntry {
+            status: WaitlistStatus::CONFIRMED,
+            position: None,
+            ..entry.clone()
+        };
+        self.waitlist_repo
+            .update_waitlist_entry(&confirmed, None)
+            .await?;
+
+        self.audit_service
+            .record_update(
+                actor,
+                "CONFIRM_TRAINING_SPOT",
+                AuditEntity::TRAINING_WAITLIST,
+                format!("{}/{}", entry.id_training, entry.id_user),
+                &entry,
+                &confirmed,
+            )
+            .await;
+
+        Ok(())
+    }
+}
+
+/// The member itself, the trainer leading the training or an admin
+fn check_waitlist_access(actor: &Actor, training: &Training, user_id: Uuid) -> Result<()> {
+    if actor.user_id != user_id
+        && actor.user_id != training.trainer_id
+        && actor.user_rol != URol::ADMIN
+    {
+        return Err(Error::NotWaitlistMember);
+    }
+    Ok(())
+}
+
+/// Spots held for members of the waitlist are taken, except the one held for `for_user`
+fn count_free_spots(
+    max_participants: u32,
+    registrations: usize,
+    waitlist: &[TrainingWaitlistEntry],
+    for_user: Option<Uuid>,
+) -> usize {
+    let held = waitlist
+        .iter()
+        .filter(|entry| entry.status == WaitlistStatus::OFFERED && Some(entry.id_user) != for_user)
+        .count();
+    (max_participants as usize).saturating_sub(registrations + held)
+}
+
+fn confirmation_deadline(now: NaiveDateTime, start: NaiveDateTime) -> NaiveDateTime {
+    (now + Duration::hours(WAITLIST_CONFIRMATION_HOURS)).min(start)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn at(hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 6, 7)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    fn entry(status: WaitlistStatus) -> TrainingWaitlistEntry {
+        TrainingWaitlistEntry {
+            id_training: Uuid::new_v4(),
+            id_user: Uuid::new_v4(),
+            joined_datetime: at(8),
+            status,
+            offered_datetime: None,
+            confirm_before: None,
+            position: None,
+        }
+    }
+
+    #[test]
+    fn test_offered_spots_are_held() {
+        let offered = entry(WaitlistStatus::OFFERED);
+        let waitlist = [
+            offered.clone(),
+            entry(WaitlistStatus::WAITING),
+            entry(WaitlistStatus::WAITING),
+        ];
+
+        assert_eq!(count_free_spots(10, 7, &waitlist, None), 2);
+        assert_eq!(count_free_spots(8, 7, &waitlist, None), 0);
+        // The member holding the spot can take it
+        assert_eq!(count_free_spots(8, 7, &waitlist, Some(offered.id_user)), 1);
+        assert_eq!(count_free_spots(5, 7, &waitlist, None), 0);
+    }
+
+    #[test]
+    fn test_confirmation_deadline_is_before_the_start() {
+        assert_eq!(confirmation_deadline(at(0), at(20)), at(12));
+        assert_eq!(confirmation_deadline(at(10), at(18)), at(18));
+    }
+
+    #[test]
+    fn test_waitlist_access() {
+        let training = Training {
+            id_training: Uuid::new_v4(),
+            name: "Serve".to_string(),
+            id_category: Uuid::new_v4(),
+            trainer_id: Uuid::new_v4(),
+            start_datetime: at(18),
+            end_datetime: at(19),
+            minimum_payment: 0.0,
+            max_participants: Some(8),
+        };
+        let member = Uuid::new_v4();
+        let actor = |user_id, user_rol| Actor { user_id, user_rol };
+
+        assert!(check_waitlist_access(&actor(member, URol::USER), &training, member).is_ok());
+        assert!(check_waitlist_access(
+            &actor(training.trainer_id, URol::TRAINER),
+            &training,
+            member
+        )
+        .is_ok());
+        assert!(
+            check_waitlist_access(&actor(Uuid::new_v4(), URol::ADMIN), &training, member).is_ok()
+        );
+        assert!(matches!(
+            check_waitlist_access(&actor(Uuid::new_v4(), URol::USER), &training, member),
+            Err(Error::NotWaitlistMember)
+        ));
+        assert!(matches!(
+            check_waitlist_access(&actor(Uuid::new_v4(), URol::TRAINER), &training, member),
+            Err(Error::NotWaitlistMember)
+        ));
+    }
+}
@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use entities::audit::Actor;
+
+use super::TuitionService;
+use crate::job_service::{
+    err::{Error, Result},
+    job_trait::Job,
+};
+
+/// Members are warned this many days before their tuition expires
+const EXPIRY_NOTICE_DAYS: i64 = 3;
+
+/// Daily warning to the members whose tuition is about to expire
+pub struct TuitionExpiryJob(pub TuitionService);
+
+#[async_trait]
+impl Job for TuitionExpiryJob {
+    fn name(&self) -> &'static str {
+        "tuition_expiry"
+    }
+
+    fn schedule(&self) -> &'static str {
+        "0 8 * * *"
+    }
+
+    async fn run(&self, actor: &Actor) -> Result<String> {
+        let announced = self
+            .0
+            .announce_expiring_tuitions(actor, EXPIRY_NOTICE_DAYS)
+            .await
+            .map_err(|err| Error::JobFailed(err.to_string()))?;
+
+        Ok(format!(
+            "{announced} members warned of their tuition expiry"
+        ))
+    }
+}
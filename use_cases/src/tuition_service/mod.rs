@@ -1,46 +1,130 @@
 pub mod err;
+pub mod jobs;
 pub mod repository_trait;
+mod request_executor;
 // mod tests; // Already commented
 
 use self::err::{Error, Result};
-use chrono::Utc;
-use entities::tuition::Tuition;
+use crate::{audit_service::AuditService, event_service::EventService};
+use chrono::{Duration, NaiveTime, Utc};
+use entities::audit::{Actor, AuditEntity};
+use entities::event::{DomainEvent, OutboxEvent};
+use entities::pagination::{Page, PageQuery};
+use entities::tuition::{Tuition, TuitionFilter};
 use repository_trait::TuitionRepository;
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// A tuition keeps the member active for this many days after its payment
+const TUITION_VALIDITY_DAYS: i64 = 30;
+
 #[derive(Clone)]
 pub struct TuitionService {
     tuition_repo: Arc<dyn TuitionRepository + Send + Sync>,
+    event_service: EventService,
+    audit_service: AuditService,
 }
 
 impl TuitionService {
-    pub fn new(tuition_repo: Arc<dyn TuitionRepository + Send + Sync>) -> Self {
-        Self { tuition_repo }
+    pub fn new(
+        tuition_repo: Arc<dyn TuitionRepository + Send + Sync>,
+        event_service: EventService,
+        audit_service: AuditService,
+    ) -> Self {
+        Self {
+            tuition_repo,
+            event_service,
+            audit_service,
+        }
     }
 
-    pub async fn pay_tuition(&self, user_id: Uuid, amount: f64) -> Result<Tuition> {
-        if amount <= 0.0 {
-            return Err(Error::InvalidAmount);
-        }
+    pub async fn pay_tuition(&self, actor: &Actor, user_id: Uuid, amount: f64) -> Result<Tuition> {
+        let tuition = new_tuition(user_id, amount)?;
+        let event = self.event_service.event(
+            actor,
+            DomainEvent::TUITION_PAID {
+                id_tuition: tuition.id_tuition,
+                id_user: tuition.id_user,
+                amount: tuition.amount,
+            },
+        );
+        self.record_tuition(actor, "PAY_TUITION", &tuition, Some(&event))
+            .await?;
 
-        // Decided to remove the "active tuition exists" check here, as a user might pay multiple times
-        // or for different periods. The `has_active_tuition_with_amount` is more specific for training registration.
-        // if self.tuition_repo.has_active_tuition(user_id).await? {
-        //     return Err(Error::ActiveTuitionExists);
-        // }
+        Ok(tuition)
+    }
 
-        let new_tuition = Tuition {
-            id_tuition: Uuid::new_v4(),
-            id_user: user_id,
-            amount,
-            payment_date: Utc::now().naive_utc(),
-        };
+    /// Grants an active tuition without a payment, as approved through a tuition exemption request
+    pub async fn grant_tuition_exemption(
+        &self,
+        actor: &Actor,
+        user_id: Uuid,
+        amount: f64,
+    ) -> Result<Tuition> {
+        let tuition = new_tuition(user_id, amount)?;
+        self.record_tuition(actor, "GRANT_TUITION_EXEMPTION", &tuition, None)
+            .await?;
 
+        Ok(tuition)
+    }
+
+    async fn record_tuition(
+        &self,
+        actor: &Actor,
+        action: &str,
+        tuition: &Tuition,
+        event: Option<&OutboxEvent>,
+    ) -> Result<()> {
         self.tuition_repo
-            .record_tuition_payment(&new_tuition)
+            .record_tuition_payment(tuition, event)
+            .await?;
+
+        self.audit_service
+            .record_creation(
+                actor,
+                action,
+                AuditEntity::TUITION,
+                tuition.id_tuition,
+                tuition,
+            )
+            .await;
+
+        Ok(())
+    }
+
+    /// Publishes `TUITION_EXPIRING` for the members whose last tuition expires `days_ahead` days
+    /// from today. Running it once a day warns every member once
+    pub async fn announce_expiring_tuitions(
+        &self,
+        actor: &Actor,
+        days_ahead: i64,
+    ) -> Result<usize> {
+        let validity = Duration::days(TUITION_VALIDITY_DAYS);
+        let expiry_day = (Utc::now().naive_utc() + Duration::days(days_ahead))
+            .date()
+            .and_time(NaiveTime::MIN);
+        let tuitions = self
+            .tuition_repo
+            .list_latest_tuitions_paid_between(
+                expiry_day - validity,
+                expiry_day - validity + Duration::days(1),
+            )
             .await?;
-        Ok(new_tuition)
+
+        for tuition in &tuitions {
+            self.event_service
+                .publish(
+                    actor,
+                    DomainEvent::TUITION_EXPIRING {
+                        id_user: tuition.id_user,
+                        expires_at: tuition.payment_date + validity,
+                    },
+                )
+                .await
+                .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+        }
+
+        Ok(tuitions.len())
     }
 
     pub async fn has_active_tuition(&self, user_id: Uuid) -> Result<bool> {
@@ -66,4 +150,33 @@ impl TuitionService {
     pub async fn get_all_tuitions(&self) -> Result<Vec<Tuition>> {
         self.tuition_repo.list_all_tuition_payments().await
     }
+
+    pub async fn list_tuitions_page(
+        &self,
+        filter: &TuitionFilter,
+        page: &PageQuery,
+    ) -> Result<Page<Tuition>> {
+        self.tuition_repo
+            .list_tuition_payments_page(filter, page)
+            .await
+    }
+}
+
+fn new_tuition(user_id: Uuid, amount: f64) -> Result<Tuition> {
+    if amount <= 0.0 {
+        return Err(Error::InvalidAmount);
+    }
+
+    // Decided to remove the "active tuition exists" check here, as a user might pay multiple times
+    // or for different periods. The `has_active_tuition_with_amount` is more specific for training registration.
+    // if self.tuition_repo.has_active_tuition(user_id).await? {
+    //     return Err(Error::ActiveTuitionExists);
+    // }
+
+    Ok(Tuition {
+        id_tuition: Uuid::new_v4(),
+        id_user: user_id,
+        amount,
+        payment_date: Utc::now().naive_utc(),
+    })
 }
@@ -1,18 +1,37 @@
 use super::err::Result;
 use async_trait::async_trait;
-use entities::tuition::Tuition;
+use chrono::NaiveDateTime;
+use entities::event::OutboxEvent;
+use entities::pagination::{Page, PageQuery};
+use entities::tuition::{Tuition, TuitionFilter};
 use uuid::Uuid;
 
 #[async_trait]
 pub trait TuitionRepository: Send + Sync {
-    async fn record_tuition_payment(&self, tuition: &Tuition) -> Result<()>;
+    /// `event` is stored in the outbox along with the tuition
+    async fn record_tuition_payment(
+        &self,
+        tuition: &Tuition,
+        event: Option<&OutboxEvent>,
+    ) -> Result<()>;
     async fn get_tuition_by_id(&self, id: Uuid) -> Result<Option<Tuition>>;
     async fn list_tuition_payments_for_user(&self, user_id: Uuid) -> Result<Vec<Tuition>>;
     async fn list_all_tuition_payments(&self) -> Result<Vec<Tuition>>;
+    async fn list_tuition_payments_page(
+        &self,
+        filter: &TuitionFilter,
+        page: &PageQuery,
+    ) -> Result<Page<Tuition>>;
     async fn has_active_tuition(&self, user_id: Uuid) -> Result<bool>;
     async fn has_active_tuition_with_amount(
         &self,
         user_id: Uuid,
         required_amount: f64,
     ) -> Result<bool>;
+    /// Latest payment of each member, only when it was paid between `from` and `to` (excluded)
+    async fn list_latest_tuitions_paid_between(
+        &self,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<Vec<Tuition>>;
 }
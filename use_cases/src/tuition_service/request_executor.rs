@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use entities::audit::Actor;
+use entities::request::{Request, RequestCommand};
+use uuid::Uuid;
+
+use super::TuitionService;
+use crate::request_service::{
+    err::{Error, Result},
+    executor::RequestExecutor,
+};
+
+#[async_trait]
+impl RequestExecutor for TuitionService {
+    fn handles(&self, command: &RequestCommand) -> bool {
+        matches!(command, RequestCommand::TUITION_EXEMPTION(_))
+    }
+
+    async fn validate(&self, _requester_id: Uuid, command: &RequestCommand) -> Result<()> {
+        match command {
+            RequestCommand::TUITION_EXEMPTION(exemption) if exemption.amount <= 0.0 => Err(
+                Error::InvalidRequestPayload("The exempted amount must be positive".to_string()),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    async fn execute(&self, approver: &Actor, request: &Request) -> Result<()> {
+        let RequestCommand::TUITION_EXEMPTION(exemption) = &request.requested_command else {
+            return Ok(());
+        };
+
+        self.grant_tuition_exemption(approver, exemption.user_id, exemption.amount)
+            .await
+            .map(|_| ())
+            .map_err(|err| Error::ExecutionFailed(err.to_string()))
+    }
+}
@@ -1,23 +1,33 @@
 use std::sync::Arc;
 
 use chrono::{Datelike, NaiveDate, Utc};
-use entities::user::{URol, UserCreation, UserInfo, UserLogInInfo};
+use entities::audit::{Actor, AuditEntity};
+use entities::event::DomainEvent;
+use entities::pagination::{Page, PageQuery};
+use entities::user::{URol, User, UserCreation, UserFilter, UserInfo, UserLogInInfo};
 use hasher_trait::PasswordHasher;
 use repository_trait::UserRepository;
 
 pub mod err;
 pub mod hasher_trait;
 pub mod repository_trait;
+mod request_executor;
 mod unique_identifier;
 
+use crate::{audit_service::AuditService, event_service::EventService};
 use err::{Error, Result};
 use unique_identifier::{EmailIdentifier, Identifier, PhoneIdentifier};
 use uuid::Uuid;
 
+const ANONYMIZED_NAME: &str = "ANONYMIZED";
+const REDACTED: &str = "[REDACTED]";
+
 #[derive(Clone)]
 pub struct UserService {
     user_repo: Arc<dyn UserRepository>,
     password_hasher: Arc<dyn PasswordHasher>,
+    event_service: EventService,
+    audit_service: AuditService,
 }
 
 #[derive(Clone, Debug)]
@@ -30,10 +40,14 @@ impl UserService {
     pub fn new(
         user_repo: Arc<dyn UserRepository>,
         password_hasher: Arc<dyn PasswordHasher>,
+        event_service: EventService,
+        audit_service: AuditService,
     ) -> Self {
         Self {
             user_repo,
             password_hasher,
+            event_service,
+            audit_service,
         }
     }
 
@@ -72,20 +86,59 @@ impl UserService {
         );
         user.password = hashed_password;
 
-        self.user_repo.create_user(&user).await?;
-        Ok(UserInfo::from(user))
+        // A self registration is performed by the new user itself
+        let actor = Actor {
+            user_id,
+            user_rol: user.user_rol.clone(),
+        };
+        let event = self
+            .event_service
+            .event(&actor, DomainEvent::USER_REGISTERED { id_user: user_id });
+        self.user_repo.create_user(&user, Some(&event)).await?;
+
+        let user_info = UserInfo::from(user);
+        self.audit_service
+            .record_creation(
+                &actor,
+                "REGISTER_USER",
+                AuditEntity::USER,
+                user_id,
+                &audit_snapshot(&user_info),
+            )
+            .await;
+
+        Ok(user_info)
     }
 
-    pub async fn update_user_role(&self, user_id: Uuid, user_rol: URol) -> Result<UserInfo> {
+    pub async fn update_user_role(
+        &self,
+        actor: &Actor,
+        user_id: Uuid,
+        user_rol: URol,
+    ) -> Result<UserInfo> {
         let mut user = self
             .user_repo
             .get_user_by_id(user_id)
             .await?
             .ok_or(Error::UserIdDontExist)?;
+        let before = UserInfo::from(user.clone());
 
         user.user_rol = user_rol;
         self.user_repo.update_user(&user).await?;
-        Ok(UserInfo::from(user))
+
+        let user_info = UserInfo::from(user);
+        self.audit_service
+            .record_update(
+                actor,
+                "UPDATE_USER_ROLE",
+                AuditEntity::USER,
+                user_id,
+                &audit_snapshot(&before),
+                &audit_snapshot(&user_info),
+            )
+            .await;
+
+        Ok(user_info)
     }
 
     pub async fn get_all_users(&self) -> Result<Vec<UserInfo>> {
@@ -94,6 +147,15 @@ impl UserService {
         Ok(users_info)
     }
 
+    pub async fn list_users_page(
+        &self,
+        filter: &UserFilter,
+        page: &PageQuery,
+    ) -> Result<Page<UserInfo>> {
+        let users = self.user_repo.list_users_page(filter, page).await?;
+        Ok(users.map(UserInfo::from))
+    }
+
     pub async fn get_user_by_id(&self, user_id: Uuid) -> Result<UserInfo> {
         self.user_repo
             .get_user_by_id(user_id)
@@ -104,6 +166,7 @@ impl UserService {
 
     pub async fn update_user(
         &self,
+        actor: &Actor,
         user_id: Uuid,
         user_update_payload: UserCreation,
     ) -> Result<UserInfo> {
@@ -113,6 +176,7 @@ impl UserService {
             .get_user_by_id(user_id) // Corrected: use user_id
             .await?
             .ok_or(Error::UserIdDontExist)?;
+        let before = UserInfo::from(current_user.clone());
 
         validate_birth_date(user_update_payload.birth_date)?;
 
@@ -163,7 +227,20 @@ impl UserService {
         }
 
         self.user_repo.update_user(&current_user).await?;
-        Ok(UserInfo::from(current_user))
+
+        let user_info = UserInfo::from(current_user);
+        self.audit_service
+            .record_update(
+                actor,
+                "UPDATE_USER",
+                AuditEntity::USER,
+                user_id,
+                &audit_snapshot(&before),
+                &audit_snapshot(&user_info),
+            )
+            .await;
+
+        Ok(user_info)
     }
 
     pub async fn log_in_user(&self, user_log_in_info: &UserLogInInfo) -> Result<LogInResponse> {
@@ -200,16 +277,82 @@ impl UserService {
         })
     }
 
+    pub async fn delete_user(&self, actor: &Actor, user_id: Uuid) -> Result<()> {
+        let user = self
+            .user_repo
+            .get_user_by_id(user_id)
+            .await?
+            .ok_or(Error::UserIdDontExist)?;
+
+        self.user_repo.delete_user(user_id).await?;
+
+        self.audit_service
+            .record_deletion(
+                actor,
+                "DELETE_USER",
+                AuditEntity::USER,
+                user_id,
+                &audit_snapshot(&UserInfo::from(user)),
+            )
+            .await;
+
+        Ok(())
+    }
+
+    /// Replaces every piece of personal data of the user with placeholders and soft deletes it.
+    /// The id is kept, so tuition and tournament records stay linked for legal retention.
+    pub async fn anonymize_user(&self, actor: &Actor, user_id: Uuid) -> Result<()> {
+        let user = self
+            .user_repo
+            .get_user_by_id(user_id)
+            .await?
+            .ok_or(Error::UserIdDontExist)?;
+
+        let user = anonymized(user);
+        self.user_repo.anonymize_user(&user).await?;
+
+        // Audit snapshots never hold personal data, so nothing erased here survives in the log
+        self.audit_service
+            .record_deletion(
+                actor,
+                "ANONYMIZE_USER",
+                AuditEntity::USER,
+                user_id,
+                &audit_snapshot(&UserInfo::from(user)),
+            )
+            .await;
+
+        Ok(())
+    }
+
     // Method for verifying email with code (placeholder for actual implementation)
-    pub async fn verify_email_with_code(&self, user_id: Uuid, _code: &str) -> Result<()> {
+    pub async fn verify_email_with_code(
+        &self,
+        actor: &Actor,
+        user_id: Uuid,
+        _code: &str,
+    ) -> Result<()> {
         let mut user = self
             .user_repo
             .get_user_by_id(user_id)
             .await?
             .ok_or(Error::UserIdDontExist)?;
+        let before = UserInfo::from(user.clone());
         // Add logic here to validate the code (e.g., from a cache or temp table)
         user.email_verified = true;
         self.user_repo.update_user(&user).await?;
+
+        self.audit_service
+            .record_update(
+                actor,
+                "VERIFY_EMAIL",
+                AuditEntity::USER,
+                user_id,
+                &audit_snapshot(&before),
+                &audit_snapshot(&UserInfo::from(user)),
+            )
+            .await;
+
         Ok(())
     }
 }
@@ -236,3 +379,119 @@ fn validate_birth_date(birth_date: NaiveDate) -> Result<()> {
     }
     Ok(())
 }
+
+/// The profile as written to the audit log. Personal data is masked, the log only needs to
+/// show which account changed and how its role and verification evolved
+fn audit_snapshot(user: &UserInfo) -> UserInfo {
+    UserInfo {
+        first_name: REDACTED.to_string(),
+        last_name: REDACTED.to_string(),
+        birth_date: NaiveDate::from_ymd_opt(user.birth_date.year(), 1, 1)
+            .unwrap_or(user.birth_date),
+        email: REDACTED.to_string(),
+        phone_number: REDACTED.to_string(),
+        identification_number: REDACTED.to_string(),
+        ..user.clone()
+    }
+}
+
+/// The user with every piece of personal data replaced by placeholders
+fn anonymized(mut user: User) -> User {
+    let placeholder = format!("erased-{}", user.id_user.simple());
+
+    user.first_name = ANONYMIZED_NAME.to_string();
+    user.last_name = ANONYMIZED_NAME.to_string();
+    // Only the birth year is kept, category age statistics still need it
+    user.birth_date =
+        NaiveDate::from_ymd_opt(user.birth_date.year(), 1, 1).unwrap_or(user.birth_date);
+    user.email = format!("{placeholder}@erased.invalid");
+    user.email_verified = false;
+    user.phone_number = placeholder.clone();
+    user.country_code = String::new();
+    user.password = String::new();
+    user.identification_number = placeholder;
+    user
+}
+
+#[cfg(test)]
+mod tests {
+    use entities::user::IdType;
+
+    use super::*;
+
+    #[test]
+    fn test_anonymized_user_keeps_no_personal_data() {
+        let user = User {
+            id_user: Uuid::new_v4(),
+            first_name: "Maria".to_string(),
+            last_name: "Gomez".to_string(),
+            birth_date: NaiveDate::from_ymd_opt(1990, 7, 23).unwrap(),
+            registration_date: NaiveDate::from_ymd_opt(2024, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+            email: "maria@example.com".to_string(),
+            email_verified: true,
+            phone_number: "3001234567".to_string(),
+            country_code: "CO".to_string(),
+            password: "hashed".to_string(),
+            identification_number: "1020304050".to_string(),
+            identification_type: IdType::CC,
+            user_rol: URol::USER,
+        };
+
+        let erased = anonymized(user.clone());
+
+        let serialized = serde_json::to_string(&erased).unwrap();
+        for personal_data in [
+            "Maria",
+            "Gomez",
+            "maria@example.com",
+            "3001234567",
+            "1020304050",
+            "hashed",
+            "1990-07-23",
+        ] {
+            assert!(!serialized.contains(personal_data), "{personal_data}");
+        }
+        assert_eq!(erased.id_user, user.id_user);
+        assert_eq!(
+            erased.birth_date,
+            NaiveDate::from_ymd_opt(1990, 1, 1).unwrap()
+        );
+        assert!(!erased.email_verified);
+    }
+
+    #[test]
+    fn test_audit_snapshot_keeps_no_personal_data() {
+        let user = UserInfo {
+            id_user: Uuid::new_v4(),
+            first_name: "Maria".to_string(),
+            last_name: "Gomez".to_string(),
+            birth_date: NaiveDate::from_ymd_opt(1990, 7, 23).unwrap(),
+            email: "maria@example.com".to_string(),
+            email_verified: true,
+            phone_number: "3001234567".to_string(),
+            identification_number: "1020304050".to_string(),
+            user_rol: URol::TRAINER,
+            ..UserInfo::default()
+        };
+
+        let snapshot = audit_snapshot(&user);
+
+        let serialized = serde_json::to_string(&snapshot).unwrap();
+        for personal_data in [
+            "Maria",
+            "Gomez",
+            "maria@example.com",
+            "3001234567",
+            "1020304050",
+            "1990-07-23",
+        ] {
+            assert!(!serialized.contains(personal_data), "{personal_data}");
+        }
+        assert_eq!(snapshot.id_user, user.id_user);
+        assert_eq!(snapshot.user_rol, URol::TRAINER);
+        assert!(snapshot.email_verified);
+    }
+}
@@ -1,11 +1,14 @@
 use super::err::Result;
 use async_trait::async_trait;
+use entities::event::OutboxEvent;
+use entities::pagination::{Page, PageQuery};
 use entities::user::*;
 use uuid::Uuid;
 
 #[async_trait]
 pub trait UserRepository: Sync + Send {
-    async fn create_user(&self, user: &User) -> Result<()>;
+    /// `event` is stored in the outbox along with the user
+    async fn create_user(&self, user: &User, event: Option<&OutboxEvent>) -> Result<()>;
     async fn get_user_by_id(&self, id: Uuid) -> Result<Option<User>>;
 
     async fn get_user_id_by_email(&self, email: &str) -> Result<Option<Uuid>>;
@@ -18,7 +21,11 @@ pub trait UserRepository: Sync + Send {
 
     async fn update_user(&self, user: &User) -> Result<()>;
     async fn delete_user(&self, id: Uuid) -> Result<()>;
+    /// Writes the anonymized profile and soft deletes the user at once, a failure leaves the
+    /// account untouched
+    async fn anonymize_user(&self, user: &User) -> Result<()>;
     async fn list_users(&self) -> Result<Vec<User>>;
+    async fn list_users_page(&self, filter: &UserFilter, page: &PageQuery) -> Result<Page<User>>;
 }
 
 pub trait UserRoleRepository {
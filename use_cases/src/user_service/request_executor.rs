@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use entities::audit::Actor;
+use entities::request::{Request, RequestCommand};
+use uuid::Uuid;
+
+use super::UserService;
+use crate::request_service::{
+    err::{Error, Result},
+    executor::RequestExecutor,
+};
+
+#[async_trait]
+impl RequestExecutor for UserService {
+    fn handles(&self, command: &RequestCommand) -> bool {
+        matches!(
+            command,
+            RequestCommand::ROLE_CHANGE(_) | RequestCommand::ERASE_USER_ACCOUNT
+        )
+    }
+
+    async fn validate(&self, requester_id: Uuid, command: &RequestCommand) -> Result<()> {
+        let user_id = match command {
+            RequestCommand::ROLE_CHANGE(change) => change.user_id,
+            _ => requester_id,
+        };
+
+        self.get_user_by_id(user_id)
+            .await
+            .map_err(|err| Error::InvalidRequestPayload(err.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn execute(&self, approver: &Actor, request: &Request) -> Result<()> {
+        match &request.requested_command {
+            RequestCommand::ROLE_CHANGE(change) => self
+                .update_user_role(approver, change.user_id, change.user_rol.clone())
+                .await
+                .map(|_| ()),
+            // Financial and tournament records are kept, they only lose the personal data
+            RequestCommand::ERASE_USER_ACCOUNT => {
+                self.anonymize_user(approver, request.requester_id).await
+            }
+            _ => Ok(()),
+        }
+        .map_err(|err| Error::ExecutionFailed(err.to_string()))
+    }
+}
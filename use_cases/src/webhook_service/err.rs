@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Database error: {0}")]
+    UnknownDatabaseError(String),
+    #[error("Webhook not found")]
+    WebhookNotFound,
+    #[error("Webhook delivery not found")]
+    DeliveryNotFound,
+    #[error("Webhook urls must be absolute http or https urls")]
+    InvalidUrl,
+    #[error("A webhook must subscribe to at least one event kind")]
+    MissingEventKinds,
+    #[error("Error sending webhook: {0}")]
+    SendFailed(String),
+}
@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+use entities::event::{EventKind, OutboxEvent};
+
+use super::WebhookService;
+use crate::event_service::{
+    err::{Error, Result},
+    subscriber_trait::EventSubscriber,
+};
+
+/// Every event kind is accepted, the subscriptions of each webhook are checked when enqueuing
+#[async_trait]
+impl EventSubscriber for WebhookService {
+    fn name(&self) -> &'static str {
+        "webhooks"
+    }
+
+    fn handles(&self, _kind: EventKind) -> bool {
+        true
+    }
+
+    async fn handle(&self, event: &OutboxEvent) -> Result<()> {
+        self.enqueue(event)
+            .await
+            .map_err(|err| Error::SubscriberFailed(err.to_string()))
+    }
+}
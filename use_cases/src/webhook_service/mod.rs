@@ -0,0 +1,301 @@
+pub mod err;
+mod event_subscriber;
+pub mod repository_trait;
+pub mod sender_trait;
+
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use entities::audit::{Actor, AuditEntity};
+use entities::event::OutboxEvent;
+use entities::pagination::{Page, PageQuery};
+use entities::webhook::{
+    CreatedWebhook, Webhook, WebhookCreation, WebhookDelivery, WebhookDeliveryFilter,
+    WebhookDeliveryStatus,
+};
+use repository_trait::WebhookRepository;
+use sender_trait::WebhookSender;
+use serde_json::json;
+use tracing::warn;
+use uuid::Uuid;
+
+use self::err::{Error, Result};
+use crate::audit_service::AuditService;
+
+/// Deliveries still failing after this many attempts are marked as failed
+const MAX_DELIVERY_ATTEMPTS: u32 = 6;
+/// Delay before the first retry, doubled on every following one
+const RETRY_BASE_SECONDS: i64 = 30;
+
+#[derive(Clone)]
+pub struct WebhookService {
+    webhook_repo: Arc<dyn WebhookRepository>,
+    sender: Arc<dyn WebhookSender>,
+    audit_service: AuditService,
+}
+
+impl WebhookService {
+    pub fn new(
+        webhook_repo: Arc<dyn WebhookRepository>,
+        sender: Arc<dyn WebhookSender>,
+        audit_service: AuditService,
+    ) -> Self {
+        Self {
+            webhook_repo,
+            sender,
+            audit_service,
+        }
+    }
+
+    /// The secret is returned here and never again, the receiver has to store it
+    pub async fn create_webhook(
+        &self,
+        actor: &Actor,
+        creation: WebhookCreation,
+    ) -> Result<CreatedWebhook> {
+        if !creation.url.starts_with("https://") && !creation.url.starts_with("http://") {
+            return Err(Error::InvalidUrl);
+        }
+        if creation.event_kinds.is_empty() {
+            return Err(Error::MissingEventKinds);
+        }
+
+        let webhook = creation.to_webhook(
+            Uuid::new_v4(),
+            format!("whsec_{}", Uuid::new_v4().simple()),
+            true,
+            Utc::now().naive_utc(),
+        );
+        self.webhook_repo.create_webhook(&webhook).await?;
+
+        self.audit_service
+            .record_creation(
+                actor,
+                "CREATE_WEBHOOK",
+                AuditEntity::WEBHOOK,
+                webhook.id_webhook,
+                &webhook,
+            )
+            .await;
+
+        Ok(CreatedWebhook {
+            secret: webhook.secret.clone(),
+            webhook,
+        })
+    }
+
+    pub async fn get_webhook(&self, id_webhook: Uuid) -> Result<Webhook> {
+        self.webhook_repo
+            .get_webhook(id_webhook)
+            .await?
+            .ok_or(Error::WebhookNotFound)
+    }
+
+    pub async fn list_webhooks(&self) -> Result<Vec<Webhook>> {
+        self.webhook_repo.list_webhooks().await
+    }
+
+    /// Inactive webhooks don't get new deliveries, the pending ones are still retried
+    pub async fn set_webhook_active(
+        &self,
+        actor: &Actor,
+        id_webhook: Uuid,
+        active: bool,
+    ) -> Result<Webhook> {
+        let before = self.get_webhook(id_webhook).await?;
+        let webhook = Webhook {
+            active,
+            ..before.clone()
+        };
+        self.webhook_repo.update_webhook(&webhook).await?;
+
+        self.audit_service
+            .record_update(
+                actor,
+                "UPDATE_WEBHOOK",
+                AuditEntity::WEBHOOK,
+                id_webhook,
+                &before,
+                &webhook,
+            )
+            .await;
+
+        Ok(webhook)
+    }
+
+    pub async fn delete_webhook(&self, actor: &Actor, id_webhook: Uuid) -> Result<()> {
+        let webhook = self.get_webhook(id_webhook).await?;
+        self.webhook_repo.delete_webhook(id_webhook).await?;
+
+        self.audit_service
+            .record_deletion(
+                actor,
+                "DELETE_WEBHOOK",
+                AuditEntity::WEBHOOK,
+                id_webhook,
+                &webhook,
+            )
+            .await;
+
+        Ok(())
+    }
+
+    pub async fn list_deliveries_page(
+        &self,
+        filter: &WebhookDeliveryFilter,
+        page: &PageQuery,
+    ) -> Result<Page<WebhookDelivery>> {
+        self.webhook_repo.list_deliveries_page(filter, page).await
+    }
+
+    /// Sends the delivery again right away, whatever its status
+    pub async fn redeliver(&self, id_delivery: Uuid) -> Result<WebhookDelivery> {
+        let delivery = self
+            .webhook_repo
+            .get_delivery(id_delivery)
+            .await?
+            .ok_or(Error::DeliveryNotFound)?;
+        let webhook = self.get_webhook(delivery.id_webhook).await?;
+
+        self.attempt(&webhook, delivery).await
+    }
+
+    /// Retries the pending deliveries whose backoff elapsed and returns how many succeeded
+    pub async fn retry_due_deliveries(&self, batch_size: u32) -> Result<usize> {
+        let deliveries = self
+            .webhook_repo
+            .list_due_deliveries(Utc::now().naive_utc(), batch_size)
+            .await?;
+
+        let mut succeeded = 0;
+        for mut delivery in deliveries {
+            let Some(webhook) = self.webhook_repo.get_webhook(delivery.id_webhook).await? else {
+                delivery.status = WebhookDeliveryStatus::FAILED;
+                delivery.next_attempt_at = None;
+                delivery.last_error = Some("The webhook was deleted".to_string());
+                self.webhook_repo.update_delivery(&delivery).await?;
+                continue;
+            };
+
+            let delivery = self.attempt(&webhook, delivery).await?;
+            if delivery.status == WebhookDeliveryStatus::SUCCEEDED {
+                succeeded += 1;
+            }
+        }
+
+        Ok(succeeded)
+    }
+
+    /// Creates a pending delivery of the event for every webhook subscribed to its kind. Every
+    /// attempt, the first one included, is made by `retry_due_deliveries`, so a slow endpoint
+    /// never holds up the dispatch of the outbox
+    async fn enqueue(&self, event: &OutboxEvent) -> Result<()> {
+        let kind = event.event.kind();
+        let webhooks = self.webhook_repo.list_webhooks().await?;
+        let payload = payload(event)?;
+
+        for webhook in webhooks
+            .iter()
+            .filter(|webhook| webhook.is_subscribed_to(kind))
+        {
+            let now = Utc::now().naive_utc();
+            let delivery = WebhookDelivery {
+                id_delivery: Uuid::new_v4(),
+                id_webhook: webhook.id_webhook,
+                id_event: event.id_event,
+                event_kind: kind,
+                payload: payload.clone(),
+                status: WebhookDeliveryStatus::PENDING,
+                attempts: 0,
+                response_status: None,
+                last_error: None,
+                next_attempt_at: Some(now),
+                created_at: now,
+                delivered_at: None,
+            };
+
+            self.webhook_repo.create_delivery(&delivery).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn attempt(
+        &self,
+        webhook: &Webhook,
+        mut delivery: WebhookDelivery,
+    ) -> Result<WebhookDelivery> {
+        let now = Utc::now().naive_utc();
+        delivery.attempts += 1;
+
+        let (response_status, error) = match self.sender.send(webhook, &delivery).await {
+            Ok(status) if (200..300).contains(&status) => (Some(status), None),
+            Ok(status) => (
+                Some(status),
+                Some(format!("The endpoint answered with status {status}")),
+            ),
+            Err(err) => (None, Some(err.to_string())),
+        };
+        delivery.response_status = response_status;
+
+        match error {
+            None => {
+                delivery.status = WebhookDeliveryStatus::SUCCEEDED;
+                delivery.last_error = None;
+                delivery.next_attempt_at = None;
+                delivery.delivered_at = Some(now);
+            }
+            Some(error) => {
+                warn!(
+                    "Delivery {} to webhook {} failed: {error}",
+                    delivery.id_delivery, webhook.id_webhook
+                );
+                delivery.last_error = Some(error);
+                if delivery.attempts >= MAX_DELIVERY_ATTEMPTS {
+                    delivery.status = WebhookDeliveryStatus::FAILED;
+                    delivery.next_attempt_at = None;
+                } else {
+                    delivery.status = WebhookDeliveryStatus::PENDING;
+                    delivery.next_attempt_at = Some(now + retry_delay(delivery.attempts));
+                }
+            }
+        }
+
+        self.webhook_repo.update_delivery(&delivery).await?;
+
+        Ok(delivery)
+    }
+}
+
+/// 30 seconds after the first attempt, then 1, 2, 4 and 8 minutes
+fn retry_delay(attempts: u32) -> Duration {
+    Duration::seconds(RETRY_BASE_SECONDS << attempts.saturating_sub(1).min(16))
+}
+
+/// Body sent to the webhooks, `data` holds the payload of the event
+fn payload(event: &OutboxEvent) -> Result<String> {
+    let value = serde_json::to_value(&event.event)
+        .map_err(|err| Error::UnknownDatabaseError(err.to_string()))?;
+
+    Ok(json!({
+        "id_event": event.id_event,
+        "kind": event.event.kind(),
+        "occurred_at": event.occurred_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        "data": value.get("payload"),
+    })
+    .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_doubles() {
+        let delays: Vec<i64> = (1..=5)
+            .map(|attempts| retry_delay(attempts).num_seconds())
+            .collect();
+
+        assert_eq!(delays, vec![30, 60, 120, 240, 480]);
+    }
+}
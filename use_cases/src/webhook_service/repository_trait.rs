@@ -0,0 +1,32 @@
+use super::err::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use entities::pagination::{Page, PageQuery};
+use entities::webhook::{Webhook, WebhookDelivery, WebhookDeliveryFilter};
+use uuid::Uuid;
+
+#[async_trait]
+pub trait WebhookRepository: Send + Sync {
+    async fn create_webhook(&self, webhook: &Webhook) -> Result<()>;
+    async fn get_webhook(&self, id_webhook: Uuid) -> Result<Option<Webhook>>;
+    async fn list_webhooks(&self) -> Result<Vec<Webhook>>;
+    async fn update_webhook(&self, webhook: &Webhook) -> Result<()>;
+    /// Soft deletes the webhook, its delivery history is kept
+    async fn delete_webhook(&self, id_webhook: Uuid) -> Result<()>;
+
+    /// Returns false when the webhook already has a delivery for the event
+    async fn create_delivery(&self, delivery: &WebhookDelivery) -> Result<bool>;
+    async fn get_delivery(&self, id_delivery: Uuid) -> Result<Option<WebhookDelivery>>;
+    async fn update_delivery(&self, delivery: &WebhookDelivery) -> Result<()>;
+    async fn list_deliveries_page(
+        &self,
+        filter: &WebhookDeliveryFilter,
+        page: &PageQuery,
+    ) -> Result<Page<WebhookDelivery>>;
+    /// Pending deliveries whose next attempt is due at `now`, oldest first
+    async fn list_due_deliveries(
+        &self,
+        now: NaiveDateTime,
+        limit: u32,
+    ) -> Result<Vec<WebhookDelivery>>;
+}
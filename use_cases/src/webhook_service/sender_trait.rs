@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use entities::webhook::{Webhook, WebhookDelivery};
+
+use super::err::Result;
+
+/// Posts the payload of a delivery to its webhook, signed with the webhook secret. Returns the
+/// HTTP status the endpoint answered with, errors are reserved for requests that got no answer
+#[async_trait]
+pub trait WebhookSender: Send + Sync {
+    async fn send(&self, webhook: &Webhook, delivery: &WebhookDelivery) -> Result<u16>;
+}